@@ -1,9 +1,26 @@
 use rehber360_core::{
-    models::{ExamResult, BehaviorIncident, AcademicGoal},
-    repositories::AcademicRepository,
+    error::AppError,
+    models::{
+        ExamResult, BehaviorIncident, AcademicGoal, ExamImportLayout, ExamImportResult,
+        CreateExamImportLayoutRequest, UpdateExamImportLayoutRequest, ImportExamResultsRequest,
+        CalculateExamScoreRequest, ExamScoreCalculation, ExamSubjectCount, ExamTypeTrend, ExamRankingSummary,
+        UpdateAcademicGoalRequest, ExamTopicResult, ExamTopicResultInput, ImportExamTopicReportRequest,
+        ExamTopicImportResult, TopicWeaknessAnalysis,
+    },
+    repositories::{AcademicRepository, ExamImportRepository, UserRepository},
+    services::{
+        academic_goal_service::AcademicGoalService,
+        exam_import_service::ExamImportService,
+        exam_ranking_service::ExamRankingService,
+        exam_score_service::{self, ExamScoreService},
+        exam_topic_service::ExamTopicService,
+        exam_trend_service::ExamTrendService,
+    },
 };
 use sqlx::SqlitePool;
 use tauri::State;
+use super::institution::{session_institution, student_session};
+use super::risk::refresh_student_risk;
 
 #[tauri::command]
 pub async fn create_exam_result(
@@ -60,13 +77,20 @@ pub async fn create_exam_result(
         counselorNotes: None,
         actionPlan: None,
         notes: None,
+        scoreType: None,
         created_at: Utc::now().to_rfc3339(),
         updated_at: Utc::now().to_rfc3339(),
     };
 
-    AcademicRepository::create_exam_result(pool.inner(), result)
+    let result = AcademicRepository::create_exam_result(pool.inner(), result)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    refresh_academic_goals(pool.inner(), &result.studentId).await;
+    refresh_exam_trends(pool.inner(), &result.studentId).await;
+    refresh_student_risk(pool.inner(), &result.studentId).await;
+    refresh_exam_ranking(pool.inner(), &result.examName, &result.examDate).await;
+    Ok(result)
 }
 
 #[tauri::command]
@@ -140,9 +164,15 @@ pub async fn update_exam_result(
 
     existing.updated_at = Utc::now().to_rfc3339();
 
+    let student_id = existing.studentId.clone();
     AcademicRepository::update_exam_result(pool.inner(), &id, existing)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    refresh_academic_goals(pool.inner(), &student_id).await;
+    refresh_exam_trends(pool.inner(), &student_id).await;
+    refresh_student_risk(pool.inner(), &student_id).await;
+    Ok(())
 }
 
 #[tauri::command]
@@ -150,11 +180,58 @@ pub async fn delete_exam_result(
     pool: State<'_, SqlitePool>,
     id: String,
 ) -> Result<(), String> {
+    let existing = AcademicRepository::get_exam_result_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
     AcademicRepository::delete_exam_result(pool.inner(), &id)
         .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_academic_goals(pool.inner(), &existing.studentId).await;
+    refresh_exam_trends(pool.inner(), &existing.studentId).await;
+    refresh_student_risk(pool.inner(), &existing.studentId).await;
+    refresh_exam_ranking(pool.inner(), &existing.examName, &existing.examDate).await;
+    Ok(())
+}
+
+/// Nets and score of raw answer counts under the exam's official rules, without saving
+#[tauri::command]
+pub async fn calculate_exam_score(
+    request: CalculateExamScoreRequest,
+) -> Result<ExamScoreCalculation, String> {
+    let tyt = request
+        .tytSubjects
+        .map(|subjects| exam_score_service::calculate("TYT", None, &subjects, None))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+
+    exam_score_service::calculate(&request.examType, request.scoreType.as_deref(), &request.subjects, tyt.as_ref())
         .map_err(|e| e.to_string())
 }
 
+/// Fill an exam result's nets and score from answer counts, or from its stored subject counts
+#[tauri::command]
+pub async fn score_exam_result(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    score_type: Option<String>,
+    subjects: Option<Vec<ExamSubjectCount>>,
+) -> Result<ExamResult, String> {
+    accessible_result(pool.inner(), &token, &id).await?;
+
+    let result = ExamScoreService::score_result(pool.inner(), &id, score_type, subjects)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_academic_goals(pool.inner(), &result.studentId).await;
+    refresh_exam_trends(pool.inner(), &result.studentId).await;
+    refresh_student_risk(pool.inner(), &result.studentId).await;
+    refresh_exam_ranking(pool.inner(), &result.examName, &result.examDate).await;
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn create_behavior_incident(
     pool: State<'_, SqlitePool>,
@@ -207,9 +284,12 @@ pub async fn create_behavior_incident(
         updated_at: Utc::now().to_rfc3339(),
     };
 
-    AcademicRepository::create_behavior_incident(pool.inner(), incident)
+    let incident = AcademicRepository::create_behavior_incident(pool.inner(), incident)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    refresh_student_risk(pool.inner(), &incident.studentId).await;
+    Ok(incident)
 }
 
 #[tauri::command]
@@ -235,12 +315,15 @@ pub async fn get_student_behavior_incidents(
 #[tauri::command]
 pub async fn create_academic_goal(
     pool: State<'_, SqlitePool>,
+    token: String,
     student_id: String,
     title: String,
     target_score: Option<f64>,
     exam_type: Option<String>,
     deadline: Option<String>,
 ) -> Result<AcademicGoal, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -254,21 +337,282 @@ pub async fn create_academic_goal(
         examType: exam_type,
         deadline,
         status: "active".to_string(),
+        lastExamResultId: None,
+        closedAt: None,
         created_at: Utc::now().to_rfc3339(),
         updated_at: Utc::now().to_rfc3339(),
     };
 
-    AcademicRepository::create_academic_goal(pool.inner(), goal)
+    let goal = AcademicGoalService::create_goal(pool.inner(), goal)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    refresh_exam_trends(pool.inner(), &goal.studentId).await;
+    Ok(goal)
 }
 
+/// The student's active goals, or goals of every status with `include_closed`
 #[tauri::command]
 pub async fn get_student_academic_goals(
     pool: State<'_, SqlitePool>,
+    token: String,
     student_id: String,
+    include_closed: Option<bool>,
 ) -> Result<Vec<AcademicGoal>, String> {
-    AcademicRepository::get_academic_goals_by_student(pool.inner(), &student_id)
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    if include_closed.unwrap_or(false) {
+        AcademicRepository::get_all_academic_goals_by_student(pool.inner(), &student_id).await
+    } else {
+        AcademicRepository::get_academic_goals_by_student(pool.inner(), &student_id).await
+    }
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_academic_goal(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateAcademicGoalRequest,
+) -> Result<AcademicGoal, String> {
+    accessible_goal(pool.inner(), &token, &id).await?;
+
+    let goal = AcademicGoalService::update_goal(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_exam_trends(pool.inner(), &goal.studentId).await;
+    Ok(goal)
+}
+
+#[tauri::command]
+pub async fn delete_academic_goal(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    accessible_goal(pool.inner(), &token, &id).await?;
+
+    let goal = AcademicGoalService::delete_goal(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_exam_trends(pool.inner(), &goal.studentId).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_exam_import_layouts(
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<ExamImportLayout>, String> {
+    ExamImportRepository::get_layouts(pool.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_exam_import_layout(
+    pool: State<'_, SqlitePool>,
+    request: CreateExamImportLayoutRequest,
+) -> Result<ExamImportLayout, String> {
+    ExamImportService::create_layout(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_exam_import_layout(
+    pool: State<'_, SqlitePool>,
+    id: String,
+    request: UpdateExamImportLayoutRequest,
+) -> Result<ExamImportLayout, String> {
+    ExamImportService::update_layout(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_exam_import_layout(
+    pool: State<'_, SqlitePool>,
+    id: String,
+) -> Result<(), String> {
+    ExamImportRepository::delete_layout(pool.inner(), &id)
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Import an optical reader or publisher result file of a practice exam
+#[tauri::command]
+pub async fn import_exam_results(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: ImportExamResultsRequest,
+) -> Result<ExamImportResult, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+    let (exam_name, exam_date) = (request.examName.clone(), request.examDate.clone());
+    let result = ExamImportService::import_results(pool.inner(), &institution_id, request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for student_id in &result.studentIds {
+        refresh_academic_goals(pool.inner(), student_id).await;
+        refresh_exam_trends(pool.inner(), student_id).await;
+        refresh_student_risk(pool.inner(), student_id).await;
+    }
+    refresh_exam_ranking(pool.inner(), &exam_name, &exam_date).await;
+
+    Ok(result)
+}
+
+/// Net trends of a student's exams per exam type, optionally of one type
+#[tauri::command]
+pub async fn get_student_exam_trends(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    exam_type: Option<String>,
+) -> Result<Vec<ExamTypeTrend>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    ExamTrendService::get_trends(pool.inner(), &student_id, exam_type.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rank an exam's results within their class and school; the summary covers the session's institution
+#[tauri::command]
+pub async fn rank_exam(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    exam_name: String,
+    exam_date: String,
+) -> Result<ExamRankingSummary, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ExamRankingService::rank_exam_for(pool.inner(), &viewer, &exam_name, &exam_date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_exam_topic_results(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    exam_result_id: String,
+) -> Result<Vec<ExamTopicResult>, String> {
+    accessible_result(pool.inner(), &token, &exam_result_id).await?;
+
+    ExamTopicService::get_topic_results(pool.inner(), &exam_result_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace an exam result's topic results with topics entered by hand
+#[tauri::command]
+pub async fn save_exam_topic_results(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    exam_result_id: String,
+    topics: Vec<ExamTopicResultInput>,
+) -> Result<Vec<ExamTopicResult>, String> {
+    accessible_result(pool.inner(), &token, &exam_result_id).await?;
+
+    let results = ExamTopicService::save_topic_results(pool.inner(), &exam_result_id, topics)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let Some(result) = results.first() {
+        refresh_weak_topics(pool.inner(), &result.studentId).await;
+    }
+    Ok(results)
+}
+
+/// Import a publisher's topic report for an already imported exam
+#[tauri::command]
+pub async fn import_exam_topic_report(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: ImportExamTopicReportRequest,
+) -> Result<ExamTopicImportResult, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+    let result = ExamTopicService::import_topic_report(pool.inner(), &institution_id, request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    for student_id in &result.studentIds {
+        refresh_weak_topics(pool.inner(), student_id).await;
+    }
+    Ok(result)
+}
+
+/// Topics a student keeps missing across their recent exams
+#[tauri::command]
+pub async fn get_student_weak_topics(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<TopicWeaknessAnalysis, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    ExamTopicService::analyze_student(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The exam result, if the session may work with its student
+async fn accessible_result(pool: &SqlitePool, token: &str, id: &str) -> Result<ExamResult, String> {
+    let result = AcademicRepository::get_exam_result_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &result.studentId).await?;
+    Ok(result)
+}
+
+/// The academic goal, if the session may work with its student
+async fn accessible_goal(pool: &SqlitePool, token: &str, id: &str) -> Result<AcademicGoal, String> {
+    let goal = AcademicRepository::get_academic_goal_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &goal.studentId).await?;
+    Ok(goal)
+}
+
+/// Update a student's goal progress after their exam results changed.
+///
+/// Failures are logged rather than failing the change itself.
+pub(crate) async fn refresh_academic_goals(pool: &SqlitePool, student_id: &str) {
+    if let Err(e) = AcademicGoalService::track_student(pool, student_id).await {
+        log::warn!("Failed to track academic goals of student {}: {}", student_id, e);
+    }
+}
+
+/// Refresh a student's exam comparisons and decline flags after their results or goals changed.
+///
+/// Failures are logged rather than failing the change itself.
+pub(crate) async fn refresh_exam_trends(pool: &SqlitePool, student_id: &str) {
+    if let Err(e) = ExamTrendService::refresh_student(pool, student_id).await {
+        log::warn!("Failed to refresh exam trends of student {}: {}", student_id, e);
+    }
+}
+
+/// Re-rank an exam after its results changed; an exam left without results has nothing to rank.
+///
+/// Failures are logged rather than failing the change itself.
+pub(crate) async fn refresh_exam_ranking(pool: &SqlitePool, exam_name: &str, exam_date: &str) {
+    match ExamRankingService::rank_exam(pool, exam_name, exam_date).await {
+        Ok(_) | Err(AppError::NotFound(_)) => {}
+        Err(e) => log::warn!("Failed to rank exam {} ({}): {}", exam_name, exam_date, e),
+    }
+}
+
+/// Queue a counseling suggestion for recurring weak topics after a student's topic results changed.
+///
+/// Failures are logged rather than failing the change itself.
+pub(crate) async fn refresh_weak_topics(pool: &SqlitePool, student_id: &str) {
+    if let Err(e) = ExamTopicService::refresh_student(pool, student_id).await {
+        log::warn!("Failed to analyze weak topics of student {}: {}", student_id, e);
+    }
+}
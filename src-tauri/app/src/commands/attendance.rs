@@ -0,0 +1,189 @@
+use rehber360_core::{
+    models::{
+        AttendanceRecord, AttendanceAlert, AttendanceThresholds, StudentAttendanceReport, AttendanceImportResult,
+        CreateAttendanceRequest, UpdateAttendanceRequest,
+    },
+    repositories::{AttendanceRepository, UserRepository},
+    services::{attendance_service::AttendanceService, attendance_import_service::AttendanceImportService},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::{admin_session, session_institution, student_session};
+use super::risk::refresh_student_risk;
+
+#[tauri::command]
+pub async fn create_attendance_record(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateAttendanceRequest,
+) -> Result<AttendanceRecord, String> {
+    student_session(pool.inner(), &token, &request.studentId).await?;
+
+    let record = AttendanceService::create_record(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_student_risk(pool.inner(), &record.studentId).await;
+    Ok(record)
+}
+
+#[tauri::command]
+pub async fn get_attendance_record(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<AttendanceRecord, String> {
+    accessible_record(pool.inner(), &token, &id).await
+}
+
+#[tauri::command]
+pub async fn update_attendance_record(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateAttendanceRequest,
+) -> Result<AttendanceRecord, String> {
+    accessible_record(pool.inner(), &token, &id).await?;
+
+    let record = AttendanceService::update_record(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_student_risk(pool.inner(), &record.studentId).await;
+    Ok(record)
+}
+
+#[tauri::command]
+pub async fn delete_attendance_record(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let record = accessible_record(pool.inner(), &token, &id).await?;
+
+    AttendanceRepository::delete(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    refresh_student_risk(pool.inner(), &record.studentId).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_student_attendance(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    school_year: Option<String>,
+) -> Result<Vec<AttendanceRecord>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    AttendanceRepository::get_by_student(pool.inner(), &student_id, school_year.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_attendance_by_date(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    date: String,
+) -> Result<Vec<AttendanceRecord>, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    AttendanceRepository::get_by_date(pool.inner(), &institution_id, &date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_attendance_summary(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    school_year: Option<String>,
+) -> Result<StudentAttendanceReport, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    AttendanceService::get_student_report(pool.inner(), &student_id, school_year)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_attendance_alerts(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<AttendanceAlert>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    AttendanceRepository::get_alerts_by_student(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_attendance_thresholds(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<AttendanceThresholds, String> {
+    UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    AttendanceRepository::get_thresholds(pool.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_attendance_thresholds(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    thresholds: AttendanceThresholds,
+) -> Result<AttendanceThresholds, String> {
+    admin_session(pool.inner(), &token).await?;
+
+    AttendanceService::update_thresholds(pool.inner(), thresholds)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_eokul_attendance(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    content: String,
+    date: Option<String>,
+    recorded_by: Option<String>,
+) -> Result<AttendanceImportResult, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+    let result = AttendanceImportService::import_daily_report(pool.inner(), &institution_id, &content, date, recorded_by)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut student_ids = Vec::new();
+    for date in &result.dates {
+        let records = AttendanceRepository::get_by_date(pool.inner(), &institution_id, date)
+            .await
+            .map_err(|e| e.to_string())?;
+        student_ids.extend(records.into_iter().map(|r| r.studentId));
+    }
+    student_ids.sort();
+    student_ids.dedup();
+    for student_id in &student_ids {
+        refresh_student_risk(pool.inner(), student_id).await;
+    }
+
+    Ok(result)
+}
+
+/// The attendance record, if the session may work with its student
+async fn accessible_record(pool: &SqlitePool, token: &str, id: &str) -> Result<AttendanceRecord, String> {
+    let record = AttendanceRepository::get_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &record.studentId).await?;
+    Ok(record)
+}
@@ -1,4 +1,5 @@
 use rehber360_core::models::{User, UserSession, LoginRequest};
+use rehber360_core::services::institution_service::InstitutionService;
 use sqlx::SqlitePool;
 use tauri::State;
 use uuid::Uuid;
@@ -41,6 +42,11 @@ pub async fn login(
         .execute(pool.inner())
         .await
         .map_err(|e| format!("Failed to create session: {}", e))?;
+
+    let institution = InstitutionService::start_session(pool.inner(), &token, &user)
+        .await
+        .map_err(|e| e.to_string())?;
+    let user = User { activeInstitutionId: institution.map(|i| i.id), ..user };
     
     Ok(UserSession { user, token })
 }
@@ -143,8 +149,8 @@ pub async fn create_initial_admin(
     let user_id = Uuid::new_v4().to_string();
     
     sqlx::query(
-        "INSERT INTO users (id, email, passwordHash, name, surname, role, isActive) 
-         VALUES (?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO users (id, email, passwordHash, name, surname, role, isActive, isDistrictAdmin) 
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
     )
     .bind(&user_id)
     .bind(&email)
@@ -153,6 +159,8 @@ pub async fn create_initial_admin(
     .bind(&surname)
     .bind("admin")
     .bind(true)
+    // The installation's first admin manages its institutions
+    .bind(true)
     .execute(pool.inner())
     .await
     .map_err(|e| format!("Failed to create admin user: {}", e))?;
@@ -0,0 +1,294 @@
+use rehber360_core::{
+    models::{
+        BepAccommodation, BepEvaluation, BepGoal, BepGoalProgress, BepPlan, BepPlanDetail, BepResponsibleTeacher,
+        NotificationLog, StudentDocument, AssignBepTeacherRequest, ChangeBepPlanStatusRequest,
+        CreateBepAccommodationRequest, CreateBepEvaluationRequest, CreateBepGoalRequest, CreateBepPlanRequest,
+        RecordBepGoalProgressRequest, UpdateBepGoalRequest, UpdateBepPlanRequest, User,
+    },
+    repositories::BepRepository,
+    services::bep_service::{BepService, DEFAULT_REVIEW_REMINDER_DAYS},
+};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
+use super::file::get_uploads_dir;
+use super::institution::{admin_session, student_session};
+
+#[tauri::command]
+pub async fn create_bep_plan(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateBepPlanRequest,
+) -> Result<BepPlanDetail, String> {
+    student_session(pool.inner(), &token, &request.studentId).await?;
+
+    BepService::create_plan(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_bep_plan(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<BepPlanDetail, String> {
+    plan_session(pool.inner(), &token, &id).await?;
+
+    BepService::get_detail(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_bep_plans(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<BepPlan>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    BepRepository::get_plans_by_student(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_bep_plan(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateBepPlanRequest,
+) -> Result<BepPlan, String> {
+    plan_session(pool.inner(), &token, &id).await?;
+
+    BepService::update_plan(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn change_bep_plan_status(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: ChangeBepPlanStatusRequest,
+) -> Result<BepPlan, String> {
+    plan_session(pool.inner(), &token, &id).await?;
+
+    BepService::change_status(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_bep_plan(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    plan_session(pool.inner(), &token, &id).await?;
+
+    BepRepository::delete_plan(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_bep_goal(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    plan_id: String,
+    request: CreateBepGoalRequest,
+) -> Result<BepGoal, String> {
+    plan_session(pool.inner(), &token, &plan_id).await?;
+
+    BepService::add_goal(pool.inner(), &plan_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_bep_goal(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateBepGoalRequest,
+) -> Result<BepGoal, String> {
+    goal_session(pool.inner(), &token, &id).await?;
+
+    BepService::update_goal(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_bep_goal(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    goal_session(pool.inner(), &token, &id).await?;
+
+    BepRepository::delete_goal(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn record_bep_goal_progress(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    goal_id: String,
+    request: RecordBepGoalProgressRequest,
+) -> Result<BepGoalProgress, String> {
+    goal_session(pool.inner(), &token, &goal_id).await?;
+
+    BepService::record_goal_progress(pool.inner(), &goal_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_bep_goal_progress(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    goal_id: String,
+) -> Result<Vec<BepGoalProgress>, String> {
+    goal_session(pool.inner(), &token, &goal_id).await?;
+
+    BepRepository::get_goal_progress(pool.inner(), &goal_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_bep_accommodation(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    plan_id: String,
+    request: CreateBepAccommodationRequest,
+) -> Result<BepAccommodation, String> {
+    plan_session(pool.inner(), &token, &plan_id).await?;
+
+    BepService::add_accommodation(pool.inner(), &plan_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_bep_accommodation(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let accommodation = BepRepository::get_accommodation_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    plan_session(pool.inner(), &token, &accommodation.planId).await?;
+
+    BepRepository::delete_accommodation(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn assign_bep_teacher(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    plan_id: String,
+    request: AssignBepTeacherRequest,
+) -> Result<BepResponsibleTeacher, String> {
+    plan_session(pool.inner(), &token, &plan_id).await?;
+
+    BepService::assign_teacher(pool.inner(), &plan_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_bep_teacher(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let teacher = BepRepository::get_teacher_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    plan_session(pool.inner(), &token, &teacher.planId).await?;
+
+    BepRepository::remove_teacher(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_bep_evaluation(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    plan_id: String,
+    request: CreateBepEvaluationRequest,
+) -> Result<BepEvaluation, String> {
+    plan_session(pool.inner(), &token, &plan_id).await?;
+
+    BepService::add_evaluation(pool.inner(), &plan_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_bep_document(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    plan_id: String,
+) -> Result<String, String> {
+    plan_session(pool.inner(), &token, &plan_id).await?;
+
+    BepService::render_document(pool.inner(), &plan_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_bep_document(
+    app: AppHandle,
+    pool: State<'_, SqlitePool>,
+    token: String,
+    plan_id: String,
+    uploaded_by: Option<String>,
+) -> Result<StudentDocument, String> {
+    plan_session(pool.inner(), &token, &plan_id).await?;
+
+    let uploads_dir = get_uploads_dir(&app)?;
+    BepService::save_document(pool.inner(), &uploads_dir, &plan_id, uploaded_by)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn send_bep_review_reminders(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    days_ahead: Option<i64>,
+) -> Result<Vec<NotificationLog>, String> {
+    admin_session(pool.inner(), &token).await?;
+
+    BepService::send_review_reminders(pool.inner(), days_ahead.unwrap_or(DEFAULT_REVIEW_REMINDER_DAYS))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Signed-in user of a session, if they may work with the plan's student
+async fn plan_session(pool: &SqlitePool, token: &str, plan_id: &str) -> Result<User, String> {
+    let plan = BepRepository::get_plan_by_id(pool, plan_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &plan.studentId).await
+}
+
+/// Signed-in user of a session, if they may work with the student of the goal's plan
+async fn goal_session(pool: &SqlitePool, token: &str, goal_id: &str) -> Result<User, String> {
+    let goal = BepRepository::get_goal_by_id(pool, goal_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    plan_session(pool, token, &goal.planId).await
+}
@@ -1,15 +1,20 @@
 use rehber360_core::{
     models::{CounselingSession, MeetingNote, CounselingFollowUp},
-    repositories::CounselingRepository,
+    repositories::{CounselingRepository, UserRepository},
 };
 use sqlx::SqlitePool;
 use tauri::State;
+use super::institution::session_institution;
+use super::risk::{refresh_session_risk, refresh_student_risk};
 
 #[tauri::command]
 pub async fn get_all_counseling_sessions(
     pool: State<'_, SqlitePool>,
+    token: String,
 ) -> Result<Vec<CounselingSession>, String> {
-    CounselingRepository::get_all_sessions(pool.inner())
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    CounselingRepository::get_sessions_by_institution(pool.inner(), &institution_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -37,6 +42,7 @@ pub async fn get_student_counseling_sessions(
 #[tauri::command]
 pub async fn create_counseling_session(
     pool: State<'_, SqlitePool>,
+    token: String,
     session_type: String,
     counselor_id: String,
     session_date: String,
@@ -45,10 +51,13 @@ pub async fn create_counseling_session(
     participant_type: String,
     session_mode: String,
     session_location: String,
+    is_confidential: Option<bool>,
 ) -> Result<CounselingSession, String> {
     use chrono::Utc;
     use uuid::Uuid;
 
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
     let session = rehber360_core::models::CounselingSession {
         id: Uuid::new_v4().to_string(),
         sessionType: session_type,
@@ -88,6 +97,8 @@ pub async fn create_counseling_session(
         autoCompleted: false,
         extensionGranted: false,
         completed: false,
+        isConfidential: is_confidential.unwrap_or(false),
+        institutionId: Some(institution_id),
         created_at: Utc::now().to_rfc3339(),
         updated_at: Utc::now().to_rfc3339(),
     };
@@ -107,6 +118,7 @@ pub async fn update_counseling_session(
     detailed_notes: Option<String>,
     follow_up_needed: Option<bool>,
     completed: Option<bool>,
+    is_confidential: Option<bool>,
 ) -> Result<(), String> {
     use chrono::Utc;
 
@@ -134,6 +146,9 @@ pub async fn update_counseling_session(
     if let Some(val) = completed {
         existing.completed = val;
     }
+    if let Some(val) = is_confidential {
+        existing.isConfidential = val;
+    }
 
     existing.updated_at = Utc::now().to_rfc3339();
 
@@ -160,19 +175,34 @@ pub async fn add_student_to_session(
 ) -> Result<(), String> {
     CounselingRepository::add_student_to_session(pool.inner(), &session_id, &student_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    refresh_student_risk(pool.inner(), &student_id).await;
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn create_meeting_note(
     pool: State<'_, SqlitePool>,
+    token: Option<String>,
     student_id: String,
     date: String,
     note_type: String,
     note: String,
     plan: Option<String>,
 ) -> Result<MeetingNote, String> {
-    CounselingRepository::create_meeting_note(pool.inner(), &student_id, &date, &note_type, &note, plan)
+    // The author owns the note if it is later marked confidential
+    let author = match token {
+        Some(token) => Some(
+            UserRepository::get_session_by_token(pool.inner(), &token)
+                .await
+                .map_err(|e| e.to_string())?
+                .id,
+        ),
+        None => None,
+    };
+
+    CounselingRepository::create_meeting_note(pool.inner(), &student_id, &date, &note_type, &note, plan, author.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
@@ -192,9 +222,14 @@ pub async fn create_follow_up(
     pool: State<'_, SqlitePool>,
     follow_up: CounselingFollowUp,
 ) -> Result<CounselingFollowUp, String> {
-    CounselingRepository::create_follow_up(pool.inner(), follow_up)
+    let follow_up = CounselingRepository::create_follow_up(pool.inner(), follow_up)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    if let Some(session_id) = &follow_up.sessionId {
+        refresh_session_risk(pool.inner(), session_id).await;
+    }
+    Ok(follow_up)
 }
 
 #[tauri::command]
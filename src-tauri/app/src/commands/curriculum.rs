@@ -0,0 +1,122 @@
+use rehber360_core::{
+    models::{
+        CreateSubjectRequest, CreateTopicRequest, CurriculumImportResult, CurriculumVersion, Subject,
+        SubjectWithTopics, Topic, UpdateSubjectRequest, UpdateTopicRequest,
+    },
+    repositories::{CurriculumRepository, UserRepository},
+    services::curriculum_service::CurriculumService,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Subjects with their topics, optionally of one exam type
+#[tauri::command]
+pub async fn get_curriculum(
+    pool: State<'_, SqlitePool>,
+    exam_type: Option<String>,
+    include_archived: Option<bool>,
+) -> Result<Vec<SubjectWithTopics>, String> {
+    CurriculumService::get_catalog(pool.inner(), exam_type.as_deref(), include_archived.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_subject_topics(
+    pool: State<'_, SqlitePool>,
+    subject_id: String,
+    include_archived: Option<bool>,
+) -> Result<Vec<Topic>, String> {
+    CurriculumRepository::get_topics(pool.inner(), &subject_id, include_archived.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_subject(
+    pool: State<'_, SqlitePool>,
+    request: CreateSubjectRequest,
+) -> Result<Subject, String> {
+    CurriculumService::create_subject(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_subject(
+    pool: State<'_, SqlitePool>,
+    id: String,
+    request: UpdateSubjectRequest,
+) -> Result<Subject, String> {
+    CurriculumService::update_subject(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a school subject; catalog subjects are archived
+#[tauri::command]
+pub async fn delete_subject(
+    pool: State<'_, SqlitePool>,
+    id: String,
+) -> Result<(), String> {
+    CurriculumService::delete_subject(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_topic(
+    pool: State<'_, SqlitePool>,
+    request: CreateTopicRequest,
+) -> Result<Topic, String> {
+    CurriculumService::create_topic(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_topic(
+    pool: State<'_, SqlitePool>,
+    id: String,
+    request: UpdateTopicRequest,
+) -> Result<Topic, String> {
+    CurriculumService::update_topic(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a school topic; catalog topics are archived
+#[tauri::command]
+pub async fn delete_topic(
+    pool: State<'_, SqlitePool>,
+    id: String,
+) -> Result<(), String> {
+    CurriculumService::delete_topic(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Merge a curriculum catalog JSON file into subjects and topics
+#[tauri::command]
+pub async fn import_curriculum_catalog(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    content: String,
+) -> Result<CurriculumImportResult, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    CurriculumService::import_catalog(pool.inner(), &viewer, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_curriculum_versions(
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<CurriculumVersion>, String> {
+    CurriculumRepository::get_versions(pool.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use rehber360_core::{
+    models::{
+        CustomFieldDefinition, CustomFieldImportResult, StudentAttribute, StudentCustomData, TagCount,
+        CreateCustomFieldRequest, UpdateCustomFieldRequest,
+    },
+    repositories::UserRepository,
+    services::custom_field_service::CustomFieldService,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::{session_institution, student_session};
+
+#[tauri::command]
+pub async fn get_custom_fields(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    include_inactive: Option<bool>,
+) -> Result<Vec<CustomFieldDefinition>, String> {
+    UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    CustomFieldService::get_fields(pool.inner(), include_inactive.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_custom_field(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateCustomFieldRequest,
+) -> Result<CustomFieldDefinition, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    CustomFieldService::create_field(pool.inner(), &viewer, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_custom_field(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateCustomFieldRequest,
+) -> Result<CustomFieldDefinition, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    CustomFieldService::update_field(pool.inner(), &viewer, &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_custom_field(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    CustomFieldService::delete_field(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_custom_data(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<StudentCustomData, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    CustomFieldService::get_student_data(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_student_attributes(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    values: HashMap<String, Option<String>>,
+) -> Result<Vec<StudentAttribute>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    CustomFieldService::set_student_attributes(pool.inner(), &student_id, values)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_student_tags(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    tags: Vec<String>,
+) -> Result<Vec<String>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    CustomFieldService::set_student_tags(pool.inner(), &student_id, tags)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_tag_counts(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<Vec<TagCount>, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    CustomFieldService::get_tag_counts(pool.inner(), &institution_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_custom_field_values(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    content: String,
+) -> Result<CustomFieldImportResult, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    CustomFieldService::import_values(pool.inner(), &institution_id, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,20 @@
+use rehber360_core::{
+    models::DashboardStats,
+    services::dashboard_service::DashboardService,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::session_institution;
+
+/// Early-warning statistics of the session's institution for the analytics dashboard
+#[tauri::command]
+pub async fn get_dashboard_stats(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<DashboardStats, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    DashboardService::get_stats(pool.inner(), Some(&institution_id))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,90 @@
+use rehber360_core::{
+    models::{AttachDocumentRequest, StudentDocument},
+    repositories::DocumentRepository,
+    services::document_service::DocumentService,
+};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
+use super::file::get_uploads_dir;
+use super::institution::student_session;
+
+#[tauri::command]
+pub async fn get_student_documents(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    category: Option<String>,
+) -> Result<Vec<StudentDocument>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    match category {
+        Some(category) => DocumentRepository::get_by_student_and_category(pool.inner(), &student_id, &category).await,
+        None => DocumentRepository::get_by_student(pool.inner(), &student_id).await,
+    }
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_document(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<StudentDocument, String> {
+    accessible_document(pool.inner(), &token, &id).await
+}
+
+#[tauri::command]
+pub async fn attach_student_document(
+    app: AppHandle,
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: AttachDocumentRequest,
+    file_data: Vec<u8>,
+) -> Result<StudentDocument, String> {
+    student_session(pool.inner(), &token, &request.studentId).await?;
+
+    let uploads_dir = get_uploads_dir(&app)?;
+    DocumentService::attach(pool.inner(), &uploads_dir, request, file_data)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn detach_student_document(
+    app: AppHandle,
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    accessible_document(pool.inner(), &token, &id).await?;
+
+    let uploads_dir = get_uploads_dir(&app)?;
+    DocumentService::detach(pool.inner(), &uploads_dir, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn download_student_document(
+    app: AppHandle,
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<Vec<u8>, String> {
+    accessible_document(pool.inner(), &token, &id).await?;
+
+    let uploads_dir = get_uploads_dir(&app)?;
+    DocumentService::read(pool.inner(), &uploads_dir, &id)
+        .await
+        .map(|(_, data)| data)
+        .map_err(|e| e.to_string())
+}
+
+/// The document, if the session may work with its student
+async fn accessible_document(pool: &SqlitePool, token: &str, id: &str) -> Result<StudentDocument, String> {
+    let document = DocumentRepository::get_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &document.studentId).await?;
+    Ok(document)
+}
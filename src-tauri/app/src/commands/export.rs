@@ -1,7 +1,14 @@
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 use tauri::State;
-use rehber360_core::models::{Student, User};
+use rehber360_core::error::AppError;
+use rehber360_core::models::{CustomFieldDefinition, Institution, Student, StudentAttributeValue, StudentTag, User};
+use rehber360_core::repositories::{CustomFieldRepository, InstitutionRepository, UserRepository};
+use rehber360_core::services::class_service::ClassService;
+use rehber360_core::services::custom_field_service::STUDENT_EXPORT_COLUMNS;
+use rehber360_core::services::institution_service::InstitutionService;
+use super::institution::session_institution;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseExport {
@@ -12,6 +19,14 @@ pub struct DatabaseExport {
     pub counseling_sessions: Vec<CounselingSessionExport>,
     pub academic_records: Vec<AcademicRecordExport>,
     pub surveys: Vec<SurveyExport>,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomFieldDefinition>,
+    #[serde(default)]
+    pub student_attributes: Vec<StudentAttributeValue>,
+    #[serde(default)]
+    pub student_tags: Vec<StudentTag>,
+    #[serde(default)]
+    pub institutions: Vec<Institution>,
 }
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
@@ -70,12 +85,18 @@ pub struct ImportResult {
     pub users_imported: usize,
     pub sessions_imported: usize,
     pub records_imported: usize,
+    pub attributes_imported: usize,
+    pub tags_imported: usize,
 }
 
+/// Back up every institution's data; district admins only
 #[tauri::command]
 pub async fn export_database_json(
     pool: State<'_, SqlitePool>,
+    token: String,
 ) -> Result<String, String> {
+    district_admin_session(pool.inner(), &token).await?;
+
     let students = sqlx::query_as::<_, Student>("SELECT * FROM students")
         .fetch_all(pool.inner())
         .await
@@ -111,6 +132,22 @@ pub async fn export_database_json(
         .await
         .map_err(|e| format!("Failed to fetch surveys: {}", e))?;
 
+    let custom_fields = CustomFieldRepository::get_fields(pool.inner(), true)
+        .await
+        .map_err(|e| format!("Failed to fetch custom fields: {}", e))?;
+
+    let student_attributes = CustomFieldRepository::get_all_attribute_values(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch custom field values: {}", e))?;
+
+    let student_tags = CustomFieldRepository::get_all_tags(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch student tags: {}", e))?;
+
+    let institutions = InstitutionRepository::get_all(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch institutions: {}", e))?;
+
     let export = DatabaseExport {
         version: "2.0.0".to_string(),
         exported_at: chrono::Utc::now().to_rfc3339(),
@@ -119,17 +156,25 @@ pub async fn export_database_json(
         counseling_sessions,
         academic_records,
         surveys,
+        custom_fields,
+        student_attributes,
+        student_tags,
+        institutions,
     };
 
     serde_json::to_string_pretty(&export)
         .map_err(|e| format!("Failed to serialize data: {}", e))
 }
 
+/// Restore a backup made by `export_database_json`; district admins only
 #[tauri::command]
 pub async fn import_database_json(
     pool: State<'_, SqlitePool>,
+    token: String,
     json_data: String,
 ) -> Result<ImportResult, String> {
+    district_admin_session(pool.inner(), &token).await?;
+
     let import_data: DatabaseExport = serde_json::from_str(&json_data)
         .map_err(|e| format!("Invalid JSON format: {}", e))?;
 
@@ -148,13 +193,44 @@ pub async fn import_database_json(
     let mut users_imported = 0;
     let mut sessions_imported = 0;
     let mut records_imported = 0;
+    let mut attributes_imported = 0;
+    let mut tags_imported = 0;
+
+    // Institutions are matched by name so records land on an existing school of the same name
+    let mut institution_ids: HashMap<String, String> = HashMap::new();
+    for institution in import_data.institutions {
+        let result = sqlx::query_scalar::<_, String>(
+            "INSERT INTO institutions (id, name, code, district, isActive, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(name) DO UPDATE SET 
+                code = COALESCE(excluded.code, code), district = COALESCE(excluded.district, district),
+                updated_at = excluded.updated_at
+             RETURNING id"
+        )
+        .bind(&institution.id)
+        .bind(&institution.name)
+        .bind(&institution.code)
+        .bind(&institution.district)
+        .bind(institution.isActive)
+        .bind(&institution.created_at)
+        .bind(&institution.updated_at)
+        .fetch_one(&mut *tx)
+        .await;
+
+        if let Ok(id) = result {
+            institution_ids.insert(institution.id, id);
+        }
+    }
 
     for student in import_data.students {
+        let institution_id = student.institutionId.as_ref().and_then(|id| institution_ids.get(id));
+
         let result = sqlx::query(
             "INSERT OR REPLACE INTO students 
              (id, name, surname, email, phone, birthDate, address, class, enrollmentDate, 
-              status, avatar, parentContact, notes, gender, risk, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+              status, avatar, parentContact, notes, gender, risk, created_at, updated_at, schoolNumber,
+              institutionId)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&student.id)
         .bind(&student.name)
@@ -173,6 +249,8 @@ pub async fn import_database_json(
         .bind(&student.risk)
         .bind(&student.created_at)
         .bind(&student.updated_at)
+        .bind(&student.schoolNumber)
+        .bind(institution_id)
         .execute(&mut *tx)
         .await;
 
@@ -249,20 +327,97 @@ pub async fn import_database_json(
         }
     }
 
+    // Fields are matched by key so values land on an existing field of the same name
+    let mut field_ids: HashMap<String, String> = HashMap::new();
+    for field in import_data.custom_fields {
+        let result = sqlx::query_scalar::<_, String>(
+            "INSERT INTO custom_field_definitions 
+             (id, key, label, fieldType, options, sortOrder, isActive, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(key) DO UPDATE SET 
+                label = excluded.label, options = excluded.options, sortOrder = excluded.sortOrder,
+                isActive = excluded.isActive, updated_at = excluded.updated_at
+             RETURNING id"
+        )
+        .bind(&field.id)
+        .bind(&field.key)
+        .bind(&field.label)
+        .bind(&field.fieldType)
+        .bind(&field.options)
+        .bind(field.sortOrder)
+        .bind(field.isActive)
+        .bind(&field.created_at)
+        .bind(&field.updated_at)
+        .fetch_one(&mut *tx)
+        .await;
+
+        if let Ok(id) = result {
+            field_ids.insert(field.id, id);
+        }
+    }
+
+    for attribute in import_data.student_attributes {
+        let Some(field_id) = field_ids.get(&attribute.fieldId) else {
+            continue;
+        };
+
+        let result = sqlx::query(
+            "INSERT OR REPLACE INTO student_attributes (studentId, fieldId, value, updated_at)
+             VALUES (?, ?, ?, ?)"
+        )
+        .bind(&attribute.studentId)
+        .bind(field_id)
+        .bind(&attribute.value)
+        .bind(&attribute.updated_at)
+        .execute(&mut *tx)
+        .await;
+
+        if result.is_ok() {
+            attributes_imported += 1;
+        }
+    }
+
+    for tag in import_data.student_tags {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO student_tags (studentId, tag, created_at) VALUES (?, ?, ?)"
+        )
+        .bind(&tag.studentId)
+        .bind(&tag.tag)
+        .bind(&tag.created_at)
+        .execute(&mut *tx)
+        .await;
+
+        if matches!(result, Ok(r) if r.rows_affected() > 0) {
+            tags_imported += 1;
+        }
+    }
+
     tx.commit()
         .await
         .map_err(|e| format!("Failed to commit transaction: {}", e))?;
 
+    // Records without a known institution go to the default one
+    InstitutionService::migrate_institutions(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to assign institutions: {}", e))?;
+
+    // Classes aren't part of the export; relink imported students by class name
+    ClassService::migrate_student_classes(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to link student classes: {}", e))?;
+
     Ok(ImportResult {
         success: true,
         message: format!(
-            "Import completed successfully. {} students, {} users, {} sessions, {} academic records imported",
-            students_imported, users_imported, sessions_imported, records_imported
+            "Import completed successfully. {} students, {} users, {} sessions, {} academic records, {} custom field values, {} tags imported",
+            students_imported, users_imported, sessions_imported, records_imported, attributes_imported, tags_imported
         ),
         students_imported,
         users_imported,
         sessions_imported,
         records_imported,
+        attributes_imported,
+        tags_imported,
     })
 }
 
@@ -281,21 +436,53 @@ pub struct StudentExportRow {
     pub cinsiyet: String,
 }
 
+/// Students of the session's institution with their custom fields and tags
 #[tauri::command]
 pub async fn export_students_csv(
     pool: State<'_, SqlitePool>,
+    token: String,
 ) -> Result<String, String> {
-    let students = sqlx::query_as::<_, Student>("SELECT * FROM students ORDER BY class, surname, name")
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    let students = sqlx::query_as::<_, Student>(
+        "SELECT * FROM students WHERE institutionId = ? ORDER BY class, surname, name"
+    )
+        .bind(&institution_id)
         .fetch_all(pool.inner())
         .await
         .map_err(|e| format!("Failed to fetch students: {}", e))?;
 
-    let mut csv = String::from("ID,Ad,Soyad,E-posta,Telefon,Doğum Tarihi,Sınıf,Kayıt Tarihi,Durum,Risk Seviyesi,Cinsiyet\n");
+    let fields = CustomFieldRepository::get_fields(pool.inner(), false)
+        .await
+        .map_err(|e| format!("Failed to fetch custom fields: {}", e))?;
+
+    let mut values: HashMap<(String, String), String> = HashMap::new();
+    for attribute in CustomFieldRepository::get_all_attribute_values(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch custom field values: {}", e))?
+    {
+        values.insert((attribute.studentId, attribute.fieldId), attribute.value);
+    }
+
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    for tag in CustomFieldRepository::get_all_tags(pool.inner())
+        .await
+        .map_err(|e| format!("Failed to fetch student tags: {}", e))?
+    {
+        tags.entry(tag.studentId).or_default().push(tag.tag);
+    }
+
+    let mut csv = STUDENT_EXPORT_COLUMNS.join(",");
+    for field in &fields {
+        csv.push(',');
+        csv.push_str(&csv_cell(&field.label));
+    }
+    csv.push_str(",Etiketler\n");
 
     for student in students {
-        csv.push_str(&format!(
-            "{},{},{},{},{},{},{},{},{},{},{}\n",
-            student.id,
+        let mut row = vec![
+            student.id.clone(),
+            student.schoolNumber.unwrap_or_default(),
             student.name,
             student.surname,
             student.email.unwrap_or_default(),
@@ -305,33 +492,75 @@ pub async fn export_students_csv(
             student.enrollmentDate,
             student.status,
             student.risk,
-            student.gender
-        ));
+            student.gender,
+        ];
+        for field in &fields {
+            row.push(values.remove(&(student.id.clone(), field.id.clone())).unwrap_or_default());
+        }
+        row.push(tags.remove(&student.id).unwrap_or_default().join(";"));
+
+        csv.push_str(&row.iter().map(|value| csv_cell(value)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
     }
 
     Ok(csv)
 }
 
+/// Signed-in user of a session, if they are a district admin
+async fn district_admin_session(pool: &SqlitePool, token: &str) -> Result<User, String> {
+    let viewer = UserRepository::get_session_by_token(pool, token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !viewer.isDistrictAdmin {
+        return Err(AppError::Unauthorized.to_string());
+    }
+    Ok(viewer)
+}
+
+/// Quote a CSV cell when it contains a delimiter, quote or line break
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 #[tauri::command]
 pub async fn get_export_statistics(
     pool: State<'_, SqlitePool>,
+    token: String,
 ) -> Result<ExportStatistics, String> {
-    let student_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM students")
+    // The numbers cover only the session's institution
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    let student_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM students WHERE institutionId = ?")
+        .bind(&institution_id)
         .fetch_one(pool.inner())
         .await
         .map_err(|e| format!("Failed to count students: {}", e))?;
 
-    let user_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+    let user_count: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM users u 
+         WHERE EXISTS (SELECT 1 FROM user_institutions ui WHERE ui.userId = u.id AND ui.institutionId = ?)"
+    )
+        .bind(&institution_id)
         .fetch_one(pool.inner())
         .await
         .map_err(|e| format!("Failed to count users: {}", e))?;
 
-    let session_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM counseling_sessions")
+    let session_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM counseling_sessions WHERE institutionId = ?")
+        .bind(&institution_id)
         .fetch_one(pool.inner())
         .await
         .map_err(|e| format!("Failed to count sessions: {}", e))?;
 
-    let record_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM academic_records")
+    let record_count: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM academic_records r 
+         WHERE EXISTS (SELECT 1 FROM students s WHERE s.id = r.studentId AND s.institutionId = ?)"
+    )
+        .bind(&institution_id)
         .fetch_one(pool.inner())
         .await
         .map_err(|e| format!("Failed to count records: {}", e))?;
@@ -0,0 +1,191 @@
+use rehber360_core::{
+    error::AppError,
+    models::{Family, FamilyUnit, SiblingSuggestion, Student, CreateFamilyRequest, User},
+    repositories::{FamilyRepository, UserRepository},
+    services::family_service::FamilyService,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::{accessible_students, can_access_any, student_session};
+
+#[tauri::command]
+pub async fn create_family(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateFamilyRequest,
+) -> Result<FamilyUnit, String> {
+    for student_id in &request.studentIds {
+        student_session(pool.inner(), &token, student_id).await?;
+    }
+
+    FamilyService::create_family(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_family_unit(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    family_id: String,
+) -> Result<FamilyUnit, String> {
+    family_session(pool.inner(), &token, &family_id).await?;
+
+    FamilyService::get_family_unit(pool.inner(), &family_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_family_unit(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Option<FamilyUnit>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    FamilyService::get_student_family_unit(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_siblings(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<Student>, String> {
+    let viewer = student_session(pool.inner(), &token, &student_id).await?;
+    let siblings = FamilyService::get_siblings(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    accessible_students(pool.inner(), &viewer, siblings).await
+}
+
+#[tauri::command]
+pub async fn update_family(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    name: String,
+    notes: Option<String>,
+) -> Result<Family, String> {
+    family_session(pool.inner(), &token, &id).await?;
+
+    FamilyRepository::update(pool.inner(), &id, &name, notes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_family(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    family_session(pool.inner(), &token, &id).await?;
+
+    FamilyRepository::delete(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_family_member(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    family_id: String,
+    student_id: String,
+) -> Result<FamilyUnit, String> {
+    family_session(pool.inner(), &token, &family_id).await?;
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    FamilyService::add_member(pool.inner(), &family_id, &student_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    FamilyService::get_family_unit(pool.inner(), &family_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_family_member(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    family_id: String,
+    student_id: String,
+) -> Result<(), String> {
+    family_session(pool.inner(), &token, &family_id).await?;
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    FamilyService::remove_member(pool.inner(), &family_id, &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn link_siblings(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    sibling_id: String,
+) -> Result<FamilyUnit, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+    student_session(pool.inner(), &token, &sibling_id).await?;
+
+    FamilyService::link_siblings(pool.inner(), &student_id, &sibling_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn suggest_siblings(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<SiblingSuggestion>, String> {
+    let viewer = student_session(pool.inner(), &token, &student_id).await?;
+    let suggestions = FamilyService::suggest_siblings(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut visible = Vec::with_capacity(suggestions.len());
+    for suggestion in suggestions {
+        if can_access_any(pool.inner(), &viewer, std::slice::from_ref(&suggestion.student)).await? {
+            visible.push(suggestion);
+        }
+    }
+    Ok(visible)
+}
+
+#[tauri::command]
+pub async fn dismiss_sibling_suggestion(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    other_student_id: String,
+    dismissed_by: Option<String>,
+) -> Result<(), String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    FamilyService::dismiss_suggestion(pool.inner(), &student_id, &other_student_id, dismissed_by)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Signed-in user of a session, if they may work with one of the family's members
+async fn family_session(pool: &SqlitePool, token: &str, family_id: &str) -> Result<User, String> {
+    let viewer = UserRepository::get_session_by_token(pool, token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let members = FamilyRepository::get_members(pool, family_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !can_access_any(pool, &viewer, &members).await? {
+        return Err(AppError::Unauthorized.to_string());
+    }
+    Ok(viewer)
+}
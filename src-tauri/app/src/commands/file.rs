@@ -1,4 +1,11 @@
-use tauri::AppHandle;
+use rehber360_core::{
+    error::AppError,
+    models::{AttachDocumentRequest, StudentDocument},
+    repositories::DocumentRepository,
+    services::document_service::DocumentService,
+};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, State};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 use serde::{Deserialize, Serialize};
@@ -47,6 +54,15 @@ fn generate_safe_filename(extension: Option<&str>) -> String {
     }
 }
 
+/// The student document with this id; other ids name files directly in the uploads directory
+async fn find_document(pool: &SqlitePool, file_id: &str) -> Result<Option<StudentDocument>, String> {
+    match DocumentRepository::get_by_id(pool, file_id).await {
+        Ok(document) => Ok(Some(document)),
+        Err(AppError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileInfo {
     pub id: String,
@@ -59,11 +75,30 @@ pub struct FileInfo {
 #[tauri::command]
 pub async fn upload_file(
     app: AppHandle,
+    pool: State<'_, SqlitePool>,
     file_data: Vec<u8>,
     metadata: FileMetadata,
 ) -> Result<String, String> {
     // Validate and sanitize filename
     let safe_filename = validate_and_sanitize_filename(&metadata.name)?;
+
+    // Student files go through the documents subsystem so their metadata is kept
+    if let Some(student_id) = metadata.student_id {
+        let uploads_dir = get_uploads_dir(&app)?;
+        let request = AttachDocumentRequest {
+            studentId: student_id,
+            name: safe_filename,
+            mime_type: Some(metadata.file_type),
+            category: None,
+            uploadedBy: None,
+        };
+
+        let document = DocumentService::attach(pool.inner(), &uploads_dir, request, file_data)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        return Ok(document.id);
+    }
     
     // Generate unique safe filename (ignore user-provided ID for security)
     let extension = safe_filename.split('.').last();
@@ -90,12 +125,20 @@ pub async fn upload_file(
 #[tauri::command]
 pub async fn download_file(
     app: AppHandle,
+    pool: State<'_, SqlitePool>,
     file_id: String,
 ) -> Result<Vec<u8>, String> {
     // Validate filename
     validate_and_sanitize_filename(&file_id)?;
     
     let uploads_dir = get_uploads_dir(&app)?;
+    if find_document(pool.inner(), &file_id).await?.is_some() {
+        return DocumentService::read(pool.inner(), &uploads_dir, &file_id)
+            .await
+            .map(|(_, data)| data)
+            .map_err(|e| e.to_string());
+    }
+
     let file_path = uploads_dir.join(&file_id);
 
     // Security check: ensure path is within uploads directory
@@ -115,12 +158,19 @@ pub async fn download_file(
 #[tauri::command]
 pub async fn delete_file(
     app: AppHandle,
+    pool: State<'_, SqlitePool>,
     file_id: String,
 ) -> Result<(), String> {
     // Validate filename
     validate_and_sanitize_filename(&file_id)?;
     
     let uploads_dir = get_uploads_dir(&app)?;
+    if find_document(pool.inner(), &file_id).await?.is_some() {
+        return DocumentService::detach(pool.inner(), &uploads_dir, &file_id)
+            .await
+            .map_err(|e| e.to_string());
+    }
+
     let file_path = uploads_dir.join(&file_id);
 
     // Security check: ensure path is within uploads directory
@@ -140,9 +190,31 @@ pub async fn delete_file(
 #[tauri::command]
 pub async fn get_file_list(
     app: AppHandle,
+    pool: State<'_, SqlitePool>,
     student_id: Option<String>,
 ) -> Result<Vec<FileInfo>, String> {
     let uploads_dir = get_uploads_dir(&app)?;
+
+    if let Some(student_id) = student_id {
+        let documents = DocumentRepository::get_by_student(pool.inner(), &student_id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        return Ok(documents
+            .into_iter()
+            .map(|doc| FileInfo {
+                path: doc
+                    .storagePath
+                    .as_ref()
+                    .map(|p| uploads_dir.join(p).to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                size: doc.size.unwrap_or(0) as u64,
+                id: doc.id,
+                name: doc.name,
+                created_at: doc.created_at,
+            })
+            .collect());
+    }
     
     if !uploads_dir.exists() {
         return Ok(Vec::new());
@@ -176,13 +248,19 @@ pub async fn get_file_list(
 #[tauri::command]
 pub async fn open_file_in_explorer(
     app: AppHandle,
+    pool: State<'_, SqlitePool>,
     file_id: String,
 ) -> Result<(), String> {
     // Validate filename
     validate_and_sanitize_filename(&file_id)?;
     
     let uploads_dir = get_uploads_dir(&app)?;
-    let file_path = uploads_dir.join(&file_id);
+    let file_path = match find_document(pool.inner(), &file_id).await? {
+        Some(_) => DocumentService::file_path(pool.inner(), &uploads_dir, &file_id)
+            .await
+            .map_err(|e| e.to_string())?,
+        None => uploads_dir.join(&file_id),
+    };
 
     // Security check: ensure path is within uploads directory
     if !file_path.starts_with(&uploads_dir) {
@@ -223,7 +301,7 @@ pub async fn open_file_in_explorer(
 }
 
 // Helper function to get uploads directory
-fn get_uploads_dir(app: &AppHandle) -> Result<PathBuf, String> {
+pub(crate) fn get_uploads_dir(app: &AppHandle) -> Result<PathBuf, String> {
     let app_data_dir = app
         .path()
         .app_data_dir()
@@ -0,0 +1,168 @@
+use rehber360_core::{
+    error::AppError,
+    models::{
+        Guardian, StudentGuardian, Student, CreateGuardianRequest, UpdateGuardianRequest, LinkGuardianRequest, User,
+    },
+    repositories::{GuardianRepository, UserRepository},
+    services::guardian_service::{GuardianService, ParsedGuardian},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::{accessible_students, can_access_any, student_session};
+
+#[tauri::command]
+pub async fn create_guardian(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateGuardianRequest,
+) -> Result<Guardian, String> {
+    UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    GuardianRepository::create(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_guardian(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<Guardian, String> {
+    guardian_session(pool.inner(), &token, &id).await?;
+
+    GuardianRepository::get_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn search_guardians(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    query: String,
+) -> Result<Vec<Guardian>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let guardians = GuardianRepository::search(pool.inner(), &query)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut visible = Vec::with_capacity(guardians.len());
+    for guardian in guardians {
+        let students = GuardianRepository::get_students(pool.inner(), &guardian.id)
+            .await
+            .map_err(|e| e.to_string())?;
+        if can_access_any(pool.inner(), &viewer, &students).await? {
+            visible.push(guardian);
+        }
+    }
+    Ok(visible)
+}
+
+#[tauri::command]
+pub async fn update_guardian(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateGuardianRequest,
+) -> Result<Guardian, String> {
+    guardian_session(pool.inner(), &token, &id).await?;
+
+    GuardianRepository::update(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_guardian(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    guardian_session(pool.inner(), &token, &id).await?;
+
+    GuardianRepository::delete(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_guardians(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<StudentGuardian>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    GuardianRepository::get_by_student(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_guardian_students(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    guardian_id: String,
+) -> Result<Vec<Student>, String> {
+    let viewer = guardian_session(pool.inner(), &token, &guardian_id).await?;
+    let students = GuardianRepository::get_students(pool.inner(), &guardian_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    accessible_students(pool.inner(), &viewer, students).await
+}
+
+#[tauri::command]
+pub async fn link_guardian_to_student(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: LinkGuardianRequest,
+) -> Result<StudentGuardian, String> {
+    student_session(pool.inner(), &token, &request.studentId).await?;
+    guardian_session(pool.inner(), &token, &request.guardianId).await?;
+
+    GuardianRepository::link_student(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unlink_guardian_from_student(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    guardian_id: String,
+) -> Result<(), String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    GuardianRepository::unlink_student(pool.inner(), &student_id, &guardian_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn parse_parent_contact(
+    parent_contact: String,
+) -> Result<Vec<ParsedGuardian>, String> {
+    Ok(GuardianService::parse_parent_contact(&parent_contact))
+}
+
+/// Signed-in user of a session, if they may work with one of the guardian's students
+async fn guardian_session(pool: &SqlitePool, token: &str, guardian_id: &str) -> Result<User, String> {
+    let viewer = UserRepository::get_session_by_token(pool, token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let students = GuardianRepository::get_students(pool, guardian_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !can_access_any(pool, &viewer, &students).await? {
+        return Err(AppError::Unauthorized.to_string());
+    }
+    Ok(viewer)
+}
@@ -0,0 +1,220 @@
+use rehber360_core::{
+    error::AppError,
+    models::{CreateInstitutionRequest, Institution, InstitutionSummary, Student, UpdateInstitutionRequest, User},
+    repositories::UserRepository,
+    services::institution_service::{accessible_student, active_institution, can_access, InstitutionService},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Institutions the signed-in user can switch between
+#[tauri::command]
+pub async fn get_institutions(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<Vec<Institution>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::get_institutions(pool.inner(), &viewer)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_institution(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateInstitutionRequest,
+) -> Result<Institution, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::create_institution(pool.inner(), &viewer, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_institution(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateInstitutionRequest,
+) -> Result<Institution, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::update_institution(pool.inner(), &viewer, &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Switch the session to another institution; returns the user with the new active institution
+#[tauri::command]
+pub async fn switch_institution(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    institution_id: String,
+) -> Result<User, String> {
+    InstitutionService::switch_institution(pool.inner(), &token, &institution_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_institution_users(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    institution_id: String,
+) -> Result<Vec<User>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::get_members(pool.inner(), &viewer, &institution_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_institution_user(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    institution_id: String,
+    user_id: String,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::add_member(pool.inner(), &viewer, &institution_id, &user_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_institution_user(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    institution_id: String,
+    user_id: String,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::remove_member(pool.inner(), &viewer, &institution_id, &user_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_district_admin(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    user_id: String,
+    is_district_admin: bool,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::set_district_admin(pool.inner(), &viewer, &user_id, is_district_admin)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Headline numbers for the session's institution
+#[tauri::command]
+pub async fn get_institution_summary(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<InstitutionSummary, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::get_summary(pool.inner(), &viewer)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Side-by-side numbers of every institution, for district admins
+#[tauri::command]
+pub async fn get_district_report(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<Vec<InstitutionSummary>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    InstitutionService::get_district_report(pool.inner(), &viewer)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Active institution of a session
+pub(crate) async fn session_institution(pool: &SqlitePool, token: &str) -> Result<String, String> {
+    let viewer = UserRepository::get_session_by_token(pool, token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    active_institution(&viewer).map_err(|e| e.to_string())
+}
+
+/// Signed-in user of a session, if they may work with the student
+pub(crate) async fn student_session(pool: &SqlitePool, token: &str, student_id: &str) -> Result<User, String> {
+    let viewer = UserRepository::get_session_by_token(pool, token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    accessible_student(pool, &viewer, student_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(viewer)
+}
+
+/// Whether the viewer may work with at least one of the students; records shared by no student are open
+pub(crate) async fn can_access_any(pool: &SqlitePool, viewer: &User, students: &[Student]) -> Result<bool, String> {
+    if students.is_empty() {
+        return Ok(true);
+    }
+    for student in students {
+        let allowed = match &student.institutionId {
+            Some(institution_id) => can_access(pool, viewer, institution_id).await.map_err(|e| e.to_string())?,
+            None => true,
+        };
+        if allowed {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The students the viewer may work with
+pub(crate) async fn accessible_students(pool: &SqlitePool, viewer: &User, students: Vec<Student>) -> Result<Vec<Student>, String> {
+    let mut accessible = Vec::with_capacity(students.len());
+    for student in students {
+        if can_access_any(pool, viewer, std::slice::from_ref(&student)).await? {
+            accessible.push(student);
+        }
+    }
+    Ok(accessible)
+}
+
+/// Signed-in user of a session, if they are an admin
+pub(crate) async fn admin_session(pool: &SqlitePool, token: &str) -> Result<User, String> {
+    let viewer = UserRepository::get_session_by_token(pool, token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if viewer.role != "admin" && !viewer.isDistrictAdmin {
+        return Err(AppError::Unauthorized.to_string());
+    }
+    Ok(viewer)
+}
@@ -0,0 +1,206 @@
+use rehber360_core::{
+    models::{
+        Intervention, InterventionDetail, InterventionProgressNote, NotificationLog,
+        CreateInterventionRequest, UpdateInterventionRequest, ChangeInterventionStatusRequest,
+        AddInterventionNoteRequest, User,
+    },
+    repositories::InterventionRepository,
+    services::intervention_service::{InterventionService, DEFAULT_REMINDER_DAYS},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::{admin_session, session_institution, student_session};
+
+#[tauri::command]
+pub async fn create_intervention(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateInterventionRequest,
+) -> Result<InterventionDetail, String> {
+    student_session(pool.inner(), &token, &request.studentId).await?;
+
+    InterventionService::create(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_intervention(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<InterventionDetail, String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionService::get_detail(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_interventions(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<Intervention>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    InterventionRepository::get_by_student(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_interventions(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    status: Option<String>,
+    owner_id: Option<String>,
+) -> Result<Vec<Intervention>, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    InterventionRepository::get_filtered(pool.inner(), &institution_id, status.as_deref(), owner_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_intervention(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateInterventionRequest,
+) -> Result<Intervention, String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionService::update(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn change_intervention_status(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: ChangeInterventionStatusRequest,
+) -> Result<Intervention, String> {
+    let viewer = intervention_session(pool.inner(), &token, &id).await?;
+
+    InterventionService::change_status(pool.inner(), &viewer, &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_intervention(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionRepository::delete(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_intervention_note(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: AddInterventionNoteRequest,
+) -> Result<InterventionProgressNote, String> {
+    let viewer = intervention_session(pool.inner(), &token, &id).await?;
+
+    InterventionService::add_note(pool.inner(), &viewer, &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn link_intervention_session(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    session_id: String,
+) -> Result<(), String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionService::link_session(pool.inner(), &id, &session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unlink_intervention_session(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    session_id: String,
+) -> Result<(), String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionRepository::unlink_session(pool.inner(), &id, &session_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn link_intervention_incident(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    incident_id: String,
+) -> Result<(), String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionService::link_incident(pool.inner(), &id, &incident_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unlink_intervention_incident(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    incident_id: String,
+) -> Result<(), String> {
+    accessible_intervention(pool.inner(), &token, &id).await?;
+
+    InterventionRepository::unlink_incident(pool.inner(), &id, &incident_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn send_intervention_reminders(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    days_ahead: Option<i64>,
+) -> Result<Vec<NotificationLog>, String> {
+    admin_session(pool.inner(), &token).await?;
+
+    InterventionService::send_due_reminders(pool.inner(), days_ahead.unwrap_or(DEFAULT_REMINDER_DAYS))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The intervention, if the session may work with its student
+async fn accessible_intervention(pool: &SqlitePool, token: &str, id: &str) -> Result<Intervention, String> {
+    let intervention = InterventionRepository::get_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &intervention.studentId).await?;
+    Ok(intervention)
+}
+
+/// Signed-in user of a session, if they may work with the intervention's student
+async fn intervention_session(pool: &SqlitePool, token: &str, id: &str) -> Result<User, String> {
+    let intervention = InterventionRepository::get_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &intervention.studentId).await
+}
@@ -8,6 +8,21 @@ pub mod notification;
 pub mod settings;
 pub mod file;
 pub mod export;
+pub mod document;
+pub mod guardian;
+pub mod family;
+pub mod attendance;
+pub mod intervention;
+pub mod timeline;
+pub mod bep;
+pub mod custom_field;
+pub mod school_class;
+pub mod institution;
+pub mod risk;
+pub mod dashboard;
+pub mod curriculum;
+pub mod study;
+pub mod placement;
 
 pub use auth::*;
 pub use student::*;
@@ -19,3 +34,18 @@ pub use notification::*;
 pub use settings::*;
 pub use file::*;
 pub use export::*;
+pub use document::*;
+pub use guardian::*;
+pub use family::*;
+pub use attendance::*;
+pub use intervention::*;
+pub use timeline::*;
+pub use bep::*;
+pub use custom_field::*;
+pub use school_class::*;
+pub use institution::*;
+pub use risk::*;
+pub use dashboard::*;
+pub use curriculum::*;
+pub use study::*;
+pub use placement::*;
@@ -0,0 +1,60 @@
+use rehber360_core::{
+    models::{
+        ImportPlacementTableRequest, PlacementEstimate, PlacementEstimateRequest, PlacementImportResult, PlacementTable,
+    },
+    repositories::UserRepository,
+    services::placement_service::PlacementService,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_placement_tables(
+    pool: State<'_, SqlitePool>,
+) -> Result<Vec<PlacementTable>, String> {
+    PlacementService::get_tables(pool.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Import an ÖSYM or MEB base-score CSV for a year, replacing that year's table
+#[tauri::command]
+pub async fn import_placement_table(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: ImportPlacementTableRequest,
+) -> Result<PlacementImportResult, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    PlacementService::import_table(pool.inner(), &viewer, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_placement_table(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    PlacementService::delete_table(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Reachable, borderline and reach programs for a student's latest YKS or LGS scores
+#[tauri::command]
+pub async fn estimate_student_placement(
+    pool: State<'_, SqlitePool>,
+    request: PlacementEstimateRequest,
+) -> Result<PlacementEstimate, String> {
+    PlacementService::estimate(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,283 @@
+use rehber360_core::{
+    models::{
+        CreateRiskRuleSetRequest, NotificationLog, RiskAlert, RiskAlertSettings, RiskAssessment, RiskRulePreview,
+        RiskRuleSet, RiskRules, SetRiskOverrideRequest,
+    },
+    repositories::UserRepository,
+    services::{risk_alert_service::RiskAlertService, risk_service::RiskService},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::{admin_session, session_institution, student_session};
+
+/// The student's risk score with per-factor contributions and any override
+#[tauri::command]
+pub async fn get_student_risk(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<RiskAssessment, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    RiskService::get_assessment(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn recalculate_student_risk(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<RiskAssessment, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    RiskService::recalculate_student(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Recalculate every active student; returns the number recalculated
+#[tauri::command]
+pub async fn recalculate_all_risk(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<usize, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::recalculate_all(pool.inner(), &viewer)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_risk_override(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    request: SetRiskOverrideRequest,
+) -> Result<RiskAssessment, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::set_override(pool.inner(), &viewer, &student_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn clear_risk_override(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<RiskAssessment, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::clear_override(pool.inner(), &viewer, &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Set the answers of a survey question that count as a risk flag
+#[tauri::command]
+pub async fn set_survey_question_risk_answers(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    question_id: String,
+    answers: Vec<String>,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::set_question_risk_answers(pool.inner(), &viewer, &question_id, answers)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Saved risk rule versions, newest first
+#[tauri::command]
+pub async fn get_risk_rule_sets(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<Vec<RiskRuleSet>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::get_rule_sets(pool.inner(), &viewer)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rules in use in the session's institution, as a starting point for a new version
+#[tauri::command]
+pub async fn get_active_risk_rules(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<RiskRules, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
+
+    RiskService::active_rules(pool.inner(), Some(&institution_id))
+        .await
+        .map(|(rules, _)| rules)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_risk_rule_set(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateRiskRuleSetRequest,
+) -> Result<RiskRuleSet, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::create_rule_set(pool.inner(), &viewer, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// What would change if the rule set were activated
+#[tauri::command]
+pub async fn preview_risk_rule_set(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<RiskRulePreview, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::preview_rule_set(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Activate a rule set, or the built-in rules without an id; returns the number of students recalculated
+#[tauri::command]
+pub async fn activate_risk_rule_set(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: Option<String>,
+) -> Result<usize, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::activate_rule_set(pool.inner(), &viewer, id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_risk_rule_set(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskService::delete_rule_set(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Risk alerts of the session's institution, optionally by status or student
+#[tauri::command]
+pub async fn get_risk_alerts(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    status: Option<String>,
+    student_id: Option<String>,
+) -> Result<Vec<RiskAlert>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskAlertService::get_alerts(pool.inner(), &viewer, status.as_deref(), student_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn acknowledge_risk_alert(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<RiskAlert, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskAlertService::acknowledge(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Escalate overdue alerts and send notifications held back by quiet hours
+#[tauri::command]
+pub async fn process_risk_alerts(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<Vec<NotificationLog>, String> {
+    admin_session(pool.inner(), &token).await?;
+
+    RiskAlertService::process_alerts(pool.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_risk_alert_settings(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<RiskAlertSettings, String> {
+    UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskAlertService::get_settings(pool.inner())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_risk_alert_settings(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    settings: RiskAlertSettings,
+) -> Result<RiskAlertSettings, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    RiskAlertService::update_settings(pool.inner(), &viewer, settings)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Recalculate a student's risk after their data changed.
+///
+/// A failed recalculation is logged rather than failing the change itself;
+/// the scheduler picks the student up again.
+pub(crate) async fn refresh_student_risk(pool: &SqlitePool, student_id: &str) {
+    if let Err(e) = RiskService::recalculate_student(pool, student_id).await {
+        log::warn!("Failed to recalculate risk of student {}: {}", student_id, e);
+    }
+}
+
+/// Recalculate the risk of a counseling session's students after its follow-ups changed
+pub(crate) async fn refresh_session_risk(pool: &SqlitePool, session_id: &str) {
+    if let Err(e) = RiskService::recalculate_session_students(pool, session_id).await {
+        log::warn!("Failed to recalculate risk of session {} students: {}", session_id, e);
+    }
+}
@@ -0,0 +1,132 @@
+use rehber360_core::{
+    models::{ClassExamResultTable, ClassOverview, CreateClassRequest, SchoolClass, Student, UpdateClassRequest},
+    repositories::{ClassRepository, UserRepository},
+    services::{class_service::ClassService, exam_ranking_service::ExamRankingService},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_classes(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    school_year: Option<String>,
+) -> Result<Vec<SchoolClass>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassService::get_classes(pool.inner(), &viewer, school_year.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Classes the signed-in user is homeroom teacher or counselor of
+#[tauri::command]
+pub async fn get_my_classes(
+    pool: State<'_, SqlitePool>,
+    token: String,
+) -> Result<Vec<SchoolClass>, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassRepository::get_by_staff(pool.inner(), &viewer.id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_class_overview(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    class_id: String,
+) -> Result<ClassOverview, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassService::get_overview(pool.inner(), &viewer, &class_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The class's ranked results in one exam with class and school averages
+#[tauri::command]
+pub async fn get_class_exam_results(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    class_id: String,
+    exam_name: String,
+    exam_date: String,
+) -> Result<ClassExamResultTable, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ExamRankingService::get_class_results(pool.inner(), &viewer, &class_id, &exam_name, &exam_date)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_class(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateClassRequest,
+) -> Result<SchoolClass, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassService::create_class(pool.inner(), &viewer, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_class(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateClassRequest,
+) -> Result<SchoolClass, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassService::update_class(pool.inner(), &viewer, &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_class(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassService::delete_class(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn assign_student_class(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    class_id: String,
+) -> Result<Student, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    ClassService::assign_student(pool.inner(), &viewer, &student_id, &class_id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,48 +1,75 @@
-use rehber360_core::models::{Student, CreateStudentRequest, UpdateStudentRequest};
+use rehber360_core::models::{Student, StudentProfile, CreateStudentRequest, UpdateStudentRequest, StudentListQuery, User};
+use rehber360_core::repositories::{StudentRepository, UserRepository};
+use rehber360_core::services::class_service::ClassService;
+use rehber360_core::services::custom_field_service::CustomFieldService;
+use rehber360_core::services::institution_service::{accessible_student, active_institution};
+use rehber360_core::services::student_profile_service::StudentProfileService;
 use sqlx::SqlitePool;
 use tauri::State;
 use uuid::Uuid;
+use super::institution::session_institution;
 
 #[tauri::command]
 pub async fn get_all_students(
     pool: State<'_, SqlitePool>,
+    token: String,
+    query: Option<StudentListQuery>,
 ) -> Result<Vec<Student>, String> {
-    let students = sqlx::query_as::<_, Student>(
-        "SELECT * FROM students ORDER BY created_at DESC"
-    )
-        .fetch_all(pool.inner())
+    let mut query = query.unwrap_or_default();
+    query.institutionId = Some(session_institution(pool.inner(), &token).await?);
+
+    CustomFieldService::list_students(pool.inner(), query)
         .await
-        .map_err(|e| format!("Database error: {}", e))?;
-    
-    Ok(students)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn get_student(
     pool: State<'_, SqlitePool>,
+    token: String,
     id: String,
 ) -> Result<Student, String> {
-    let student = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE id = ?")
-        .bind(&id)
-        .fetch_optional(pool.inner())
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
         .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .ok_or_else(|| "Student not found".to_string())?;
-    
-    Ok(student)
+        .map_err(|e| e.to_string())?;
+
+    accessible_student(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Student with guardians, summary statistics and recent records in one call
+#[tauri::command]
+pub async fn get_student_profile(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<StudentProfile, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    StudentProfileService::get_profile(pool.inner(), &viewer, &student_id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn create_student(
     pool: State<'_, SqlitePool>,
+    token: String,
     request: CreateStudentRequest,
 ) -> Result<Student, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let institution_id = active_institution(&viewer).map_err(|e| e.to_string())?;
     let id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
     let gender = request.gender.unwrap_or_else(|| "K".to_string());
     
     sqlx::query(
-        "INSERT INTO students (id, name, surname, email, phone, birthDate, address, class, enrollmentDate, parentContact, notes, gender, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        "INSERT INTO students (id, name, surname, email, phone, birthDate, address, class, enrollmentDate, parentContact, notes, gender, schoolNumber, institutionId, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
     )
         .bind(&id)
         .bind(&request.name)
@@ -56,21 +83,34 @@ pub async fn create_student(
         .bind(&request.parentContact)
         .bind(&request.notes)
         .bind(&gender)
+        .bind(&request.schoolNumber)
+        .bind(&institution_id)
         .bind(&now)
         .bind(&now)
         .execute(pool.inner())
         .await
         .map_err(|e| format!("Failed to create student: {}", e))?;
+
+    sync_class(&pool, &viewer, &id, request.classId.as_deref()).await?;
     
-    get_student(pool, id).await
+    StudentRepository::get_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn update_student(
     pool: State<'_, SqlitePool>,
+    token: String,
     id: String,
     request: UpdateStudentRequest,
 ) -> Result<Student, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+    let student = accessible_student(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())?;
     let now = chrono::Utc::now().to_rfc3339();
     
     // Build dynamic update query
@@ -87,10 +127,10 @@ pub async fn update_student(
     if request.parentContact.is_some() { query_parts.push("parentContact = ?"); has_updates = true; }
     if request.notes.is_some() { query_parts.push("notes = ?"); has_updates = true; }
     if request.gender.is_some() { query_parts.push("gender = ?"); has_updates = true; }
-    if request.risk.is_some() { query_parts.push("risk = ?"); has_updates = true; }
+    if request.schoolNumber.is_some() { query_parts.push("schoolNumber = ?"); has_updates = true; }
     
     if !has_updates {
-        return get_student(pool, id).await;
+        return Ok(student);
     }
     
     let query_str = format!("UPDATE students SET {} WHERE id = ?", query_parts.join(", "));
@@ -106,22 +146,36 @@ pub async fn update_student(
     if let Some(v) = &request.parentContact { query = query.bind(v); }
     if let Some(v) = &request.notes { query = query.bind(v); }
     if let Some(v) = &request.gender { query = query.bind(v); }
-    if let Some(v) = &request.risk { query = query.bind(v); }
+    if let Some(v) = &request.schoolNumber { query = query.bind(v); }
     
     query = query.bind(&id);
     
     query.execute(pool.inner())
         .await
         .map_err(|e| format!("Failed to update student: {}", e))?;
+
+    if request.classId.is_some() || request.class.is_some() {
+        sync_class(&pool, &viewer, &id, request.classId.as_deref()).await?;
+    }
     
-    get_student(pool, id).await
+    StudentRepository::get_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn delete_student(
     pool: State<'_, SqlitePool>,
+    token: String,
     id: String,
 ) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+    accessible_student(pool.inner(), &viewer, &id)
+        .await
+        .map_err(|e| e.to_string())?;
+
     sqlx::query("DELETE FROM students WHERE id = ?")
         .bind(&id)
         .execute(pool.inner())
@@ -134,19 +188,32 @@ pub async fn delete_student(
 #[tauri::command]
 pub async fn search_students(
     pool: State<'_, SqlitePool>,
+    token: String,
     query: String,
 ) -> Result<Vec<Student>, String> {
+    let institution_id = session_institution(pool.inner(), &token).await?;
     let search_pattern = format!("%{}%", query);
     
     let students = sqlx::query_as::<_, Student>(
-        "SELECT * FROM students WHERE name LIKE ? OR surname LIKE ? OR email LIKE ? ORDER BY created_at DESC"
+        "SELECT * FROM students WHERE (name LIKE ? OR surname LIKE ? OR email LIKE ?) AND institutionId = ? ORDER BY created_at DESC"
     )
         .bind(&search_pattern)
         .bind(&search_pattern)
         .bind(&search_pattern)
+        .bind(&institution_id)
         .fetch_all(pool.inner())
         .await
         .map_err(|e| format!("Database error: {}", e))?;
     
     Ok(students)
 }
+
+/// Link the student to the given class, or to the class named by their class text
+async fn sync_class(pool: &SqlitePool, viewer: &User, student_id: &str, class_id: Option<&str>) -> Result<(), String> {
+    match class_id {
+        Some(class_id) => ClassService::assign_student(pool, viewer, student_id, class_id).await,
+        None => ClassService::sync_student_class(pool, student_id).await,
+    }
+    .map(|_| ())
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,178 @@
+use rehber360_core::{
+    models::{
+        CreateStudyAssignmentRequest, Progress, RecordStudySessionRequest, StudyAssignment, StudyAssignmentDetail,
+        StudySession, TopicProgress, UpdateProgressRequest, UpdateStudyAssignmentRequest, WeeklyStudyPlan,
+    },
+    repositories::StudyRepository,
+    services::{exam_topic_service::ExamTopicService, study_service::StudyService},
+};
+use sqlx::SqlitePool;
+use tauri::State;
+use super::institution::student_session;
+
+#[tauri::command]
+pub async fn record_study_session(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: RecordStudySessionRequest,
+) -> Result<StudySession, String> {
+    student_session(pool.inner(), &token, &request.studentId).await?;
+
+    StudyService::record_session(pool.inner(), request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// A student's sessions, latest first, optionally starting between two dates
+#[tauri::command]
+pub async fn get_student_study_sessions(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<StudySession>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    StudyRepository::get_sessions_by_student(pool.inner(), &student_id, from.as_deref(), to.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_study_session(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    let session = StudyRepository::get_session_by_id(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool.inner(), &token, &session.studentId).await?;
+
+    StudyService::delete_session(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_topic_progress(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+) -> Result<Vec<TopicProgress>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    StudyService::get_progress(pool.inner(), &student_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_student_topic_progress(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    topic_id: String,
+    request: UpdateProgressRequest,
+) -> Result<Progress, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    StudyService::update_progress(pool.inner(), &student_id, &topic_id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_study_assignment(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    request: CreateStudyAssignmentRequest,
+) -> Result<StudyAssignment, String> {
+    let viewer = student_session(pool.inner(), &token, &request.studentId).await?;
+
+    StudyService::create_assignment(pool.inner(), &viewer, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Assign a student's recurring weak exam topics that have no open assignment
+#[tauri::command]
+pub async fn assign_weak_topics(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    due_date: Option<String>,
+) -> Result<Vec<StudyAssignment>, String> {
+    let viewer = student_session(pool.inner(), &token, &student_id).await?;
+
+    ExamTopicService::assign_weak_topics(pool.inner(), &viewer, &student_id, due_date.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_student_study_assignments(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    status: Option<String>,
+) -> Result<Vec<StudyAssignmentDetail>, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    StudyService::get_assignments(pool.inner(), &student_id, status.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_study_assignment(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+    request: UpdateStudyAssignmentRequest,
+) -> Result<StudyAssignment, String> {
+    accessible_assignment(pool.inner(), &token, &id).await?;
+
+    StudyService::update_assignment(pool.inner(), &id, request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_study_assignment(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    id: String,
+) -> Result<(), String> {
+    accessible_assignment(pool.inner(), &token, &id).await?;
+
+    StudyService::delete_assignment(pool.inner(), &id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Study plan for the seven days from `week_start` (today by default)
+#[tauri::command]
+pub async fn get_weekly_study_plan(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    student_id: String,
+    week_start: Option<String>,
+    daily_minutes: Option<i32>,
+) -> Result<WeeklyStudyPlan, String> {
+    student_session(pool.inner(), &token, &student_id).await?;
+
+    StudyService::weekly_plan(pool.inner(), &student_id, week_start.as_deref(), daily_minutes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The assignment, if the session may work with its student
+async fn accessible_assignment(pool: &SqlitePool, token: &str, id: &str) -> Result<StudyAssignment, String> {
+    let assignment = StudyRepository::get_assignment_by_id(pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    student_session(pool, token, &assignment.studentId).await?;
+    Ok(assignment)
+}
@@ -4,6 +4,7 @@ use rehber360_core::{
 };
 use sqlx::SqlitePool;
 use tauri::State;
+use super::risk::refresh_student_risk;
 
 #[tauri::command]
 pub async fn create_survey_template(
@@ -49,9 +50,14 @@ pub async fn create_survey_response(
     pool: State<'_, SqlitePool>,
     response: SurveyResponse,
 ) -> Result<SurveyResponse, String> {
-    SurveyRepository::create_response(pool.inner(), response)
+    let response = SurveyRepository::create_response(pool.inner(), response)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    if let Some(student_id) = &response.studentId {
+        refresh_student_risk(pool.inner(), student_id).await;
+    }
+    Ok(response)
 }
 
 #[tauri::command]
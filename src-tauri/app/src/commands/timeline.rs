@@ -0,0 +1,39 @@
+use rehber360_core::{
+    models::{TimelinePage, TimelineQuery},
+    repositories::UserRepository,
+    services::timeline_service::TimelineService,
+};
+use sqlx::SqlitePool;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_student_timeline(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    query: TimelineQuery,
+) -> Result<TimelinePage, String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    TimelineService::get_timeline(pool.inner(), &viewer, query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_record_confidentiality(
+    pool: State<'_, SqlitePool>,
+    token: String,
+    event_type: String,
+    id: String,
+    is_confidential: bool,
+) -> Result<(), String> {
+    let viewer = UserRepository::get_session_by_token(pool.inner(), &token)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    TimelineService::set_confidential(pool.inner(), &viewer, &event_type, &id, is_confidential)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -4,8 +4,16 @@
 mod commands;
 
 use log::{info, error};
-use commands::{auth, student, counseling, academic, ai, survey, notification, settings, file, export, migration};
+use commands::{auth, student, counseling, academic, ai, survey, notification, settings, file, export, migration, document, guardian, family, attendance, intervention, timeline, bep, custom_field, school_class, institution, risk, dashboard, curriculum, study, placement};
 use rehber360_core::database;
+use rehber360_core::services::{
+    class_service::ClassService,
+    curriculum_service::CurriculumService,
+    document_service::DocumentService,
+    guardian_service::GuardianService,
+    institution_service::InstitutionService,
+    scheduler_service::SchedulerService,
+};
 use tauri::{
     Manager, RunEvent, WindowEvent,
     menu::{MenuBuilder, MenuItemBuilder, SubmenuBuilder},
@@ -43,6 +51,45 @@ async fn main() {
             })?;
             
             info!("Database initialized successfully");
+
+            // Move documents still stored inline as dataUrl blobs onto disk
+            let uploads_dir = app_data_dir.join("uploads");
+            if let Err(e) = tauri::async_runtime::block_on(
+                DocumentService::migrate_legacy_documents(&pool, &uploads_dir)
+            ) {
+                error!("Failed to migrate legacy documents: {}", e);
+            }
+
+            // Create guardian records from free-text parentContact values
+            if let Err(e) = tauri::async_runtime::block_on(
+                GuardianService::migrate_parent_contacts(&pool)
+            ) {
+                error!("Failed to migrate parent contacts: {}", e);
+            }
+
+            // Create institutions from users' free-text institution names and
+            // move records from before multi-institution support into one
+            if let Err(e) = tauri::async_runtime::block_on(
+                InstitutionService::migrate_institutions(&pool)
+            ) {
+                error!("Failed to migrate institutions: {}", e);
+            }
+
+            // Add or update the bundled MEB curriculum subjects and topics
+            if let Err(e) = tauri::async_runtime::block_on(CurriculumService::seed_meb_curriculum(&pool)) {
+                error!("Failed to seed the MEB curriculum: {}", e);
+            }
+
+            // Link free-text student classes to class records
+            if let Err(e) = tauri::async_runtime::block_on(
+                ClassService::migrate_student_classes(&pool)
+            ) {
+                error!("Failed to migrate student classes: {}", e);
+            }
+
+            // Send reminders and other time-based jobs now and periodically after
+            tauri::async_runtime::spawn(SchedulerService::run(pool.clone()));
+
             app.manage(pool);
             
             // Create system tray menu (Turkish)
@@ -224,6 +271,7 @@ async fn main() {
             // Student commands
             student::get_all_students,
             student::get_student,
+            student::get_student_profile,
             student::create_student,
             student::update_student,
             student::delete_student,
@@ -247,11 +295,26 @@ async fn main() {
             academic::get_student_exam_results_by_type,
             academic::update_exam_result,
             academic::delete_exam_result,
+            academic::calculate_exam_score,
+            academic::score_exam_result,
+            academic::get_student_exam_trends,
+            academic::rank_exam,
+            academic::get_exam_topic_results,
+            academic::save_exam_topic_results,
+            academic::import_exam_topic_report,
+            academic::get_student_weak_topics,
             academic::create_behavior_incident,
             academic::get_behavior_incident,
             academic::get_student_behavior_incidents,
             academic::create_academic_goal,
             academic::get_student_academic_goals,
+            academic::update_academic_goal,
+            academic::delete_academic_goal,
+            academic::get_exam_import_layouts,
+            academic::create_exam_import_layout,
+            academic::update_exam_import_layout,
+            academic::delete_exam_import_layout,
+            academic::import_exam_results,
             // AI commands
             ai::create_ai_suggestion,
             ai::get_ai_suggestion,
@@ -304,6 +367,162 @@ async fn main() {
             file::delete_file,
             file::get_file_list,
             file::open_file_in_explorer,
+            // Student document commands
+            document::get_student_documents,
+            document::get_student_document,
+            document::attach_student_document,
+            document::detach_student_document,
+            document::download_student_document,
+            // Guardian commands
+            guardian::create_guardian,
+            guardian::get_guardian,
+            guardian::search_guardians,
+            guardian::update_guardian,
+            guardian::delete_guardian,
+            guardian::get_student_guardians,
+            guardian::get_guardian_students,
+            guardian::link_guardian_to_student,
+            guardian::unlink_guardian_from_student,
+            guardian::parse_parent_contact,
+            // Family commands
+            family::create_family,
+            family::get_family_unit,
+            family::get_student_family_unit,
+            family::get_student_siblings,
+            family::update_family,
+            family::delete_family,
+            family::add_family_member,
+            family::remove_family_member,
+            family::link_siblings,
+            family::suggest_siblings,
+            family::dismiss_sibling_suggestion,
+            // Attendance commands
+            attendance::create_attendance_record,
+            attendance::get_attendance_record,
+            attendance::update_attendance_record,
+            attendance::delete_attendance_record,
+            attendance::get_student_attendance,
+            attendance::get_attendance_by_date,
+            attendance::get_student_attendance_summary,
+            attendance::get_student_attendance_alerts,
+            attendance::get_attendance_thresholds,
+            attendance::update_attendance_thresholds,
+            attendance::import_eokul_attendance,
+            // Intervention commands
+            intervention::create_intervention,
+            intervention::get_intervention,
+            intervention::get_student_interventions,
+            intervention::get_interventions,
+            intervention::update_intervention,
+            intervention::change_intervention_status,
+            intervention::delete_intervention,
+            intervention::add_intervention_note,
+            intervention::link_intervention_session,
+            intervention::unlink_intervention_session,
+            intervention::link_intervention_incident,
+            intervention::unlink_intervention_incident,
+            intervention::send_intervention_reminders,
+            // Timeline commands
+            timeline::get_student_timeline,
+            timeline::set_record_confidentiality,
+            // BEP commands
+            bep::create_bep_plan,
+            bep::get_bep_plan,
+            bep::get_student_bep_plans,
+            bep::update_bep_plan,
+            bep::change_bep_plan_status,
+            bep::delete_bep_plan,
+            bep::add_bep_goal,
+            bep::update_bep_goal,
+            bep::delete_bep_goal,
+            bep::record_bep_goal_progress,
+            bep::get_bep_goal_progress,
+            bep::add_bep_accommodation,
+            bep::delete_bep_accommodation,
+            bep::assign_bep_teacher,
+            bep::remove_bep_teacher,
+            bep::add_bep_evaluation,
+            bep::get_bep_document,
+            bep::save_bep_document,
+            bep::send_bep_review_reminders,
+            // Custom field commands
+            custom_field::get_custom_fields,
+            custom_field::create_custom_field,
+            custom_field::update_custom_field,
+            custom_field::delete_custom_field,
+            custom_field::get_student_custom_data,
+            custom_field::set_student_attributes,
+            custom_field::set_student_tags,
+            custom_field::get_student_tag_counts,
+            custom_field::import_custom_field_values,
+            // Class commands
+            school_class::get_classes,
+            school_class::get_my_classes,
+            school_class::get_class_overview,
+            school_class::get_class_exam_results,
+            school_class::create_class,
+            school_class::update_class,
+            school_class::delete_class,
+            school_class::assign_student_class,
+            // Institution commands
+            institution::get_institutions,
+            institution::create_institution,
+            institution::update_institution,
+            institution::switch_institution,
+            institution::get_institution_users,
+            institution::add_institution_user,
+            institution::remove_institution_user,
+            institution::set_district_admin,
+            institution::get_institution_summary,
+            institution::get_district_report,
+            // Risk commands
+            risk::get_student_risk,
+            risk::recalculate_student_risk,
+            risk::recalculate_all_risk,
+            risk::set_risk_override,
+            risk::clear_risk_override,
+            risk::set_survey_question_risk_answers,
+            risk::get_risk_rule_sets,
+            risk::get_active_risk_rules,
+            risk::create_risk_rule_set,
+            risk::preview_risk_rule_set,
+            risk::activate_risk_rule_set,
+            risk::delete_risk_rule_set,
+            risk::get_risk_alerts,
+            risk::acknowledge_risk_alert,
+            risk::process_risk_alerts,
+            risk::get_risk_alert_settings,
+            risk::update_risk_alert_settings,
+            // Dashboard commands
+            dashboard::get_dashboard_stats,
+            // Curriculum commands
+            curriculum::get_curriculum,
+            curriculum::get_subject_topics,
+            curriculum::create_subject,
+            curriculum::update_subject,
+            curriculum::delete_subject,
+            curriculum::create_topic,
+            curriculum::update_topic,
+            curriculum::delete_topic,
+            curriculum::import_curriculum_catalog,
+            curriculum::get_curriculum_versions,
+            // Study planning commands
+            study::record_study_session,
+            study::get_student_study_sessions,
+            study::delete_study_session,
+            study::get_student_topic_progress,
+            study::update_student_topic_progress,
+            study::create_study_assignment,
+            study::assign_weak_topics,
+            study::get_student_study_assignments,
+            study::update_study_assignment,
+            study::delete_study_assignment,
+            study::get_weekly_study_plan,
+            // Placement estimation commands
+            placement::get_placement_tables,
+            placement::import_placement_table,
+            placement::delete_placement_table,
+            placement::estimate_student_placement,
             // Export/Import commands
             export::export_database_json,
             export::import_database_json,
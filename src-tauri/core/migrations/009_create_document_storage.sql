@@ -0,0 +1,29 @@
+-- Rebuild student_documents as a metadata table for files stored on disk.
+-- Legacy rows keep their inline dataUrl until DocumentService migrates them
+-- into the uploads directory and clears the column.
+CREATE TABLE IF NOT EXISTS student_documents_new (
+    id TEXT PRIMARY KEY,
+    studentId TEXT NOT NULL,
+    name TEXT NOT NULL,
+    type TEXT NOT NULL,
+    category TEXT,
+    storagePath TEXT,
+    size INTEGER,
+    sha256 TEXT,
+    uploadedBy TEXT,
+    dataUrl TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (uploadedBy) REFERENCES users (id) ON DELETE SET NULL
+);
+
+INSERT INTO student_documents_new (id, studentId, name, type, dataUrl, created_at, updated_at)
+SELECT id, studentId, name, type, dataUrl, created_at, created_at FROM student_documents;
+
+DROP TABLE student_documents;
+ALTER TABLE student_documents_new RENAME TO student_documents;
+
+CREATE INDEX IF NOT EXISTS idx_student_documents_student ON student_documents(studentId);
+CREATE INDEX IF NOT EXISTS idx_student_documents_category ON student_documents(category);
+CREATE INDEX IF NOT EXISTS idx_student_documents_sha256 ON student_documents(studentId, sha256);
@@ -0,0 +1,41 @@
+-- Create guardians table
+CREATE TABLE IF NOT EXISTS guardians (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    phone TEXT,
+    secondaryPhone TEXT,
+    email TEXT,
+    address TEXT,
+    occupation TEXT,
+    preferredLanguage TEXT DEFAULT 'tr',
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create student guardians junction table
+-- Relationship, custody and contact restrictions are per student, since the
+-- same guardian can be linked to several siblings.
+CREATE TABLE IF NOT EXISTS student_guardians (
+    studentId TEXT NOT NULL,
+    guardianId TEXT NOT NULL,
+    relationship TEXT NOT NULL DEFAULT 'Veli',
+    isPrimary BOOLEAN DEFAULT FALSE,
+    hasCustody BOOLEAN DEFAULT TRUE,
+    livesWithStudent BOOLEAN DEFAULT TRUE,
+    contactRestricted BOOLEAN DEFAULT FALSE,
+    restrictionNotes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (studentId, guardianId),
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (guardianId) REFERENCES guardians (id) ON DELETE CASCADE
+);
+
+-- Parent access tokens can now point at a concrete guardian
+ALTER TABLE parent_access_tokens ADD COLUMN guardianId TEXT REFERENCES guardians (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_guardians_phone ON guardians(phone);
+CREATE INDEX IF NOT EXISTS idx_guardians_email ON guardians(email);
+CREATE INDEX IF NOT EXISTS idx_student_guardians_guardian ON student_guardians(guardianId);
+CREATE INDEX IF NOT EXISTS idx_parent_access_tokens_guardian ON parent_access_tokens(guardianId);
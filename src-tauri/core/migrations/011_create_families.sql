@@ -0,0 +1,32 @@
+-- Create families table
+CREATE TABLE IF NOT EXISTS families (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create family members table (a student belongs to at most one family unit)
+CREATE TABLE IF NOT EXISTS family_members (
+    familyId TEXT NOT NULL,
+    studentId TEXT NOT NULL UNIQUE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (familyId, studentId),
+    FOREIGN KEY (familyId) REFERENCES families (id) ON DELETE CASCADE,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE
+);
+
+-- Sibling suggestions a counselor has rejected, stored with studentId < otherStudentId
+CREATE TABLE IF NOT EXISTS sibling_suggestion_dismissals (
+    studentId TEXT NOT NULL,
+    otherStudentId TEXT NOT NULL,
+    dismissedBy TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (studentId, otherStudentId),
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (otherStudentId) REFERENCES students (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_family_members_family ON family_members(familyId);
+CREATE INDEX IF NOT EXISTS idx_students_surname ON students(surname);
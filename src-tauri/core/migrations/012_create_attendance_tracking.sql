@@ -0,0 +1,102 @@
+-- Extend attendance_records for day-based absenteeism tracking
+ALTER TABLE attendance_records ADD COLUMN duration REAL NOT NULL DEFAULT 1.0;
+ALTER TABLE attendance_records ADD COLUMN schoolYear TEXT;
+ALTER TABLE attendance_records ADD COLUMN term TEXT;
+ALTER TABLE attendance_records ADD COLUMN source TEXT NOT NULL DEFAULT 'MANUAL';
+ALTER TABLE attendance_records ADD COLUMN recordedBy TEXT;
+ALTER TABLE attendance_records ADD COLUMN updated_at DATETIME;
+
+-- Retire the unused attendance table from 002; its rows move into
+-- attendance_records so they count towards absenteeism
+INSERT INTO attendance_records (id, studentId, date, status, notes, created_at, source)
+SELECT a.id, a.studentId, a.date, a.status, a.notes, a.created_at, 'LEGACY'
+FROM attendance a
+WHERE NOT EXISTS (SELECT 1 FROM attendance_records r WHERE r.id = a.id);
+
+DROP TABLE IF EXISTS attendance;
+
+UPDATE attendance_records SET updated_at = created_at WHERE updated_at IS NULL;
+
+-- School year runs September to August; 1st term until the end of January
+UPDATE attendance_records SET
+    schoolYear = CASE
+        WHEN CAST(strftime('%m', date) AS INTEGER) >= 9
+            THEN strftime('%Y', date) || '-' || (CAST(strftime('%Y', date) AS INTEGER) + 1)
+        ELSE (CAST(strftime('%Y', date) AS INTEGER) - 1) || '-' || strftime('%Y', date)
+    END,
+    term = CASE
+        WHEN CAST(strftime('%m', date) AS INTEGER) >= 9 OR CAST(strftime('%m', date) AS INTEGER) = 1
+            THEN '1'
+        ELSE '2'
+    END
+WHERE schoolYear IS NULL AND strftime('%m', date) IS NOT NULL;
+
+-- One record per student per day. When a day was recorded more than once
+-- the earliest record is kept with the notes of the others, and the others
+-- are moved to attendance_record_duplicates for review.
+CREATE TABLE IF NOT EXISTS attendance_record_duplicates (
+    id TEXT PRIMARY KEY,
+    keptRecordId TEXT NOT NULL,
+    studentId TEXT NOT NULL,
+    date TEXT NOT NULL,
+    status TEXT NOT NULL,
+    reason TEXT,
+    notes TEXT,
+    source TEXT,
+    created_at DATETIME,
+    FOREIGN KEY (keptRecordId) REFERENCES attendance_records (id) ON DELETE CASCADE
+);
+
+INSERT INTO attendance_record_duplicates (id, keptRecordId, studentId, date, status, reason, notes, source, created_at)
+SELECT r.id, kept.id, r.studentId, r.date, r.status, r.reason, r.notes, r.source, r.created_at
+FROM attendance_records r
+INNER JOIN attendance_records kept ON kept.rowid = (
+    SELECT MIN(rowid) FROM attendance_records k WHERE k.studentId = r.studentId AND k.date = r.date
+)
+WHERE r.rowid != kept.rowid;
+
+UPDATE attendance_records SET notes = TRIM(
+    COALESCE(notes, '') || char(10) || COALESCE((
+        SELECT GROUP_CONCAT(TRIM(d.notes), char(10)) FROM attendance_record_duplicates d
+        WHERE d.keptRecordId = attendance_records.id
+          AND TRIM(COALESCE(d.notes, '')) != ''
+          AND TRIM(d.notes) IS NOT TRIM(attendance_records.notes)
+    ), ''),
+    char(10)
+) WHERE id IN (SELECT keptRecordId FROM attendance_record_duplicates);
+
+DELETE FROM attendance_records WHERE id IN (SELECT id FROM attendance_record_duplicates);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_attendance_records_student_date ON attendance_records(studentId, date);
+CREATE INDEX IF NOT EXISTS idx_attendance_records_date ON attendance_records(date);
+CREATE INDEX IF NOT EXISTS idx_attendance_records_year ON attendance_records(schoolYear, term);
+
+-- Create attendance thresholds table (MEB absenteeism limits, in days)
+CREATE TABLE IF NOT EXISTS attendance_thresholds (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    unexcusedWarningDays REAL NOT NULL DEFAULT 5,
+    unexcusedFailureDays REAL NOT NULL DEFAULT 10,
+    totalWarningDays REAL NOT NULL DEFAULT 20,
+    totalFailureDays REAL NOT NULL DEFAULT 30,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO attendance_thresholds (id) VALUES (1);
+
+-- Threshold crossings already alerted, so each level is reported once per school year
+CREATE TABLE IF NOT EXISTS attendance_alerts (
+    id TEXT PRIMARY KEY,
+    studentId TEXT NOT NULL,
+    schoolYear TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('UNEXCUSED', 'TOTAL')),
+    level TEXT NOT NULL CHECK (level IN ('WARNING', 'FAILURE')),
+    days REAL NOT NULL,
+    notificationId TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (notificationId) REFERENCES notification_logs (id) ON DELETE SET NULL,
+    UNIQUE(studentId, schoolYear, kind, level)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attendance_alerts_student ON attendance_alerts(studentId);
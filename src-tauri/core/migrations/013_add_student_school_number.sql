@@ -0,0 +1,4 @@
+-- School number (okul numarası) as assigned in e-Okul, used to match imported records
+ALTER TABLE students ADD COLUMN schoolNumber TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_students_school_number ON students(schoolNumber);
@@ -0,0 +1,67 @@
+-- Extend interventions with ownership, planning dates and outcome
+ALTER TABLE interventions ADD COLUMN description TEXT;
+ALTER TABLE interventions ADD COLUMN category TEXT;
+ALTER TABLE interventions ADD COLUMN ownerId TEXT REFERENCES users (id) ON DELETE SET NULL;
+ALTER TABLE interventions ADD COLUMN startDate TEXT;
+ALTER TABLE interventions ADD COLUMN targetDate TEXT;
+ALTER TABLE interventions ADD COLUMN completedAt TEXT;
+ALTER TABLE interventions ADD COLUMN outcome TEXT;
+ALTER TABLE interventions ADD COLUMN priority TEXT NOT NULL DEFAULT 'NORMAL';
+ALTER TABLE interventions ADD COLUMN updated_at DATETIME;
+
+UPDATE interventions SET updated_at = created_at WHERE updated_at IS NULL;
+
+-- Create intervention links to counseling sessions
+CREATE TABLE IF NOT EXISTS intervention_sessions (
+    interventionId TEXT NOT NULL,
+    sessionId TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (interventionId, sessionId),
+    FOREIGN KEY (interventionId) REFERENCES interventions (id) ON DELETE CASCADE,
+    FOREIGN KEY (sessionId) REFERENCES counseling_sessions (id) ON DELETE CASCADE
+);
+
+-- Create intervention links to behavior incidents
+CREATE TABLE IF NOT EXISTS intervention_incidents (
+    interventionId TEXT NOT NULL,
+    incidentId TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (interventionId, incidentId),
+    FOREIGN KEY (interventionId) REFERENCES interventions (id) ON DELETE CASCADE,
+    FOREIGN KEY (incidentId) REFERENCES behavior_incidents (id) ON DELETE CASCADE
+);
+
+-- Create intervention progress notes table
+CREATE TABLE IF NOT EXISTS intervention_progress_notes (
+    id TEXT PRIMARY KEY,
+    interventionId TEXT NOT NULL,
+    authorId TEXT,
+    note TEXT NOT NULL,
+    progress INTEGER CHECK (progress IS NULL OR (progress >= 0 AND progress <= 100)),
+    fromStatus TEXT,
+    toStatus TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (interventionId) REFERENCES interventions (id) ON DELETE CASCADE,
+    FOREIGN KEY (authorId) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Deadline reminders already sent, so each is sent once per target date
+CREATE TABLE IF NOT EXISTS intervention_reminders (
+    id TEXT PRIMARY KEY,
+    interventionId TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('UPCOMING', 'OVERDUE')),
+    targetDate TEXT NOT NULL,
+    notificationId TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (interventionId) REFERENCES interventions (id) ON DELETE CASCADE,
+    FOREIGN KEY (notificationId) REFERENCES notification_logs (id) ON DELETE SET NULL,
+    UNIQUE(interventionId, kind, targetDate)
+);
+
+CREATE INDEX IF NOT EXISTS idx_interventions_student ON interventions(studentId);
+CREATE INDEX IF NOT EXISTS idx_interventions_status ON interventions(status);
+CREATE INDEX IF NOT EXISTS idx_interventions_owner ON interventions(ownerId);
+CREATE INDEX IF NOT EXISTS idx_interventions_target_date ON interventions(targetDate);
+CREATE INDEX IF NOT EXISTS idx_intervention_sessions_session ON intervention_sessions(sessionId);
+CREATE INDEX IF NOT EXISTS idx_intervention_incidents_incident ON intervention_incidents(incidentId);
+CREATE INDEX IF NOT EXISTS idx_intervention_progress_notes_intervention ON intervention_progress_notes(interventionId);
@@ -0,0 +1,9 @@
+-- Confidential counseling records are only shown to admins and the counselor who owns them
+ALTER TABLE counseling_sessions ADD COLUMN isConfidential BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE meeting_notes ADD COLUMN isConfidential BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE parent_meetings ADD COLUMN isConfidential BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE home_visits ADD COLUMN isConfidential BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Authors of meeting notes and parent meetings own them when they are confidential
+ALTER TABLE meeting_notes ADD COLUMN createdBy TEXT REFERENCES users (id) ON DELETE SET NULL;
+ALTER TABLE parent_meetings ADD COLUMN createdBy TEXT REFERENCES users (id) ON DELETE SET NULL;
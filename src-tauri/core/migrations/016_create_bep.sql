@@ -0,0 +1,170 @@
+-- Rebuild notification_logs so BEP review reminders get their own type.
+-- Dropping the old table nulls the notificationId of attendance alerts and
+-- intervention reminders, so those links are kept aside.
+CREATE TABLE attendance_alert_links AS
+SELECT id, notificationId FROM attendance_alerts WHERE notificationId IS NOT NULL;
+CREATE TABLE intervention_reminder_links AS
+SELECT id, notificationId FROM intervention_reminders WHERE notificationId IS NOT NULL;
+
+CREATE TABLE notification_logs_new (
+    id TEXT PRIMARY KEY,
+    recipientType TEXT NOT NULL CHECK (recipientType IN ('COUNSELOR', 'PARENT', 'ADMIN', 'TEACHER')),
+    recipientId TEXT,
+    recipientName TEXT,
+    recipientContact TEXT,
+    notificationType TEXT NOT NULL CHECK (notificationType IN ('RISK_ALERT', 'INTERVENTION_REMINDER', 'BEP_REVIEW_REMINDER', 'PROGRESS_UPDATE', 'MEETING_SCHEDULED', 'WEEKLY_DIGEST', 'MONTHLY_REPORT', 'CUSTOM')),
+    channel TEXT NOT NULL CHECK (channel IN ('EMAIL', 'SMS', 'PUSH', 'IN_APP')),
+    subject TEXT,
+    message TEXT NOT NULL,
+    studentId TEXT,
+    alertId TEXT,
+    interventionId TEXT,
+    status TEXT NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'SENT', 'DELIVERED', 'FAILED', 'READ')),
+    priority TEXT NOT NULL DEFAULT 'NORMAL' CHECK (priority IN ('LOW', 'NORMAL', 'HIGH', 'URGENT')),
+    metadata TEXT,
+    templateId TEXT,
+    sentAt TEXT,
+    deliveredAt TEXT,
+    readAt TEXT,
+    failureReason TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE
+);
+
+INSERT INTO notification_logs_new SELECT * FROM notification_logs;
+
+DROP TABLE notification_logs;
+ALTER TABLE notification_logs_new RENAME TO notification_logs;
+
+CREATE INDEX IF NOT EXISTS idx_notification_logs_student ON notification_logs(studentId);
+CREATE INDEX IF NOT EXISTS idx_notification_logs_status ON notification_logs(status);
+CREATE INDEX IF NOT EXISTS idx_notification_logs_recipient ON notification_logs(recipientId);
+CREATE INDEX IF NOT EXISTS idx_notification_logs_type ON notification_logs(notificationType);
+CREATE INDEX IF NOT EXISTS idx_notification_logs_created ON notification_logs(created_at DESC);
+
+UPDATE attendance_alerts SET notificationId = (SELECT l.notificationId FROM attendance_alert_links l WHERE l.id = attendance_alerts.id)
+WHERE id IN (SELECT id FROM attendance_alert_links);
+UPDATE intervention_reminders SET notificationId = (SELECT l.notificationId FROM intervention_reminder_links l WHERE l.id = intervention_reminders.id)
+WHERE id IN (SELECT id FROM intervention_reminder_links);
+
+DROP TABLE attendance_alert_links;
+DROP TABLE intervention_reminder_links;
+
+-- Create individualized education plans (BEP) table
+CREATE TABLE IF NOT EXISTS bep_plans (
+    id TEXT PRIMARY KEY,
+    studentId TEXT NOT NULL,
+    ramReportNumber TEXT,
+    ramReportDate TEXT,
+    ramReportSummary TEXT,
+    diagnosis TEXT,
+    educationalPlacement TEXT,
+    supportServices TEXT,
+    startDate TEXT NOT NULL,
+    endDate TEXT,
+    status TEXT NOT NULL DEFAULT 'Taslak' CHECK (status IN ('Taslak', 'Aktif', 'Tamamlandı', 'İptal')),
+    coordinatorId TEXT,
+    reviewIntervalDays INTEGER NOT NULL DEFAULT 90 CHECK (reviewIntervalDays > 0),
+    nextReviewDate TEXT,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (coordinatorId) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Create BEP goals table
+CREATE TABLE IF NOT EXISTS bep_goals (
+    id TEXT PRIMARY KEY,
+    planId TEXT NOT NULL,
+    subjectId TEXT,
+    area TEXT,
+    longTermGoal TEXT NOT NULL,
+    shortTermGoal TEXT,
+    successCriteria TEXT,
+    method TEXT,
+    targetDate TEXT,
+    progress INTEGER NOT NULL DEFAULT 0 CHECK (progress >= 0 AND progress <= 100),
+    status TEXT NOT NULL DEFAULT 'Başlanmadı' CHECK (status IN ('Başlanmadı', 'Devam', 'Tamamlandı')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (planId) REFERENCES bep_plans (id) ON DELETE CASCADE,
+    FOREIGN KEY (subjectId) REFERENCES subjects (id) ON DELETE SET NULL
+);
+
+-- Create BEP accommodations table
+CREATE TABLE IF NOT EXISTS bep_accommodations (
+    id TEXT PRIMARY KEY,
+    planId TEXT NOT NULL,
+    subjectId TEXT,
+    category TEXT,
+    description TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (planId) REFERENCES bep_plans (id) ON DELETE CASCADE,
+    FOREIGN KEY (subjectId) REFERENCES subjects (id) ON DELETE SET NULL
+);
+
+-- Create BEP responsible teachers table
+CREATE TABLE IF NOT EXISTS bep_responsible_teachers (
+    id TEXT PRIMARY KEY,
+    planId TEXT NOT NULL,
+    userId TEXT NOT NULL,
+    subjectId TEXT,
+    responsibility TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (planId) REFERENCES bep_plans (id) ON DELETE CASCADE,
+    FOREIGN KEY (userId) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (subjectId) REFERENCES subjects (id) ON DELETE SET NULL
+);
+
+-- Create BEP periodic evaluations table
+CREATE TABLE IF NOT EXISTS bep_evaluations (
+    id TEXT PRIMARY KEY,
+    planId TEXT NOT NULL,
+    evaluationDate TEXT NOT NULL,
+    evaluatorId TEXT,
+    summary TEXT NOT NULL,
+    decision TEXT NOT NULL DEFAULT 'Devam' CHECK (decision IN ('Devam', 'Revize', 'Sonlandır')),
+    nextReviewDate TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (planId) REFERENCES bep_plans (id) ON DELETE CASCADE,
+    FOREIGN KEY (evaluatorId) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Create BEP goal progress history table
+CREATE TABLE IF NOT EXISTS bep_goal_progress (
+    id TEXT PRIMARY KEY,
+    goalId TEXT NOT NULL,
+    evaluationId TEXT,
+    progress INTEGER NOT NULL CHECK (progress >= 0 AND progress <= 100),
+    note TEXT,
+    recordedBy TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (goalId) REFERENCES bep_goals (id) ON DELETE CASCADE,
+    FOREIGN KEY (evaluationId) REFERENCES bep_evaluations (id) ON DELETE SET NULL,
+    FOREIGN KEY (recordedBy) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Review reminders already sent, so each is sent once per review date
+CREATE TABLE IF NOT EXISTS bep_reminders (
+    id TEXT PRIMARY KEY,
+    planId TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('UPCOMING', 'OVERDUE')),
+    reviewDate TEXT NOT NULL,
+    notificationId TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (planId) REFERENCES bep_plans (id) ON DELETE CASCADE,
+    FOREIGN KEY (notificationId) REFERENCES notification_logs (id) ON DELETE SET NULL,
+    UNIQUE(planId, kind, reviewDate)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bep_plans_student ON bep_plans(studentId);
+CREATE INDEX IF NOT EXISTS idx_bep_plans_status ON bep_plans(status);
+CREATE INDEX IF NOT EXISTS idx_bep_plans_next_review ON bep_plans(nextReviewDate);
+CREATE INDEX IF NOT EXISTS idx_bep_goals_plan ON bep_goals(planId);
+CREATE INDEX IF NOT EXISTS idx_bep_accommodations_plan ON bep_accommodations(planId);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_bep_responsible_teachers_unique ON bep_responsible_teachers(planId, userId, COALESCE(subjectId, ''));
+CREATE INDEX IF NOT EXISTS idx_bep_responsible_teachers_user ON bep_responsible_teachers(userId);
+CREATE INDEX IF NOT EXISTS idx_bep_evaluations_plan ON bep_evaluations(planId);
+CREATE INDEX IF NOT EXISTS idx_bep_goal_progress_goal ON bep_goal_progress(goalId);
@@ -0,0 +1,35 @@
+-- Create admin-defined custom student fields table
+CREATE TABLE IF NOT EXISTS custom_field_definitions (
+    id TEXT PRIMARY KEY,
+    key TEXT NOT NULL UNIQUE,
+    label TEXT NOT NULL,
+    fieldType TEXT NOT NULL CHECK (fieldType IN ('text', 'number', 'date', 'enum')),
+    options TEXT,
+    sortOrder INTEGER NOT NULL DEFAULT 0,
+    isActive BOOLEAN NOT NULL DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create student custom field values table
+CREATE TABLE IF NOT EXISTS student_attributes (
+    studentId TEXT NOT NULL,
+    fieldId TEXT NOT NULL,
+    value TEXT NOT NULL,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (studentId, fieldId),
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (fieldId) REFERENCES custom_field_definitions (id) ON DELETE CASCADE
+);
+
+-- Create free-form student tags table
+CREATE TABLE IF NOT EXISTS student_tags (
+    studentId TEXT NOT NULL,
+    tag TEXT NOT NULL COLLATE NOCASE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (studentId, tag),
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_student_attributes_field_value ON student_attributes(fieldId, value);
+CREATE INDEX IF NOT EXISTS idx_student_tags_tag ON student_tags(tag);
@@ -0,0 +1,23 @@
+-- Create classes table (one row per grade and section in a school year)
+CREATE TABLE IF NOT EXISTS classes (
+    id TEXT PRIMARY KEY,
+    grade INTEGER NOT NULL CHECK (grade >= 0 AND grade <= 12),
+    section TEXT NOT NULL,
+    schoolYear TEXT NOT NULL,
+    name TEXT NOT NULL,
+    homeroomTeacherId TEXT,
+    counselorId TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (homeroomTeacherId) REFERENCES users (id) ON DELETE SET NULL,
+    FOREIGN KEY (counselorId) REFERENCES users (id) ON DELETE SET NULL,
+    UNIQUE(grade, section, schoolYear)
+);
+
+-- Students reference their class; the free-text class column keeps the class name
+ALTER TABLE students ADD COLUMN classId TEXT REFERENCES classes (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_classes_school_year ON classes(schoolYear);
+CREATE INDEX IF NOT EXISTS idx_classes_homeroom_teacher ON classes(homeroomTeacherId);
+CREATE INDEX IF NOT EXISTS idx_classes_counselor ON classes(counselorId);
+CREATE INDEX IF NOT EXISTS idx_students_class_id ON students(classId);
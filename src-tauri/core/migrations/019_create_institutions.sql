@@ -0,0 +1,79 @@
+-- Create institutions table (schools served from one installation)
+CREATE TABLE IF NOT EXISTS institutions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    code TEXT UNIQUE,
+    district TEXT,
+    isActive BOOLEAN NOT NULL DEFAULT 1,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create user_institutions table (institutions a user works in)
+CREATE TABLE IF NOT EXISTS user_institutions (
+    userId TEXT NOT NULL,
+    institutionId TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (userId, institutionId),
+    FOREIGN KEY (userId) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (institutionId) REFERENCES institutions (id) ON DELETE CASCADE
+);
+
+-- District admins see every institution and the cross-institution reports
+ALTER TABLE users ADD COLUMN isDistrictAdmin BOOLEAN NOT NULL DEFAULT 0;
+
+-- The first admin of an existing installation becomes its district admin
+UPDATE users SET isDistrictAdmin = 1
+WHERE id = (SELECT id FROM users WHERE role = 'admin' ORDER BY created_at, id LIMIT 1);
+
+-- The institution a session is currently working in
+ALTER TABLE user_sessions ADD COLUMN institutionId TEXT REFERENCES institutions (id) ON DELETE SET NULL;
+
+ALTER TABLE students ADD COLUMN institutionId TEXT REFERENCES institutions (id);
+ALTER TABLE counseling_sessions ADD COLUMN institutionId TEXT REFERENCES institutions (id);
+
+-- Rebuild classes so the same class name can exist in each institution.
+-- Dropping the old table nulls students.classId, so the links are kept aside.
+CREATE TABLE class_links AS SELECT id, classId FROM students WHERE classId IS NOT NULL;
+
+CREATE TABLE classes_new (
+    id TEXT PRIMARY KEY,
+    institutionId TEXT,
+    grade INTEGER NOT NULL CHECK (grade >= 0 AND grade <= 12),
+    section TEXT NOT NULL,
+    schoolYear TEXT NOT NULL,
+    name TEXT NOT NULL,
+    homeroomTeacherId TEXT,
+    counselorId TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (institutionId) REFERENCES institutions (id),
+    FOREIGN KEY (homeroomTeacherId) REFERENCES users (id) ON DELETE SET NULL,
+    FOREIGN KEY (counselorId) REFERENCES users (id) ON DELETE SET NULL,
+    UNIQUE(institutionId, grade, section, schoolYear)
+);
+
+INSERT INTO classes_new (
+    id, grade, section, schoolYear, name, homeroomTeacherId, counselorId, created_at, updated_at
+)
+SELECT id, grade, section, schoolYear, name, homeroomTeacherId, counselorId, created_at, updated_at
+FROM classes;
+
+DROP TABLE classes;
+ALTER TABLE classes_new RENAME TO classes;
+
+UPDATE students SET classId = (SELECT l.classId FROM class_links l WHERE l.id = students.id)
+WHERE id IN (SELECT id FROM class_links);
+
+DROP TABLE class_links;
+
+CREATE INDEX IF NOT EXISTS idx_user_institutions_institution ON user_institutions(institutionId);
+CREATE INDEX IF NOT EXISTS idx_students_institution ON students(institutionId);
+-- School numbers are unique within an institution, not across the district
+DROP INDEX IF EXISTS idx_students_school_number;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_students_institution_school_number ON students(institutionId, schoolNumber);
+CREATE INDEX IF NOT EXISTS idx_counseling_sessions_institution ON counseling_sessions(institutionId);
+CREATE INDEX IF NOT EXISTS idx_classes_institution ON classes(institutionId);
+CREATE INDEX IF NOT EXISTS idx_classes_school_year ON classes(schoolYear);
+CREATE INDEX IF NOT EXISTS idx_classes_homeroom_teacher ON classes(homeroomTeacherId);
+CREATE INDEX IF NOT EXISTS idx_classes_counselor ON classes(counselorId);
@@ -0,0 +1,31 @@
+-- Create risk_scores table (latest computed risk of each student)
+CREATE TABLE IF NOT EXISTS risk_scores (
+    studentId TEXT PRIMARY KEY,
+    score REAL NOT NULL,
+    level TEXT NOT NULL CHECK (level IN ('Düşük', 'Orta', 'Yüksek')),
+    factors TEXT NOT NULL,
+    calculatedAt DATETIME NOT NULL,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE
+);
+
+-- Create risk_overrides table (risk level set by hand, with the reason)
+CREATE TABLE IF NOT EXISTS risk_overrides (
+    studentId TEXT PRIMARY KEY,
+    level TEXT NOT NULL CHECK (level IN ('Düşük', 'Orta', 'Yüksek')),
+    reason TEXT NOT NULL,
+    overriddenBy TEXT,
+    expiresAt TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (overriddenBy) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Keep levels set by hand before scoring as overrides, so recalculation does not reset them
+INSERT OR IGNORE INTO risk_overrides (studentId, level, reason)
+SELECT id, risk, 'legacy' FROM students WHERE risk IN ('Orta', 'Yüksek');
+
+-- Answers that raise a risk flag, as a JSON array of values
+ALTER TABLE survey_questions ADD COLUMN riskAnswers TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_risk_scores_level ON risk_scores(level);
+CREATE INDEX IF NOT EXISTS idx_risk_scores_calculated ON risk_scores(calculatedAt);
@@ -0,0 +1,21 @@
+-- Create risk_rule_sets table (versioned risk rules of each institution, one active at a time)
+CREATE TABLE IF NOT EXISTS risk_rule_sets (
+    id TEXT PRIMARY KEY,
+    institutionId TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    rules TEXT NOT NULL,
+    notes TEXT,
+    isActive BOOLEAN DEFAULT FALSE,
+    createdBy TEXT,
+    activatedAt DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (institutionId) REFERENCES institutions (id) ON DELETE CASCADE,
+    FOREIGN KEY (createdBy) REFERENCES users (id) ON DELETE SET NULL,
+    UNIQUE(institutionId, version)
+);
+
+-- Version of the rule set a score was computed with; NULL for the built-in rules
+ALTER TABLE risk_scores ADD COLUMN ruleVersion INTEGER;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_risk_rule_sets_active ON risk_rule_sets(institutionId) WHERE isActive = 1;
@@ -0,0 +1,44 @@
+-- Create risk_alerts table (rises of a student's risk level)
+CREATE TABLE IF NOT EXISTS risk_alerts (
+    id TEXT PRIMARY KEY,
+    studentId TEXT NOT NULL,
+    previousLevel TEXT,
+    newLevel TEXT NOT NULL CHECK (newLevel IN ('Düşük', 'Orta', 'Yüksek')),
+    score REAL,
+    status TEXT NOT NULL DEFAULT 'OPEN' CHECK (status IN ('OPEN', 'ESCALATED', 'ACKNOWLEDGED', 'RESOLVED')),
+    acknowledgedBy TEXT,
+    acknowledgedAt DATETIME,
+    escalatedAt DATETIME,
+    resolvedAt DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (acknowledgedBy) REFERENCES users (id) ON DELETE SET NULL
+);
+
+-- Create risk_alert_recipients table (who is told about an alert; the
+-- notification is created once the recipient is outside quiet hours)
+CREATE TABLE IF NOT EXISTS risk_alert_recipients (
+    id TEXT PRIMARY KEY,
+    alertId TEXT NOT NULL,
+    userId TEXT,
+    kind TEXT NOT NULL CHECK (kind IN ('ALERT', 'ESCALATION')),
+    notificationId TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (alertId) REFERENCES risk_alerts (id) ON DELETE CASCADE,
+    FOREIGN KEY (userId) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (notificationId) REFERENCES notification_logs (id) ON DELETE SET NULL
+);
+
+-- Create risk_alert_settings table (single row)
+CREATE TABLE IF NOT EXISTS risk_alert_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    escalationHours INTEGER NOT NULL DEFAULT 48,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO risk_alert_settings (id) VALUES (1);
+
+CREATE INDEX IF NOT EXISTS idx_risk_alerts_student ON risk_alerts(studentId);
+CREATE INDEX IF NOT EXISTS idx_risk_alerts_status ON risk_alerts(status);
+CREATE INDEX IF NOT EXISTS idx_risk_alert_recipients_alert ON risk_alert_recipients(alertId);
+CREATE INDEX IF NOT EXISTS idx_risk_alert_recipients_pending ON risk_alert_recipients(notificationId) WHERE notificationId IS NULL;
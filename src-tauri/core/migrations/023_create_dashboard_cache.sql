@@ -0,0 +1,114 @@
+-- Create dashboard_data_version table (single row, bumped by every write the dashboard reads)
+CREATE TABLE IF NOT EXISTS dashboard_data_version (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    version INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO dashboard_data_version (id) VALUES (1);
+
+-- Create dashboard_cache table (statistics per institution scope, '' for all)
+CREATE TABLE IF NOT EXISTS dashboard_cache (
+    scope TEXT PRIMARY KEY,
+    dataVersion INTEGER NOT NULL,
+    cacheDate TEXT NOT NULL,
+    stats TEXT NOT NULL,
+    computedAt DATETIME NOT NULL
+);
+
+-- Invalidate the dashboard cache on writes to students
+CREATE TRIGGER IF NOT EXISTS dashboard_students_insert AFTER INSERT ON students
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_students_update AFTER UPDATE ON students
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_students_delete AFTER DELETE ON students
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+
+-- Invalidate the dashboard cache on writes to risk_alerts
+CREATE TRIGGER IF NOT EXISTS dashboard_risk_alerts_insert AFTER INSERT ON risk_alerts
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_risk_alerts_update AFTER UPDATE ON risk_alerts
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_risk_alerts_delete AFTER DELETE ON risk_alerts
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+
+-- Invalidate the dashboard cache on writes to counseling_sessions
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_sessions_insert AFTER INSERT ON counseling_sessions
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_sessions_update AFTER UPDATE ON counseling_sessions
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_sessions_delete AFTER DELETE ON counseling_sessions
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+
+-- Invalidate the dashboard cache on writes to counseling_follow_ups
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_follow_ups_insert AFTER INSERT ON counseling_follow_ups
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_follow_ups_update AFTER UPDATE ON counseling_follow_ups
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_follow_ups_delete AFTER DELETE ON counseling_follow_ups
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+
+-- Invalidate the dashboard cache on writes to ai_suggestion_queue
+CREATE TRIGGER IF NOT EXISTS dashboard_ai_suggestion_queue_insert AFTER INSERT ON ai_suggestion_queue
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_ai_suggestion_queue_update AFTER UPDATE ON ai_suggestion_queue
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_ai_suggestion_queue_delete AFTER DELETE ON ai_suggestion_queue
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+
+-- Invalidate the dashboard cache on writes to attendance_records
+CREATE TRIGGER IF NOT EXISTS dashboard_attendance_records_insert AFTER INSERT ON attendance_records
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_attendance_records_update AFTER UPDATE ON attendance_records
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_attendance_records_delete AFTER DELETE ON attendance_records
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+
+-- Invalidate the dashboard cache on writes to counseling_reminders
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_reminders_insert AFTER INSERT ON counseling_reminders
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_reminders_update AFTER UPDATE ON counseling_reminders
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
+CREATE TRIGGER IF NOT EXISTS dashboard_counseling_reminders_delete AFTER DELETE ON counseling_reminders
+BEGIN
+    UPDATE dashboard_data_version SET version = version + 1 WHERE id = 1;
+END;
@@ -0,0 +1,15 @@
+-- Create exam_import_layouts table (where a publisher's result file keeps numbers, booklets and answers)
+CREATE TABLE IF NOT EXISTS exam_import_layouts (
+    id TEXT PRIMARY KEY,
+    publisher TEXT NOT NULL,
+    name TEXT NOT NULL,
+    format TEXT NOT NULL CHECK (format IN ('FIXED_WIDTH', 'CSV')),
+    examType TEXT NOT NULL DEFAULT 'DENEME' CHECK (examType IN ('LGS', 'YKS', 'TYT', 'AYT', 'YDT', 'DENEME', 'KONU_TARAMA', 'DİĞER')),
+    config TEXT NOT NULL,
+    notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (publisher, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_exam_results_exam ON exam_results(examName, examDate);
@@ -0,0 +1,2 @@
+-- Score type an AYT or YDT result's total score was calculated for (SAY, EA, SÖZ, DİL)
+ALTER TABLE exam_results ADD COLUMN scoreType TEXT;
@@ -0,0 +1,6 @@
+-- Link academic goals to the latest exam result counted toward them
+ALTER TABLE academic_goals ADD COLUMN lastExamResultId TEXT REFERENCES exam_results (id) ON DELETE SET NULL;
+-- When a goal was marked achieved or missed
+ALTER TABLE academic_goals ADD COLUMN closedAt TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_academic_goals_student_status ON academic_goals(studentId, status);
@@ -0,0 +1,34 @@
+-- Curriculum catalog fields of subjects. Catalog subjects carry the catalog
+-- name in source and a stable catalogKey; school subjects have source SCHOOL
+ALTER TABLE subjects ADD COLUMN examType TEXT;
+ALTER TABLE subjects ADD COLUMN examSubject TEXT;
+ALTER TABLE subjects ADD COLUMN source TEXT NOT NULL DEFAULT 'SCHOOL';
+ALTER TABLE subjects ADD COLUMN catalogKey TEXT;
+ALTER TABLE subjects ADD COLUMN curriculumVersion INTEGER;
+ALTER TABLE subjects ADD COLUMN sortOrder INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE subjects ADD COLUMN isArchived BOOLEAN NOT NULL DEFAULT 0;
+
+ALTER TABLE topics ADD COLUMN source TEXT NOT NULL DEFAULT 'SCHOOL';
+ALTER TABLE topics ADD COLUMN catalogKey TEXT;
+ALTER TABLE topics ADD COLUMN curriculumVersion INTEGER;
+ALTER TABLE topics ADD COLUMN sortOrder INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE topics ADD COLUMN isArchived BOOLEAN NOT NULL DEFAULT 0;
+
+-- Create curriculum_versions table (catalog versions merged into subjects and topics)
+CREATE TABLE IF NOT EXISTS curriculum_versions (
+    id TEXT PRIMARY KEY,
+    catalog TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    subjectCount INTEGER NOT NULL DEFAULT 0,
+    topicCount INTEGER NOT NULL DEFAULT 0,
+    importedBy TEXT,
+    imported_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (importedBy) REFERENCES users (id) ON DELETE SET NULL,
+    UNIQUE(catalog, version)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_subjects_catalog_key ON subjects(catalogKey) WHERE catalogKey IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_topics_catalog_key ON topics(catalogKey) WHERE catalogKey IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_subjects_exam_type ON subjects(examType);
+CREATE INDEX IF NOT EXISTS idx_topics_subject ON topics(subjectId);
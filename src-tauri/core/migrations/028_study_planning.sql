@@ -0,0 +1,7 @@
+-- Who assigned a study topic and when it was completed
+ALTER TABLE study_assignments ADD COLUMN assignedBy TEXT REFERENCES users (id) ON DELETE SET NULL;
+ALTER TABLE study_assignments ADD COLUMN completedAt TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_study_sessions_student ON study_sessions(studentId, startTime);
+CREATE INDEX IF NOT EXISTS idx_study_assignments_student ON study_assignments(studentId, status, dueDate);
+CREATE INDEX IF NOT EXISTS idx_study_assignments_status_due ON study_assignments(status, dueDate);
@@ -0,0 +1,26 @@
+-- Create exam_topic_results table (per-topic outcome of an exam result, from
+-- manual entry or a publisher's topic report). Topics named in a report are
+-- linked to the curriculum when their name matches
+CREATE TABLE IF NOT EXISTS exam_topic_results (
+    id TEXT PRIMARY KEY,
+    examResultId TEXT NOT NULL,
+    studentId TEXT NOT NULL,
+    subjectId TEXT,
+    topicId TEXT,
+    subjectName TEXT NOT NULL,
+    topicName TEXT NOT NULL,
+    questionCount INTEGER NOT NULL,
+    correct INTEGER NOT NULL DEFAULT 0,
+    wrong INTEGER NOT NULL DEFAULT 0,
+    empty INTEGER NOT NULL DEFAULT 0,
+    successRate REAL NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (examResultId) REFERENCES exam_results (id) ON DELETE CASCADE,
+    FOREIGN KEY (studentId) REFERENCES students (id) ON DELETE CASCADE,
+    FOREIGN KEY (subjectId) REFERENCES subjects (id) ON DELETE SET NULL,
+    FOREIGN KEY (topicId) REFERENCES topics (id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_exam_topic_results_result ON exam_topic_results(examResultId);
+CREATE INDEX IF NOT EXISTS idx_exam_topic_results_student ON exam_topic_results(studentId);
+CREATE INDEX IF NOT EXISTS idx_exam_topic_results_topic ON exam_topic_results(topicId);
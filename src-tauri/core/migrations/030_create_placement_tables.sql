@@ -0,0 +1,34 @@
+-- Create placement_tables table (a year's ÖSYM base scores of YKS programs or
+-- MEB base scores of LGS schools, imported from the published lists so that
+-- placement estimates work offline)
+CREATE TABLE IF NOT EXISTS placement_tables (
+    id TEXT PRIMARY KEY,
+    examType TEXT NOT NULL,
+    year INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    programCount INTEGER NOT NULL DEFAULT 0,
+    importedBy TEXT,
+    imported_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (importedBy) REFERENCES users (id) ON DELETE SET NULL,
+    UNIQUE(examType, year)
+);
+
+-- Create placement_programs table (one university program or high school
+-- program of a table with the lowest score it was filled with)
+CREATE TABLE IF NOT EXISTS placement_programs (
+    id TEXT PRIMARY KEY,
+    tableId TEXT NOT NULL,
+    code TEXT,
+    institutionName TEXT NOT NULL,
+    programName TEXT,
+    city TEXT,
+    schoolType TEXT,
+    scoreType TEXT NOT NULL,
+    quota INTEGER,
+    baseScore REAL NOT NULL,
+    baseRank INTEGER,
+    basePercentile REAL,
+    FOREIGN KEY (tableId) REFERENCES placement_tables (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_placement_programs_table ON placement_programs(tableId, scoreType, baseScore);
@@ -1,3 +1,25 @@
 pub mod connection;
 
 pub use connection::*;
+
+/// In-memory database with all migrations applied, for repository and service tests
+#[cfg(test)]
+pub(crate) async fn test_pool() -> sqlx::SqlitePool {
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use std::str::FromStr;
+
+    let options = SqliteConnectOptions::from_str("sqlite::memory:")
+        .expect("valid in-memory database url")
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("Failed to open test database");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+
+    pool
+}
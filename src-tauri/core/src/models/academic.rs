@@ -1,5 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+pub const GOAL_ACTIVE: &str = "active";
+pub const GOAL_ACHIEVED: &str = "achieved";
+pub const GOAL_MISSED: &str = "missed";
+pub const GOAL_CANCELLED: &str = "cancelled";
+pub const GOAL_STATUSES: &[&str] = &[GOAL_ACTIVE, GOAL_ACHIEVED, GOAL_MISSED, GOAL_CANCELLED];
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct AcademicRecord {
     pub id: i64,
@@ -12,16 +18,6 @@ pub struct AcademicRecord {
     pub created_at: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
-pub struct Intervention {
-    pub id: String,
-    pub studentId: String,
-    pub date: String,
-    pub title: String,
-    pub status: String,
-    pub created_at: String,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Subject {
     pub id: String,
@@ -29,6 +25,15 @@ pub struct Subject {
     pub code: Option<String>,
     pub description: Option<String>,
     pub color: Option<String>,
+    pub examType: Option<String>,
+    /// The exam subject key (`EXAM_SUBJECT_*`) the subject's questions count toward
+    pub examSubject: Option<String>,
+    /// The catalog the subject was imported from, or SCHOOL
+    pub source: String,
+    pub catalogKey: Option<String>,
+    pub curriculumVersion: Option<i64>,
+    pub sortOrder: i64,
+    pub isArchived: bool,
     pub created_at: String,
 }
 
@@ -40,6 +45,11 @@ pub struct Topic {
     pub description: Option<String>,
     pub difficulty: Option<String>,
     pub estimatedHours: i32,
+    pub source: String,
+    pub catalogKey: Option<String>,
+    pub curriculumVersion: Option<i64>,
+    pub sortOrder: i64,
+    pub isArchived: bool,
     pub created_at: String,
 }
 
@@ -48,6 +58,7 @@ pub struct Progress {
     pub id: String,
     pub studentId: String,
     pub topicId: String,
+    /// Minutes studied on the topic and minutes left of its estimated hours
     pub completed: i32,
     pub remaining: i32,
     pub lastStudied: Option<String>,
@@ -67,10 +78,24 @@ pub struct AcademicGoal {
     pub examType: Option<String>,
     pub deadline: Option<String>,
     pub status: String,
+    /// The latest exam result counted toward the goal, which set `currentScore`
+    pub lastExamResultId: Option<String>,
+    pub closedAt: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// An empty description, exam type or deadline clears it
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateAcademicGoalRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub targetScore: Option<f64>,
+    pub examType: Option<String>,
+    pub deadline: Option<String>,
+    pub status: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct StudySession {
     pub id: String,
@@ -104,6 +129,8 @@ pub struct StudyAssignment {
     pub dueDate: String,
     pub status: String,
     pub notes: Option<String>,
+    pub assignedBy: Option<String>,
+    pub completedAt: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -152,6 +179,7 @@ pub struct ExamResult {
     pub counselorNotes: Option<String>,
     pub actionPlan: Option<String>,
     pub notes: Option<String>,
+    pub scoreType: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+pub const ATTENDANCE_PRESENT: &str = "Var";
+pub const ATTENDANCE_EXCUSED: &str = "Özürlü";
+pub const ATTENDANCE_UNEXCUSED: &str = "Özürsüz";
+pub const ATTENDANCE_MEDICAL: &str = "Raporlu";
+pub const ATTENDANCE_LEAVE: &str = "İzinli";
+pub const ATTENDANCE_LATE: &str = "Geç";
+
+pub const ATTENDANCE_STATUSES: &[&str] = &[
+    ATTENDANCE_PRESENT,
+    ATTENDANCE_EXCUSED,
+    ATTENDANCE_UNEXCUSED,
+    ATTENDANCE_MEDICAL,
+    ATTENDANCE_LEAVE,
+    ATTENDANCE_LATE,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AttendanceRecord {
+    pub id: String,
+    pub studentId: String,
+    pub date: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub notes: Option<String>,
+    pub duration: f64,
+    pub schoolYear: Option<String>,
+    pub term: Option<String>,
+    pub source: String,
+    pub recordedBy: Option<String>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateAttendanceRequest {
+    pub studentId: String,
+    pub date: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub notes: Option<String>,
+    pub duration: Option<f64>,
+    pub recordedBy: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateAttendanceRequest {
+    pub status: Option<String>,
+    pub reason: Option<String>,
+    pub notes: Option<String>,
+    pub duration: Option<f64>,
+}
+
+/// Absence totals in days for one school year, or one term of it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AttendanceSummary {
+    pub studentId: String,
+    pub schoolYear: String,
+    pub term: Option<String>,
+    pub excusedDays: f64,
+    pub unexcusedDays: f64,
+    pub totalDays: f64,
+    pub lateCount: i64,
+    pub recordCount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentAttendanceReport {
+    pub studentId: String,
+    pub schoolYear: String,
+    pub year: AttendanceSummary,
+    pub terms: Vec<AttendanceSummary>,
+    pub thresholds: AttendanceThresholds,
+    pub unexcusedStatus: String,
+    pub totalStatus: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AttendanceThresholds {
+    pub unexcusedWarningDays: f64,
+    pub unexcusedFailureDays: f64,
+    pub totalWarningDays: f64,
+    pub totalFailureDays: f64,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AttendanceAlert {
+    pub id: String,
+    pub studentId: String,
+    pub schoolYear: String,
+    pub kind: String,
+    pub level: String,
+    pub days: f64,
+    pub notificationId: Option<String>,
+    pub created_at: String,
+}
+
+/// A row of an e-Okul daily absence report that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedAttendanceRow {
+    pub line: usize,
+    pub schoolNumber: Option<String>,
+    pub studentName: Option<String>,
+    pub className: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttendanceImportResult {
+    pub dates: Vec<String>,
+    pub totalRows: usize,
+    pub inserted: usize,
+    pub updated: usize,
+    pub unmatched: Vec<UnmatchedAttendanceRow>,
+}
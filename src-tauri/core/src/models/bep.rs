@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+pub const BEP_DRAFT: &str = "Taslak";
+pub const BEP_ACTIVE: &str = "Aktif";
+pub const BEP_COMPLETED: &str = "Tamamlandı";
+pub const BEP_CANCELLED: &str = "İptal";
+
+pub const BEP_STATUSES: &[&str] = &[BEP_DRAFT, BEP_ACTIVE, BEP_COMPLETED, BEP_CANCELLED];
+
+pub const BEP_GOAL_NOT_STARTED: &str = "Başlanmadı";
+pub const BEP_GOAL_IN_PROGRESS: &str = "Devam";
+pub const BEP_GOAL_COMPLETED: &str = "Tamamlandı";
+
+pub const BEP_DECISION_CONTINUE: &str = "Devam";
+pub const BEP_DECISION_REVISE: &str = "Revize";
+pub const BEP_DECISION_END: &str = "Sonlandır";
+
+pub const BEP_DECISIONS: &[&str] = &[BEP_DECISION_CONTINUE, BEP_DECISION_REVISE, BEP_DECISION_END];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BepPlan {
+    pub id: String,
+    pub studentId: String,
+    pub ramReportNumber: Option<String>,
+    pub ramReportDate: Option<String>,
+    pub ramReportSummary: Option<String>,
+    pub diagnosis: Option<String>,
+    pub educationalPlacement: Option<String>,
+    pub supportServices: Option<String>,
+    pub startDate: String,
+    pub endDate: Option<String>,
+    pub status: String,
+    pub coordinatorId: Option<String>,
+    pub reviewIntervalDays: i64,
+    pub nextReviewDate: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateBepPlanRequest {
+    pub studentId: String,
+    pub ramReportNumber: Option<String>,
+    pub ramReportDate: Option<String>,
+    pub ramReportSummary: Option<String>,
+    pub diagnosis: Option<String>,
+    pub educationalPlacement: Option<String>,
+    pub supportServices: Option<String>,
+    pub startDate: String,
+    pub endDate: Option<String>,
+    pub coordinatorId: Option<String>,
+    pub reviewIntervalDays: Option<i64>,
+    pub nextReviewDate: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Editable fields; status changes go through `ChangeBepPlanStatusRequest`
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateBepPlanRequest {
+    pub ramReportNumber: Option<String>,
+    pub ramReportDate: Option<String>,
+    pub ramReportSummary: Option<String>,
+    pub diagnosis: Option<String>,
+    pub educationalPlacement: Option<String>,
+    pub supportServices: Option<String>,
+    pub startDate: Option<String>,
+    pub endDate: Option<String>,
+    pub coordinatorId: Option<String>,
+    pub reviewIntervalDays: Option<i64>,
+    pub nextReviewDate: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangeBepPlanStatusRequest {
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BepGoal {
+    pub id: String,
+    pub planId: String,
+    pub subjectId: Option<String>,
+    #[sqlx(default)]
+    pub subjectName: Option<String>,
+    pub area: Option<String>,
+    pub longTermGoal: String,
+    pub shortTermGoal: Option<String>,
+    pub successCriteria: Option<String>,
+    pub method: Option<String>,
+    pub targetDate: Option<String>,
+    pub progress: i64,
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateBepGoalRequest {
+    pub subjectId: Option<String>,
+    pub area: Option<String>,
+    pub longTermGoal: String,
+    pub shortTermGoal: Option<String>,
+    pub successCriteria: Option<String>,
+    pub method: Option<String>,
+    pub targetDate: Option<String>,
+}
+
+/// Editable fields; progress goes through `RecordBepGoalProgressRequest`
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateBepGoalRequest {
+    pub subjectId: Option<String>,
+    pub area: Option<String>,
+    pub longTermGoal: Option<String>,
+    pub shortTermGoal: Option<String>,
+    pub successCriteria: Option<String>,
+    pub method: Option<String>,
+    pub targetDate: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BepGoalProgress {
+    pub id: String,
+    pub goalId: String,
+    pub evaluationId: Option<String>,
+    pub progress: i64,
+    pub note: Option<String>,
+    pub recordedBy: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordBepGoalProgressRequest {
+    pub progress: i64,
+    pub note: Option<String>,
+    pub recordedBy: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BepAccommodation {
+    pub id: String,
+    pub planId: String,
+    pub subjectId: Option<String>,
+    #[sqlx(default)]
+    pub subjectName: Option<String>,
+    pub category: Option<String>,
+    pub description: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateBepAccommodationRequest {
+    pub subjectId: Option<String>,
+    pub category: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BepResponsibleTeacher {
+    pub id: String,
+    pub planId: String,
+    pub userId: String,
+    #[sqlx(default)]
+    pub teacherName: Option<String>,
+    pub subjectId: Option<String>,
+    #[sqlx(default)]
+    pub subjectName: Option<String>,
+    pub responsibility: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssignBepTeacherRequest {
+    pub userId: String,
+    pub subjectId: Option<String>,
+    pub responsibility: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BepEvaluation {
+    pub id: String,
+    pub planId: String,
+    pub evaluationDate: String,
+    pub evaluatorId: Option<String>,
+    pub summary: String,
+    pub decision: String,
+    pub nextReviewDate: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BepGoalProgressEntry {
+    pub goalId: String,
+    pub progress: i64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateBepEvaluationRequest {
+    pub evaluationDate: String,
+    pub evaluatorId: Option<String>,
+    pub summary: String,
+    pub decision: Option<String>,
+    pub nextReviewDate: Option<String>,
+    pub goalProgress: Option<Vec<BepGoalProgressEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BepPlanDetail {
+    pub plan: BepPlan,
+    pub goals: Vec<BepGoal>,
+    pub accommodations: Vec<BepAccommodation>,
+    pub teachers: Vec<BepResponsibleTeacher>,
+    pub evaluations: Vec<BepEvaluation>,
+}
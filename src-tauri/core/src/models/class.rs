@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use super::{CategoryCount, Student};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SchoolClass {
+    pub id: String,
+    pub institutionId: Option<String>,
+    pub grade: i64,
+    pub section: String,
+    pub schoolYear: String,
+    /// Display name such as "9/A", also copied to `students.class`
+    pub name: String,
+    pub homeroomTeacherId: Option<String>,
+    #[sqlx(default)]
+    pub homeroomTeacherName: Option<String>,
+    pub counselorId: Option<String>,
+    #[sqlx(default)]
+    pub counselorName: Option<String>,
+    #[sqlx(default)]
+    pub studentCount: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateClassRequest {
+    pub grade: i64,
+    pub section: String,
+    /// Defaults to the current school year
+    pub schoolYear: Option<String>,
+    pub homeroomTeacherId: Option<String>,
+    pub counselorId: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateClassRequest {
+    pub grade: Option<i64>,
+    pub section: Option<String>,
+    pub homeroomTeacherId: Option<String>,
+    pub counselorId: Option<String>,
+}
+
+/// A class's average results in one exam
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ClassExamAverage {
+    pub examType: String,
+    pub examName: String,
+    pub examDate: String,
+    pub studentCount: i64,
+    pub averageScore: Option<f64>,
+    pub averageNet: Option<f64>,
+    pub averageTurkishNet: Option<f64>,
+    pub averageMathNet: Option<f64>,
+    pub averageScienceNet: Option<f64>,
+    pub averageSocialNet: Option<f64>,
+    pub averageForeignLanguageNet: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassOverview {
+    pub class: SchoolClass,
+    pub students: Vec<Student>,
+    pub riskDistribution: Vec<CategoryCount>,
+    pub examAverages: Vec<ClassExamAverage>,
+}
@@ -9,6 +9,9 @@ pub struct MeetingNote {
     pub meeting_type: String,
     pub note: String,
     pub plan: Option<String>,
+    pub isConfidential: bool,
+    #[sqlx(default)]
+    pub createdBy: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -53,6 +56,9 @@ pub struct CounselingSession {
     pub autoCompleted: bool,
     pub extensionGranted: bool,
     pub completed: bool,
+    pub isConfidential: bool,
+    #[sqlx(default)]
+    pub institutionId: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -75,6 +81,7 @@ pub struct ParentMeeting {
     pub outcomes: Option<String>,
     pub followUpActions: Option<String>,
     pub nextMeetingDate: Option<String>,
+    pub isConfidential: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -99,6 +106,7 @@ pub struct HomeVisit {
     pub notes: Option<String>,
     pub createdBy: Option<String>,
     pub createdAt: Option<String>,
+    pub isConfidential: bool,
     pub created_at: String,
     pub updated_at: String,
 }
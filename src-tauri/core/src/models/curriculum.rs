@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use super::{Subject, Topic};
+
+/// Source of subjects and topics added by the school rather than a catalog
+pub const CURRICULUM_SOURCE_SCHOOL: &str = "SCHOOL";
+pub const CURRICULUM_CATALOG_MEB: &str = "MEB";
+
+pub const TOPIC_EASY: &str = "EASY";
+pub const TOPIC_MEDIUM: &str = "MEDIUM";
+pub const TOPIC_HARD: &str = "HARD";
+pub const TOPIC_DIFFICULTIES: &[&str] = &[TOPIC_EASY, TOPIC_MEDIUM, TOPIC_HARD];
+
+/// A curriculum catalog file. Keys identify subjects and topics across
+/// versions, so a later version updates them in place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CurriculumCatalog {
+    pub catalog: String,
+    pub version: i64,
+    pub name: String,
+    pub subjects: Vec<CatalogSubject>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogSubject {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub examType: Option<String>,
+    #[serde(default)]
+    pub examSubject: Option<String>,
+    pub topics: Vec<CatalogTopic>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogTopic {
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub difficulty: Option<String>,
+    pub estimatedHours: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CurriculumVersion {
+    pub id: String,
+    pub catalog: String,
+    pub version: i64,
+    pub name: String,
+    pub subjectCount: i64,
+    pub topicCount: i64,
+    pub importedBy: Option<String>,
+    pub imported_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurriculumImportResult {
+    pub catalog: String,
+    pub version: i64,
+    pub subjectsAdded: usize,
+    pub subjectsUpdated: usize,
+    pub topicsAdded: usize,
+    pub topicsUpdated: usize,
+    /// Catalog subjects and topics left out of this version, archived rather than deleted
+    pub subjectsArchived: u64,
+    pub topicsArchived: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateSubjectRequest {
+    pub name: String,
+    pub code: Option<String>,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub examType: Option<String>,
+    pub examSubject: Option<String>,
+}
+
+/// An empty code, description, color, exam type or exam subject clears it
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateSubjectRequest {
+    pub name: Option<String>,
+    pub code: Option<String>,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub examType: Option<String>,
+    pub examSubject: Option<String>,
+    pub sortOrder: Option<i64>,
+    pub isArchived: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateTopicRequest {
+    pub subjectId: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub difficulty: Option<String>,
+    pub estimatedHours: Option<i32>,
+}
+
+/// An empty description or difficulty clears it
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateTopicRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub difficulty: Option<String>,
+    pub estimatedHours: Option<i32>,
+    pub sortOrder: Option<i64>,
+    pub isArchived: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectWithTopics {
+    pub subject: Subject,
+    pub topics: Vec<Topic>,
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+pub const CUSTOM_FIELD_TEXT: &str = "text";
+pub const CUSTOM_FIELD_NUMBER: &str = "number";
+pub const CUSTOM_FIELD_DATE: &str = "date";
+pub const CUSTOM_FIELD_ENUM: &str = "enum";
+
+pub const CUSTOM_FIELD_TYPES: &[&str] = &[CUSTOM_FIELD_TEXT, CUSTOM_FIELD_NUMBER, CUSTOM_FIELD_DATE, CUSTOM_FIELD_ENUM];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CustomFieldDefinition {
+    pub id: String,
+    pub key: String,
+    pub label: String,
+    pub fieldType: String,
+    /// JSON array of allowed values for enum fields
+    pub options: Option<String>,
+    pub sortOrder: i64,
+    pub isActive: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateCustomFieldRequest {
+    pub key: String,
+    pub label: String,
+    pub fieldType: String,
+    pub options: Option<Vec<String>>,
+    pub sortOrder: Option<i64>,
+}
+
+/// Key and type are fixed once values exist; only presentation can change
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateCustomFieldRequest {
+    pub label: Option<String>,
+    pub options: Option<Vec<String>>,
+    pub sortOrder: Option<i64>,
+    pub isActive: Option<bool>,
+}
+
+/// A student's stored value for one custom field
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentAttributeValue {
+    pub studentId: String,
+    pub fieldId: String,
+    pub value: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentAttribute {
+    pub fieldId: String,
+    pub key: String,
+    pub label: String,
+    pub fieldType: String,
+    pub value: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentTag {
+    pub studentId: String,
+    pub tag: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentCustomData {
+    pub attributes: Vec<StudentAttribute>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomFieldImportError {
+    pub line: usize,
+    pub schoolNumber: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomFieldImportResult {
+    pub studentsUpdated: usize,
+    pub valuesImported: usize,
+    pub tagsImported: usize,
+    pub errors: Vec<CustomFieldImportError>,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use super::{CategoryCount, CounselingReminder};
+
+/// Early-warning figures for the analytics dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardStats {
+    pub totalStudents: i64,
+    pub riskDistribution: Vec<CategoryCount>,
+    pub newAlertsThisWeek: i64,
+    pub openAlerts: i64,
+    pub sessionsThisWeek: i64,
+    pub sessionsThisMonth: i64,
+    pub overdueFollowUps: i64,
+    pub pendingAiSuggestions: i64,
+    /// Classes with the most absence days recently
+    pub attendanceHotspots: Vec<AttendanceHotspot>,
+    pub upcomingReminders: Vec<CounselingReminder>,
+    pub computedAt: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AttendanceHotspot {
+    pub className: String,
+    pub absentStudents: i64,
+    pub absenceDays: f64,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentDocument {
+    pub id: String,
+    pub studentId: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    #[sqlx(rename = "type")]
+    pub mime_type: String,
+    pub category: Option<String>,
+    pub storagePath: Option<String>,
+    pub size: Option<i64>,
+    pub sha256: Option<String>,
+    pub uploadedBy: Option<String>,
+    #[serde(skip_serializing)]
+    pub dataUrl: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttachDocumentRequest {
+    pub studentId: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub mime_type: Option<String>,
+    pub category: Option<String>,
+    pub uploadedBy: Option<String>,
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+pub const EXAM_LAYOUT_FIXED_WIDTH: &str = "FIXED_WIDTH";
+pub const EXAM_LAYOUT_CSV: &str = "CSV";
+pub const EXAM_LAYOUT_EXCEL: &str = "EXCEL";
+pub const EXAM_LAYOUT_FORMATS: &[&str] = &[EXAM_LAYOUT_FIXED_WIDTH, EXAM_LAYOUT_CSV, EXAM_LAYOUT_EXCEL];
+
+/// Where a value is in a result file row.
+///
+/// In fixed-width files it is `length` characters from `start` (1-based) of
+/// the line. In CSV and Excel files it is the cell under the `column` header
+/// (or the 1-based column number), optionally cut to `start`/`length` when
+/// several subjects share one answer string.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExamLayoutField {
+    pub column: Option<String>,
+    pub start: Option<usize>,
+    pub length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExamLayoutSubject {
+    pub subject: String,
+    pub answers: ExamLayoutField,
+}
+
+/// Layout of a publisher's result file, stored as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExamLayoutConfig {
+    /// Lines before the first student; in CSV and Excel files the last of them holds the headers
+    #[serde(default)]
+    pub headerRows: usize,
+    pub schoolNumber: ExamLayoutField,
+    #[serde(default)]
+    pub studentName: Option<ExamLayoutField>,
+    #[serde(default)]
+    pub booklet: Option<ExamLayoutField>,
+    pub subjects: Vec<ExamLayoutSubject>,
+    /// Wrong answers that cancel one correct answer, none for no penalty; exam
+    /// types with official scoring rules always use the official ratio
+    #[serde(default)]
+    pub wrongsPerCorrect: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExamImportLayout {
+    pub id: String,
+    pub publisher: String,
+    pub name: String,
+    pub format: String,
+    pub examType: String,
+    pub config: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateExamImportLayoutRequest {
+    pub publisher: String,
+    pub name: String,
+    pub format: String,
+    pub examType: Option<String>,
+    pub config: serde_json::Value,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateExamImportLayoutRequest {
+    pub publisher: Option<String>,
+    pub name: Option<String>,
+    pub format: Option<String>,
+    pub examType: Option<String>,
+    pub config: Option<serde_json::Value>,
+    pub notes: Option<String>,
+}
+
+/// Correct answers of one booklet, keyed by subject
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExamAnswerKey {
+    pub booklet: Option<String>,
+    pub answers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportExamResultsRequest {
+    pub layoutId: String,
+    pub examName: String,
+    pub examDate: String,
+    /// Defaults to the layout's exam type
+    pub examType: Option<String>,
+    /// Score type of AYT results (SAY, EA or SÖZ)
+    pub scoreType: Option<String>,
+    pub answerKeys: Vec<ExamAnswerKey>,
+    /// Text of the result file; for Excel layouts the workbook as a base64
+    /// data URL, of which the first sheet is read
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmatchedExamRow {
+    pub line: usize,
+    pub schoolNumber: Option<String>,
+    pub studentName: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamImportResult {
+    pub totalRows: usize,
+    pub inserted: usize,
+    /// Results of the same exam imported before and replaced
+    pub updated: usize,
+    pub studentIds: Vec<String>,
+    pub unmatched: Vec<UnmatchedExamRow>,
+}
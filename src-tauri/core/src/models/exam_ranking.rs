@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use super::SchoolClass;
+
+/// An exam result with the student's class and school, as the ranking job reads it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExamRankingEntry {
+    pub id: String,
+    pub studentId: String,
+    pub studentName: String,
+    pub schoolNumber: Option<String>,
+    pub institutionId: Option<String>,
+    pub institutionName: Option<String>,
+    /// The linked class id, or the free-text class name of students without a class link
+    pub classKey: Option<String>,
+    pub className: Option<String>,
+    pub examType: String,
+    pub isOfficial: bool,
+    pub totalScore: Option<f64>,
+    pub totalNet: Option<f64>,
+    pub turkishNet: Option<f64>,
+    pub mathNet: Option<f64>,
+    pub scienceNet: Option<f64>,
+    pub socialNet: Option<f64>,
+    pub foreignLanguageNet: Option<f64>,
+    pub percentileRank: Option<f64>,
+    pub comparedToClassAverage: Option<f64>,
+    pub schoolRank: Option<i32>,
+    pub classRank: Option<i32>,
+}
+
+/// Average results of a class or a school in one exam type of an exam
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamGroupAverage {
+    pub examType: String,
+    pub groupId: Option<String>,
+    pub name: String,
+    pub studentCount: usize,
+    pub averageScore: Option<f64>,
+    pub averageNet: Option<f64>,
+    pub averageTurkishNet: Option<f64>,
+    pub averageMathNet: Option<f64>,
+    pub averageScienceNet: Option<f64>,
+    pub averageSocialNet: Option<f64>,
+    pub averageForeignLanguageNet: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamRankingSummary {
+    pub examName: String,
+    pub examDate: String,
+    pub resultCount: usize,
+    /// Results whose ranks or class comparison changed
+    pub updated: usize,
+    pub schools: Vec<ExamGroupAverage>,
+    pub classes: Vec<ExamGroupAverage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassExamResultRow {
+    pub examResultId: String,
+    pub studentId: String,
+    pub studentName: String,
+    pub schoolNumber: Option<String>,
+    pub examType: String,
+    pub totalScore: Option<f64>,
+    pub totalNet: Option<f64>,
+    pub turkishNet: Option<f64>,
+    pub mathNet: Option<f64>,
+    pub scienceNet: Option<f64>,
+    pub socialNet: Option<f64>,
+    pub foreignLanguageNet: Option<f64>,
+    pub classRank: Option<i32>,
+    pub schoolRank: Option<i32>,
+    pub percentileRank: Option<f64>,
+    pub comparedToClassAverage: Option<f64>,
+}
+
+/// A class's results in one exam, ranked, with class and school averages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassExamResultTable {
+    pub class: SchoolClass,
+    pub examName: String,
+    pub examDate: String,
+    pub rows: Vec<ClassExamResultRow>,
+    pub classAverages: Vec<ExamGroupAverage>,
+    pub schoolAverages: Vec<ExamGroupAverage>,
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+pub const EXAM_TYPES: &[&str] = &["LGS", "YKS", "TYT", "AYT", "YDT", "DENEME", "KONU_TARAMA", "DİĞER"];
+
+pub const EXAM_TYPE_LGS: &str = "LGS";
+pub const EXAM_TYPE_YKS: &str = "YKS";
+pub const EXAM_TYPE_TYT: &str = "TYT";
+pub const EXAM_TYPE_AYT: &str = "AYT";
+pub const EXAM_TYPE_YDT: &str = "YDT";
+
+/// AYT and YDT score types
+pub const SCORE_TYPE_SAY: &str = "SAY";
+pub const SCORE_TYPE_EA: &str = "EA";
+pub const SCORE_TYPE_SOZ: &str = "SÖZ";
+pub const SCORE_TYPE_DIL: &str = "DİL";
+pub const SCORE_TYPES: &[&str] = &[SCORE_TYPE_SAY, SCORE_TYPE_EA, SCORE_TYPE_SOZ, SCORE_TYPE_DIL];
+
+/// Subjects of the exams. The first five have their own net and score
+/// columns in `exam_results`; the others add to the column of their field
+/// (literature to Turkish, physics to science, history to social studies)
+pub const EXAM_SUBJECT_TURKISH: &str = "turkish";
+pub const EXAM_SUBJECT_MATH: &str = "math";
+pub const EXAM_SUBJECT_SCIENCE: &str = "science";
+pub const EXAM_SUBJECT_SOCIAL: &str = "social";
+pub const EXAM_SUBJECT_FOREIGN_LANGUAGE: &str = "foreignLanguage";
+pub const EXAM_SUBJECT_HISTORY: &str = "history";
+pub const EXAM_SUBJECT_RELIGION: &str = "religion";
+pub const EXAM_SUBJECT_PHYSICS: &str = "physics";
+pub const EXAM_SUBJECT_CHEMISTRY: &str = "chemistry";
+pub const EXAM_SUBJECT_BIOLOGY: &str = "biology";
+pub const EXAM_SUBJECT_LITERATURE: &str = "literature";
+pub const EXAM_SUBJECT_HISTORY_1: &str = "history1";
+pub const EXAM_SUBJECT_GEOGRAPHY_1: &str = "geography1";
+pub const EXAM_SUBJECT_HISTORY_2: &str = "history2";
+pub const EXAM_SUBJECT_GEOGRAPHY_2: &str = "geography2";
+pub const EXAM_SUBJECT_PHILOSOPHY: &str = "philosophy";
+
+/// Raw answer counts of one subject; the rest of its questions are empty
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamSubjectCount {
+    pub subject: String,
+    pub correct: i32,
+    pub wrong: i32,
+}
+
+/// A subject's counts with its net; stored in `subjectBreakdown`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExamSubjectResult {
+    pub subject: String,
+    pub correct: i32,
+    pub wrong: i32,
+    pub empty: i32,
+    pub net: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CalculateExamScoreRequest {
+    pub examType: String,
+    pub scoreType: Option<String>,
+    pub subjects: Vec<ExamSubjectCount>,
+    /// TYT counts of the same session, needed for AYT and YDT scores
+    pub tytSubjects: Option<Vec<ExamSubjectCount>>,
+}
+
+/// Nets and score of an exam, as filled into `exam_results`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamScoreCalculation {
+    pub examType: String,
+    pub scoreType: Option<String>,
+    pub subjects: Vec<ExamSubjectResult>,
+    pub turkishNet: Option<f64>,
+    pub mathNet: Option<f64>,
+    pub scienceNet: Option<f64>,
+    pub socialNet: Option<f64>,
+    pub foreignLanguageNet: Option<f64>,
+    pub totalNet: f64,
+    /// None when the exam has no official score or the score cannot be computed
+    pub totalScore: Option<f64>,
+}
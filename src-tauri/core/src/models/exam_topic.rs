@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use super::UnmatchedExamRow;
+
+/// How a student did on one topic of an exam
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ExamTopicResult {
+    pub id: String,
+    pub examResultId: String,
+    pub studentId: String,
+    /// The curriculum subject and topic, when the names could be matched
+    pub subjectId: Option<String>,
+    pub topicId: Option<String>,
+    pub subjectName: String,
+    pub topicName: String,
+    pub questionCount: i32,
+    pub correct: i32,
+    pub wrong: i32,
+    pub empty: i32,
+    /// Percent of the topic's questions answered correctly
+    pub successRate: f64,
+    pub created_at: String,
+}
+
+/// A topic result with the exam it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentExamTopicResult {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub result: ExamTopicResult,
+    pub examName: String,
+    pub examDate: String,
+    pub examType: String,
+}
+
+/// One topic line entered by hand. Without a topic id the topic is looked up
+/// by subject and topic name; empty answers default to the unanswered rest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExamTopicResultInput {
+    pub topicId: Option<String>,
+    #[serde(default)]
+    pub subjectName: String,
+    #[serde(default)]
+    pub topicName: String,
+    pub questionCount: Option<i32>,
+    pub correct: i32,
+    pub wrong: i32,
+    pub empty: Option<i32>,
+}
+
+/// Topic results replacing those of one exam result, with the strength and
+/// weakness summaries written to the result
+#[derive(Debug, Clone)]
+pub struct ExamTopicResultSet {
+    pub examResultId: String,
+    pub results: Vec<ExamTopicResult>,
+    pub strengthAreas: Option<String>,
+    pub weaknessAreas: Option<String>,
+}
+
+/// Headers (or 1-based column numbers) of a topic report; unset columns are
+/// found by their usual Turkish headers
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExamTopicReportColumns {
+    pub schoolNumber: Option<String>,
+    pub subject: Option<String>,
+    pub topic: Option<String>,
+    pub questionCount: Option<String>,
+    pub correct: Option<String>,
+    pub wrong: Option<String>,
+    pub empty: Option<String>,
+}
+
+/// A publisher's topic report: one row per student and topic, for an exam
+/// whose results are already imported
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportExamTopicReportRequest {
+    pub examName: String,
+    pub examDate: String,
+    pub columns: Option<ExamTopicReportColumns>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamTopicImportResult {
+    pub totalRows: usize,
+    /// Exam results whose topic results were replaced
+    pub examResults: usize,
+    pub topicResults: usize,
+    pub studentIds: Vec<String>,
+    /// Report topics that match no curriculum topic, kept by name only
+    pub unlinkedTopics: Vec<String>,
+    pub unmatched: Vec<UnmatchedExamRow>,
+}
+
+/// A topic the student misses across their recent exams
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeakTopic {
+    pub topicId: Option<String>,
+    pub subjectId: Option<String>,
+    pub subjectName: String,
+    pub topicName: String,
+    pub examCount: usize,
+    /// Exams where the topic's success rate was under the weakness threshold
+    pub weakExamCount: usize,
+    pub questionCount: i32,
+    pub correct: i32,
+    pub wrong: i32,
+    pub empty: i32,
+    pub successRate: f64,
+    pub latestSuccessRate: f64,
+    pub lastExamDate: String,
+    /// Weak in at least two exams
+    pub isRecurring: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicWeaknessAnalysis {
+    pub studentId: String,
+    /// Exams with topic results taken into the analysis
+    pub examCount: usize,
+    /// Recurring weak topics first, then by success rate
+    pub weakTopics: Vec<WeakTopic>,
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+pub const TREND_IMPROVING: &str = "IMPROVING";
+pub const TREND_STABLE: &str = "STABLE";
+pub const TREND_DECLINING: &str = "DECLINING";
+
+/// Net series key of the total net, next to the `EXAM_SUBJECT_*` keys
+pub const EXAM_TREND_TOTAL: &str = "total";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamTrendPoint {
+    pub examId: String,
+    pub examName: String,
+    pub examDate: String,
+    pub net: f64,
+    /// Average of this and the preceding exams in the moving average window
+    pub movingAverage: f64,
+}
+
+/// A student's nets in one subject (or the total) across exams of one type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamSubjectTrend {
+    pub subject: String,
+    pub points: Vec<ExamTrendPoint>,
+    /// Least-squares change in net per exam; None with fewer than two exams
+    pub slope: Option<f64>,
+    /// Standard deviation of the nets
+    pub volatility: Option<f64>,
+    pub direction: String,
+    /// Percent the latest net fell below the average of the exams before it, when the fall is sharp
+    pub sharpDeclinePercent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamTypeTrend {
+    pub examType: String,
+    pub examCount: usize,
+    pub subjects: Vec<ExamSubjectTrend>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamNetChange {
+    pub subject: String,
+    pub change: f64,
+}
+
+/// Stored as JSON in `exam_results.comparedToPrevious`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamPreviousComparison {
+    pub previousExamId: String,
+    pub previousExamName: String,
+    pub previousExamDate: String,
+    pub totalNetChange: Option<f64>,
+    pub totalScoreChange: Option<f64>,
+    pub subjectChanges: Vec<ExamNetChange>,
+}
+
+/// Stored as JSON in `exam_results.comparedToGoal`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExamGoalComparison {
+    pub goalId: String,
+    pub goalTitle: String,
+    pub targetScore: f64,
+    /// The exam's total score, or its total net when it has no score
+    pub achieved: f64,
+    pub difference: f64,
+    pub met: bool,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use super::{Guardian, Student};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Family {
+    pub id: String,
+    pub name: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A family member with the indicators a counselor needs during a family crisis
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FamilyMemberSummary {
+    pub id: String,
+    pub name: String,
+    pub surname: String,
+    pub class: Option<String>,
+    pub birthDate: Option<String>,
+    pub risk: String,
+    pub openIncidents: i64,
+    pub pendingFollowUps: i64,
+    pub activeInterventions: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FamilyUnit {
+    pub family: Family,
+    pub members: Vec<FamilyMemberSummary>,
+    pub sharedGuardians: Vec<Guardian>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiblingSuggestion {
+    pub student: Student,
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateFamilyRequest {
+    pub name: String,
+    pub notes: Option<String>,
+    pub studentIds: Vec<String>,
+}
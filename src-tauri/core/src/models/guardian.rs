@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Guardian {
+    pub id: String,
+    pub name: String,
+    pub phone: Option<String>,
+    pub secondaryPhone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub occupation: Option<String>,
+    pub preferredLanguage: String,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A guardian as seen from one student, including the per-student link details
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentGuardian {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub guardian: Guardian,
+    pub studentId: String,
+    pub relationship: String,
+    pub isPrimary: bool,
+    pub hasCustody: bool,
+    pub livesWithStudent: bool,
+    pub contactRestricted: bool,
+    pub restrictionNotes: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateGuardianRequest {
+    pub name: String,
+    pub phone: Option<String>,
+    pub secondaryPhone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub occupation: Option<String>,
+    pub preferredLanguage: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateGuardianRequest {
+    pub name: Option<String>,
+    pub phone: Option<String>,
+    pub secondaryPhone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+    pub occupation: Option<String>,
+    pub preferredLanguage: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkGuardianRequest {
+    pub studentId: String,
+    pub guardianId: String,
+    pub relationship: String,
+    pub isPrimary: Option<bool>,
+    pub hasCustody: Option<bool>,
+    pub livesWithStudent: Option<bool>,
+    pub contactRestricted: Option<bool>,
+    pub restrictionNotes: Option<String>,
+}
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Institution {
+    pub id: String,
+    pub name: String,
+    /// MEB institution code
+    pub code: Option<String>,
+    pub district: Option<String>,
+    pub isActive: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateInstitutionRequest {
+    pub name: String,
+    pub code: Option<String>,
+    pub district: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateInstitutionRequest {
+    pub name: Option<String>,
+    pub code: Option<String>,
+    pub district: Option<String>,
+    pub isActive: Option<bool>,
+}
+
+/// Headline numbers for one institution
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct InstitutionSummary {
+    pub institutionId: String,
+    pub institutionName: String,
+    pub district: Option<String>,
+    pub studentCount: i64,
+    pub activeStudentCount: i64,
+    pub highRiskStudentCount: i64,
+    pub userCount: i64,
+    pub classCount: i64,
+    pub counselingSessionCount: i64,
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use super::{BehaviorIncident, CounselingSession};
+
+pub const INTERVENTION_PLANNED: &str = "Planlandı";
+pub const INTERVENTION_IN_PROGRESS: &str = "Devam";
+pub const INTERVENTION_COMPLETED: &str = "Tamamlandı";
+
+pub const INTERVENTION_PRIORITIES: &[&str] = &["LOW", "NORMAL", "HIGH", "URGENT"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Intervention {
+    pub id: String,
+    pub studentId: String,
+    pub date: String,
+    pub title: String,
+    pub status: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub ownerId: Option<String>,
+    pub startDate: Option<String>,
+    pub targetDate: Option<String>,
+    pub completedAt: Option<String>,
+    pub outcome: Option<String>,
+    pub priority: String,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateInterventionRequest {
+    pub studentId: String,
+    pub title: String,
+    pub date: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub ownerId: Option<String>,
+    pub startDate: Option<String>,
+    pub targetDate: Option<String>,
+    pub priority: Option<String>,
+    pub sessionIds: Option<Vec<String>>,
+    pub incidentIds: Option<Vec<String>>,
+}
+
+/// Editable fields; status changes go through `ChangeInterventionStatusRequest`
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateInterventionRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub ownerId: Option<String>,
+    pub startDate: Option<String>,
+    pub targetDate: Option<String>,
+    pub priority: Option<String>,
+    pub outcome: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangeInterventionStatusRequest {
+    pub status: String,
+    pub note: Option<String>,
+    pub outcome: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct InterventionProgressNote {
+    pub id: String,
+    pub interventionId: String,
+    pub authorId: Option<String>,
+    pub note: String,
+    pub progress: Option<i64>,
+    pub fromStatus: Option<String>,
+    pub toStatus: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddInterventionNoteRequest {
+    pub note: String,
+    pub progress: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterventionDetail {
+    pub intervention: Intervention,
+    pub sessions: Vec<CounselingSession>,
+    pub incidents: Vec<BehaviorIncident>,
+    pub notes: Vec<InterventionProgressNote>,
+}
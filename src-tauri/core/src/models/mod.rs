@@ -6,6 +6,27 @@ pub mod survey;
 pub mod counseling;
 pub mod notification;
 pub mod settings;
+pub mod document;
+pub mod guardian;
+pub mod family;
+pub mod attendance;
+pub mod intervention;
+pub mod profile;
+pub mod timeline;
+pub mod bep;
+pub mod custom_field;
+pub mod class;
+pub mod institution;
+pub mod risk;
+pub mod dashboard;
+pub mod exam_import;
+pub mod exam_ranking;
+pub mod exam_score;
+pub mod exam_trend;
+pub mod curriculum;
+pub mod study;
+pub mod exam_topic;
+pub mod placement;
 
 pub use user::*;
 pub use student::*;
@@ -15,3 +36,24 @@ pub use survey::*;
 pub use counseling::*;
 pub use notification::*;
 pub use settings::*;
+pub use document::*;
+pub use guardian::*;
+pub use family::*;
+pub use attendance::*;
+pub use intervention::*;
+pub use profile::*;
+pub use timeline::*;
+pub use bep::*;
+pub use custom_field::*;
+pub use class::*;
+pub use institution::*;
+pub use risk::*;
+pub use dashboard::*;
+pub use exam_import::*;
+pub use exam_ranking::*;
+pub use exam_score::*;
+pub use exam_trend::*;
+pub use curriculum::*;
+pub use study::*;
+pub use exam_topic::*;
+pub use placement::*;
@@ -78,6 +78,7 @@ pub struct ParentAccessToken {
     pub createdBy: Option<String>,
     pub lastAccessedAt: Option<String>,
     pub accessCount: i32,
+    pub guardianId: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
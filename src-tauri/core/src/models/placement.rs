@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use super::{EXAM_TYPE_LGS, EXAM_TYPE_YKS};
+
+/// Exams with published base-score tables
+pub const PLACEMENT_EXAM_TYPES: &[&str] = &[EXAM_TYPE_YKS, EXAM_TYPE_LGS];
+
+/// How a program's base score compares with the student's score
+pub const PLACEMENT_REACHABLE: &str = "reachable";
+pub const PLACEMENT_BORDERLINE: &str = "borderline";
+pub const PLACEMENT_REACH: &str = "reach";
+
+/// A year's published base-score table of YKS programs or LGS schools
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PlacementTable {
+    pub id: String,
+    /// YKS or LGS
+    pub examType: String,
+    pub year: i64,
+    pub name: String,
+    pub programCount: i64,
+    pub importedBy: Option<String>,
+    pub imported_at: String,
+}
+
+/// A university program or high school program with the lowest score and
+/// rank it was filled with that year
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PlacementProgram {
+    pub id: String,
+    pub tableId: String,
+    pub code: Option<String>,
+    /// University or school
+    pub institutionName: String,
+    pub programName: Option<String>,
+    pub city: Option<String>,
+    /// State or foundation university, or the kind of high school
+    pub schoolType: Option<String>,
+    /// SAY, EA, SÖZ, DİL or TYT for YKS programs, LGS for schools
+    pub scoreType: String,
+    pub quota: Option<i32>,
+    pub baseScore: f64,
+    pub baseRank: Option<i64>,
+    /// LGS percentile of the base score
+    pub basePercentile: Option<f64>,
+}
+
+/// Headers (or 1-based column numbers) of a base-score table; unset columns
+/// are found by the headers ÖSYM and MEB use
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlacementTableColumns {
+    pub code: Option<String>,
+    pub institution: Option<String>,
+    pub program: Option<String>,
+    pub city: Option<String>,
+    pub schoolType: Option<String>,
+    pub scoreType: Option<String>,
+    pub quota: Option<String>,
+    pub baseScore: Option<String>,
+    pub baseRank: Option<String>,
+    pub basePercentile: Option<String>,
+}
+
+/// A base-score table file; importing a year again replaces its programs
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImportPlacementTableRequest {
+    pub examType: String,
+    pub year: i64,
+    pub name: Option<String>,
+    pub columns: Option<PlacementTableColumns>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedPlacementRow {
+    pub line: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementImportResult {
+    pub table: PlacementTable,
+    pub totalRows: usize,
+    pub programs: usize,
+    /// Rows left out, such as programs that were not filled and have no base score
+    pub skipped: Vec<SkippedPlacementRow>,
+}
+
+/// Which programs to estimate for. The exam type defaults to the one of the
+/// student's latest scored result and the year to the latest imported table.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlacementEstimateRequest {
+    pub studentId: String,
+    pub examType: Option<String>,
+    pub year: Option<i64>,
+    pub scoreType: Option<String>,
+    pub city: Option<String>,
+    pub schoolType: Option<String>,
+    /// Diploma grade (50-100); its school points (OBP) are added to YKS scores
+    /// as in ÖSYM placement scores
+    pub diplomaGrade: Option<f64>,
+    /// Programs listed per category, 50 by default
+    pub limit: Option<usize>,
+}
+
+/// The student's latest score of a score type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementScore {
+    pub scoreType: String,
+    pub examScore: f64,
+    /// The exam score with the diploma grade's school points
+    pub score: f64,
+    pub percentileRank: Option<f64>,
+    pub examResultId: String,
+    pub examName: String,
+    pub examDate: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementOption {
+    #[serde(flatten)]
+    pub program: PlacementProgram,
+    pub studentScore: f64,
+    /// Student's score minus the base score
+    pub margin: f64,
+    pub category: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacementEstimate {
+    pub studentId: String,
+    pub examType: String,
+    pub year: i64,
+    pub tableName: String,
+    pub scores: Vec<PlacementScore>,
+    /// Base score well under the student's score, highest base score first
+    pub reachable: Vec<PlacementOption>,
+    /// Base score close to the student's score, closest first
+    pub borderline: Vec<PlacementOption>,
+    /// Base score somewhat above the student's score, nearest first
+    pub reach: Vec<PlacementOption>,
+}
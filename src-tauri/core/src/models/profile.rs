@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use super::{
+    AcademicGoal, AiSuggestion, AttendanceSummary, BehaviorIncident, CounselingFollowUp, CounselingSession,
+    ExamResult, Intervention, Student, StudentGuardian,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CategoryCount {
+    pub category: String,
+    pub count: i64,
+}
+
+/// Record counts for one student. Counts of records the profile lists in full
+/// are taken from those lists; the rest come from a single query.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudentProfileSummary {
+    pub examCount: i64,
+    #[sqlx(default)]
+    pub incidentCount: i64,
+    #[sqlx(default)]
+    pub openIncidentCount: i64,
+    pub sessionCount: i64,
+    pub lastSessionDate: Option<String>,
+    #[sqlx(default)]
+    pub openFollowUpCount: i64,
+    #[sqlx(default)]
+    pub activeGoalCount: i64,
+    #[sqlx(default)]
+    pub activeInterventionCount: i64,
+    #[sqlx(default)]
+    pub pendingSuggestionCount: i64,
+    pub documentCount: i64,
+}
+
+/// Everything the student page shows, loaded in one call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudentProfile {
+    pub student: Student,
+    pub guardians: Vec<StudentGuardian>,
+    pub summary: StudentProfileSummary,
+    pub latestExam: Option<ExamResult>,
+    pub recentExams: Vec<ExamResult>,
+    pub incidentsByCategory: Vec<CategoryCount>,
+    pub recentIncidents: Vec<BehaviorIncident>,
+    pub recentSessions: Vec<CounselingSession>,
+    pub openFollowUps: Vec<CounselingFollowUp>,
+    pub attendance: AttendanceSummary,
+    pub activeGoals: Vec<AcademicGoal>,
+    pub activeInterventions: Vec<Intervention>,
+    pub aiSuggestions: Vec<AiSuggestion>,
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use super::CategoryCount;
+
+pub const RISK_LOW: &str = "Düşük";
+pub const RISK_MEDIUM: &str = "Orta";
+pub const RISK_HIGH: &str = "Yüksek";
+pub const RISK_LEVELS: &[&str] = &[RISK_LOW, RISK_MEDIUM, RISK_HIGH];
+
+pub const RISK_FACTOR_ATTENDANCE: &str = "ATTENDANCE";
+pub const RISK_FACTOR_BEHAVIOR: &str = "BEHAVIOR";
+pub const RISK_FACTOR_EXAM_DECLINE: &str = "EXAM_DECLINE";
+pub const RISK_FACTOR_FOLLOW_UPS: &str = "FOLLOW_UPS";
+pub const RISK_FACTOR_SURVEY_FLAGS: &str = "SURVEY_FLAGS";
+
+/// Weights and limits of the risk score.
+///
+/// Each factor contributes up to its weight; the factor reaches its full
+/// weight at the given maximum (absence days use the attendance thresholds).
+/// Stored as JSON in rule sets, where omitted keys take the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskRules {
+    /// How far back incidents and survey answers count
+    pub lookbackDays: i64,
+    pub mediumThreshold: f64,
+    pub highThreshold: f64,
+    pub attendanceWeight: f64,
+    pub behaviorWeight: f64,
+    /// Intensity points at which behavior reaches its full weight
+    pub behaviorMaxPoints: f64,
+    pub lowIntensityPoints: f64,
+    pub mediumIntensityPoints: f64,
+    pub highIntensityPoints: f64,
+    pub examDeclineWeight: f64,
+    /// Net decline (percent) at which exam decline reaches its full weight
+    pub examDeclineMaxPercent: f64,
+    /// Number of recent exams compared with the same number before them
+    pub examWindow: usize,
+    pub followUpWeight: f64,
+    pub followUpMaxPoints: f64,
+    /// Points of an overdue follow-up; an open one on schedule counts 1
+    pub overdueFollowUpPoints: f64,
+    pub surveyFlagWeight: f64,
+    pub surveyFlagMax: f64,
+    /// Conditions that raise the level regardless of the total score
+    pub levelRules: Vec<RiskLevelRule>,
+}
+
+impl Default for RiskRules {
+    fn default() -> Self {
+        Self {
+            lookbackDays: 90,
+            mediumThreshold: 30.0,
+            highThreshold: 60.0,
+            attendanceWeight: 30.0,
+            behaviorWeight: 25.0,
+            behaviorMaxPoints: 6.0,
+            lowIntensityPoints: 1.0,
+            mediumIntensityPoints: 2.0,
+            highIntensityPoints: 3.0,
+            examDeclineWeight: 20.0,
+            examDeclineMaxPercent: 20.0,
+            examWindow: 3,
+            followUpWeight: 10.0,
+            followUpMaxPoints: 4.0,
+            overdueFollowUpPoints: 2.0,
+            surveyFlagWeight: 15.0,
+            surveyFlagMax: 3.0,
+            levelRules: Vec::new(),
+        }
+    }
+}
+
+/// Raise the level to at least `level` once a factor reaches `minRatio` of its weight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RiskLevelRule {
+    pub factor: String,
+    pub minRatio: f64,
+    pub level: String,
+}
+
+/// What one factor added to a student's score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskFactorContribution {
+    pub factor: String,
+    pub label: String,
+    pub weight: f64,
+    /// Share of the weight reached, 0 to 1
+    pub ratio: f64,
+    pub points: f64,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskScore {
+    pub studentId: String,
+    pub score: f64,
+    pub level: String,
+    /// JSON array of `RiskFactorContribution`
+    pub factors: String,
+    pub calculatedAt: String,
+    #[sqlx(default)]
+    pub ruleVersion: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskOverride {
+    pub studentId: String,
+    pub level: String,
+    pub reason: String,
+    pub overriddenBy: Option<String>,
+    #[sqlx(default)]
+    pub overriddenByName: Option<String>,
+    pub expiresAt: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetRiskOverrideRequest {
+    pub level: String,
+    pub reason: String,
+    /// Date the override stops applying, YYYY-MM-DD
+    pub expiresAt: Option<String>,
+}
+
+/// A student's computed risk with its explanation and any manual override
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskAssessment {
+    pub studentId: String,
+    pub score: f64,
+    pub computedLevel: String,
+    /// Level in effect: the override's when there is one, else the computed one
+    pub level: String,
+    pub factors: Vec<RiskFactorContribution>,
+    pub riskOverride: Option<RiskOverride>,
+    pub calculatedAt: String,
+    /// Rule set version the score was computed with; None for the built-in rules
+    pub ruleVersion: Option<i64>,
+}
+
+/// A saved version of the risk rules
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskRuleSet {
+    pub id: String,
+    pub institutionId: String,
+    pub version: i64,
+    pub name: String,
+    /// JSON of `RiskRules`
+    pub rules: String,
+    pub notes: Option<String>,
+    pub isActive: bool,
+    pub createdBy: Option<String>,
+    #[sqlx(default)]
+    pub createdByName: Option<String>,
+    pub activatedAt: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateRiskRuleSetRequest {
+    pub name: String,
+    /// Rules as JSON; omitted keys take the defaults
+    pub rules: serde_json::Value,
+    pub notes: Option<String>,
+}
+
+/// A student whose risk would change under a rule set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskPreviewChange {
+    pub studentId: String,
+    pub studentName: String,
+    pub currentScore: Option<f64>,
+    pub currentLevel: Option<String>,
+    pub newScore: f64,
+    pub newLevel: String,
+    /// Whether a manual override keeps the level in effect unchanged
+    pub overridden: bool,
+}
+
+/// What activating a rule set would change, computed from the current data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskRulePreview {
+    pub ruleSetId: String,
+    pub version: i64,
+    pub evaluatedCount: usize,
+    pub currentLevelCounts: Vec<CategoryCount>,
+    pub newLevelCounts: Vec<CategoryCount>,
+    /// Students whose computed level changes
+    pub changes: Vec<RiskPreviewChange>,
+}
+
+pub const RISK_ALERT_OPEN: &str = "OPEN";
+pub const RISK_ALERT_ESCALATED: &str = "ESCALATED";
+pub const RISK_ALERT_ACKNOWLEDGED: &str = "ACKNOWLEDGED";
+pub const RISK_ALERT_RESOLVED: &str = "RESOLVED";
+
+/// A rise of a student's risk level, open until someone acknowledges it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskAlert {
+    pub id: String,
+    pub studentId: String,
+    #[sqlx(default)]
+    pub studentName: Option<String>,
+    pub previousLevel: Option<String>,
+    pub newLevel: String,
+    pub score: Option<f64>,
+    pub status: String,
+    pub acknowledgedBy: Option<String>,
+    #[sqlx(default)]
+    pub acknowledgedByName: Option<String>,
+    pub acknowledgedAt: Option<String>,
+    pub escalatedAt: Option<String>,
+    pub resolvedAt: Option<String>,
+    pub created_at: String,
+}
+
+/// A recipient of a risk alert whose notification may still wait for quiet hours to end
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskAlertRecipient {
+    pub id: String,
+    pub alertId: String,
+    pub userId: Option<String>,
+    pub kind: String,
+    pub notificationId: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskAlertSettings {
+    /// Hours an alert may stay unacknowledged before admins are told
+    pub escalationHours: i64,
+    #[serde(default)]
+    pub updated_at: String,
+}
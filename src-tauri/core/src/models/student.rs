@@ -19,6 +19,9 @@ pub struct Student {
     pub risk: String,
     pub created_at: String,
     pub updated_at: String,
+    pub schoolNumber: Option<String>,
+    pub classId: Option<String>,
+    pub institutionId: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -34,8 +37,12 @@ pub struct CreateStudentRequest {
     pub parentContact: Option<String>,
     pub notes: Option<String>,
     pub gender: Option<String>,
+    pub schoolNumber: Option<String>,
+    pub classId: Option<String>,
 }
 
+/// The risk level is not edited here; it is computed, or overridden with a
+/// reason, by the risk service
 #[derive(Debug, Clone, Deserialize)]
 pub struct UpdateStudentRequest {
     pub name: Option<String>,
@@ -48,5 +55,29 @@ pub struct UpdateStudentRequest {
     pub parentContact: Option<String>,
     pub notes: Option<String>,
     pub gender: Option<String>,
+    pub schoolNumber: Option<String>,
+    pub classId: Option<String>,
+}
+
+/// Filters for the student list; every given condition must match
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StudentListQuery {
+    pub search: Option<String>,
+    pub class: Option<String>,
+    pub classId: Option<String>,
+    pub institutionId: Option<String>,
+    pub status: Option<String>,
     pub risk: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub attributes: Option<Vec<StudentAttributeFilter>>,
+}
+
+/// Match a custom field by key: exact `value`, or a `min`/`max` range for
+/// number and date fields
+#[derive(Debug, Clone, Deserialize)]
+pub struct StudentAttributeFilter {
+    pub key: String,
+    pub value: Option<String>,
+    pub min: Option<String>,
+    pub max: Option<String>,
 }
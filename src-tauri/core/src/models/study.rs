@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use super::{Progress, StudyAssignment};
+
+pub const ASSIGNMENT_PENDING: &str = "pending";
+pub const ASSIGNMENT_COMPLETED: &str = "completed";
+pub const ASSIGNMENT_OVERDUE: &str = "overdue";
+pub const ASSIGNMENT_STATUSES: &[&str] = &[ASSIGNMENT_PENDING, ASSIGNMENT_COMPLETED, ASSIGNMENT_OVERDUE];
+
+/// A study session on one topic. The duration in minutes is taken from the
+/// end time when not given.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordStudySessionRequest {
+    pub studentId: String,
+    pub topicId: String,
+    pub startTime: String,
+    pub endTime: Option<String>,
+    pub duration: Option<i32>,
+    pub notes: Option<String>,
+    /// How well the session went, 0-100
+    pub efficiency: Option<f64>,
+}
+
+/// Correct a student's progress on a topic by hand; an empty note clears it
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateProgressRequest {
+    pub completed: Option<i32>,
+    pub remaining: Option<i32>,
+    pub notes: Option<String>,
+}
+
+/// A student's progress on a topic with the topic and its sessions
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TopicProgress {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub progress: Progress,
+    pub topicName: String,
+    pub subjectId: String,
+    pub subjectName: String,
+    pub examType: Option<String>,
+    pub difficulty: Option<String>,
+    pub estimatedHours: i32,
+    pub sessionCount: i64,
+    pub averageEfficiency: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateStudyAssignmentRequest {
+    pub studentId: String,
+    pub topicId: String,
+    pub dueDate: String,
+    pub notes: Option<String>,
+}
+
+/// An empty note clears it
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateStudyAssignmentRequest {
+    pub dueDate: Option<String>,
+    pub status: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct StudyAssignmentDetail {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub assignment: StudyAssignment,
+    pub topicName: String,
+    pub subjectId: String,
+    pub subjectName: String,
+}
+
+/// An exam the student is preparing for: an active goal's deadline or an
+/// exam result entered ahead of its date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingExam {
+    pub examType: String,
+    pub examDate: String,
+    pub title: String,
+}
+
+/// A topic the weekly plan can schedule, with what the student has left on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudyPlanTopic {
+    pub topicId: String,
+    pub topicName: String,
+    pub subjectName: String,
+    pub examType: Option<String>,
+    pub difficulty: Option<String>,
+    pub remainingMinutes: i32,
+    /// Share of the topic's estimated time already studied, 0-1
+    pub completion: f64,
+    pub averageEfficiency: Option<f64>,
+    /// Success rate in recent exams, when the topic is among the student's weak topics
+    pub examSuccessRate: Option<f64>,
+    /// The earliest open assignment of the topic
+    pub assignmentId: Option<String>,
+    pub dueDate: Option<String>,
+    pub isOverdue: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudyPlanItem {
+    pub topicId: String,
+    pub topicName: String,
+    pub subjectName: String,
+    pub minutes: i32,
+    pub priority: f64,
+    pub reasons: Vec<String>,
+    pub assignmentId: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StudyPlanDay {
+    pub date: String,
+    pub totalMinutes: i32,
+    pub items: Vec<StudyPlanItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyStudyPlan {
+    pub studentId: String,
+    pub weekStart: String,
+    pub dailyMinutes: i32,
+    pub upcomingExams: Vec<UpcomingExam>,
+    pub days: Vec<StudyPlanDay>,
+    /// Minutes of the prioritized topics that did not fit into the week
+    pub unscheduledMinutes: i32,
+}
@@ -27,6 +27,9 @@ pub struct SurveyQuestion {
     pub orderIndex: i32,
     pub options: Option<String>,
     pub validation: Option<String>,
+    /// JSON array of answers that raise a risk flag
+    #[sqlx(default)]
+    pub riskAnswers: Option<String>,
     pub created_at: String,
 }
 
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+pub const TIMELINE_EXAM_RESULT: &str = "EXAM_RESULT";
+pub const TIMELINE_BEHAVIOR_INCIDENT: &str = "BEHAVIOR_INCIDENT";
+pub const TIMELINE_COUNSELING_SESSION: &str = "COUNSELING_SESSION";
+pub const TIMELINE_MEETING_NOTE: &str = "MEETING_NOTE";
+pub const TIMELINE_PARENT_MEETING: &str = "PARENT_MEETING";
+pub const TIMELINE_HOME_VISIT: &str = "HOME_VISIT";
+pub const TIMELINE_NOTIFICATION: &str = "NOTIFICATION";
+pub const TIMELINE_AI_SUGGESTION: &str = "AI_SUGGESTION";
+pub const TIMELINE_INTERVENTION: &str = "INTERVENTION";
+pub const TIMELINE_ATTENDANCE: &str = "ATTENDANCE";
+
+/// One entry of a student's timeline; `sourceId` is the id of the underlying record
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TimelineEvent {
+    pub eventType: String,
+    pub sourceId: String,
+    pub date: String,
+    pub title: String,
+    pub summary: Option<String>,
+    pub category: Option<String>,
+    pub isConfidential: bool,
+    #[serde(skip_serializing)]
+    pub ownerId: Option<String>,
+    #[sqlx(default)]
+    pub redacted: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TimelineQuery {
+    pub studentId: String,
+    pub eventTypes: Option<Vec<String>>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub page: Option<i64>,
+    pub pageSize: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelinePage {
+    pub events: Vec<TimelineEvent>,
+    pub total: i64,
+    pub page: i64,
+    pub pageSize: i64,
+    pub hasMore: bool,
+}
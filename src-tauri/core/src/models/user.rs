@@ -10,6 +10,10 @@ pub struct User {
     pub role: String,
     pub institution: String,
     pub isActive: bool,
+    pub isDistrictAdmin: bool,
+    /// Institution the current session works in; only set for session lookups
+    #[sqlx(default)]
+    pub activeInstitutionId: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -1,4 +1,4 @@
-use crate::models::{ExamResult, BehaviorIncident, AcademicGoal, Progress};
+use crate::models::{ExamResult, ExamRankingEntry, BehaviorIncident, AcademicGoal, Progress, Subject};
 use crate::error::{AppError, AppResult};
 use sqlx::SqlitePool;
 use uuid::Uuid;
@@ -11,6 +11,51 @@ impl AcademicRepository {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now().to_rfc3339();
 
+        Self::insert_exam_result(pool, &id, &now, &result).await?;
+
+        Self::get_exam_result_by_id(pool, &id).await
+    }
+
+    /// Insert the results of an imported exam in one transaction.
+    ///
+    /// A student's earlier result with the same exam name and date is replaced,
+    /// so importing a file again does not duplicate it. Returns the number of
+    /// results inserted and replaced.
+    pub async fn import_exam_results(pool: &SqlitePool, results: &[ExamResult]) -> AppResult<(usize, usize)> {
+        let now = Utc::now().to_rfc3339();
+        let mut inserted = 0;
+        let mut replaced = 0;
+        let mut tx = pool.begin().await?;
+
+        for result in results {
+            let deleted = sqlx::query(
+                "DELETE FROM exam_results WHERE studentId = ? AND examName = ? AND examDate = ?"
+            )
+            .bind(&result.studentId)
+            .bind(&result.examName)
+            .bind(&result.examDate)
+            .execute(&mut *tx)
+            .await?;
+
+            if deleted.rows_affected() > 0 {
+                replaced += 1;
+            } else {
+                inserted += 1;
+            }
+
+            let id = Uuid::new_v4().to_string();
+            Self::insert_exam_result(&mut *tx, &id, &now, result).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok((inserted, replaced))
+    }
+
+    async fn insert_exam_result<'e, E>(executor: E, id: &str, now: &str, result: &ExamResult) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
         sqlx::query(
             r#"
             INSERT INTO exam_results (
@@ -22,34 +67,34 @@ impl AcademicRepository {
                 improvementSuggestions, comparedToGoal, comparedToPrevious, comparedToClassAverage,
                 schoolRank, classRank, isOfficial, certificateUrl, answerKeyUrl,
                 detailedReportUrl, goalsMet, parentNotified, counselorNotes, actionPlan,
-                notes, created_at, updated_at
+                notes, scoreType, created_at, updated_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
-        .bind(&id)
+        .bind(id)
         .bind(&result.studentId)
         .bind(&result.examType)
         .bind(&result.examName)
         .bind(&result.examDate)
         .bind(&result.examProvider)
-        .bind(&result.totalScore)
-        .bind(&result.percentileRank)
-        .bind(&result.turkishScore)
-        .bind(&result.mathScore)
-        .bind(&result.scienceScore)
-        .bind(&result.socialScore)
-        .bind(&result.foreignLanguageScore)
-        .bind(&result.turkishNet)
-        .bind(&result.mathNet)
-        .bind(&result.scienceNet)
-        .bind(&result.socialNet)
-        .bind(&result.foreignLanguageNet)
-        .bind(&result.totalNet)
-        .bind(&result.correctAnswers)
-        .bind(&result.wrongAnswers)
-        .bind(&result.emptyAnswers)
-        .bind(&result.totalQuestions)
+        .bind(result.totalScore)
+        .bind(result.percentileRank)
+        .bind(result.turkishScore)
+        .bind(result.mathScore)
+        .bind(result.scienceScore)
+        .bind(result.socialScore)
+        .bind(result.foreignLanguageScore)
+        .bind(result.turkishNet)
+        .bind(result.mathNet)
+        .bind(result.scienceNet)
+        .bind(result.socialNet)
+        .bind(result.foreignLanguageNet)
+        .bind(result.totalNet)
+        .bind(result.correctAnswers)
+        .bind(result.wrongAnswers)
+        .bind(result.emptyAnswers)
+        .bind(result.totalQuestions)
         .bind(&result.subjectBreakdown)
         .bind(&result.topicAnalysis)
         .bind(&result.strengthAreas)
@@ -57,24 +102,25 @@ impl AcademicRepository {
         .bind(&result.improvementSuggestions)
         .bind(&result.comparedToGoal)
         .bind(&result.comparedToPrevious)
-        .bind(&result.comparedToClassAverage)
-        .bind(&result.schoolRank)
-        .bind(&result.classRank)
-        .bind(&result.isOfficial)
+        .bind(result.comparedToClassAverage)
+        .bind(result.schoolRank)
+        .bind(result.classRank)
+        .bind(result.isOfficial)
         .bind(&result.certificateUrl)
         .bind(&result.answerKeyUrl)
         .bind(&result.detailedReportUrl)
-        .bind(&result.goalsMet)
-        .bind(&result.parentNotified)
+        .bind(result.goalsMet)
+        .bind(result.parentNotified)
         .bind(&result.counselorNotes)
         .bind(&result.actionPlan)
         .bind(&result.notes)
-        .bind(&now)
-        .bind(&now)
-        .execute(pool)
+        .bind(&result.scoreType)
+        .bind(now)
+        .bind(now)
+        .execute(executor)
         .await?;
 
-        Self::get_exam_result_by_id(pool, &id).await
+        Ok(())
     }
 
     pub async fn get_exam_result_by_id(pool: &SqlitePool, id: &str) -> AppResult<ExamResult> {
@@ -98,6 +144,69 @@ impl AcademicRepository {
         Ok(results)
     }
 
+    /// The student's exam results, oldest first
+    pub async fn get_exam_results_in_order(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<ExamResult>> {
+        let results = sqlx::query_as::<_, ExamResult>(
+            "SELECT * FROM exam_results WHERE studentId = ? ORDER BY examDate ASC, created_at ASC"
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// A student's result of one exam, identified by its name and date
+    pub async fn find_exam_result(
+        pool: &SqlitePool,
+        student_id: &str,
+        exam_name: &str,
+        exam_date: &str,
+    ) -> AppResult<Option<ExamResult>> {
+        let result = sqlx::query_as::<_, ExamResult>(
+            "SELECT * FROM exam_results WHERE studentId = ? AND examName = ? AND examDate = ? ORDER BY created_at DESC"
+        )
+        .bind(student_id)
+        .bind(exam_name)
+        .bind(exam_date)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Results of one exam with each student's class and school, for ranking
+    pub async fn get_exam_ranking_entries(
+        pool: &SqlitePool,
+        exam_name: &str,
+        exam_date: &str,
+    ) -> AppResult<Vec<ExamRankingEntry>> {
+        let entries = sqlx::query_as::<_, ExamRankingEntry>(
+            r#"
+            SELECT
+                e.id, e.studentId, s.name || ' ' || s.surname AS studentName, s.schoolNumber,
+                s.institutionId, i.name AS institutionName,
+                COALESCE(s.classId, NULLIF(TRIM(s.class), '')) AS classKey,
+                COALESCE(c.name, NULLIF(TRIM(s.class), '')) AS className,
+                e.examType, e.isOfficial, e.totalScore, e.totalNet,
+                e.turkishNet, e.mathNet, e.scienceNet, e.socialNet, e.foreignLanguageNet,
+                e.percentileRank, e.comparedToClassAverage, e.schoolRank, e.classRank
+            FROM exam_results e
+            INNER JOIN students s ON s.id = e.studentId
+            LEFT JOIN institutions i ON i.id = s.institutionId
+            LEFT JOIN classes c ON c.id = s.classId
+            WHERE e.examName = ? AND e.examDate = ?
+            ORDER BY e.examType, s.schoolNumber, s.surname, s.name
+            "#
+        )
+        .bind(exam_name)
+        .bind(exam_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+
     pub async fn get_exam_results_by_type(pool: &SqlitePool, student_id: &str, exam_type: &str) -> AppResult<Vec<ExamResult>> {
         let results = sqlx::query_as::<_, ExamResult>(
             "SELECT * FROM exam_results WHERE studentId = ? AND examType = ? ORDER BY examDate DESC"
@@ -192,6 +301,16 @@ impl AcademicRepository {
         Ok(incidents)
     }
 
+    pub async fn get_subject_by_id(pool: &SqlitePool, id: &str) -> AppResult<Subject> {
+        let subject = sqlx::query_as::<_, Subject>("SELECT * FROM subjects WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Subject {}", id)))?;
+
+        Ok(subject)
+    }
+
     pub async fn create_academic_goal(pool: &SqlitePool, goal: AcademicGoal) -> AppResult<AcademicGoal> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now().to_rfc3339();
@@ -234,6 +353,86 @@ impl AcademicRepository {
         Ok(goals)
     }
 
+    pub async fn get_academic_goal_by_id(pool: &SqlitePool, id: &str) -> AppResult<AcademicGoal> {
+        let goal = sqlx::query_as::<_, AcademicGoal>("SELECT * FROM academic_goals WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Academic goal {}", id)))?;
+
+        Ok(goal)
+    }
+
+    /// The student's goals of every status, those without a deadline last
+    pub async fn get_all_academic_goals_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<AcademicGoal>> {
+        let goals = sqlx::query_as::<_, AcademicGoal>(
+            "SELECT * FROM academic_goals WHERE studentId = ? ORDER BY deadline IS NULL, deadline ASC, created_at ASC"
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(goals)
+    }
+
+    /// Students with an active goal whose deadline is before `today`
+    pub async fn get_students_with_overdue_goals(pool: &SqlitePool, today: &str) -> AppResult<Vec<String>> {
+        let student_ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT studentId FROM academic_goals
+            WHERE status = 'active' AND targetScore IS NOT NULL AND deadline IS NOT NULL AND deadline < ?
+            "#
+        )
+        .bind(today)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(student_ids)
+    }
+
+    pub async fn update_academic_goal(pool: &SqlitePool, goal: &AcademicGoal) -> AppResult<AcademicGoal> {
+        let now = Utc::now().to_rfc3339();
+
+        let updated = sqlx::query_as::<_, AcademicGoal>(
+            r#"
+            UPDATE academic_goals SET
+                title = ?, description = ?, targetScore = ?, currentScore = ?, examType = ?, deadline = ?,
+                status = ?, lastExamResultId = ?, closedAt = ?, updated_at = ?
+            WHERE id = ?
+            RETURNING *
+            "#
+        )
+        .bind(&goal.title)
+        .bind(&goal.description)
+        .bind(goal.targetScore)
+        .bind(goal.currentScore)
+        .bind(&goal.examType)
+        .bind(&goal.deadline)
+        .bind(&goal.status)
+        .bind(&goal.lastExamResultId)
+        .bind(&goal.closedAt)
+        .bind(&now)
+        .bind(&goal.id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Academic goal {}", goal.id)))?;
+
+        Ok(updated)
+    }
+
+    pub async fn delete_academic_goal(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM academic_goals WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Academic goal {}", id)));
+        }
+
+        Ok(())
+    }
+
     pub async fn update_exam_result(pool: &SqlitePool, id: &str, result: ExamResult) -> AppResult<()> {
         let now = Utc::now().to_rfc3339();
 
@@ -255,6 +454,85 @@ impl AcademicRepository {
         Ok(())
     }
 
+    /// Save the nets, answer counts and score of an exam result
+    pub async fn update_exam_scores(pool: &SqlitePool, id: &str, result: &ExamResult) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            UPDATE exam_results SET
+                turkishNet = ?, mathNet = ?, scienceNet = ?, socialNet = ?, foreignLanguageNet = ?,
+                totalNet = ?, correctAnswers = ?, wrongAnswers = ?, emptyAnswers = ?, totalQuestions = ?,
+                subjectBreakdown = ?, totalScore = ?, scoreType = ?, updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(result.turkishNet)
+        .bind(result.mathNet)
+        .bind(result.scienceNet)
+        .bind(result.socialNet)
+        .bind(result.foreignLanguageNet)
+        .bind(result.totalNet)
+        .bind(result.correctAnswers)
+        .bind(result.wrongAnswers)
+        .bind(result.emptyAnswers)
+        .bind(result.totalQuestions)
+        .bind(&result.subjectBreakdown)
+        .bind(result.totalScore)
+        .bind(&result.scoreType)
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_exam_comparisons(
+        pool: &SqlitePool,
+        id: &str,
+        compared_to_previous: Option<&str>,
+        compared_to_goal: Option<&str>,
+        goals_met: bool,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE exam_results SET comparedToPrevious = ?, comparedToGoal = ?, goalsMet = ? WHERE id = ?"
+        )
+        .bind(compared_to_previous)
+        .bind(compared_to_goal)
+        .bind(goals_met)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Save the class and school ranks of an exam's results in one transaction
+    pub async fn update_exam_rankings(pool: &SqlitePool, entries: &[ExamRankingEntry]) -> AppResult<()> {
+        let mut tx = pool.begin().await?;
+
+        for entry in entries {
+            sqlx::query(
+                r#"
+                UPDATE exam_results SET
+                    classRank = ?, schoolRank = ?, percentileRank = ?, comparedToClassAverage = ?
+                WHERE id = ?
+                "#
+            )
+            .bind(entry.classRank)
+            .bind(entry.schoolRank)
+            .bind(entry.percentileRank)
+            .bind(entry.comparedToClassAverage)
+            .bind(&entry.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     pub async fn delete_exam_result(pool: &SqlitePool, id: &str) -> AppResult<()> {
         let result = sqlx::query("DELETE FROM exam_results WHERE id = ?")
             .bind(id)
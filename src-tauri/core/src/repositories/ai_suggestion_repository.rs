@@ -154,6 +154,19 @@ impl AiSuggestionRepository {
         Ok(())
     }
 
+    /// Whether a suggestion was already raised from this source record
+    pub async fn exists_for_source(pool: &SqlitePool, source: &str, source_id: &str) -> AppResult<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM ai_suggestion_queue WHERE source = ? AND sourceId = ?"
+        )
+        .bind(source)
+        .bind(source_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
     pub async fn clean_expired(pool: &SqlitePool) -> AppResult<i64> {
         let result = sqlx::query(
             "DELETE FROM ai_suggestion_queue WHERE expiresAt < datetime('now') AND status = 'PENDING'"
@@ -0,0 +1,403 @@
+use crate::models::{
+    AttendanceRecord, AttendanceSummary, AttendanceThresholds, AttendanceAlert, UpdateAttendanceRequest,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+/// Fields of a record as stored, after the service has derived year and term
+pub struct NewAttendanceRecord {
+    pub student_id: String,
+    pub date: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub notes: Option<String>,
+    pub duration: f64,
+    pub school_year: String,
+    pub term: String,
+    pub source: String,
+    pub recorded_by: Option<String>,
+}
+
+const SUMMARY_COLUMNS: &str = r#"
+    TOTAL(CASE WHEN status IN ('Özürlü', 'Raporlu') THEN duration ELSE 0 END) AS excusedDays,
+    TOTAL(CASE WHEN status = 'Özürsüz' THEN duration ELSE 0 END) AS unexcusedDays,
+    TOTAL(CASE WHEN status IN ('Özürlü', 'Raporlu', 'Özürsüz') THEN duration ELSE 0 END) AS totalDays,
+    COUNT(CASE WHEN status = 'Geç' THEN 1 END) AS lateCount,
+    COUNT(*) AS recordCount
+"#;
+
+pub struct AttendanceRepository;
+
+impl AttendanceRepository {
+    pub async fn create(pool: &SqlitePool, record: NewAttendanceRecord) -> AppResult<AttendanceRecord> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query_as::<_, AttendanceRecord>(
+            r#"
+            INSERT INTO attendance_records (
+                id, studentId, date, status, reason, notes, duration, schoolYear, term,
+                source, recordedBy, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&record.student_id)
+        .bind(&record.date)
+        .bind(&record.status)
+        .bind(&record.reason)
+        .bind(&record.notes)
+        .bind(record.duration)
+        .bind(&record.school_year)
+        .bind(&record.term)
+        .bind(&record.source)
+        .bind(&record.recorded_by)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Insert records or overwrite the ones already stored for those students
+    /// and days, all in one transaction.
+    ///
+    /// Returns each record and whether it was newly inserted.
+    pub async fn upsert_many(
+        pool: &SqlitePool,
+        records: Vec<NewAttendanceRecord>,
+    ) -> AppResult<Vec<(AttendanceRecord, bool)>> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+        let mut saved = Vec::with_capacity(records.len());
+
+        for record in records {
+            let existing = sqlx::query_scalar::<_, String>(
+                "SELECT id FROM attendance_records WHERE studentId = ? AND date = ?"
+            )
+            .bind(&record.student_id)
+            .bind(&record.date)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            let result = sqlx::query_as::<_, AttendanceRecord>(
+                r#"
+                INSERT INTO attendance_records (
+                    id, studentId, date, status, reason, notes, duration, schoolYear, term,
+                    source, recordedBy, created_at, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(studentId, date) DO UPDATE SET
+                    status = excluded.status,
+                    reason = excluded.reason,
+                    notes = COALESCE(excluded.notes, attendance_records.notes),
+                    duration = excluded.duration,
+                    schoolYear = excluded.schoolYear,
+                    term = excluded.term,
+                    source = excluded.source,
+                    recordedBy = COALESCE(excluded.recordedBy, attendance_records.recordedBy),
+                    updated_at = excluded.updated_at
+                RETURNING *
+                "#
+            )
+            .bind(existing.clone().unwrap_or_else(|| Uuid::new_v4().to_string()))
+            .bind(&record.student_id)
+            .bind(&record.date)
+            .bind(&record.status)
+            .bind(&record.reason)
+            .bind(&record.notes)
+            .bind(record.duration)
+            .bind(&record.school_year)
+            .bind(&record.term)
+            .bind(&record.source)
+            .bind(&record.recorded_by)
+            .bind(&now)
+            .bind(&now)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            saved.push((result, existing.is_none()));
+        }
+
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<AttendanceRecord> {
+        let record = sqlx::query_as::<_, AttendanceRecord>("SELECT * FROM attendance_records WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Attendance record {}", id)))?;
+
+        Ok(record)
+    }
+
+    pub async fn find_by_student_and_date(
+        pool: &SqlitePool,
+        student_id: &str,
+        date: &str,
+    ) -> AppResult<Option<AttendanceRecord>> {
+        let record = sqlx::query_as::<_, AttendanceRecord>(
+            "SELECT * FROM attendance_records WHERE studentId = ? AND date = ?"
+        )
+        .bind(student_id)
+        .bind(date)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn get_by_student(
+        pool: &SqlitePool,
+        student_id: &str,
+        school_year: Option<&str>,
+    ) -> AppResult<Vec<AttendanceRecord>> {
+        let records = sqlx::query_as::<_, AttendanceRecord>(
+            r#"
+            SELECT * FROM attendance_records
+            WHERE studentId = ? AND (? IS NULL OR schoolYear = ?)
+            ORDER BY date DESC
+            "#
+        )
+        .bind(student_id)
+        .bind(school_year)
+        .bind(school_year)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn get_by_date(pool: &SqlitePool, institution_id: &str, date: &str) -> AppResult<Vec<AttendanceRecord>> {
+        let records = sqlx::query_as::<_, AttendanceRecord>(
+            r#"
+            SELECT ar.* FROM attendance_records ar
+            INNER JOIN students s ON ar.studentId = s.id
+            WHERE s.institutionId = ? AND ar.date = ?
+            ORDER BY s.class, s.name, s.surname
+            "#
+        )
+        .bind(institution_id)
+        .bind(date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn update(pool: &SqlitePool, id: &str, req: UpdateAttendanceRequest) -> AppResult<AttendanceRecord> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE attendance_records SET
+                status = COALESCE(?, status),
+                reason = COALESCE(?, reason),
+                notes = COALESCE(?, notes),
+                duration = COALESCE(?, duration),
+                updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(&req.status)
+        .bind(&req.reason)
+        .bind(&req.notes)
+        .bind(req.duration)
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Attendance record {}", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM attendance_records WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Attendance record {}", id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_year_summary(
+        pool: &SqlitePool,
+        student_id: &str,
+        school_year: &str,
+    ) -> AppResult<AttendanceSummary> {
+        let query = format!(
+            r#"
+            SELECT ? AS studentId, ? AS schoolYear, NULL AS term, {}
+            FROM attendance_records
+            WHERE studentId = ? AND schoolYear = ?
+            "#,
+            SUMMARY_COLUMNS
+        );
+
+        let summary = sqlx::query_as::<_, AttendanceSummary>(&query)
+            .bind(student_id)
+            .bind(school_year)
+            .bind(student_id)
+            .bind(school_year)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(summary)
+    }
+
+    pub async fn get_term_summaries(
+        pool: &SqlitePool,
+        student_id: &str,
+        school_year: &str,
+    ) -> AppResult<Vec<AttendanceSummary>> {
+        let query = format!(
+            r#"
+            SELECT studentId, schoolYear, term, {}
+            FROM attendance_records
+            WHERE studentId = ? AND schoolYear = ?
+            GROUP BY term
+            ORDER BY term
+            "#,
+            SUMMARY_COLUMNS
+        );
+
+        let summaries = sqlx::query_as::<_, AttendanceSummary>(&query)
+            .bind(student_id)
+            .bind(school_year)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(summaries)
+    }
+
+    pub async fn get_thresholds(pool: &SqlitePool) -> AppResult<AttendanceThresholds> {
+        let thresholds = sqlx::query_as::<_, AttendanceThresholds>(
+            r#"
+            SELECT unexcusedWarningDays, unexcusedFailureDays, totalWarningDays, totalFailureDays, updated_at
+            FROM attendance_thresholds WHERE id = 1
+            "#
+        )
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Attendance thresholds".to_string()))?;
+
+        Ok(thresholds)
+    }
+
+    pub async fn update_thresholds(pool: &SqlitePool, thresholds: &AttendanceThresholds) -> AppResult<AttendanceThresholds> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO attendance_thresholds (
+                id, unexcusedWarningDays, unexcusedFailureDays, totalWarningDays, totalFailureDays, updated_at
+            )
+            VALUES (1, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                unexcusedWarningDays = excluded.unexcusedWarningDays,
+                unexcusedFailureDays = excluded.unexcusedFailureDays,
+                totalWarningDays = excluded.totalWarningDays,
+                totalFailureDays = excluded.totalFailureDays,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(thresholds.unexcusedWarningDays)
+        .bind(thresholds.unexcusedFailureDays)
+        .bind(thresholds.totalWarningDays)
+        .bind(thresholds.totalFailureDays)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Self::get_thresholds(pool).await
+    }
+
+    /// Record that an alert was raised; returns false when it had already been raised
+    pub async fn record_alert<'e, E>(
+        executor: E,
+        student_id: &str,
+        school_year: &str,
+        kind: &str,
+        level: &str,
+        days: f64,
+    ) -> AppResult<bool>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO attendance_alerts (id, studentId, schoolYear, kind, level, days, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(student_id)
+        .bind(school_year)
+        .bind(kind)
+        .bind(level)
+        .bind(days)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_alert_notification<'e, E>(
+        executor: E,
+        student_id: &str,
+        school_year: &str,
+        kind: &str,
+        level: &str,
+        notification_id: &str,
+    ) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query(
+            r#"
+            UPDATE attendance_alerts SET notificationId = ?
+            WHERE studentId = ? AND schoolYear = ? AND kind = ? AND level = ?
+            "#
+        )
+        .bind(notification_id)
+        .bind(student_id)
+        .bind(school_year)
+        .bind(kind)
+        .bind(level)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_alerts_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<AttendanceAlert>> {
+        let alerts = sqlx::query_as::<_, AttendanceAlert>(
+            "SELECT * FROM attendance_alerts WHERE studentId = ? ORDER BY created_at DESC"
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+}
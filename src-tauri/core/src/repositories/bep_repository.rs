@@ -0,0 +1,602 @@
+use crate::models::{
+    BepAccommodation, BepEvaluation, BepGoal, BepGoalProgress, BepPlan, BepResponsibleTeacher,
+    AssignBepTeacherRequest, CreateBepAccommodationRequest, CreateBepEvaluationRequest, CreateBepGoalRequest,
+    CreateBepPlanRequest, UpdateBepGoalRequest, UpdateBepPlanRequest, BEP_DRAFT, BEP_GOAL_NOT_STARTED,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+const GOAL_SELECT: &str = r#"
+    SELECT g.*, s.name AS subjectName
+    FROM bep_goals g
+    LEFT JOIN subjects s ON s.id = g.subjectId
+"#;
+
+const ACCOMMODATION_SELECT: &str = r#"
+    SELECT a.*, s.name AS subjectName
+    FROM bep_accommodations a
+    LEFT JOIN subjects s ON s.id = a.subjectId
+"#;
+
+const TEACHER_SELECT: &str = r#"
+    SELECT t.*, u.name AS teacherName, s.name AS subjectName
+    FROM bep_responsible_teachers t
+    INNER JOIN users u ON u.id = t.userId
+    LEFT JOIN subjects s ON s.id = t.subjectId
+"#;
+
+pub struct BepRepository;
+
+impl BepRepository {
+    pub async fn create_plan(
+        pool: &SqlitePool,
+        req: &CreateBepPlanRequest,
+        review_interval_days: i64,
+        next_review_date: Option<&str>,
+    ) -> AppResult<BepPlan> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let plan = sqlx::query_as::<_, BepPlan>(
+            r#"
+            INSERT INTO bep_plans (
+                id, studentId, ramReportNumber, ramReportDate, ramReportSummary, diagnosis,
+                educationalPlacement, supportServices, startDate, endDate, status, coordinatorId,
+                reviewIntervalDays, nextReviewDate, notes, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&req.studentId)
+        .bind(&req.ramReportNumber)
+        .bind(&req.ramReportDate)
+        .bind(&req.ramReportSummary)
+        .bind(&req.diagnosis)
+        .bind(&req.educationalPlacement)
+        .bind(&req.supportServices)
+        .bind(&req.startDate)
+        .bind(&req.endDate)
+        .bind(BEP_DRAFT)
+        .bind(&req.coordinatorId)
+        .bind(review_interval_days)
+        .bind(next_review_date)
+        .bind(&req.notes)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(plan)
+    }
+
+    pub async fn get_plan_by_id(pool: &SqlitePool, id: &str) -> AppResult<BepPlan> {
+        let plan = sqlx::query_as::<_, BepPlan>("SELECT * FROM bep_plans WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("BEP plan {}", id)))?;
+
+        Ok(plan)
+    }
+
+    pub async fn get_plans_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<BepPlan>> {
+        let plans = sqlx::query_as::<_, BepPlan>(
+            "SELECT * FROM bep_plans WHERE studentId = ? ORDER BY startDate DESC, created_at DESC"
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(plans)
+    }
+
+    pub async fn update_plan(pool: &SqlitePool, id: &str, req: UpdateBepPlanRequest) -> AppResult<BepPlan> {
+        let now = Utc::now().to_rfc3339();
+
+        let mut query = String::from(
+            "UPDATE bep_plans SET updated_at = ?, reviewIntervalDays = COALESCE(?, reviewIntervalDays)"
+        );
+        let mut params: Vec<String> = Vec::new();
+
+        let fields = [
+            ("ramReportNumber", &req.ramReportNumber),
+            ("ramReportDate", &req.ramReportDate),
+            ("ramReportSummary", &req.ramReportSummary),
+            ("diagnosis", &req.diagnosis),
+            ("educationalPlacement", &req.educationalPlacement),
+            ("supportServices", &req.supportServices),
+            ("startDate", &req.startDate),
+            ("endDate", &req.endDate),
+            ("coordinatorId", &req.coordinatorId),
+            ("nextReviewDate", &req.nextReviewDate),
+            ("notes", &req.notes),
+        ];
+        for (column, value) in fields {
+            if let Some(value) = value {
+                query.push_str(&format!(", {} = ?", column));
+                params.push(value.clone());
+            }
+        }
+
+        query.push_str(" WHERE id = ?");
+
+        let mut q = sqlx::query(&query).bind(&now).bind(req.reviewIntervalDays);
+        for param in params {
+            q = q.bind(param);
+        }
+        q = q.bind(id);
+
+        let result = q.execute(pool).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP plan {}", id)));
+        }
+
+        Self::get_plan_by_id(pool, id).await
+    }
+
+    pub async fn set_review<'e, E>(
+        executor: E,
+        id: &str,
+        status: &str,
+        next_review_date: Option<&str>,
+    ) -> AppResult<BepPlan>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        let plan = sqlx::query_as::<_, BepPlan>(
+            "UPDATE bep_plans SET status = ?, nextReviewDate = ?, updated_at = ? WHERE id = ? RETURNING *"
+        )
+        .bind(status)
+        .bind(next_review_date)
+        .bind(&now)
+        .bind(id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("BEP plan {}", id)))?;
+
+        Ok(plan)
+    }
+
+    pub async fn delete_plan(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM bep_plans WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP plan {}", id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn create_goal(pool: &SqlitePool, plan_id: &str, req: &CreateBepGoalRequest) -> AppResult<BepGoal> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO bep_goals (
+                id, planId, subjectId, area, longTermGoal, shortTermGoal, successCriteria,
+                method, targetDate, progress, status, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(plan_id)
+        .bind(&req.subjectId)
+        .bind(&req.area)
+        .bind(req.longTermGoal.trim())
+        .bind(&req.shortTermGoal)
+        .bind(&req.successCriteria)
+        .bind(&req.method)
+        .bind(&req.targetDate)
+        .bind(BEP_GOAL_NOT_STARTED)
+        .bind(&now)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Self::get_goal_by_id(pool, &id).await
+    }
+
+    pub async fn get_goal_by_id(pool: &SqlitePool, id: &str) -> AppResult<BepGoal> {
+        let goal = sqlx::query_as::<_, BepGoal>(&format!("{} WHERE g.id = ?", GOAL_SELECT))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("BEP goal {}", id)))?;
+
+        Ok(goal)
+    }
+
+    pub async fn get_goals(pool: &SqlitePool, plan_id: &str) -> AppResult<Vec<BepGoal>> {
+        let goals = sqlx::query_as::<_, BepGoal>(&format!(
+            "{} WHERE g.planId = ? ORDER BY s.name IS NULL, s.name, g.created_at",
+            GOAL_SELECT
+        ))
+        .bind(plan_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(goals)
+    }
+
+    pub async fn update_goal(pool: &SqlitePool, id: &str, req: UpdateBepGoalRequest) -> AppResult<BepGoal> {
+        let now = Utc::now().to_rfc3339();
+
+        let mut query = String::from("UPDATE bep_goals SET updated_at = ?");
+        let mut params: Vec<String> = Vec::new();
+
+        let fields = [
+            ("subjectId", &req.subjectId),
+            ("area", &req.area),
+            ("longTermGoal", &req.longTermGoal),
+            ("shortTermGoal", &req.shortTermGoal),
+            ("successCriteria", &req.successCriteria),
+            ("method", &req.method),
+            ("targetDate", &req.targetDate),
+        ];
+        for (column, value) in fields {
+            if let Some(value) = value {
+                query.push_str(&format!(", {} = ?", column));
+                params.push(value.trim().to_string());
+            }
+        }
+
+        query.push_str(" WHERE id = ?");
+
+        let mut q = sqlx::query(&query).bind(&now);
+        for param in params {
+            q = q.bind(param);
+        }
+        q = q.bind(id);
+
+        let result = q.execute(pool).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP goal {}", id)));
+        }
+
+        Self::get_goal_by_id(pool, id).await
+    }
+
+    pub async fn delete_goal(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM bep_goals WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP goal {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Set a goal's current progress and status
+    pub async fn set_goal_progress<'e, E>(executor: E, goal_id: &str, progress: i64, status: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query("UPDATE bep_goals SET progress = ?, status = ?, updated_at = ? WHERE id = ?")
+            .bind(progress)
+            .bind(status)
+            .bind(&now)
+            .bind(goal_id)
+            .execute(executor)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP goal {}", goal_id)));
+        }
+
+        Ok(())
+    }
+
+    /// Append an entry to a goal's progress history
+    pub async fn add_goal_progress<'e, E>(
+        executor: E,
+        goal_id: &str,
+        evaluation_id: Option<&str>,
+        progress: i64,
+        note: Option<&str>,
+        recorded_by: Option<&str>,
+    ) -> AppResult<BepGoalProgress>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let entry = sqlx::query_as::<_, BepGoalProgress>(
+            r#"
+            INSERT INTO bep_goal_progress (id, goalId, evaluationId, progress, note, recordedBy, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(goal_id)
+        .bind(evaluation_id)
+        .bind(progress)
+        .bind(note)
+        .bind(recorded_by)
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(entry)
+    }
+
+    pub async fn get_goal_progress(pool: &SqlitePool, goal_id: &str) -> AppResult<Vec<BepGoalProgress>> {
+        let entries = sqlx::query_as::<_, BepGoalProgress>(
+            "SELECT * FROM bep_goal_progress WHERE goalId = ? ORDER BY created_at DESC"
+        )
+        .bind(goal_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    pub async fn create_accommodation(
+        pool: &SqlitePool,
+        plan_id: &str,
+        req: &CreateBepAccommodationRequest,
+    ) -> AppResult<BepAccommodation> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO bep_accommodations (id, planId, subjectId, category, description, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(plan_id)
+        .bind(&req.subjectId)
+        .bind(&req.category)
+        .bind(req.description.trim())
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        let accommodation = sqlx::query_as::<_, BepAccommodation>(&format!("{} WHERE a.id = ?", ACCOMMODATION_SELECT))
+            .bind(&id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(accommodation)
+    }
+
+    pub async fn get_accommodations(pool: &SqlitePool, plan_id: &str) -> AppResult<Vec<BepAccommodation>> {
+        let accommodations = sqlx::query_as::<_, BepAccommodation>(&format!(
+            "{} WHERE a.planId = ? ORDER BY a.category IS NULL, a.category, a.created_at",
+            ACCOMMODATION_SELECT
+        ))
+        .bind(plan_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(accommodations)
+    }
+
+    pub async fn get_accommodation_by_id(pool: &SqlitePool, id: &str) -> AppResult<BepAccommodation> {
+        let accommodation = sqlx::query_as::<_, BepAccommodation>(&format!("{} WHERE a.id = ?", ACCOMMODATION_SELECT))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("BEP accommodation {}", id)))?;
+
+        Ok(accommodation)
+    }
+
+    pub async fn delete_accommodation(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM bep_accommodations WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP accommodation {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Assign a teacher to a plan; assigning the same teacher and subject twice is a no-op
+    pub async fn assign_teacher(
+        pool: &SqlitePool,
+        plan_id: &str,
+        req: &AssignBepTeacherRequest,
+    ) -> AppResult<BepResponsibleTeacher> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO bep_responsible_teachers (id, planId, userId, subjectId, responsibility, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(plan_id)
+        .bind(&req.userId)
+        .bind(&req.subjectId)
+        .bind(&req.responsibility)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        let teacher = sqlx::query_as::<_, BepResponsibleTeacher>(&format!(
+            "{} WHERE t.planId = ? AND t.userId = ? AND COALESCE(t.subjectId, '') = COALESCE(?, '')",
+            TEACHER_SELECT
+        ))
+        .bind(plan_id)
+        .bind(&req.userId)
+        .bind(&req.subjectId)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(teacher)
+    }
+
+    pub async fn get_teachers(pool: &SqlitePool, plan_id: &str) -> AppResult<Vec<BepResponsibleTeacher>> {
+        let teachers = sqlx::query_as::<_, BepResponsibleTeacher>(&format!(
+            "{} WHERE t.planId = ? ORDER BY u.name, s.name",
+            TEACHER_SELECT
+        ))
+        .bind(plan_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(teachers)
+    }
+
+    pub async fn get_teacher_by_id(pool: &SqlitePool, id: &str) -> AppResult<BepResponsibleTeacher> {
+        let teacher = sqlx::query_as::<_, BepResponsibleTeacher>(&format!("{} WHERE t.id = ?", TEACHER_SELECT))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("BEP responsible teacher {}", id)))?;
+
+        Ok(teacher)
+    }
+
+    pub async fn remove_teacher(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM bep_responsible_teachers WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("BEP responsible teacher {}", id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn create_evaluation<'e, E>(
+        executor: E,
+        plan_id: &str,
+        req: &CreateBepEvaluationRequest,
+        decision: &str,
+        next_review_date: Option<&str>,
+    ) -> AppResult<BepEvaluation>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let evaluation = sqlx::query_as::<_, BepEvaluation>(
+            r#"
+            INSERT INTO bep_evaluations (
+                id, planId, evaluationDate, evaluatorId, summary, decision, nextReviewDate, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(plan_id)
+        .bind(&req.evaluationDate)
+        .bind(&req.evaluatorId)
+        .bind(req.summary.trim())
+        .bind(decision)
+        .bind(next_review_date)
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(evaluation)
+    }
+
+    pub async fn get_evaluations(pool: &SqlitePool, plan_id: &str) -> AppResult<Vec<BepEvaluation>> {
+        let evaluations = sqlx::query_as::<_, BepEvaluation>(
+            "SELECT * FROM bep_evaluations WHERE planId = ? ORDER BY evaluationDate DESC, created_at DESC"
+        )
+        .bind(plan_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(evaluations)
+    }
+
+    /// Active plans whose next review is on or before `cutoff_date`
+    pub async fn get_active_due_by(pool: &SqlitePool, cutoff_date: &str) -> AppResult<Vec<BepPlan>> {
+        let plans = sqlx::query_as::<_, BepPlan>(
+            r#"
+            SELECT * FROM bep_plans
+            WHERE status = 'Aktif' AND nextReviewDate IS NOT NULL AND nextReviewDate <= ?
+            ORDER BY nextReviewDate
+            "#
+        )
+        .bind(cutoff_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(plans)
+    }
+
+    /// Record that a review reminder was sent; returns false when it had already been sent
+    pub async fn record_reminder<'e, E>(executor: E, plan_id: &str, kind: &str, review_date: &str) -> AppResult<bool>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO bep_reminders (id, planId, kind, reviewDate, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(plan_id)
+        .bind(kind)
+        .bind(review_date)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_reminder_notification<'e, E>(
+        executor: E,
+        plan_id: &str,
+        kind: &str,
+        review_date: &str,
+        notification_id: &str,
+    ) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query(
+            r#"
+            UPDATE bep_reminders SET notificationId = ?
+            WHERE planId = ? AND kind = ? AND reviewDate = ?
+            "#
+        )
+        .bind(notification_id)
+        .bind(plan_id)
+        .bind(kind)
+        .bind(review_date)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use crate::models::{CategoryCount, ClassExamAverage, CreateClassRequest, SchoolClass, Student};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+const CLASS_SELECT: &str = r#"
+    SELECT c.*,
+        ht.name AS homeroomTeacherName,
+        co.name AS counselorName,
+        (SELECT COUNT(*) FROM students s WHERE s.classId = c.id) AS studentCount
+    FROM classes c
+    LEFT JOIN users ht ON ht.id = c.homeroomTeacherId
+    LEFT JOIN users co ON co.id = c.counselorId
+"#;
+
+pub struct ClassRepository;
+
+impl ClassRepository {
+    /// Create a class from a request whose section is already normalized
+    pub async fn create(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        school_year: &str,
+        name: &str,
+        req: &CreateClassRequest,
+    ) -> AppResult<SchoolClass> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO classes (
+                id, institutionId, grade, section, schoolYear, name, homeroomTeacherId, counselorId,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(institution_id)
+        .bind(req.grade)
+        .bind(&req.section)
+        .bind(school_year)
+        .bind(name)
+        .bind(&req.homeroomTeacherId)
+        .bind(&req.counselorId)
+        .bind(&now)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Self::get_by_id(pool, &id).await
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<SchoolClass> {
+        let class = sqlx::query_as::<_, SchoolClass>(&format!("{} WHERE c.id = ?", CLASS_SELECT))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Class {}", id)))?;
+
+        Ok(class)
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        grade: i64,
+        section: &str,
+        school_year: &str,
+    ) -> AppResult<Option<SchoolClass>> {
+        let class = sqlx::query_as::<_, SchoolClass>(&format!(
+            "{} WHERE c.institutionId IS ? AND c.grade = ? AND c.section = ? AND c.schoolYear = ?",
+            CLASS_SELECT
+        ))
+        .bind(institution_id)
+        .bind(grade)
+        .bind(section)
+        .bind(school_year)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(class)
+    }
+
+    pub async fn get_all(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        school_year: Option<&str>,
+    ) -> AppResult<Vec<SchoolClass>> {
+        let classes = sqlx::query_as::<_, SchoolClass>(&format!(
+            r#"{}
+            WHERE (? IS NULL OR c.institutionId = ?) AND (? IS NULL OR c.schoolYear = ?)
+            ORDER BY c.schoolYear DESC, c.grade, c.section"#,
+            CLASS_SELECT
+        ))
+        .bind(institution_id)
+        .bind(institution_id)
+        .bind(school_year)
+        .bind(school_year)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(classes)
+    }
+
+    /// Classes where the user is the homeroom teacher or the counselor
+    pub async fn get_by_staff(pool: &SqlitePool, user_id: &str) -> AppResult<Vec<SchoolClass>> {
+        let classes = sqlx::query_as::<_, SchoolClass>(&format!(
+            "{} WHERE c.homeroomTeacherId = ? OR c.counselorId = ? ORDER BY c.schoolYear DESC, c.grade, c.section",
+            CLASS_SELECT
+        ))
+        .bind(user_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(classes)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        grade: i64,
+        section: &str,
+        name: &str,
+        homeroom_teacher_id: Option<&str>,
+        counselor_id: Option<&str>,
+    ) -> AppResult<SchoolClass> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE classes SET
+                grade = ?, section = ?, name = ?, homeroomTeacherId = ?, counselorId = ?, updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(grade)
+        .bind(section)
+        .bind(name)
+        .bind(homeroom_teacher_id)
+        .bind(counselor_id)
+        .bind(&now)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Class {}", id)));
+        }
+
+        // Keep the denormalized class name on students in step with a rename
+        sqlx::query("UPDATE students SET class = ?, updated_at = ? WHERE classId = ? AND (class IS NULL OR class <> ?)")
+            .bind(name)
+            .bind(&now)
+            .bind(id)
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Self::get_by_id(pool, id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM classes WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Class {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Link a student to a class and copy the class name to `students.class`
+    pub async fn set_student_class(pool: &SqlitePool, student_id: &str, class_id: &str, name: &str) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query("UPDATE students SET classId = ?, class = ?, updated_at = ? WHERE id = ?")
+            .bind(class_id)
+            .bind(name)
+            .bind(&now)
+            .bind(student_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Student {}", student_id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn unlink_student(pool: &SqlitePool, student_id: &str) -> AppResult<()> {
+        sqlx::query("UPDATE students SET classId = NULL WHERE id = ?")
+            .bind(student_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Students with a class name but no class link yet
+    pub async fn get_unlinked_students(pool: &SqlitePool) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            "SELECT * FROM students WHERE classId IS NULL AND class IS NOT NULL AND TRIM(class) <> ''"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn get_roster(pool: &SqlitePool, class_id: &str) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            "SELECT * FROM students WHERE classId = ? ORDER BY schoolNumber, surname, name"
+        )
+        .bind(class_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn get_risk_distribution(pool: &SqlitePool, class_id: &str) -> AppResult<Vec<CategoryCount>> {
+        let counts = sqlx::query_as::<_, CategoryCount>(
+            r#"
+            SELECT COALESCE(risk, 'Belirtilmemiş') AS category, COUNT(*) AS count
+            FROM students
+            WHERE classId = ?
+            GROUP BY COALESCE(risk, 'Belirtilmemiş')
+            ORDER BY count DESC
+            "#
+        )
+        .bind(class_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Average results of the class's current students per exam, newest first
+    pub async fn get_exam_averages(pool: &SqlitePool, class_id: &str) -> AppResult<Vec<ClassExamAverage>> {
+        let averages = sqlx::query_as::<_, ClassExamAverage>(
+            r#"
+            SELECT
+                e.examType, e.examName, e.examDate,
+                COUNT(DISTINCT e.studentId) AS studentCount,
+                AVG(e.totalScore) AS averageScore,
+                AVG(e.totalNet) AS averageNet,
+                AVG(e.turkishNet) AS averageTurkishNet,
+                AVG(e.mathNet) AS averageMathNet,
+                AVG(e.scienceNet) AS averageScienceNet,
+                AVG(e.socialNet) AS averageSocialNet,
+                AVG(e.foreignLanguageNet) AS averageForeignLanguageNet
+            FROM exam_results e
+            INNER JOIN students s ON s.id = e.studentId
+            WHERE s.classId = ?
+            GROUP BY e.examType, e.examName, e.examDate
+            ORDER BY e.examDate DESC, e.examName
+            "#
+        )
+        .bind(class_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(averages)
+    }
+}
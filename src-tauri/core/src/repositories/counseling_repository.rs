@@ -23,9 +23,10 @@ impl CounselingRepository {
                 sessionMode, sessionLocation, disciplineStatus, institutionalCooperation, sessionDetails,
                 detailedNotes, sessionFlow, studentParticipationLevel, cooperationLevel, emotionalState,
                 physicalState, communicationQuality, sessionTags, achievedOutcomes, followUpNeeded,
-                followUpPlan, actionItems, autoCompleted, extensionGranted, completed, created_at, updated_at
+                followUpPlan, actionItems, autoCompleted, extensionGranted, completed, isConfidential,
+                institutionId, created_at, updated_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&id)
@@ -66,6 +67,8 @@ impl CounselingRepository {
         .bind(&session.autoCompleted)
         .bind(&session.extensionGranted)
         .bind(&session.completed)
+        .bind(session.isConfidential)
+        .bind(&session.institutionId)
         .bind(&now)
         .bind(&now)
         .execute(pool)
@@ -112,6 +115,17 @@ impl CounselingRepository {
         Ok(sessions)
     }
 
+    pub async fn get_sessions_by_institution(pool: &SqlitePool, institution_id: &str) -> AppResult<Vec<CounselingSession>> {
+        let sessions = sqlx::query_as::<_, CounselingSession>(
+            "SELECT * FROM counseling_sessions WHERE institutionId = ? ORDER BY sessionDate DESC, entryTime DESC"
+        )
+        .bind(institution_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
     pub async fn add_student_to_session(pool: &SqlitePool, session_id: &str, student_id: &str) -> AppResult<()> {
         let now = Utc::now().to_rfc3339();
 
@@ -127,14 +141,26 @@ impl CounselingRepository {
         Ok(())
     }
 
-    pub async fn create_meeting_note(pool: &SqlitePool, student_id: &str, date: &str, note_type: &str, note: &str, plan: Option<String>) -> AppResult<MeetingNote> {
+    pub async fn session_has_student(pool: &SqlitePool, session_id: &str, student_id: &str) -> AppResult<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM counseling_session_students WHERE sessionId = ? AND studentId = ?"
+        )
+        .bind(session_id)
+        .bind(student_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    pub async fn create_meeting_note(pool: &SqlitePool, student_id: &str, date: &str, note_type: &str, note: &str, plan: Option<String>, created_by: Option<&str>) -> AppResult<MeetingNote> {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now().to_rfc3339();
 
         let meeting_note = sqlx::query_as::<_, MeetingNote>(
             r#"
-            INSERT INTO meeting_notes (id, studentId, date, type, note, plan, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO meeting_notes (id, studentId, date, type, note, plan, createdBy, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *
             "#
         )
@@ -144,6 +170,7 @@ impl CounselingRepository {
         .bind(note_type)
         .bind(note)
         .bind(&plan)
+        .bind(created_by)
         .bind(&now)
         .bind(&now)
         .fetch_one(pool)
@@ -210,7 +237,7 @@ impl CounselingRepository {
             UPDATE counseling_sessions SET
                 detailedNotes = ?, sessionFlow = ?, studentParticipationLevel = ?,
                 cooperationLevel = ?, emotionalState = ?, followUpNeeded = ?,
-                followUpPlan = ?, completed = ?, updated_at = ?
+                followUpPlan = ?, completed = ?, isConfidential = ?, updated_at = ?
             WHERE id = ?
             "#
         )
@@ -222,6 +249,7 @@ impl CounselingRepository {
         .bind(&session.followUpNeeded)
         .bind(&session.followUpPlan)
         .bind(&session.completed)
+        .bind(session.isConfidential)
         .bind(&now)
         .bind(id)
         .execute(pool)
@@ -0,0 +1,395 @@
+use crate::models::{
+    CreateSubjectRequest, CreateTopicRequest, CurriculumCatalog, CurriculumImportResult, CurriculumVersion, Subject,
+    Topic, CURRICULUM_SOURCE_SCHOOL,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct CurriculumRepository;
+
+impl CurriculumRepository {
+    pub async fn get_subjects(
+        pool: &SqlitePool,
+        exam_type: Option<&str>,
+        include_archived: bool,
+    ) -> AppResult<Vec<Subject>> {
+        let subjects = sqlx::query_as::<_, Subject>(
+            r#"
+            SELECT * FROM subjects
+            WHERE (? IS NULL OR examType = ?) AND (? OR isArchived = 0)
+            ORDER BY examType IS NULL, examType, sortOrder, name
+            "#
+        )
+        .bind(exam_type)
+        .bind(exam_type)
+        .bind(include_archived)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(subjects)
+    }
+
+    pub async fn create_subject(pool: &SqlitePool, req: &CreateSubjectRequest) -> AppResult<Subject> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let subject = sqlx::query_as::<_, Subject>(
+            r#"
+            INSERT INTO subjects (
+                id, name, code, description, color, examType, examSubject, source, sortOrder, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(sortOrder), 0) + 1 FROM subjects WHERE examType IS ?), ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&req.name)
+        .bind(&req.code)
+        .bind(&req.description)
+        .bind(&req.color)
+        .bind(&req.examType)
+        .bind(&req.examSubject)
+        .bind(CURRICULUM_SOURCE_SCHOOL)
+        .bind(&req.examType)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(subject)
+    }
+
+    pub async fn update_subject(pool: &SqlitePool, subject: &Subject) -> AppResult<Subject> {
+        let updated = sqlx::query_as::<_, Subject>(
+            r#"
+            UPDATE subjects SET
+                name = ?, code = ?, description = ?, color = ?, examType = ?, examSubject = ?,
+                sortOrder = ?, isArchived = ?
+            WHERE id = ?
+            RETURNING *
+            "#
+        )
+        .bind(&subject.name)
+        .bind(&subject.code)
+        .bind(&subject.description)
+        .bind(&subject.color)
+        .bind(&subject.examType)
+        .bind(&subject.examSubject)
+        .bind(subject.sortOrder)
+        .bind(subject.isArchived)
+        .bind(&subject.id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Subject {}", subject.id)))?;
+
+        Ok(updated)
+    }
+
+    /// Delete a subject with its topics and the progress recorded on them
+    pub async fn delete_subject(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM subjects WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Subject {}", id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_topic_by_id(pool: &SqlitePool, id: &str) -> AppResult<Topic> {
+        let topic = sqlx::query_as::<_, Topic>("SELECT * FROM topics WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Topic {}", id)))?;
+
+        Ok(topic)
+    }
+
+    pub async fn get_topics(pool: &SqlitePool, subject_id: &str, include_archived: bool) -> AppResult<Vec<Topic>> {
+        let topics = sqlx::query_as::<_, Topic>(
+            "SELECT * FROM topics WHERE subjectId = ? AND (? OR isArchived = 0) ORDER BY sortOrder, name"
+        )
+        .bind(subject_id)
+        .bind(include_archived)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(topics)
+    }
+
+    /// Add a school topic after the subject's existing topics
+    pub async fn create_topic(pool: &SqlitePool, req: &CreateTopicRequest, estimated_hours: i32) -> AppResult<Topic> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let topic = sqlx::query_as::<_, Topic>(
+            r#"
+            INSERT INTO topics (
+                id, subjectId, name, description, difficulty, estimatedHours, source, sortOrder, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, (SELECT COALESCE(MAX(sortOrder), 0) + 1 FROM topics WHERE subjectId = ?), ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&req.subjectId)
+        .bind(&req.name)
+        .bind(&req.description)
+        .bind(&req.difficulty)
+        .bind(estimated_hours)
+        .bind(CURRICULUM_SOURCE_SCHOOL)
+        .bind(&req.subjectId)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(topic)
+    }
+
+    pub async fn update_topic(pool: &SqlitePool, topic: &Topic) -> AppResult<Topic> {
+        let updated = sqlx::query_as::<_, Topic>(
+            r#"
+            UPDATE topics SET
+                name = ?, description = ?, difficulty = ?, estimatedHours = ?, sortOrder = ?, isArchived = ?
+            WHERE id = ?
+            RETURNING *
+            "#
+        )
+        .bind(&topic.name)
+        .bind(&topic.description)
+        .bind(&topic.difficulty)
+        .bind(topic.estimatedHours)
+        .bind(topic.sortOrder)
+        .bind(topic.isArchived)
+        .bind(&topic.id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Topic {}", topic.id)))?;
+
+        Ok(updated)
+    }
+
+    pub async fn delete_topic(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM topics WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Topic {}", id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_versions(pool: &SqlitePool) -> AppResult<Vec<CurriculumVersion>> {
+        let versions = sqlx::query_as::<_, CurriculumVersion>(
+            "SELECT * FROM curriculum_versions ORDER BY catalog, version DESC"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(versions)
+    }
+
+    pub async fn get_latest_version(pool: &SqlitePool, catalog: &str) -> AppResult<Option<i64>> {
+        let version = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT MAX(version) FROM curriculum_versions WHERE catalog = ?"
+        )
+        .bind(catalog)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(version)
+    }
+
+    /// Merge a catalog version into subjects and topics in one transaction.
+    ///
+    /// Entries are matched on their catalog key and updated in place, keeping
+    /// their ids, a color the school chose and their archived flag. Entries of
+    /// the catalog missing from this version are archived; school subjects and
+    /// topics are left alone.
+    pub async fn merge_catalog(
+        pool: &SqlitePool,
+        catalog: &CurriculumCatalog,
+        imported_by: Option<&str>,
+    ) -> AppResult<CurriculumImportResult> {
+        let now = Utc::now().to_rfc3339();
+        let mut result = CurriculumImportResult {
+            catalog: catalog.catalog.clone(),
+            version: catalog.version,
+            subjectsAdded: 0,
+            subjectsUpdated: 0,
+            topicsAdded: 0,
+            topicsUpdated: 0,
+            subjectsArchived: 0,
+            topicsArchived: 0,
+        };
+        let mut tx = pool.begin().await?;
+
+        for (subject_index, subject) in catalog.subjects.iter().enumerate() {
+            let existing = sqlx::query_scalar::<_, String>("SELECT id FROM subjects WHERE catalogKey = ?")
+                .bind(&subject.key)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+            let subject_id = match existing {
+                Some(id) => {
+                    sqlx::query(
+                        r#"
+                        UPDATE subjects SET
+                            name = ?, code = ?, description = ?, color = COALESCE(color, ?), examType = ?,
+                            examSubject = ?, source = ?, curriculumVersion = ?, sortOrder = ?
+                        WHERE id = ?
+                        "#
+                    )
+                    .bind(&subject.name)
+                    .bind(&subject.code)
+                    .bind(&subject.description)
+                    .bind(&subject.color)
+                    .bind(&subject.examType)
+                    .bind(&subject.examSubject)
+                    .bind(&catalog.catalog)
+                    .bind(catalog.version)
+                    .bind(subject_index as i64)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+                    result.subjectsUpdated += 1;
+                    id
+                }
+                None => {
+                    let id = Uuid::new_v4().to_string();
+                    sqlx::query(
+                        r#"
+                        INSERT INTO subjects (
+                            id, name, code, description, color, examType, examSubject, source, catalogKey,
+                            curriculumVersion, sortOrder, created_at
+                        )
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        "#
+                    )
+                    .bind(&id)
+                    .bind(&subject.name)
+                    .bind(&subject.code)
+                    .bind(&subject.description)
+                    .bind(&subject.color)
+                    .bind(&subject.examType)
+                    .bind(&subject.examSubject)
+                    .bind(&catalog.catalog)
+                    .bind(&subject.key)
+                    .bind(catalog.version)
+                    .bind(subject_index as i64)
+                    .bind(&now)
+                    .execute(&mut *tx)
+                    .await?;
+                    result.subjectsAdded += 1;
+                    id
+                }
+            };
+
+            for (topic_index, topic) in subject.topics.iter().enumerate() {
+                let updated = sqlx::query(
+                    r#"
+                    UPDATE topics SET
+                        subjectId = ?, name = ?, description = ?, difficulty = ?, estimatedHours = ?,
+                        source = ?, curriculumVersion = ?, sortOrder = ?
+                    WHERE catalogKey = ?
+                    "#
+                )
+                .bind(&subject_id)
+                .bind(&topic.name)
+                .bind(&topic.description)
+                .bind(&topic.difficulty)
+                .bind(topic.estimatedHours)
+                .bind(&catalog.catalog)
+                .bind(catalog.version)
+                .bind(topic_index as i64)
+                .bind(&topic.key)
+                .execute(&mut *tx)
+                .await?;
+
+                if updated.rows_affected() > 0 {
+                    result.topicsUpdated += 1;
+                    continue;
+                }
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO topics (
+                        id, subjectId, name, description, difficulty, estimatedHours, source, catalogKey,
+                        curriculumVersion, sortOrder, created_at
+                    )
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&subject_id)
+                .bind(&topic.name)
+                .bind(&topic.description)
+                .bind(&topic.difficulty)
+                .bind(topic.estimatedHours)
+                .bind(&catalog.catalog)
+                .bind(&topic.key)
+                .bind(catalog.version)
+                .bind(topic_index as i64)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+                result.topicsAdded += 1;
+            }
+        }
+
+        result.subjectsArchived = sqlx::query(
+            r#"
+            UPDATE subjects SET isArchived = 1
+            WHERE source = ? AND catalogKey IS NOT NULL AND curriculumVersion < ? AND isArchived = 0
+            "#
+        )
+        .bind(&catalog.catalog)
+        .bind(catalog.version)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        result.topicsArchived = sqlx::query(
+            r#"
+            UPDATE topics SET isArchived = 1
+            WHERE source = ? AND catalogKey IS NOT NULL AND curriculumVersion < ? AND isArchived = 0
+            "#
+        )
+        .bind(&catalog.catalog)
+        .bind(catalog.version)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        sqlx::query(
+            r#"
+            INSERT INTO curriculum_versions (id, catalog, version, name, subjectCount, topicCount, importedBy, imported_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(catalog, version) DO UPDATE SET
+                name = excluded.name, subjectCount = excluded.subjectCount, topicCount = excluded.topicCount,
+                importedBy = excluded.importedBy, imported_at = excluded.imported_at
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&catalog.catalog)
+        .bind(catalog.version)
+        .bind(&catalog.name)
+        .bind(catalog.subjects.len() as i64)
+        .bind(catalog.subjects.iter().map(|s| s.topics.len() as i64).sum::<i64>())
+        .bind(imported_by)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,274 @@
+use crate::models::{
+    CreateCustomFieldRequest, CustomFieldDefinition, StudentAttribute, StudentAttributeValue, StudentTag, TagCount,
+    UpdateCustomFieldRequest,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct CustomFieldRepository;
+
+impl CustomFieldRepository {
+    pub async fn create_field(
+        pool: &SqlitePool,
+        req: &CreateCustomFieldRequest,
+        options: Option<String>,
+    ) -> AppResult<CustomFieldDefinition> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let field = sqlx::query_as::<_, CustomFieldDefinition>(
+            r#"
+            INSERT INTO custom_field_definitions (
+                id, key, label, fieldType, options, sortOrder, isActive, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, 1, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&req.key)
+        .bind(req.label.trim())
+        .bind(&req.fieldType)
+        .bind(&options)
+        .bind(req.sortOrder.unwrap_or(0))
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(field)
+    }
+
+    pub async fn get_field_by_id(pool: &SqlitePool, id: &str) -> AppResult<CustomFieldDefinition> {
+        let field = sqlx::query_as::<_, CustomFieldDefinition>("SELECT * FROM custom_field_definitions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Custom field {}", id)))?;
+
+        Ok(field)
+    }
+
+    pub async fn find_field_by_key(pool: &SqlitePool, key: &str) -> AppResult<Option<CustomFieldDefinition>> {
+        let field = sqlx::query_as::<_, CustomFieldDefinition>("SELECT * FROM custom_field_definitions WHERE key = ?")
+            .bind(key)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(field)
+    }
+
+    pub async fn get_fields(pool: &SqlitePool, include_inactive: bool) -> AppResult<Vec<CustomFieldDefinition>> {
+        let fields = sqlx::query_as::<_, CustomFieldDefinition>(
+            r#"
+            SELECT * FROM custom_field_definitions
+            WHERE ? OR isActive = 1
+            ORDER BY sortOrder, label
+            "#
+        )
+        .bind(include_inactive)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(fields)
+    }
+
+    pub async fn update_field(
+        pool: &SqlitePool,
+        id: &str,
+        req: &UpdateCustomFieldRequest,
+        options: Option<String>,
+    ) -> AppResult<CustomFieldDefinition> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE custom_field_definitions SET
+                label = COALESCE(?, label),
+                options = COALESCE(?, options),
+                sortOrder = COALESCE(?, sortOrder),
+                isActive = COALESCE(?, isActive),
+                updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(req.label.as_deref().map(str::trim))
+        .bind(&options)
+        .bind(req.sortOrder)
+        .bind(req.isActive)
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Custom field {}", id)));
+        }
+
+        Self::get_field_by_id(pool, id).await
+    }
+
+    pub async fn delete_field(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM custom_field_definitions WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Custom field {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Distinct values stored for a field, used to check option changes
+    pub async fn get_distinct_values(pool: &SqlitePool, field_id: &str) -> AppResult<Vec<String>> {
+        let values = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT value FROM student_attributes WHERE fieldId = ? ORDER BY value"
+        )
+        .bind(field_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(values)
+    }
+
+    pub async fn get_student_attributes(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<StudentAttribute>> {
+        let attributes = sqlx::query_as::<_, StudentAttribute>(
+            r#"
+            SELECT f.id AS fieldId, f.key, f.label, f.fieldType, a.value, a.updated_at
+            FROM student_attributes a
+            INNER JOIN custom_field_definitions f ON f.id = a.fieldId
+            WHERE a.studentId = ?
+            ORDER BY f.sortOrder, f.label
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(attributes)
+    }
+
+    pub async fn get_all_attribute_values(pool: &SqlitePool) -> AppResult<Vec<StudentAttributeValue>> {
+        let values = sqlx::query_as::<_, StudentAttributeValue>(
+            "SELECT * FROM student_attributes ORDER BY studentId, fieldId"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(values)
+    }
+
+    pub async fn set_attribute<'e, E>(executor: E, student_id: &str, field_id: &str, value: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO student_attributes (studentId, fieldId, value, updated_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(studentId, fieldId) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+            "#
+        )
+        .bind(student_id)
+        .bind(field_id)
+        .bind(value)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_attribute<'e, E>(executor: E, student_id: &str, field_id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query("DELETE FROM student_attributes WHERE studentId = ? AND fieldId = ?")
+            .bind(student_id)
+            .bind(field_id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_student_tags(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<String>> {
+        let tags = sqlx::query_scalar::<_, String>("SELECT tag FROM student_tags WHERE studentId = ? ORDER BY tag")
+            .bind(student_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(tags)
+    }
+
+    pub async fn get_all_tags(pool: &SqlitePool) -> AppResult<Vec<StudentTag>> {
+        let tags = sqlx::query_as::<_, StudentTag>("SELECT * FROM student_tags ORDER BY studentId, tag")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(tags)
+    }
+
+    /// Tags in use with the number of students carrying each
+    pub async fn get_tag_counts(pool: &SqlitePool, institution_id: &str) -> AppResult<Vec<TagCount>> {
+        let tags = sqlx::query_as::<_, TagCount>(
+            r#"
+            SELECT t.tag, COUNT(*) AS count FROM student_tags t
+            INNER JOIN students s ON t.studentId = s.id
+            WHERE s.institutionId = ?
+            GROUP BY t.tag ORDER BY count DESC, t.tag
+            "#
+        )
+        .bind(institution_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    /// Add a tag; returns false when the student already had it
+    pub async fn add_tag<'e, E>(executor: E, student_id: &str, tag: &str) -> AppResult<bool>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query("INSERT OR IGNORE INTO student_tags (studentId, tag, created_at) VALUES (?, ?, ?)")
+            .bind(student_id)
+            .bind(tag)
+            .bind(&now)
+            .execute(executor)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Replace all of a student's tags
+    pub async fn replace_tags(pool: &SqlitePool, student_id: &str, tags: &[String]) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM student_tags WHERE studentId = ?")
+            .bind(student_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for tag in tags {
+            sqlx::query("INSERT OR IGNORE INTO student_tags (studentId, tag, created_at) VALUES (?, ?, ?)")
+                .bind(student_id)
+                .bind(tag)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
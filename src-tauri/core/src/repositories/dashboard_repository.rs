@@ -0,0 +1,207 @@
+use crate::models::{AttendanceHotspot, CategoryCount, CounselingReminder};
+use crate::error::AppResult;
+use sqlx::SqlitePool;
+use chrono::Utc;
+
+pub struct DashboardRepository;
+
+impl DashboardRepository {
+    /// Counter bumped by triggers on every write the dashboard reads
+    pub async fn get_data_version(pool: &SqlitePool) -> AppResult<i64> {
+        let version = sqlx::query_scalar::<_, i64>("SELECT version FROM dashboard_data_version WHERE id = 1")
+            .fetch_one(pool)
+            .await?;
+
+        Ok(version)
+    }
+
+    /// Cached statistics JSON, if computed on the same day from the same data version
+    pub async fn get_cached(pool: &SqlitePool, scope: &str, version: i64, date: &str) -> AppResult<Option<String>> {
+        let stats = sqlx::query_scalar::<_, String>(
+            "SELECT stats FROM dashboard_cache WHERE scope = ? AND dataVersion = ? AND cacheDate = ?"
+        )
+        .bind(scope)
+        .bind(version)
+        .bind(date)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(stats)
+    }
+
+    pub async fn save_cache(pool: &SqlitePool, scope: &str, version: i64, date: &str, stats: &str) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO dashboard_cache (scope, dataVersion, cacheDate, stats, computedAt)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(scope) DO UPDATE SET
+                dataVersion = excluded.dataVersion, cacheDate = excluded.cacheDate,
+                stats = excluded.stats, computedAt = excluded.computedAt
+            "#
+        )
+        .bind(scope)
+        .bind(version)
+        .bind(date)
+        .bind(stats)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Active students per risk level
+    pub async fn count_students_by_risk(pool: &SqlitePool, institution_id: Option<&str>) -> AppResult<Vec<CategoryCount>> {
+        let counts = sqlx::query_as::<_, CategoryCount>(
+            r#"
+            SELECT risk AS category, COUNT(*) AS count FROM students
+            WHERE status = 'active' AND (?1 IS NULL OR institutionId = ?1)
+            GROUP BY risk
+            "#
+        )
+        .bind(institution_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// (alerts raised since the date, alerts not yet acknowledged)
+    pub async fn count_alerts(pool: &SqlitePool, institution_id: Option<&str>, since: &str) -> AppResult<(i64, i64)> {
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN a.created_at >= ?2 THEN 1 ELSE 0 END), 0),
+                COALESCE(SUM(CASE WHEN a.status IN ('OPEN', 'ESCALATED') THEN 1 ELSE 0 END), 0)
+            FROM risk_alerts a
+            INNER JOIN students s ON s.id = a.studentId
+            WHERE ?1 IS NULL OR s.institutionId = ?1
+            "#
+        )
+        .bind(institution_id)
+        .bind(since)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Sessions held from each start date up to today
+    pub async fn count_sessions(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        week_start: &str,
+        month_start: &str,
+        today: &str,
+    ) -> AppResult<(i64, i64)> {
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                COALESCE(SUM(CASE WHEN sessionDate >= ?2 THEN 1 ELSE 0 END), 0),
+                COUNT(*)
+            FROM counseling_sessions
+            WHERE sessionDate >= MIN(?2, ?3) AND sessionDate <= ?4
+              AND (?1 IS NULL OR institutionId = ?1)
+            "#
+        )
+        .bind(institution_id)
+        .bind(week_start)
+        .bind(month_start)
+        .bind(today)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    pub async fn count_overdue_follow_ups(pool: &SqlitePool, institution_id: Option<&str>, today: &str) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM counseling_follow_ups f
+            LEFT JOIN counseling_sessions cs ON cs.id = f.sessionId
+            WHERE f.status <> 'completed' AND f.followUpDate < ?2
+              AND (?1 IS NULL OR cs.institutionId = ?1)
+            "#
+        )
+        .bind(institution_id)
+        .bind(today)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn count_pending_suggestions(pool: &SqlitePool, institution_id: Option<&str>) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM ai_suggestion_queue q
+            INNER JOIN students s ON s.id = q.studentId
+            WHERE q.status = 'PENDING' AND (?1 IS NULL OR s.institutionId = ?1)
+            "#
+        )
+        .bind(institution_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Classes with the most absence days since the date
+    pub async fn get_attendance_hotspots(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        since: &str,
+        limit: i64,
+    ) -> AppResult<Vec<AttendanceHotspot>> {
+        let hotspots = sqlx::query_as::<_, AttendanceHotspot>(
+            r#"
+            SELECT
+                COALESCE(c.name, s.class, 'Sınıfsız') AS className,
+                COUNT(DISTINCT r.studentId) AS absentStudents,
+                TOTAL(r.duration) AS absenceDays
+            FROM attendance_records r
+            INNER JOIN students s ON s.id = r.studentId
+            LEFT JOIN classes c ON c.id = s.classId
+            WHERE r.date >= ?2 AND r.status IN ('Özürlü', 'Raporlu', 'Özürsüz')
+              AND (?1 IS NULL OR s.institutionId = ?1)
+            GROUP BY COALESCE(c.name, s.class, 'Sınıfsız')
+            ORDER BY absenceDays DESC, absentStudents DESC
+            LIMIT ?3
+            "#
+        )
+        .bind(institution_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(hotspots)
+    }
+
+    /// Pending reminders dated between the two days, soonest first
+    pub async fn get_upcoming_reminders(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        from: &str,
+        until: &str,
+    ) -> AppResult<Vec<CounselingReminder>> {
+        let reminders = sqlx::query_as::<_, CounselingReminder>(
+            r#"
+            SELECT r.* FROM counseling_reminders r
+            LEFT JOIN counseling_sessions cs ON cs.id = r.sessionId
+            WHERE r.status = 'pending' AND r.reminderDate >= ?2 AND r.reminderDate <= ?3
+              AND (?1 IS NULL OR r.sessionId IS NULL OR cs.institutionId = ?1)
+            ORDER BY r.reminderDate, r.reminderTime
+            "#
+        )
+        .bind(institution_id)
+        .bind(from)
+        .bind(until)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(reminders)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::models::StudentDocument;
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use chrono::Utc;
+
+pub struct DocumentRepository;
+
+impl DocumentRepository {
+    pub async fn create(pool: &SqlitePool, document: StudentDocument) -> AppResult<StudentDocument> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query_as::<_, StudentDocument>(
+            r#"
+            INSERT INTO student_documents (
+                id, studentId, name, type, category, storagePath, size, sha256,
+                uploadedBy, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&document.id)
+        .bind(&document.studentId)
+        .bind(&document.name)
+        .bind(&document.mime_type)
+        .bind(&document.category)
+        .bind(&document.storagePath)
+        .bind(document.size)
+        .bind(&document.sha256)
+        .bind(&document.uploadedBy)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<StudentDocument> {
+        let document = sqlx::query_as::<_, StudentDocument>(
+            "SELECT * FROM student_documents WHERE id = ?"
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Student document {}", id)))?;
+
+        Ok(document)
+    }
+
+    pub async fn get_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<StudentDocument>> {
+        let documents = sqlx::query_as::<_, StudentDocument>(
+            "SELECT * FROM student_documents WHERE studentId = ? ORDER BY created_at DESC"
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    pub async fn get_by_student_and_category(pool: &SqlitePool, student_id: &str, category: &str) -> AppResult<Vec<StudentDocument>> {
+        let documents = sqlx::query_as::<_, StudentDocument>(
+            "SELECT * FROM student_documents WHERE studentId = ? AND category = ? ORDER BY created_at DESC"
+        )
+        .bind(student_id)
+        .bind(category)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    pub async fn find_by_hash(pool: &SqlitePool, student_id: &str, sha256: &str) -> AppResult<Option<StudentDocument>> {
+        let document = sqlx::query_as::<_, StudentDocument>(
+            "SELECT * FROM student_documents WHERE studentId = ? AND sha256 = ?"
+        )
+        .bind(student_id)
+        .bind(sha256)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    /// Whether any document still points at content with this hash
+    pub async fn is_hash_referenced(pool: &SqlitePool, sha256: &str) -> AppResult<bool> {
+        let referenced: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM student_documents WHERE sha256 = ?)"
+        )
+        .bind(sha256)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(referenced)
+    }
+
+    pub async fn get_legacy_inline(pool: &SqlitePool) -> AppResult<Vec<StudentDocument>> {
+        let documents = sqlx::query_as::<_, StudentDocument>(
+            "SELECT * FROM student_documents WHERE dataUrl IS NOT NULL AND storagePath IS NULL"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    /// Record the on-disk location of a legacy document and drop its inline payload
+    pub async fn mark_stored(
+        pool: &SqlitePool,
+        id: &str,
+        mime_type: &str,
+        storage_path: &str,
+        size: i64,
+        sha256: &str,
+    ) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            UPDATE student_documents SET
+                type = ?, storagePath = ?, size = ?, sha256 = ?, dataUrl = NULL, updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(mime_type)
+        .bind(storage_path)
+        .bind(size)
+        .bind(sha256)
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM student_documents WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Student document {}", id)));
+        }
+
+        Ok(())
+    }
+}
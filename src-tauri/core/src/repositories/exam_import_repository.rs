@@ -0,0 +1,89 @@
+use crate::models::ExamImportLayout;
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct ExamImportRepository;
+
+impl ExamImportRepository {
+    pub async fn create_layout(pool: &SqlitePool, layout: &ExamImportLayout) -> AppResult<ExamImportLayout> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO exam_import_layouts (id, publisher, name, format, examType, config, notes, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(&layout.publisher)
+        .bind(&layout.name)
+        .bind(&layout.format)
+        .bind(&layout.examType)
+        .bind(&layout.config)
+        .bind(&layout.notes)
+        .bind(&now)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Self::get_layout(pool, &id).await
+    }
+
+    pub async fn get_layout(pool: &SqlitePool, id: &str) -> AppResult<ExamImportLayout> {
+        sqlx::query_as::<_, ExamImportLayout>("SELECT * FROM exam_import_layouts WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Exam import layout {}", id)))
+    }
+
+    pub async fn get_layouts(pool: &SqlitePool) -> AppResult<Vec<ExamImportLayout>> {
+        let layouts = sqlx::query_as::<_, ExamImportLayout>(
+            "SELECT * FROM exam_import_layouts ORDER BY publisher, name"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(layouts)
+    }
+
+    pub async fn update_layout(pool: &SqlitePool, layout: &ExamImportLayout) -> AppResult<ExamImportLayout> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            UPDATE exam_import_layouts
+            SET publisher = ?, name = ?, format = ?, examType = ?, config = ?, notes = ?, updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(&layout.publisher)
+        .bind(&layout.name)
+        .bind(&layout.format)
+        .bind(&layout.examType)
+        .bind(&layout.config)
+        .bind(&layout.notes)
+        .bind(&now)
+        .bind(&layout.id)
+        .execute(pool)
+        .await?;
+
+        Self::get_layout(pool, &layout.id).await
+    }
+
+    pub async fn delete_layout(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM exam_import_layouts WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Exam import layout {}", id)));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::models::{ExamTopicResult, ExamTopicResultSet, StudentExamTopicResult};
+use crate::error::AppResult;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct ExamTopicRepository;
+
+impl ExamTopicRepository {
+    /// Replace the topic results of exam results in one transaction and write
+    /// their strength and weakness summaries. Returns the topic results saved.
+    pub async fn replace_topic_results(pool: &SqlitePool, sets: &[ExamTopicResultSet]) -> AppResult<usize> {
+        let now = Utc::now().to_rfc3339();
+        let mut saved = 0;
+        let mut tx = pool.begin().await?;
+
+        for set in sets {
+            sqlx::query("DELETE FROM exam_topic_results WHERE examResultId = ?")
+                .bind(&set.examResultId)
+                .execute(&mut *tx)
+                .await?;
+
+            for result in &set.results {
+                sqlx::query(
+                    r#"
+                    INSERT INTO exam_topic_results (
+                        id, examResultId, studentId, subjectId, topicId, subjectName, topicName,
+                        questionCount, correct, wrong, empty, successRate, created_at
+                    )
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                    "#
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&set.examResultId)
+                .bind(&result.studentId)
+                .bind(&result.subjectId)
+                .bind(&result.topicId)
+                .bind(&result.subjectName)
+                .bind(&result.topicName)
+                .bind(result.questionCount)
+                .bind(result.correct)
+                .bind(result.wrong)
+                .bind(result.empty)
+                .bind(result.successRate)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+                saved += 1;
+            }
+
+            sqlx::query("UPDATE exam_results SET strengthAreas = ?, weaknessAreas = ?, updated_at = ? WHERE id = ?")
+                .bind(&set.strengthAreas)
+                .bind(&set.weaknessAreas)
+                .bind(&now)
+                .bind(&set.examResultId)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+
+    pub async fn get_by_exam_result(pool: &SqlitePool, exam_result_id: &str) -> AppResult<Vec<ExamTopicResult>> {
+        let results = sqlx::query_as::<_, ExamTopicResult>(
+            "SELECT * FROM exam_topic_results WHERE examResultId = ? ORDER BY subjectName, successRate, topicName"
+        )
+        .bind(exam_result_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// A student's topic results with their exams, latest exam first
+    pub async fn get_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<StudentExamTopicResult>> {
+        let results = sqlx::query_as::<_, StudentExamTopicResult>(
+            r#"
+            SELECT tr.*, er.examName, er.examDate, er.examType
+            FROM exam_topic_results tr
+            JOIN exam_results er ON er.id = tr.examResultId
+            WHERE tr.studentId = ?
+            ORDER BY er.examDate DESC, er.created_at DESC, tr.subjectName, tr.topicName
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,373 @@
+use crate::models::{Family, FamilyMemberSummary, Guardian, Student};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct FamilyRepository;
+
+impl FamilyRepository {
+    pub async fn create<'e, E>(executor: E, name: &str, notes: Option<String>) -> AppResult<Family>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let family = sqlx::query_as::<_, Family>(
+            r#"
+            INSERT INTO families (id, name, notes, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(&notes)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(family)
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<Family> {
+        let family = sqlx::query_as::<_, Family>("SELECT * FROM families WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Family {}", id)))?;
+
+        Ok(family)
+    }
+
+    pub async fn get_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Option<Family>> {
+        let family = sqlx::query_as::<_, Family>(
+            r#"
+            SELECT f.* FROM families f
+            INNER JOIN family_members fm ON f.id = fm.familyId
+            WHERE fm.studentId = ?
+            "#
+        )
+        .bind(student_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(family)
+    }
+
+    pub async fn update(pool: &SqlitePool, id: &str, name: &str, notes: Option<String>) -> AppResult<Family> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query("UPDATE families SET name = ?, notes = ?, updated_at = ? WHERE id = ?")
+            .bind(name)
+            .bind(&notes)
+            .bind(&now)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Family {}", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    pub async fn delete<'e, E>(executor: E, id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let result = sqlx::query("DELETE FROM families WHERE id = ?")
+            .bind(id)
+            .execute(executor)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Family {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Create a family with its members in one transaction. Members move out
+    /// of their previous families, and families left empty are removed.
+    pub async fn create_with_members(
+        pool: &SqlitePool,
+        name: &str,
+        notes: Option<String>,
+        student_ids: &[String],
+    ) -> AppResult<Family> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        let family = sqlx::query_as::<_, Family>(
+            r#"
+            INSERT INTO families (id, name, notes, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(name)
+        .bind(&notes)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut previous_families = Vec::new();
+        for student_id in student_ids {
+            let previous = sqlx::query_scalar::<_, String>("SELECT familyId FROM family_members WHERE studentId = ?")
+                .bind(student_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            previous_families.extend(previous);
+
+            sqlx::query(
+                r#"
+                INSERT INTO family_members (familyId, studentId, created_at)
+                VALUES (?, ?, ?)
+                ON CONFLICT(studentId) DO UPDATE SET familyId = excluded.familyId
+                "#
+            )
+            .bind(&id)
+            .bind(student_id)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for previous in previous_families {
+            sqlx::query(
+                "DELETE FROM families WHERE id = ? AND NOT EXISTS (SELECT 1 FROM family_members WHERE familyId = ?)"
+            )
+            .bind(&previous)
+            .bind(&previous)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(family)
+    }
+
+    /// Put a student in a family, moving them out of any family they were in
+    pub async fn add_member<'e, E>(executor: E, family_id: &str, student_id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO family_members (familyId, studentId, created_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(studentId) DO UPDATE SET familyId = excluded.familyId
+            "#
+        )
+        .bind(family_id)
+        .bind(student_id)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_member(pool: &SqlitePool, family_id: &str, student_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM family_members WHERE familyId = ? AND studentId = ?")
+            .bind(family_id)
+            .bind(student_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Student {} in family {}", student_id, family_id)));
+        }
+
+        Ok(())
+    }
+
+    /// Move every member of one family into another
+    pub async fn move_members<'e, E>(executor: E, from_family_id: &str, to_family_id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query("UPDATE family_members SET familyId = ? WHERE familyId = ?")
+            .bind(to_family_id)
+            .bind(from_family_id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn count_members(pool: &SqlitePool, family_id: &str) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM family_members WHERE familyId = ?")
+            .bind(family_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    pub async fn get_members(pool: &SqlitePool, family_id: &str) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            r#"
+            SELECT s.* FROM students s
+            INNER JOIN family_members fm ON s.id = fm.studentId
+            WHERE fm.familyId = ?
+            ORDER BY s.birthDate, s.name
+            "#
+        )
+        .bind(family_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn get_member_summaries(pool: &SqlitePool, family_id: &str) -> AppResult<Vec<FamilyMemberSummary>> {
+        let members = sqlx::query_as::<_, FamilyMemberSummary>(
+            r#"
+            SELECT
+                s.id, s.name, s.surname, s.class, s.birthDate, s.risk,
+                (SELECT COUNT(*) FROM behavior_incidents bi
+                    WHERE bi.studentId = s.id AND bi.status = 'Açık') AS openIncidents,
+                (SELECT COUNT(DISTINCT cf.id) FROM counseling_follow_ups cf
+                    INNER JOIN counseling_session_students css ON cf.sessionId = css.sessionId
+                    WHERE css.studentId = s.id AND cf.status != 'completed') AS pendingFollowUps,
+                (SELECT COUNT(*) FROM interventions i
+                    WHERE i.studentId = s.id AND i.status != 'Tamamlandı') AS activeInterventions
+            FROM students s
+            INNER JOIN family_members fm ON s.id = fm.studentId
+            WHERE fm.familyId = ?
+            ORDER BY s.birthDate, s.name
+            "#
+        )
+        .bind(family_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    /// Guardians linked to more than one member of the family
+    pub async fn get_shared_guardians(pool: &SqlitePool, family_id: &str) -> AppResult<Vec<Guardian>> {
+        let guardians = sqlx::query_as::<_, Guardian>(
+            r#"
+            SELECT g.* FROM guardians g
+            INNER JOIN student_guardians sg ON g.id = sg.guardianId
+            INNER JOIN family_members fm ON sg.studentId = fm.studentId
+            WHERE fm.familyId = ?
+            GROUP BY g.id
+            HAVING COUNT(DISTINCT sg.studentId) > 1
+            ORDER BY COUNT(*) DESC, g.name
+            "#
+        )
+        .bind(family_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(guardians)
+    }
+
+    pub async fn get_students_sharing_guardian(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            r#"
+            SELECT DISTINCT s.* FROM students s
+            INNER JOIN student_guardians other ON s.id = other.studentId
+            INNER JOIN student_guardians mine ON other.guardianId = mine.guardianId
+            WHERE mine.studentId = ? AND s.id != ?
+            "#
+        )
+        .bind(student_id)
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn get_students_at_address(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            r#"
+            SELECT s.* FROM students s, students me
+            WHERE me.id = ? AND s.id != me.id
+              AND s.institutionId IS me.institutionId
+              AND me.address IS NOT NULL AND TRIM(me.address) != ''
+              AND LOWER(TRIM(s.address)) = LOWER(TRIM(me.address))
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn get_students_with_surname(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            r#"
+            SELECT s.* FROM students s, students me
+            WHERE me.id = ? AND s.id != me.id
+              AND s.institutionId IS me.institutionId
+              AND TRIM(s.surname) = TRIM(me.surname) COLLATE NOCASE
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn dismiss_suggestion(
+        pool: &SqlitePool,
+        student_id: &str,
+        other_student_id: &str,
+        dismissed_by: Option<String>,
+    ) -> AppResult<()> {
+        let (first, second) = ordered_pair(student_id, other_student_id);
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO sibling_suggestion_dismissals (studentId, otherStudentId, dismissedBy, created_at)
+            VALUES (?, ?, ?, ?)
+            "#
+        )
+        .bind(first)
+        .bind(second)
+        .bind(&dismissed_by)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_dismissed_for(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT otherStudentId FROM sibling_suggestion_dismissals WHERE studentId = ?
+            UNION
+            SELECT studentId FROM sibling_suggestion_dismissals WHERE otherStudentId = ?
+            "#
+        )
+        .bind(student_id)
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+}
+
+fn ordered_pair<'a>(a: &'a str, b: &'a str) -> (&'a str, &'a str) {
+    if a <= b { (a, b) } else { (b, a) }
+}
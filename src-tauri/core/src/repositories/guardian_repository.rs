@@ -0,0 +1,323 @@
+use crate::models::{
+    Guardian, StudentGuardian, Student, CreateGuardianRequest, UpdateGuardianRequest, LinkGuardianRequest,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+const STUDENT_GUARDIAN_SELECT: &str = r#"
+    SELECT g.*, sg.studentId, sg.relationship, sg.isPrimary, sg.hasCustody,
+           sg.livesWithStudent, sg.contactRestricted, sg.restrictionNotes
+    FROM guardians g
+    INNER JOIN student_guardians sg ON g.id = sg.guardianId
+"#;
+
+pub struct GuardianRepository;
+
+impl GuardianRepository {
+    pub async fn create<'e, E>(executor: E, req: CreateGuardianRequest) -> AppResult<Guardian>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let language = req.preferredLanguage.unwrap_or_else(|| "tr".to_string());
+
+        let guardian = sqlx::query_as::<_, Guardian>(
+            r#"
+            INSERT INTO guardians (
+                id, name, phone, secondaryPhone, email, address, occupation,
+                preferredLanguage, notes, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&req.name)
+        .bind(&req.phone)
+        .bind(&req.secondaryPhone)
+        .bind(&req.email)
+        .bind(&req.address)
+        .bind(&req.occupation)
+        .bind(&language)
+        .bind(&req.notes)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(guardian)
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<Guardian> {
+        let guardian = sqlx::query_as::<_, Guardian>("SELECT * FROM guardians WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Guardian {}", id)))?;
+
+        Ok(guardian)
+    }
+
+    pub async fn find_by_phone<'e, E>(executor: E, phone: &str) -> AppResult<Option<Guardian>>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let guardian = sqlx::query_as::<_, Guardian>(
+            "SELECT * FROM guardians WHERE phone = ? OR secondaryPhone = ? LIMIT 1"
+        )
+        .bind(phone)
+        .bind(phone)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(guardian)
+    }
+
+    pub async fn find_by_email<'e, E>(executor: E, email: &str) -> AppResult<Option<Guardian>>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let guardian = sqlx::query_as::<_, Guardian>(
+            "SELECT * FROM guardians WHERE email = ? COLLATE NOCASE LIMIT 1"
+        )
+        .bind(email)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(guardian)
+    }
+
+    pub async fn search(pool: &SqlitePool, query: &str) -> AppResult<Vec<Guardian>> {
+        let search_pattern = format!("%{}%", query);
+
+        let guardians = sqlx::query_as::<_, Guardian>(
+            r#"
+            SELECT * FROM guardians
+            WHERE name LIKE ? OR phone LIKE ? OR secondaryPhone LIKE ? OR email LIKE ?
+            ORDER BY name
+            "#
+        )
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(guardians)
+    }
+
+    pub async fn update(pool: &SqlitePool, id: &str, req: UpdateGuardianRequest) -> AppResult<Guardian> {
+        let now = Utc::now().to_rfc3339();
+
+        let mut query = String::from("UPDATE guardians SET updated_at = ?");
+        let mut params: Vec<String> = Vec::new();
+
+        if let Some(name) = &req.name {
+            query.push_str(", name = ?");
+            params.push(name.clone());
+        }
+        if let Some(phone) = &req.phone {
+            query.push_str(", phone = ?");
+            params.push(phone.clone());
+        }
+        if let Some(secondary_phone) = &req.secondaryPhone {
+            query.push_str(", secondaryPhone = ?");
+            params.push(secondary_phone.clone());
+        }
+        if let Some(email) = &req.email {
+            query.push_str(", email = ?");
+            params.push(email.clone());
+        }
+        if let Some(address) = &req.address {
+            query.push_str(", address = ?");
+            params.push(address.clone());
+        }
+        if let Some(occupation) = &req.occupation {
+            query.push_str(", occupation = ?");
+            params.push(occupation.clone());
+        }
+        if let Some(language) = &req.preferredLanguage {
+            query.push_str(", preferredLanguage = ?");
+            params.push(language.clone());
+        }
+        if let Some(notes) = &req.notes {
+            query.push_str(", notes = ?");
+            params.push(notes.clone());
+        }
+
+        query.push_str(" WHERE id = ?");
+
+        let mut q = sqlx::query(&query).bind(&now);
+        for param in params {
+            q = q.bind(param);
+        }
+        q = q.bind(id);
+
+        let result = q.execute(pool).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Guardian {}", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM guardians WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Guardian {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Link a guardian to a student, updating the link details if it already exists
+    pub async fn link_student(pool: &SqlitePool, req: LinkGuardianRequest) -> AppResult<StudentGuardian> {
+        let mut tx = pool.begin().await?;
+        Self::upsert_link(&mut *tx, &req).await?;
+
+        // Only one primary guardian per student
+        if req.isPrimary.unwrap_or(false) {
+            sqlx::query(
+                "UPDATE student_guardians SET isPrimary = FALSE WHERE studentId = ? AND guardianId != ?"
+            )
+            .bind(&req.studentId)
+            .bind(&req.guardianId)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Self::get_student_link(pool, &req.studentId, &req.guardianId).await
+    }
+
+    /// Insert a student's link to a guardian or update its details
+    pub async fn upsert_link<'e, E>(executor: E, req: &LinkGuardianRequest) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO student_guardians (
+                studentId, guardianId, relationship, isPrimary, hasCustody, livesWithStudent,
+                contactRestricted, restrictionNotes, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(studentId, guardianId) DO UPDATE SET
+                relationship = excluded.relationship,
+                isPrimary = excluded.isPrimary,
+                hasCustody = excluded.hasCustody,
+                livesWithStudent = excluded.livesWithStudent,
+                contactRestricted = excluded.contactRestricted,
+                restrictionNotes = excluded.restrictionNotes,
+                updated_at = excluded.updated_at
+            "#
+        )
+        .bind(&req.studentId)
+        .bind(&req.guardianId)
+        .bind(&req.relationship)
+        .bind(req.isPrimary.unwrap_or(false))
+        .bind(req.hasCustody.unwrap_or(true))
+        .bind(req.livesWithStudent.unwrap_or(true))
+        .bind(req.contactRestricted.unwrap_or(false))
+        .bind(&req.restrictionNotes)
+        .bind(&now)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unlink_student(pool: &SqlitePool, student_id: &str, guardian_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM student_guardians WHERE studentId = ? AND guardianId = ?")
+            .bind(student_id)
+            .bind(guardian_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Guardian {} for student {}", guardian_id, student_id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_student_link(pool: &SqlitePool, student_id: &str, guardian_id: &str) -> AppResult<StudentGuardian> {
+        let query = format!("{} WHERE sg.studentId = ? AND sg.guardianId = ?", STUDENT_GUARDIAN_SELECT);
+
+        let guardian = sqlx::query_as::<_, StudentGuardian>(&query)
+            .bind(student_id)
+            .bind(guardian_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Guardian {} for student {}", guardian_id, student_id)))?;
+
+        Ok(guardian)
+    }
+
+    pub async fn get_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<StudentGuardian>> {
+        let query = format!(
+            "{} WHERE sg.studentId = ? ORDER BY sg.isPrimary DESC, g.name",
+            STUDENT_GUARDIAN_SELECT
+        );
+
+        let guardians = sqlx::query_as::<_, StudentGuardian>(&query)
+            .bind(student_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(guardians)
+    }
+
+    /// Guardians of a student that may be contacted about them
+    pub async fn get_contactable_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<StudentGuardian>> {
+        let query = format!(
+            "{} WHERE sg.studentId = ? AND sg.contactRestricted = FALSE ORDER BY sg.isPrimary DESC, g.name",
+            STUDENT_GUARDIAN_SELECT
+        );
+
+        let guardians = sqlx::query_as::<_, StudentGuardian>(&query)
+            .bind(student_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(guardians)
+    }
+
+    pub async fn get_students(pool: &SqlitePool, guardian_id: &str) -> AppResult<Vec<Student>> {
+        let students = sqlx::query_as::<_, Student>(
+            r#"
+            SELECT s.* FROM students s
+            INNER JOIN student_guardians sg ON s.id = sg.studentId
+            WHERE sg.guardianId = ?
+            ORDER BY s.birthDate, s.name
+            "#
+        )
+        .bind(guardian_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    pub async fn count_for_student(pool: &SqlitePool, student_id: &str) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM student_guardians WHERE studentId = ?"
+        )
+        .bind(student_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+}
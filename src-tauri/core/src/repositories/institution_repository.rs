@@ -0,0 +1,311 @@
+use crate::models::{CreateInstitutionRequest, Institution, InstitutionSummary, UpdateInstitutionRequest, User};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+const SUMMARY_SELECT: &str = r#"
+    SELECT
+        i.id AS institutionId,
+        i.name AS institutionName,
+        i.district,
+        (SELECT COUNT(*) FROM students s WHERE s.institutionId = i.id) AS studentCount,
+        (SELECT COUNT(*) FROM students s WHERE s.institutionId = i.id AND s.status = 'active') AS activeStudentCount,
+        (SELECT COUNT(*) FROM students s WHERE s.institutionId = i.id AND s.risk = 'Yüksek') AS highRiskStudentCount,
+        (SELECT COUNT(*) FROM user_institutions ui
+            INNER JOIN users u ON u.id = ui.userId
+            WHERE ui.institutionId = i.id AND u.isActive = 1) AS userCount,
+        (SELECT COUNT(*) FROM classes c WHERE c.institutionId = i.id) AS classCount,
+        (SELECT COUNT(*) FROM counseling_sessions cs WHERE cs.institutionId = i.id) AS counselingSessionCount
+    FROM institutions i
+"#;
+
+pub struct InstitutionRepository;
+
+impl InstitutionRepository {
+    pub async fn create(pool: &SqlitePool, req: &CreateInstitutionRequest) -> AppResult<Institution> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let institution = sqlx::query_as::<_, Institution>(
+            r#"
+            INSERT INTO institutions (id, name, code, district, isActive, created_at, updated_at)
+            VALUES (?, ?, ?, ?, 1, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(req.name.trim())
+        .bind(&req.code)
+        .bind(&req.district)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(institution)
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<Institution> {
+        let institution = sqlx::query_as::<_, Institution>("SELECT * FROM institutions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Institution {}", id)))?;
+
+        Ok(institution)
+    }
+
+    pub async fn find_by_name(pool: &SqlitePool, name: &str) -> AppResult<Option<Institution>> {
+        let institution = sqlx::query_as::<_, Institution>("SELECT * FROM institutions WHERE name = ?")
+            .bind(name.trim())
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(institution)
+    }
+
+    pub async fn get_all(pool: &SqlitePool) -> AppResult<Vec<Institution>> {
+        let institutions = sqlx::query_as::<_, Institution>("SELECT * FROM institutions ORDER BY name")
+            .fetch_all(pool)
+            .await?;
+
+        Ok(institutions)
+    }
+
+    /// Active institutions the user is a member of
+    pub async fn get_for_user(pool: &SqlitePool, user_id: &str) -> AppResult<Vec<Institution>> {
+        let institutions = sqlx::query_as::<_, Institution>(
+            r#"
+            SELECT i.* FROM institutions i
+            INNER JOIN user_institutions ui ON ui.institutionId = i.id
+            WHERE ui.userId = ? AND i.isActive = 1
+            ORDER BY i.name
+            "#
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(institutions)
+    }
+
+    pub async fn update(pool: &SqlitePool, id: &str, req: &UpdateInstitutionRequest) -> AppResult<Institution> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE institutions SET
+                name = COALESCE(?, name),
+                code = COALESCE(?, code),
+                district = COALESCE(?, district),
+                isActive = COALESCE(?, isActive),
+                updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(req.name.as_deref().map(str::trim))
+        .bind(&req.code)
+        .bind(&req.district)
+        .bind(req.isActive)
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Institution {}", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Add a user to an institution; returns false when they already were a member
+    pub async fn add_member(pool: &SqlitePool, institution_id: &str, user_id: &str) -> AppResult<bool> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO user_institutions (userId, institutionId, created_at) VALUES (?, ?, ?)"
+        )
+        .bind(user_id)
+        .bind(institution_id)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove_member(pool: &SqlitePool, institution_id: &str, user_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM user_institutions WHERE userId = ? AND institutionId = ?")
+            .bind(user_id)
+            .bind(institution_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Membership of {} in {}", user_id, institution_id)));
+        }
+
+        // Sessions of the removed member must not keep working in the institution
+        sqlx::query("UPDATE user_sessions SET institutionId = NULL WHERE userId = ? AND institutionId = ?")
+            .bind(user_id)
+            .bind(institution_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn is_member(pool: &SqlitePool, institution_id: &str, user_id: &str) -> AppResult<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM user_institutions WHERE userId = ? AND institutionId = ?"
+        )
+        .bind(user_id)
+        .bind(institution_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    pub async fn get_members(pool: &SqlitePool, institution_id: &str) -> AppResult<Vec<User>> {
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM users u
+            INNER JOIN user_institutions ui ON ui.userId = u.id
+            WHERE ui.institutionId = ? AND u.isActive = 1
+            ORDER BY u.name
+            "#
+        )
+        .bind(institution_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    pub async fn set_district_admin(pool: &SqlitePool, user_id: &str, is_district_admin: bool) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query("UPDATE users SET isDistrictAdmin = ?, updated_at = ? WHERE id = ?")
+            .bind(is_district_admin)
+            .bind(&now)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::UserNotFound(user_id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_session_institution(pool: &SqlitePool, token: &str, institution_id: Option<&str>) -> AppResult<()> {
+        sqlx::query("UPDATE user_sessions SET institutionId = ? WHERE token = ?")
+            .bind(institution_id)
+            .bind(token)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_summary(pool: &SqlitePool, institution_id: &str) -> AppResult<InstitutionSummary> {
+        let summary = sqlx::query_as::<_, InstitutionSummary>(&format!("{} WHERE i.id = ?", SUMMARY_SELECT))
+            .bind(institution_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Institution {}", institution_id)))?;
+
+        Ok(summary)
+    }
+
+    pub async fn get_all_summaries(pool: &SqlitePool) -> AppResult<Vec<InstitutionSummary>> {
+        let summaries = sqlx::query_as::<_, InstitutionSummary>(&format!(
+            "{} WHERE i.isActive = 1 ORDER BY i.district, i.name",
+            SUMMARY_SELECT
+        ))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(summaries)
+    }
+
+    /// Free-text `users.institution` values that have no institution record yet
+    pub async fn get_unregistered_names(pool: &SqlitePool) -> AppResult<Vec<String>> {
+        let names = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT TRIM(u.institution) FROM users u
+            WHERE TRIM(u.institution) <> ''
+                AND NOT EXISTS (SELECT 1 FROM institutions i WHERE i.name = TRIM(u.institution))
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(names)
+    }
+
+    /// Make users members of the institution named in their `institution` column
+    pub async fn link_users_by_name(pool: &SqlitePool) -> AppResult<u64> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO user_institutions (userId, institutionId, created_at)
+            SELECT u.id, i.id, ? FROM users u
+            INNER JOIN institutions i ON i.name = TRIM(u.institution)
+            "#
+        )
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// The oldest institution, which takes over records from before tenancy
+    pub async fn get_default(pool: &SqlitePool) -> AppResult<Option<Institution>> {
+        let institution = sqlx::query_as::<_, Institution>(
+            "SELECT * FROM institutions ORDER BY created_at, name LIMIT 1"
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(institution)
+    }
+
+    pub async fn count_unscoped(pool: &SqlitePool) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM students WHERE institutionId IS NULL)
+                + (SELECT COUNT(*) FROM counseling_sessions WHERE institutionId IS NULL)
+                + (SELECT COUNT(*) FROM classes WHERE institutionId IS NULL)
+            "#
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Move students, counseling sessions and classes without an institution into one
+    pub async fn assign_unscoped(pool: &SqlitePool, institution_id: &str) -> AppResult<u64> {
+        let mut tx = pool.begin().await?;
+        let mut assigned = 0;
+
+        for table in ["students", "counseling_sessions", "classes"] {
+            let result = sqlx::query(&format!("UPDATE {} SET institutionId = ? WHERE institutionId IS NULL", table))
+                .bind(institution_id)
+                .execute(&mut *tx)
+                .await?;
+            assigned += result.rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(assigned)
+    }
+}
@@ -0,0 +1,435 @@
+use crate::models::{
+    Intervention, InterventionProgressNote, CounselingSession, BehaviorIncident,
+    CreateInterventionRequest, UpdateInterventionRequest, NotificationLog,
+};
+use crate::error::{AppError, AppResult};
+use crate::repositories::NotificationRepository;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct InterventionRepository;
+
+impl InterventionRepository {
+    /// Create an intervention, dated `today` unless the request gives a date
+    pub async fn create<'e, E>(
+        executor: E,
+        req: &CreateInterventionRequest,
+        status: &str,
+        today: &str,
+    ) -> AppResult<Intervention>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let date = req.date.as_deref().unwrap_or(today);
+        let priority = req.priority.clone().unwrap_or_else(|| "NORMAL".to_string());
+
+        let intervention = sqlx::query_as::<_, Intervention>(
+            r#"
+            INSERT INTO interventions (
+                id, studentId, date, title, status, description, category, ownerId,
+                startDate, targetDate, priority, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&req.studentId)
+        .bind(date)
+        .bind(req.title.trim())
+        .bind(status)
+        .bind(&req.description)
+        .bind(&req.category)
+        .bind(&req.ownerId)
+        .bind(&req.startDate)
+        .bind(&req.targetDate)
+        .bind(&priority)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(intervention)
+    }
+
+    pub async fn get_by_id(pool: &SqlitePool, id: &str) -> AppResult<Intervention> {
+        let intervention = sqlx::query_as::<_, Intervention>("SELECT * FROM interventions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Intervention {}", id)))?;
+
+        Ok(intervention)
+    }
+
+    pub async fn get_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<Intervention>> {
+        let interventions = sqlx::query_as::<_, Intervention>(
+            "SELECT * FROM interventions WHERE studentId = ? ORDER BY date DESC, created_at DESC"
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(interventions)
+    }
+
+    pub async fn get_filtered(
+        pool: &SqlitePool,
+        institution_id: &str,
+        status: Option<&str>,
+        owner_id: Option<&str>,
+    ) -> AppResult<Vec<Intervention>> {
+        let interventions = sqlx::query_as::<_, Intervention>(
+            r#"
+            SELECT i.* FROM interventions i
+            INNER JOIN students s ON i.studentId = s.id
+            WHERE s.institutionId = ?
+              AND (? IS NULL OR i.status = ?) AND (? IS NULL OR i.ownerId = ?)
+            ORDER BY i.targetDate IS NULL, i.targetDate, i.date DESC
+            "#
+        )
+        .bind(institution_id)
+        .bind(status)
+        .bind(status)
+        .bind(owner_id)
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(interventions)
+    }
+
+    pub async fn update(pool: &SqlitePool, id: &str, req: UpdateInterventionRequest) -> AppResult<Intervention> {
+        let now = Utc::now().to_rfc3339();
+
+        let mut query = String::from("UPDATE interventions SET updated_at = ?");
+        let mut params: Vec<String> = Vec::new();
+
+        if let Some(title) = &req.title {
+            query.push_str(", title = ?");
+            params.push(title.trim().to_string());
+        }
+        if let Some(description) = &req.description {
+            query.push_str(", description = ?");
+            params.push(description.clone());
+        }
+        if let Some(category) = &req.category {
+            query.push_str(", category = ?");
+            params.push(category.clone());
+        }
+        if let Some(owner_id) = &req.ownerId {
+            query.push_str(", ownerId = ?");
+            params.push(owner_id.clone());
+        }
+        if let Some(start_date) = &req.startDate {
+            query.push_str(", startDate = ?");
+            params.push(start_date.clone());
+        }
+        if let Some(target_date) = &req.targetDate {
+            query.push_str(", targetDate = ?");
+            params.push(target_date.clone());
+        }
+        if let Some(priority) = &req.priority {
+            query.push_str(", priority = ?");
+            params.push(priority.clone());
+        }
+        if let Some(outcome) = &req.outcome {
+            query.push_str(", outcome = ?");
+            params.push(outcome.clone());
+        }
+
+        query.push_str(" WHERE id = ?");
+
+        let mut q = sqlx::query(&query).bind(&now);
+        for param in params {
+            q = q.bind(param);
+        }
+        q = q.bind(id);
+
+        let result = q.execute(pool).await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Intervention {}", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Set an intervention's status; moving to Devam sets a missing start date to `today`
+    pub async fn set_status<'e, E>(
+        executor: E,
+        id: &str,
+        status: &str,
+        completed_at: Option<String>,
+        outcome: Option<String>,
+        today: &str,
+    ) -> AppResult<Intervention>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        let intervention = sqlx::query_as::<_, Intervention>(
+            r#"
+            UPDATE interventions SET
+                status = ?,
+                completedAt = ?,
+                outcome = COALESCE(?, outcome),
+                startDate = CASE WHEN ? = 'Devam' THEN COALESCE(startDate, ?) ELSE startDate END,
+                updated_at = ?
+            WHERE id = ?
+            RETURNING *
+            "#
+        )
+        .bind(status)
+        .bind(&completed_at)
+        .bind(&outcome)
+        .bind(status)
+        .bind(today)
+        .bind(&now)
+        .bind(id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Intervention {}", id)))?;
+
+        Ok(intervention)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM interventions WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Intervention {}", id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn link_session<'e, E>(executor: E, intervention_id: &str, session_id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO intervention_sessions (interventionId, sessionId, created_at) VALUES (?, ?, ?)"
+        )
+        .bind(intervention_id)
+        .bind(session_id)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unlink_session(pool: &SqlitePool, intervention_id: &str, session_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM intervention_sessions WHERE interventionId = ? AND sessionId = ?")
+            .bind(intervention_id)
+            .bind(session_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Session {} for intervention {}", session_id, intervention_id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_sessions(pool: &SqlitePool, intervention_id: &str) -> AppResult<Vec<CounselingSession>> {
+        let sessions = sqlx::query_as::<_, CounselingSession>(
+            r#"
+            SELECT cs.* FROM counseling_sessions cs
+            INNER JOIN intervention_sessions li ON cs.id = li.sessionId
+            WHERE li.interventionId = ?
+            ORDER BY cs.sessionDate DESC
+            "#
+        )
+        .bind(intervention_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    pub async fn link_incident<'e, E>(executor: E, intervention_id: &str, incident_id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO intervention_incidents (interventionId, incidentId, created_at) VALUES (?, ?, ?)"
+        )
+        .bind(intervention_id)
+        .bind(incident_id)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unlink_incident(pool: &SqlitePool, intervention_id: &str, incident_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM intervention_incidents WHERE interventionId = ? AND incidentId = ?")
+            .bind(intervention_id)
+            .bind(incident_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Incident {} for intervention {}", incident_id, intervention_id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_incidents(pool: &SqlitePool, intervention_id: &str) -> AppResult<Vec<BehaviorIncident>> {
+        let incidents = sqlx::query_as::<_, BehaviorIncident>(
+            r#"
+            SELECT bi.* FROM behavior_incidents bi
+            INNER JOIN intervention_incidents ii ON bi.id = ii.incidentId
+            WHERE ii.interventionId = ?
+            ORDER BY bi.incidentDate DESC
+            "#
+        )
+        .bind(intervention_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(incidents)
+    }
+
+    pub async fn add_note<'e, E>(
+        executor: E,
+        intervention_id: &str,
+        author_id: Option<&str>,
+        note: &str,
+        progress: Option<i64>,
+        status_change: Option<(&str, &str)>,
+    ) -> AppResult<InterventionProgressNote>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let note = sqlx::query_as::<_, InterventionProgressNote>(
+            r#"
+            INSERT INTO intervention_progress_notes (
+                id, interventionId, authorId, note, progress, fromStatus, toStatus, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(intervention_id)
+        .bind(author_id)
+        .bind(note)
+        .bind(progress)
+        .bind(status_change.map(|(from, _)| from))
+        .bind(status_change.map(|(_, to)| to))
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(note)
+    }
+
+    pub async fn get_notes(pool: &SqlitePool, intervention_id: &str) -> AppResult<Vec<InterventionProgressNote>> {
+        let notes = sqlx::query_as::<_, InterventionProgressNote>(
+            "SELECT * FROM intervention_progress_notes WHERE interventionId = ? ORDER BY created_at DESC"
+        )
+        .bind(intervention_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(notes)
+    }
+
+    /// Open interventions whose target date is on or before `cutoff_date`
+    pub async fn get_open_due_by(pool: &SqlitePool, cutoff_date: &str) -> AppResult<Vec<Intervention>> {
+        let interventions = sqlx::query_as::<_, Intervention>(
+            r#"
+            SELECT * FROM interventions
+            WHERE status != 'Tamamlandı' AND targetDate IS NOT NULL AND targetDate <= ?
+            ORDER BY targetDate
+            "#
+        )
+        .bind(cutoff_date)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(interventions)
+    }
+
+    /// Whether a reminder of this kind was already sent for the target date
+    pub async fn has_reminder(
+        pool: &SqlitePool,
+        intervention_id: &str,
+        kind: &str,
+        target_date: &str,
+    ) -> AppResult<bool> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM intervention_reminders WHERE interventionId = ? AND kind = ? AND targetDate = ?"
+        )
+        .bind(intervention_id)
+        .bind(kind)
+        .bind(target_date)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// Record a reminder together with its notification in one transaction;
+    /// returns `None` when the reminder had already been sent
+    pub async fn create_reminder(
+        pool: &SqlitePool,
+        intervention_id: &str,
+        kind: &str,
+        target_date: &str,
+        notification: NotificationLog,
+    ) -> AppResult<Option<NotificationLog>> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO intervention_reminders (id, interventionId, kind, targetDate, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&id)
+        .bind(intervention_id)
+        .bind(kind)
+        .bind(target_date)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let log = NotificationRepository::create_log(&mut *tx, notification).await?;
+
+        sqlx::query("UPDATE intervention_reminders SET notificationId = ? WHERE id = ?")
+            .bind(&log.id)
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(log))
+    }
+}
@@ -5,6 +5,25 @@ pub mod academic_repository;
 pub mod ai_suggestion_repository;
 pub mod survey_repository;
 pub mod notification_repository;
+pub mod document_repository;
+pub mod guardian_repository;
+pub mod family_repository;
+pub mod attendance_repository;
+pub mod intervention_repository;
+pub mod student_profile_repository;
+pub mod timeline_repository;
+pub mod bep_repository;
+pub mod custom_field_repository;
+pub mod class_repository;
+pub mod institution_repository;
+pub mod risk_repository;
+pub mod risk_alert_repository;
+pub mod dashboard_repository;
+pub mod exam_import_repository;
+pub mod curriculum_repository;
+pub mod study_repository;
+pub mod exam_topic_repository;
+pub mod placement_repository;
 
 pub use student_repository::*;
 pub use user_repository::*;
@@ -13,3 +32,22 @@ pub use academic_repository::*;
 pub use ai_suggestion_repository::*;
 pub use survey_repository::*;
 pub use notification_repository::*;
+pub use document_repository::*;
+pub use guardian_repository::*;
+pub use family_repository::*;
+pub use attendance_repository::*;
+pub use intervention_repository::*;
+pub use student_profile_repository::*;
+pub use timeline_repository::*;
+pub use bep_repository::*;
+pub use custom_field_repository::*;
+pub use class_repository::*;
+pub use institution_repository::*;
+pub use risk_repository::*;
+pub use risk_alert_repository::*;
+pub use dashboard_repository::*;
+pub use exam_import_repository::*;
+pub use curriculum_repository::*;
+pub use study_repository::*;
+pub use exam_topic_repository::*;
+pub use placement_repository::*;
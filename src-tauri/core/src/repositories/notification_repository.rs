@@ -7,7 +7,10 @@ use chrono::Utc;
 pub struct NotificationRepository;
 
 impl NotificationRepository {
-    pub async fn create_log(pool: &SqlitePool, log: NotificationLog) -> AppResult<NotificationLog> {
+    pub async fn create_log<'e, E>(executor: E, log: NotificationLog) -> AppResult<NotificationLog>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
         let id = Uuid::new_v4().to_string();
         let now = Utc::now().to_rfc3339();
 
@@ -40,7 +43,7 @@ impl NotificationRepository {
         .bind(&log.templateId)
         .bind(&now)
         .bind(&now)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
@@ -0,0 +1,173 @@
+use crate::models::{PlacementProgram, PlacementTable};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct PlacementRepository;
+
+impl PlacementRepository {
+    pub async fn get_tables(pool: &SqlitePool) -> AppResult<Vec<PlacementTable>> {
+        let tables = sqlx::query_as::<_, PlacementTable>(
+            "SELECT * FROM placement_tables ORDER BY examType, year DESC"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tables)
+    }
+
+    pub async fn get_table_by_id(pool: &SqlitePool, id: &str) -> AppResult<PlacementTable> {
+        let table = sqlx::query_as::<_, PlacementTable>("SELECT * FROM placement_tables WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Placement table {}", id)))?;
+
+        Ok(table)
+    }
+
+    /// The table of an exam's year, or its latest year
+    pub async fn find_table(pool: &SqlitePool, exam_type: &str, year: Option<i64>) -> AppResult<Option<PlacementTable>> {
+        let table = sqlx::query_as::<_, PlacementTable>(
+            r#"
+            SELECT * FROM placement_tables
+            WHERE examType = ? AND (? IS NULL OR year = ?)
+            ORDER BY year DESC
+            LIMIT 1
+            "#
+        )
+        .bind(exam_type)
+        .bind(year)
+        .bind(year)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(table)
+    }
+
+    /// Store a year's table in one transaction, replacing the programs of a
+    /// table imported for the same exam and year
+    pub async fn replace_table(
+        pool: &SqlitePool,
+        exam_type: &str,
+        year: i64,
+        name: &str,
+        programs: &[PlacementProgram],
+        imported_by: Option<&str>,
+    ) -> AppResult<PlacementTable> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        let existing = sqlx::query_scalar::<_, String>("SELECT id FROM placement_tables WHERE examType = ? AND year = ?")
+            .bind(exam_type)
+            .bind(year)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let table_id = match existing {
+            Some(id) => {
+                sqlx::query("DELETE FROM placement_programs WHERE tableId = ?")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(
+                    "UPDATE placement_tables SET name = ?, programCount = ?, importedBy = ?, imported_at = ? WHERE id = ?"
+                )
+                .bind(name)
+                .bind(programs.len() as i64)
+                .bind(imported_by)
+                .bind(&now)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+                id
+            }
+            None => {
+                let id = Uuid::new_v4().to_string();
+                sqlx::query(
+                    r#"
+                    INSERT INTO placement_tables (id, examType, year, name, programCount, importedBy, imported_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?)
+                    "#
+                )
+                .bind(&id)
+                .bind(exam_type)
+                .bind(year)
+                .bind(name)
+                .bind(programs.len() as i64)
+                .bind(imported_by)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?;
+                id
+            }
+        };
+
+        for program in programs {
+            sqlx::query(
+                r#"
+                INSERT INTO placement_programs (
+                    id, tableId, code, institutionName, programName, city, schoolType, scoreType, quota,
+                    baseScore, baseRank, basePercentile
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&table_id)
+            .bind(&program.code)
+            .bind(&program.institutionName)
+            .bind(&program.programName)
+            .bind(&program.city)
+            .bind(&program.schoolType)
+            .bind(&program.scoreType)
+            .bind(program.quota)
+            .bind(program.baseScore)
+            .bind(program.baseRank)
+            .bind(program.basePercentile)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Self::get_table_by_id(pool, &table_id).await
+    }
+
+    pub async fn delete_table(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM placement_tables WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Placement table {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Programs of a table admitting the given score types, highest base score first
+    pub async fn get_programs(
+        pool: &SqlitePool,
+        table_id: &str,
+        score_types: &[String],
+    ) -> AppResult<Vec<PlacementProgram>> {
+        if score_types.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let placeholders = vec!["?"; score_types.len()].join(", ");
+        let sql = format!(
+            "SELECT * FROM placement_programs WHERE tableId = ? AND scoreType IN ({}) ORDER BY baseScore DESC",
+            placeholders
+        );
+        let mut query = sqlx::query_as::<_, PlacementProgram>(&sql).bind(table_id);
+        for score_type in score_types {
+            query = query.bind(score_type);
+        }
+
+        Ok(query.fetch_all(pool).await?)
+    }
+}
@@ -0,0 +1,305 @@
+use crate::models::{RiskAlert, RiskAlertRecipient, RiskAlertSettings, User};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+const ALERT_SELECT: &str = r#"
+    SELECT a.*, s.name || ' ' || s.surname AS studentName, u.name AS acknowledgedByName
+    FROM risk_alerts a
+    INNER JOIN students s ON s.id = a.studentId
+    LEFT JOIN users u ON u.id = a.acknowledgedBy
+"#;
+
+pub struct RiskAlertRepository;
+
+impl RiskAlertRepository {
+    /// Insert an open alert; returns its id
+    pub async fn create_alert<'e, E>(
+        executor: E,
+        student_id: &str,
+        previous_level: Option<&str>,
+        new_level: &str,
+        score: Option<f64>,
+    ) -> AppResult<String>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO risk_alerts (id, studentId, previousLevel, newLevel, score, status, created_at)
+            VALUES (?, ?, ?, ?, ?, 'OPEN', ?)
+            "#
+        )
+        .bind(&id)
+        .bind(student_id)
+        .bind(previous_level)
+        .bind(new_level)
+        .bind(score)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn get_alert(pool: &SqlitePool, id: &str) -> AppResult<RiskAlert> {
+        let query = format!("{} WHERE a.id = ?", ALERT_SELECT);
+
+        sqlx::query_as::<_, RiskAlert>(&query)
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Risk alert {}", id)))
+    }
+
+    /// Alerts, newest first, optionally by status, student and the student's institution
+    pub async fn get_alerts(
+        pool: &SqlitePool,
+        status: Option<&str>,
+        student_id: Option<&str>,
+        institution_id: Option<&str>,
+    ) -> AppResult<Vec<RiskAlert>> {
+        let query = format!(
+            r#"{}
+            WHERE (?1 IS NULL OR a.status = ?1)
+              AND (?2 IS NULL OR a.studentId = ?2)
+              AND (?3 IS NULL OR s.institutionId = ?3)
+            ORDER BY a.created_at DESC
+            "#,
+            ALERT_SELECT
+        );
+
+        let alerts = sqlx::query_as::<_, RiskAlert>(&query)
+            .bind(status)
+            .bind(student_id)
+            .bind(institution_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(alerts)
+    }
+
+    /// Close the student's unacknowledged alerts that rose to one of the given levels
+    pub async fn resolve_alerts(pool: &SqlitePool, student_id: &str, levels: &[&str]) -> AppResult<u64> {
+        let now = Utc::now().to_rfc3339();
+        let mut resolved = 0;
+
+        for level in levels {
+            let result = sqlx::query(
+                r#"
+                UPDATE risk_alerts SET status = 'RESOLVED', resolvedAt = ?
+                WHERE studentId = ? AND newLevel = ? AND status IN ('OPEN', 'ESCALATED')
+                "#
+            )
+            .bind(&now)
+            .bind(student_id)
+            .bind(level)
+            .execute(pool)
+            .await?;
+
+            resolved += result.rows_affected();
+        }
+
+        Ok(resolved)
+    }
+
+    /// Acknowledge an unacknowledged alert; returns false when it was already closed
+    pub async fn acknowledge(pool: &SqlitePool, id: &str, user_id: &str) -> AppResult<bool> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE risk_alerts SET status = 'ACKNOWLEDGED', acknowledgedBy = ?, acknowledgedAt = ?
+            WHERE id = ? AND status IN ('OPEN', 'ESCALATED')
+            "#
+        )
+        .bind(user_id)
+        .bind(&now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Open alerts that reached a level and were raised before the given time
+    pub async fn get_overdue_alerts(pool: &SqlitePool, level: &str, before: &str) -> AppResult<Vec<RiskAlert>> {
+        let query = format!(
+            "{} WHERE a.status = 'OPEN' AND a.newLevel = ? AND a.created_at < ? ORDER BY a.created_at",
+            ALERT_SELECT
+        );
+
+        let alerts = sqlx::query_as::<_, RiskAlert>(&query)
+            .bind(level)
+            .bind(before)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(alerts)
+    }
+
+    pub async fn mark_escalated<'e, E>(executor: E, id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query("UPDATE risk_alerts SET status = 'ESCALATED', escalatedAt = ? WHERE id = ?")
+            .bind(&now)
+            .bind(id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Add a recipient to be notified; `None` notifies counselors without addressing anyone
+    pub async fn add_recipient<'e, E>(executor: E, alert_id: &str, user_id: Option<&str>, kind: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            "INSERT INTO risk_alert_recipients (id, alertId, userId, kind, created_at) VALUES (?, ?, ?, ?, ?)"
+        )
+        .bind(&id)
+        .bind(alert_id)
+        .bind(user_id)
+        .bind(kind)
+        .bind(&now)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Recipients not notified yet of alerts that are still unacknowledged
+    pub async fn get_pending_recipients(pool: &SqlitePool) -> AppResult<Vec<RiskAlertRecipient>> {
+        let recipients = sqlx::query_as::<_, RiskAlertRecipient>(
+            r#"
+            SELECT r.* FROM risk_alert_recipients r
+            INNER JOIN risk_alerts a ON a.id = r.alertId
+            WHERE r.notificationId IS NULL AND a.status IN ('OPEN', 'ESCALATED')
+            ORDER BY r.created_at
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(recipients)
+    }
+
+    pub async fn set_recipient_notification<'e, E>(executor: E, id: &str, notification_id: &str) -> AppResult<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        sqlx::query("UPDATE risk_alert_recipients SET notificationId = ? WHERE id = ?")
+            .bind(notification_id)
+            .bind(id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mark the notifications of an alert read
+    pub async fn mark_notifications_read(pool: &SqlitePool, alert_id: &str) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            UPDATE notification_logs SET status = 'READ', readAt = ?, updated_at = ?
+            WHERE alertId = ? AND notificationType = 'RISK_ALERT' AND status <> 'READ'
+            "#
+        )
+        .bind(&now)
+        .bind(&now)
+        .bind(alert_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Active counselors of the student's class, else the counselors of the student's institution
+    pub async fn get_student_counselors(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<User>> {
+        let class_counselors = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM users u
+            INNER JOIN classes c ON c.counselorId = u.id
+            INNER JOIN students s ON s.classId = c.id
+            WHERE s.id = ? AND u.isActive = 1
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        if !class_counselors.is_empty() {
+            return Ok(class_counselors);
+        }
+
+        let counselors = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM users u
+            INNER JOIN user_institutions ui ON ui.userId = u.id
+            INNER JOIN students s ON s.institutionId = ui.institutionId
+            WHERE s.id = ? AND u.role = 'counselor' AND u.isActive = 1
+            ORDER BY u.name
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counselors)
+    }
+
+    /// Active admins of the student's institution, or every admin when the student has none
+    pub async fn get_student_admins(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<User>> {
+        let admins = sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.* FROM users u, students s
+            WHERE s.id = ? AND u.role = 'admin' AND u.isActive = 1
+              AND (s.institutionId IS NULL OR EXISTS (
+                  SELECT 1 FROM user_institutions ui
+                  WHERE ui.userId = u.id AND ui.institutionId = s.institutionId
+              ))
+            ORDER BY u.name
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(admins)
+    }
+
+    pub async fn get_settings(pool: &SqlitePool) -> AppResult<RiskAlertSettings> {
+        let settings = sqlx::query_as::<_, RiskAlertSettings>(
+            "SELECT escalationHours, updated_at FROM risk_alert_settings WHERE id = 1"
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    pub async fn update_settings(pool: &SqlitePool, settings: &RiskAlertSettings) -> AppResult<RiskAlertSettings> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query("UPDATE risk_alert_settings SET escalationHours = ?, updated_at = ? WHERE id = 1")
+            .bind(settings.escalationHours)
+            .bind(&now)
+            .execute(pool)
+            .await?;
+
+        Self::get_settings(pool).await
+    }
+}
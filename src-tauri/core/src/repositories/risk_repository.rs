@@ -0,0 +1,417 @@
+use crate::models::{RiskOverride, RiskRuleSet, RiskScore};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use chrono::Utc;
+use uuid::Uuid;
+
+const RULE_SET_SELECT: &str = r#"
+    SELECT r.*, u.name AS createdByName
+    FROM risk_rule_sets r
+    LEFT JOIN users u ON u.id = r.createdBy
+"#;
+
+pub struct RiskRepository;
+
+impl RiskRepository {
+    /// Intensities of the student's behavior incidents since a date
+    pub async fn get_incident_intensities(
+        pool: &SqlitePool,
+        student_id: &str,
+        since: &str,
+    ) -> AppResult<Vec<Option<String>>> {
+        let intensities = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT intensity FROM behavior_incidents WHERE studentId = ? AND incidentDate >= ?"
+        )
+        .bind(student_id)
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(intensities)
+    }
+
+    /// (examType, examDate, totalNet) of the student's exams, oldest first
+    pub async fn get_exam_nets(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<(String, String, f64)>> {
+        let nets = sqlx::query_as::<_, (String, String, f64)>(
+            r#"
+            SELECT examType, examDate, totalNet FROM exam_results
+            WHERE studentId = ? AND totalNet IS NOT NULL
+            ORDER BY examDate, created_at
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(nets)
+    }
+
+    /// Open follow-ups of sessions the student attended, and how many are overdue
+    pub async fn count_open_follow_ups(pool: &SqlitePool, student_id: &str, today: &str) -> AppResult<(i64, i64)> {
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                COUNT(*),
+                COALESCE(SUM(CASE WHEN f.followUpDate < ? THEN 1 ELSE 0 END), 0)
+            FROM counseling_follow_ups f
+            INNER JOIN counseling_session_students ss ON ss.sessionId = f.sessionId
+            WHERE ss.studentId = ? AND f.status <> 'completed'
+            "#
+        )
+        .bind(today)
+        .bind(student_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// (templateId, responseData) of the student's survey answers since a date
+    pub async fn get_survey_answers(
+        pool: &SqlitePool,
+        student_id: &str,
+        since: &str,
+    ) -> AppResult<Vec<(String, String)>> {
+        let answers = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT d.templateId, r.responseData
+            FROM survey_responses r
+            INNER JOIN survey_distributions d ON d.id = r.distributionId
+            WHERE r.studentId = ? AND COALESCE(r.submittedAt, r.created_at) >= ?
+            "#
+        )
+        .bind(student_id)
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(answers)
+    }
+
+    /// (questionId, templateId, riskAnswers) of questions that can raise a flag
+    pub async fn get_flagging_questions(pool: &SqlitePool) -> AppResult<Vec<(String, String, String)>> {
+        let questions = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT id, templateId, riskAnswers FROM survey_questions WHERE riskAnswers IS NOT NULL"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(questions)
+    }
+
+    pub async fn set_question_risk_answers(
+        pool: &SqlitePool,
+        question_id: &str,
+        risk_answers: Option<&str>,
+    ) -> AppResult<()> {
+        let result = sqlx::query("UPDATE survey_questions SET riskAnswers = ? WHERE id = ?")
+            .bind(risk_answers)
+            .bind(question_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Survey question {}", question_id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn save_score(
+        pool: &SqlitePool,
+        student_id: &str,
+        score: f64,
+        level: &str,
+        factors: &str,
+        rule_version: Option<i64>,
+    ) -> AppResult<RiskScore> {
+        let now = Utc::now().to_rfc3339();
+
+        let saved = sqlx::query_as::<_, RiskScore>(
+            r#"
+            INSERT INTO risk_scores (studentId, score, level, factors, calculatedAt, ruleVersion)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(studentId) DO UPDATE SET
+                score = excluded.score, level = excluded.level, factors = excluded.factors,
+                calculatedAt = excluded.calculatedAt, ruleVersion = excluded.ruleVersion
+            RETURNING *
+            "#
+        )
+        .bind(student_id)
+        .bind(score)
+        .bind(level)
+        .bind(factors)
+        .bind(&now)
+        .bind(rule_version)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(saved)
+    }
+
+    pub async fn get_score(pool: &SqlitePool, student_id: &str) -> AppResult<Option<RiskScore>> {
+        let score = sqlx::query_as::<_, RiskScore>("SELECT * FROM risk_scores WHERE studentId = ?")
+            .bind(student_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(score)
+    }
+
+    /// The student's override unless it expired before `today`
+    pub async fn get_active_override(pool: &SqlitePool, student_id: &str, today: &str) -> AppResult<Option<RiskOverride>> {
+        let risk_override = sqlx::query_as::<_, RiskOverride>(
+            r#"
+            SELECT o.*, u.name AS overriddenByName
+            FROM risk_overrides o
+            LEFT JOIN users u ON u.id = o.overriddenBy
+            WHERE o.studentId = ? AND (o.expiresAt IS NULL OR o.expiresAt >= ?)
+            "#
+        )
+        .bind(student_id)
+        .bind(today)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(risk_override)
+    }
+
+    pub async fn set_override(
+        pool: &SqlitePool,
+        student_id: &str,
+        level: &str,
+        reason: &str,
+        overridden_by: &str,
+        expires_at: Option<&str>,
+    ) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO risk_overrides (studentId, level, reason, overriddenBy, expiresAt, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(studentId) DO UPDATE SET
+                level = excluded.level, reason = excluded.reason, overriddenBy = excluded.overriddenBy,
+                expiresAt = excluded.expiresAt, created_at = excluded.created_at
+            "#
+        )
+        .bind(student_id)
+        .bind(level)
+        .bind(reason)
+        .bind(overridden_by)
+        .bind(expires_at)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_override(pool: &SqlitePool, student_id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM risk_overrides WHERE studentId = ?")
+            .bind(student_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Risk override for student {}", student_id)));
+        }
+
+        Ok(())
+    }
+
+    /// Write the level in effect to `students.risk`; returns false when it was unchanged
+    pub async fn set_student_risk(pool: &SqlitePool, student_id: &str, level: &str) -> AppResult<bool> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            "UPDATE students SET risk = ?, updated_at = ? WHERE id = ? AND (risk IS NULL OR risk <> ?)"
+        )
+        .bind(level)
+        .bind(&now)
+        .bind(student_id)
+        .bind(level)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Active students, of the institution when given, never scored or last scored before the given time
+    pub async fn get_stale_student_ids(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        before: &str,
+    ) -> AppResult<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT s.id FROM students s
+            LEFT JOIN risk_scores r ON r.studentId = s.id
+            WHERE s.status = 'active' AND (r.calculatedAt IS NULL OR r.calculatedAt < ?)
+                AND (? IS NULL OR s.institutionId = ?)
+            "#
+        )
+        .bind(before)
+        .bind(institution_id)
+        .bind(institution_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Students of a counseling session
+    pub async fn get_session_student_ids(pool: &SqlitePool, session_id: &str) -> AppResult<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            "SELECT studentId FROM counseling_session_students WHERE sessionId = ?"
+        )
+        .bind(session_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// (studentId, name surname, current score, current level, has active override) of the
+    /// institution's active students
+    pub async fn get_preview_students(
+        pool: &SqlitePool,
+        institution_id: &str,
+        today: &str,
+    ) -> AppResult<Vec<(String, String, Option<f64>, Option<String>, bool)>> {
+        let students = sqlx::query_as::<_, (String, String, Option<f64>, Option<String>, bool)>(
+            r#"
+            SELECT s.id, s.name || ' ' || s.surname, r.score, r.level,
+                EXISTS (
+                    SELECT 1 FROM risk_overrides o
+                    WHERE o.studentId = s.id AND (o.expiresAt IS NULL OR o.expiresAt >= ?)
+                )
+            FROM students s
+            LEFT JOIN risk_scores r ON r.studentId = s.id
+            WHERE s.status = 'active' AND s.institutionId = ?
+            ORDER BY s.surname, s.name
+            "#
+        )
+        .bind(today)
+        .bind(institution_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(students)
+    }
+
+    /// Save the rules as the institution's next version, inactive
+    pub async fn create_rule_set(
+        pool: &SqlitePool,
+        institution_id: &str,
+        name: &str,
+        rules: &str,
+        notes: Option<&str>,
+        created_by: &str,
+    ) -> AppResult<RiskRuleSet> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        sqlx::query(
+            r#"
+            INSERT INTO risk_rule_sets (id, institutionId, version, name, rules, notes, isActive, createdBy, created_at)
+            VALUES (
+                ?, ?, (SELECT COALESCE(MAX(version), 0) + 1 FROM risk_rule_sets WHERE institutionId = ?),
+                ?, ?, ?, FALSE, ?, ?
+            )
+            "#
+        )
+        .bind(&id)
+        .bind(institution_id)
+        .bind(institution_id)
+        .bind(name)
+        .bind(rules)
+        .bind(notes)
+        .bind(created_by)
+        .bind(&now)
+        .execute(pool)
+        .await?;
+
+        Self::get_rule_set(pool, &id).await
+    }
+
+    pub async fn get_rule_set(pool: &SqlitePool, id: &str) -> AppResult<RiskRuleSet> {
+        let query = format!("{} WHERE r.id = ?", RULE_SET_SELECT);
+
+        sqlx::query_as::<_, RiskRuleSet>(&query)
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Risk rule set {}", id)))
+    }
+
+    /// All versions of the institution, newest first
+    pub async fn get_rule_sets(pool: &SqlitePool, institution_id: &str) -> AppResult<Vec<RiskRuleSet>> {
+        let query = format!("{} WHERE r.institutionId = ? ORDER BY r.version DESC", RULE_SET_SELECT);
+
+        let rule_sets = sqlx::query_as::<_, RiskRuleSet>(&query)
+            .bind(institution_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rule_sets)
+    }
+
+    pub async fn get_active_rule_set(pool: &SqlitePool, institution_id: &str) -> AppResult<Option<RiskRuleSet>> {
+        let query = format!("{} WHERE r.institutionId = ? AND r.isActive = TRUE", RULE_SET_SELECT);
+
+        let rule_set = sqlx::query_as::<_, RiskRuleSet>(&query)
+            .bind(institution_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(rule_set)
+    }
+
+    /// Make the rule set the institution's only active one; `None` falls back to the built-in rules
+    pub async fn activate_rule_set(pool: &SqlitePool, institution_id: &str, id: Option<&str>) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("UPDATE risk_rule_sets SET isActive = FALSE WHERE institutionId = ? AND isActive = TRUE")
+            .bind(institution_id)
+            .execute(&mut *tx)
+            .await?;
+
+        if let Some(id) = id {
+            let result = sqlx::query(
+                "UPDATE risk_rule_sets SET isActive = TRUE, activatedAt = ? WHERE id = ? AND institutionId = ?"
+            )
+            .bind(&now)
+            .bind(id)
+            .bind(institution_id)
+            .execute(&mut *tx)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(AppError::NotFound(format!("Risk rule set {}", id)));
+            }
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete a rule set that was never activated
+    pub async fn delete_rule_set(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let rule_set = Self::get_rule_set(pool, id).await?;
+        if rule_set.activatedAt.is_some() {
+            return Err(AppError::ValidationError(format!(
+                "Risk rule set version {} was activated and is kept for history",
+                rule_set.version
+            )));
+        }
+
+        sqlx::query("DELETE FROM risk_rule_sets WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+}
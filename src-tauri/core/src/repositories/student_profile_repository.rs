@@ -0,0 +1,146 @@
+use crate::models::{
+    BehaviorIncident, CounselingFollowUp, CounselingSession, ExamResult, Intervention,
+    StudentProfileSummary,
+};
+use crate::error::AppResult;
+use crate::repositories::ConfidentialAccess;
+use sqlx::SqlitePool;
+
+// Sessions a viewer may see; ?2 is the confidential access level (0 none,
+// 1 owned, 2 all) and ?3 the viewer id
+const VISIBLE_SESSION: &str = "(cs.isConfidential = 0 OR ?2 = 2 OR (?2 = 1 AND cs.counselorId = ?3))";
+
+pub struct StudentProfileRepository;
+
+impl StudentProfileRepository {
+    /// Counts of records the profile does not list in full; sessions count
+    /// only those the viewer may see
+    pub async fn get_summary(
+        pool: &SqlitePool,
+        student_id: &str,
+        access: &ConfidentialAccess,
+    ) -> AppResult<StudentProfileSummary> {
+        let (level, viewer_id) = access.params();
+        let query = format!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM exam_results WHERE studentId = ?1) AS examCount,
+                (SELECT COUNT(DISTINCT cs.id) FROM counseling_sessions cs
+                    INNER JOIN counseling_session_students css ON cs.id = css.sessionId
+                    WHERE css.studentId = ?1 AND {visible}) AS sessionCount,
+                (SELECT MAX(cs.sessionDate) FROM counseling_sessions cs
+                    INNER JOIN counseling_session_students css ON cs.id = css.sessionId
+                    WHERE css.studentId = ?1 AND {visible}) AS lastSessionDate,
+                (SELECT COUNT(*) FROM student_documents WHERE studentId = ?1) AS documentCount
+            "#,
+            visible = VISIBLE_SESSION
+        );
+        let summary = sqlx::query_as::<_, StudentProfileSummary>(&query)
+            .bind(student_id)
+            .bind(level)
+            .bind(viewer_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(summary)
+    }
+
+    pub async fn get_recent_exams(pool: &SqlitePool, student_id: &str, limit: i64) -> AppResult<Vec<ExamResult>> {
+        let exams = sqlx::query_as::<_, ExamResult>(
+            "SELECT * FROM exam_results WHERE studentId = ? ORDER BY examDate DESC, created_at DESC LIMIT ?"
+        )
+        .bind(student_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(exams)
+    }
+
+    /// All of a student's incidents, newest first
+    pub async fn get_incidents(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<BehaviorIncident>> {
+        let incidents = sqlx::query_as::<_, BehaviorIncident>(
+            r#"
+            SELECT * FROM behavior_incidents
+            WHERE studentId = ?
+            ORDER BY incidentDate DESC, incidentTime DESC
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(incidents)
+    }
+
+    /// Most recent sessions, leaving out confidential ones the viewer may not see
+    pub async fn get_recent_sessions(
+        pool: &SqlitePool,
+        student_id: &str,
+        access: &ConfidentialAccess,
+        limit: i64,
+    ) -> AppResult<Vec<CounselingSession>> {
+        let (level, viewer_id) = access.params();
+        let query = format!(
+            r#"
+            SELECT DISTINCT cs.* FROM counseling_sessions cs
+            INNER JOIN counseling_session_students css ON cs.id = css.sessionId
+            WHERE css.studentId = ?1 AND {}
+            ORDER BY cs.sessionDate DESC, cs.entryTime DESC
+            LIMIT ?4
+            "#,
+            VISIBLE_SESSION
+        );
+        let sessions = sqlx::query_as::<_, CounselingSession>(&query)
+            .bind(student_id)
+            .bind(level)
+            .bind(viewer_id)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(sessions)
+    }
+
+    /// Open follow-ups of the student's sessions the viewer may see
+    pub async fn get_open_follow_ups(
+        pool: &SqlitePool,
+        student_id: &str,
+        access: &ConfidentialAccess,
+    ) -> AppResult<Vec<CounselingFollowUp>> {
+        let (level, viewer_id) = access.params();
+        let query = format!(
+            r#"
+            SELECT DISTINCT cf.* FROM counseling_follow_ups cf
+            INNER JOIN counseling_session_students css ON cf.sessionId = css.sessionId
+            INNER JOIN counseling_sessions cs ON cs.id = cf.sessionId
+            WHERE css.studentId = ?1 AND cf.status != 'completed' AND {}
+            ORDER BY cf.followUpDate
+            "#,
+            VISIBLE_SESSION
+        );
+        let follow_ups = sqlx::query_as::<_, CounselingFollowUp>(&query)
+            .bind(student_id)
+            .bind(level)
+            .bind(viewer_id)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(follow_ups)
+    }
+
+    pub async fn get_active_interventions(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<Intervention>> {
+        let interventions = sqlx::query_as::<_, Intervention>(
+            r#"
+            SELECT * FROM interventions
+            WHERE studentId = ? AND status != 'Tamamlandı'
+            ORDER BY targetDate IS NULL, targetDate, date DESC
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(interventions)
+    }
+}
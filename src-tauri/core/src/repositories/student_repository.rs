@@ -1,4 +1,4 @@
-use crate::models::{Student, CreateStudentRequest, UpdateStudentRequest};
+use crate::models::{Student, CreateStudentRequest, UpdateStudentRequest, StudentListQuery};
 use crate::error::{AppError, AppResult};
 use sqlx::SqlitePool;
 use uuid::Uuid;
@@ -18,9 +18,9 @@ impl StudentRepository {
             r#"
             INSERT INTO students (
                 id, name, surname, email, phone, birthDate, address, class,
-                enrollmentDate, status, gender, risk, parentContact, notes, created_at, updated_at
+                enrollmentDate, status, gender, risk, parentContact, notes, schoolNumber, classId, created_at, updated_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             RETURNING *
             "#
         )
@@ -38,6 +38,8 @@ impl StudentRepository {
         .bind(&risk)
         .bind(&req.parentContact)
         .bind(&req.notes)
+        .bind(&req.schoolNumber)
+        .bind(&req.classId)
         .bind(&now)
         .bind(&now)
         .fetch_one(pool)
@@ -56,6 +58,21 @@ impl StudentRepository {
         Ok(student)
     }
 
+    /// The institution's student with this school number
+    pub async fn find_by_school_number(
+        pool: &SqlitePool,
+        institution_id: &str,
+        school_number: &str,
+    ) -> AppResult<Option<Student>> {
+        let student = sqlx::query_as::<_, Student>("SELECT * FROM students WHERE institutionId = ? AND schoolNumber = ?")
+            .bind(institution_id)
+            .bind(school_number)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(student)
+    }
+
     pub async fn get_all(pool: &SqlitePool) -> AppResult<Vec<Student>> {
         let students = sqlx::query_as::<_, Student>(
             "SELECT * FROM students ORDER BY created_at DESC"
@@ -66,6 +83,87 @@ impl StudentRepository {
         Ok(students)
     }
 
+    /// Students matching every condition in `query`.
+    ///
+    /// Tags match case-insensitively; attribute ranges compare numerically for
+    /// number fields and as ISO dates otherwise.
+    pub async fn list(pool: &SqlitePool, query: &StudentListQuery) -> AppResult<Vec<Student>> {
+        let mut sql = String::from("SELECT * FROM students WHERE 1 = 1");
+        let mut params: Vec<String> = Vec::new();
+
+        if let Some(search) = query.search.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+            sql.push_str(
+                " AND (name LIKE ? OR surname LIKE ? OR email LIKE ? OR schoolNumber LIKE ? OR (name || ' ' || surname) LIKE ?)"
+            );
+            let pattern = format!("%{}%", search);
+            params.extend(std::iter::repeat_n(pattern, 5));
+        }
+        if let Some(class) = &query.class {
+            sql.push_str(" AND class = ?");
+            params.push(class.clone());
+        }
+        if let Some(class_id) = &query.classId {
+            sql.push_str(" AND classId = ?");
+            params.push(class_id.clone());
+        }
+        if let Some(institution_id) = &query.institutionId {
+            sql.push_str(" AND institutionId = ?");
+            params.push(institution_id.clone());
+        }
+        if let Some(status) = &query.status {
+            sql.push_str(" AND status = ?");
+            params.push(status.clone());
+        }
+        if let Some(risk) = &query.risk {
+            sql.push_str(" AND risk = ?");
+            params.push(risk.clone());
+        }
+        for tag in query.tags.iter().flatten() {
+            sql.push_str(" AND EXISTS (SELECT 1 FROM student_tags t WHERE t.studentId = students.id AND t.tag = ?)");
+            params.push(tag.trim().to_string());
+        }
+        for filter in query.attributes.iter().flatten() {
+            sql.push_str(
+                r#" AND EXISTS (
+                    SELECT 1 FROM student_attributes a
+                    INNER JOIN custom_field_definitions f ON f.id = a.fieldId
+                    WHERE a.studentId = students.id AND f.key = ?"#
+            );
+            params.push(filter.key.clone());
+
+            if let Some(value) = &filter.value {
+                sql.push_str(" AND a.value = ?");
+                params.push(value.clone());
+            }
+            if let Some(min) = &filter.min {
+                sql.push_str(
+                    " AND (CASE WHEN f.fieldType = 'number' THEN CAST(a.value AS REAL) >= CAST(? AS REAL) ELSE a.value >= ? END)"
+                );
+                params.push(min.clone());
+                params.push(min.clone());
+            }
+            if let Some(max) = &filter.max {
+                sql.push_str(
+                    " AND (CASE WHEN f.fieldType = 'number' THEN CAST(a.value AS REAL) <= CAST(? AS REAL) ELSE a.value <= ? END)"
+                );
+                params.push(max.clone());
+                params.push(max.clone());
+            }
+            sql.push(')');
+        }
+
+        sql.push_str(" ORDER BY class, surname, name");
+
+        let mut q = sqlx::query_as::<_, Student>(&sql);
+        for param in params {
+            q = q.bind(param);
+        }
+
+        let students = q.fetch_all(pool).await?;
+
+        Ok(students)
+    }
+
     pub async fn update(pool: &SqlitePool, id: &str, req: UpdateStudentRequest) -> AppResult<Student> {
         let now = Utc::now().to_rfc3339();
 
@@ -112,9 +210,13 @@ impl StudentRepository {
             query.push_str(", gender = ?");
             params.push(gender.clone());
         }
-        if let Some(risk) = &req.risk {
-            query.push_str(", risk = ?");
-            params.push(risk.clone());
+        if let Some(school_number) = &req.schoolNumber {
+            query.push_str(", schoolNumber = ?");
+            params.push(school_number.clone());
+        }
+        if let Some(class_id) = &req.classId {
+            query.push_str(", classId = ?");
+            params.push(class_id.clone());
         }
 
         query.push_str(" WHERE id = ?");
@@ -149,7 +251,7 @@ impl StudentRepository {
         let students = sqlx::query_as::<_, Student>(
             r#"
             SELECT * FROM students 
-            WHERE name LIKE ? OR surname LIKE ? OR email LIKE ? OR class LIKE ?
+            WHERE name LIKE ? OR surname LIKE ? OR email LIKE ? OR class LIKE ? OR schoolNumber LIKE ?
             ORDER BY created_at DESC
             "#
         )
@@ -157,6 +259,7 @@ impl StudentRepository {
         .bind(&search_pattern)
         .bind(&search_pattern)
         .bind(&search_pattern)
+        .bind(&search_pattern)
         .fetch_all(pool)
         .await?;
 
@@ -0,0 +1,327 @@
+use crate::models::{
+    Progress, StudyAssignment, StudyAssignmentDetail, StudySession, TopicProgress, ASSIGNMENT_COMPLETED,
+    ASSIGNMENT_OVERDUE, ASSIGNMENT_PENDING,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+use chrono::Utc;
+
+pub struct StudyRepository;
+
+impl StudyRepository {
+    pub async fn create_session<'e, E>(executor: E, session: &StudySession) -> AppResult<StudySession>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let created = sqlx::query_as::<_, StudySession>(
+            r#"
+            INSERT INTO study_sessions (
+                id, studentId, topicId, startTime, endTime, duration, notes, efficiency, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&session.studentId)
+        .bind(&session.topicId)
+        .bind(&session.startTime)
+        .bind(&session.endTime)
+        .bind(session.duration)
+        .bind(&session.notes)
+        .bind(session.efficiency)
+        .bind(&now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn get_session_by_id(pool: &SqlitePool, id: &str) -> AppResult<StudySession> {
+        let session = sqlx::query_as::<_, StudySession>("SELECT * FROM study_sessions WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Study session {}", id)))?;
+
+        Ok(session)
+    }
+
+    /// Sessions of a student, latest first, optionally starting within a date range
+    pub async fn get_sessions_by_student(
+        pool: &SqlitePool,
+        student_id: &str,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> AppResult<Vec<StudySession>> {
+        let sessions = sqlx::query_as::<_, StudySession>(
+            r#"
+            SELECT * FROM study_sessions
+            WHERE studentId = ? AND (? IS NULL OR date(startTime) >= ?) AND (? IS NULL OR date(startTime) <= ?)
+            ORDER BY startTime DESC
+            "#
+        )
+        .bind(student_id)
+        .bind(from)
+        .bind(from)
+        .bind(to)
+        .bind(to)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    /// Delete a session together with the progress left after it. The topic's
+    /// `lastStudied` moves back to the latest remaining session.
+    pub async fn delete_session(pool: &SqlitePool, id: &str, progress: &Progress) -> AppResult<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM study_sessions WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Study session {}", id)));
+        }
+
+        Self::upsert_progress(&mut *tx, progress, &now).await?;
+        sqlx::query(
+            r#"
+            UPDATE progress SET lastStudied = (
+                SELECT MAX(startTime) FROM study_sessions WHERE studentId = progress.studentId AND topicId = progress.topicId
+            )
+            WHERE studentId = ? AND topicId = ?
+            "#
+        )
+        .bind(&progress.studentId)
+        .bind(&progress.topicId)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn get_progress(pool: &SqlitePool, student_id: &str, topic_id: &str) -> AppResult<Option<Progress>> {
+        let progress = sqlx::query_as::<_, Progress>("SELECT * FROM progress WHERE studentId = ? AND topicId = ?")
+            .bind(student_id)
+            .bind(topic_id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(progress)
+    }
+
+    pub async fn save_progress<'e, E>(executor: E, progress: &Progress) -> AppResult<Progress>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+        Self::upsert_progress(executor, progress, &now).await
+    }
+
+    async fn upsert_progress<'e, E>(executor: E, progress: &Progress, now: &str) -> AppResult<Progress>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let saved = sqlx::query_as::<_, Progress>(
+            r#"
+            INSERT INTO progress (
+                id, studentId, topicId, completed, remaining, lastStudied, notes, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(studentId, topicId) DO UPDATE SET
+                completed = excluded.completed,
+                remaining = excluded.remaining,
+                lastStudied = excluded.lastStudied,
+                notes = excluded.notes,
+                updated_at = excluded.updated_at
+            RETURNING *
+            "#
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&progress.studentId)
+        .bind(&progress.topicId)
+        .bind(progress.completed)
+        .bind(progress.remaining)
+        .bind(&progress.lastStudied)
+        .bind(&progress.notes)
+        .bind(now)
+        .bind(now)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(saved)
+    }
+
+    /// The student's progress on every topic they have studied, by subject
+    pub async fn get_progress_by_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<TopicProgress>> {
+        let progress = sqlx::query_as::<_, TopicProgress>(
+            r#"
+            SELECT p.*, t.name AS topicName, t.subjectId, s.name AS subjectName, s.examType, t.difficulty,
+                   t.estimatedHours, COUNT(ss.id) AS sessionCount, AVG(ss.efficiency) AS averageEfficiency
+            FROM progress p
+            JOIN topics t ON t.id = p.topicId
+            JOIN subjects s ON s.id = t.subjectId
+            LEFT JOIN study_sessions ss ON ss.studentId = p.studentId AND ss.topicId = p.topicId
+            WHERE p.studentId = ?
+            GROUP BY p.id
+            ORDER BY s.examType IS NULL, s.examType, s.sortOrder, s.name, t.sortOrder, t.name
+            "#
+        )
+        .bind(student_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(progress)
+    }
+
+    pub async fn create_assignment(pool: &SqlitePool, assignment: &StudyAssignment) -> AppResult<StudyAssignment> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+
+        let created = sqlx::query_as::<_, StudyAssignment>(
+            r#"
+            INSERT INTO study_assignments (
+                id, studentId, topicId, dueDate, status, notes, assignedBy, completedAt, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+            "#
+        )
+        .bind(&id)
+        .bind(&assignment.studentId)
+        .bind(&assignment.topicId)
+        .bind(&assignment.dueDate)
+        .bind(&assignment.status)
+        .bind(&assignment.notes)
+        .bind(&assignment.assignedBy)
+        .bind(&assignment.completedAt)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn get_assignment_by_id(pool: &SqlitePool, id: &str) -> AppResult<StudyAssignment> {
+        let assignment = sqlx::query_as::<_, StudyAssignment>("SELECT * FROM study_assignments WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Study assignment {}", id)))?;
+
+        Ok(assignment)
+    }
+
+    /// Assignments of a student by due date, optionally of one status
+    pub async fn get_assignments_by_student(
+        pool: &SqlitePool,
+        student_id: &str,
+        status: Option<&str>,
+    ) -> AppResult<Vec<StudyAssignmentDetail>> {
+        let assignments = sqlx::query_as::<_, StudyAssignmentDetail>(
+            r#"
+            SELECT a.*, t.name AS topicName, t.subjectId, s.name AS subjectName
+            FROM study_assignments a
+            JOIN topics t ON t.id = a.topicId
+            JOIN subjects s ON s.id = t.subjectId
+            WHERE a.studentId = ? AND (? IS NULL OR a.status = ?)
+            ORDER BY a.dueDate, a.created_at
+            "#
+        )
+        .bind(student_id)
+        .bind(status)
+        .bind(status)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(assignments)
+    }
+
+    pub async fn update_assignment(pool: &SqlitePool, assignment: &StudyAssignment) -> AppResult<StudyAssignment> {
+        let now = Utc::now().to_rfc3339();
+
+        let updated = sqlx::query_as::<_, StudyAssignment>(
+            r#"
+            UPDATE study_assignments SET dueDate = ?, status = ?, notes = ?, completedAt = ?, updated_at = ?
+            WHERE id = ?
+            RETURNING *
+            "#
+        )
+        .bind(&assignment.dueDate)
+        .bind(&assignment.status)
+        .bind(&assignment.notes)
+        .bind(&assignment.completedAt)
+        .bind(&now)
+        .bind(&assignment.id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Study assignment {}", assignment.id)))?;
+
+        Ok(updated)
+    }
+
+    pub async fn delete_assignment(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let result = sqlx::query("DELETE FROM study_assignments WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Study assignment {}", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Mark pending assignments due before today as overdue
+    pub async fn mark_overdue_assignments(pool: &SqlitePool, today: &str) -> AppResult<u64> {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            "UPDATE study_assignments SET status = ?, updated_at = ? WHERE status = ? AND dueDate < ?"
+        )
+        .bind(ASSIGNMENT_OVERDUE)
+        .bind(&now)
+        .bind(ASSIGNMENT_PENDING)
+        .bind(today)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Complete the student's open assignments of a topic
+    pub async fn complete_topic_assignments<'e, E>(executor: E, student_id: &str, topic_id: &str) -> AppResult<u64>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now = Utc::now().to_rfc3339();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE study_assignments SET status = ?, completedAt = ?, updated_at = ?
+            WHERE studentId = ? AND topicId = ? AND status IN (?, ?)
+            "#
+        )
+        .bind(ASSIGNMENT_COMPLETED)
+        .bind(&now)
+        .bind(&now)
+        .bind(student_id)
+        .bind(topic_id)
+        .bind(ASSIGNMENT_PENDING)
+        .bind(ASSIGNMENT_OVERDUE)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,271 @@
+use crate::models::{
+    TimelineEvent, TIMELINE_AI_SUGGESTION, TIMELINE_ATTENDANCE, TIMELINE_BEHAVIOR_INCIDENT,
+    TIMELINE_COUNSELING_SESSION, TIMELINE_EXAM_RESULT, TIMELINE_HOME_VISIT, TIMELINE_INTERVENTION,
+    TIMELINE_MEETING_NOTE, TIMELINE_NOTIFICATION, TIMELINE_PARENT_MEETING,
+};
+use crate::error::{AppError, AppResult};
+use sqlx::SqlitePool;
+
+/// Which confidential records a viewer may see
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfidentialAccess {
+    None,
+    /// Confidential records owned by this user; those without an owner are admin-only
+    Owned(String),
+    All,
+}
+
+impl ConfidentialAccess {
+    /// Access level (0 none, 1 owned, 2 all) and viewer id bound into queries
+    pub(crate) fn params(&self) -> (i64, Option<&str>) {
+        match self {
+            ConfidentialAccess::None => (0, None),
+            ConfidentialAccess::Owned(user_id) => (1, Some(user_id.as_str())),
+            ConfidentialAccess::All => (2, None),
+        }
+    }
+}
+
+pub struct TimelineFilter<'a> {
+    pub student_id: &'a str,
+    pub event_types: Vec<&'a str>,
+    pub from: Option<&'a str>,
+    pub to: Option<&'a str>,
+    pub confidential: ConfidentialAccess,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+// Every source is projected onto the same columns; ?1 is the student id
+const EVENT_SOURCES: &[(&str, &str)] = &[
+    (TIMELINE_EXAM_RESULT, r#"
+        SELECT 'EXAM_RESULT' AS eventType, id AS sourceId, examDate AS date, examName AS title,
+               CASE WHEN totalNet IS NOT NULL THEN 'Net: ' || totalNet END AS summary,
+               examType AS category, 0 AS isConfidential, NULL AS ownerId
+        FROM exam_results WHERE studentId = ?1"#),
+    (TIMELINE_BEHAVIOR_INCIDENT, r#"
+        SELECT 'BEHAVIOR_INCIDENT' AS eventType, id AS sourceId, incidentDate || 'T' || incidentTime AS date,
+               behaviorType AS title, description AS summary, behaviorCategory AS category,
+               0 AS isConfidential, recordedBy AS ownerId
+        FROM behavior_incidents WHERE studentId = ?1"#),
+    (TIMELINE_COUNSELING_SESSION, r#"
+        SELECT 'COUNSELING_SESSION' AS eventType, cs.id AS sourceId, cs.sessionDate || 'T' || cs.entryTime AS date,
+               cs.topic AS title, cs.sessionDetails AS summary, cs.sessionType AS category,
+               cs.isConfidential AS isConfidential, cs.counselorId AS ownerId
+        FROM counseling_sessions cs
+        WHERE EXISTS (
+            SELECT 1 FROM counseling_session_students css
+            WHERE css.sessionId = cs.id AND css.studentId = ?1
+        )"#),
+    (TIMELINE_MEETING_NOTE, r#"
+        SELECT 'MEETING_NOTE' AS eventType, id AS sourceId, date AS date, 'Görüşme notu' AS title,
+               note AS summary, type AS category, isConfidential AS isConfidential, createdBy AS ownerId
+        FROM meeting_notes WHERE studentId = ?1"#),
+    (TIMELINE_PARENT_MEETING, r#"
+        SELECT 'PARENT_MEETING' AS eventType, id AS sourceId, meetingDate AS date, 'Veli görüşmesi' AS title,
+               topics AS summary, NULL AS category, isConfidential AS isConfidential, createdBy AS ownerId
+        FROM parent_meetings WHERE studentId = ?1"#),
+    (TIMELINE_HOME_VISIT, r#"
+        SELECT 'HOME_VISIT' AS eventType, id AS sourceId, date || COALESCE('T' || time, '') AS date,
+               'Ev ziyareti' AS title, observations AS summary, NULL AS category,
+               isConfidential AS isConfidential, createdBy AS ownerId
+        FROM home_visits WHERE studentId = ?1"#),
+    (TIMELINE_NOTIFICATION, r#"
+        SELECT 'NOTIFICATION' AS eventType, id AS sourceId, created_at AS date,
+               COALESCE(subject, notificationType) AS title, message AS summary, notificationType AS category,
+               0 AS isConfidential, recipientId AS ownerId
+        FROM notification_logs WHERE studentId = ?1"#),
+    (TIMELINE_AI_SUGGESTION, r#"
+        SELECT 'AI_SUGGESTION' AS eventType, id AS sourceId, createdAt AS date, title AS title,
+               description AS summary, suggestionType AS category, 0 AS isConfidential, NULL AS ownerId
+        FROM ai_suggestion_queue WHERE studentId = ?1"#),
+    (TIMELINE_INTERVENTION, r#"
+        SELECT 'INTERVENTION' AS eventType, id AS sourceId, date AS date, title AS title,
+               description AS summary, status AS category, 0 AS isConfidential, ownerId AS ownerId
+        FROM interventions WHERE studentId = ?1"#),
+    (TIMELINE_ATTENDANCE, r#"
+        SELECT 'ATTENDANCE' AS eventType, id AS sourceId, date AS date, 'Devamsızlık' AS title,
+               reason AS summary, status AS category, 0 AS isConfidential, recordedBy AS ownerId
+        FROM attendance_records WHERE studentId = ?1 AND status != 'Var'"#),
+];
+
+// ?2/?3 date range, ?4 confidential access level (0 none, 1 owned, 2 all), ?5 viewer id
+const EVENT_FILTER: &str = r#"
+    WHERE (?2 IS NULL OR substr(date, 1, 10) >= ?2)
+      AND (?3 IS NULL OR substr(date, 1, 10) <= ?3)
+      AND (isConfidential = 0 OR ?4 = 2 OR (?4 = 1 AND ownerId = ?5))
+"#;
+
+pub struct TimelineRepository;
+
+impl TimelineRepository {
+    pub async fn get_events(pool: &SqlitePool, filter: &TimelineFilter<'_>) -> AppResult<Vec<TimelineEvent>> {
+        let Some(union) = Self::union_for(&filter.event_types) else {
+            return Ok(Vec::new());
+        };
+        let query = format!(
+            "SELECT * FROM ({}) {} ORDER BY date DESC, eventType LIMIT ?6 OFFSET ?7",
+            union, EVENT_FILTER
+        );
+        let (level, viewer_id) = filter.confidential.params();
+
+        let events = sqlx::query_as::<_, TimelineEvent>(&query)
+            .bind(filter.student_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(level)
+            .bind(viewer_id)
+            .bind(filter.limit)
+            .bind(filter.offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(events)
+    }
+
+    pub async fn count_events(pool: &SqlitePool, filter: &TimelineFilter<'_>) -> AppResult<i64> {
+        let Some(union) = Self::union_for(&filter.event_types) else {
+            return Ok(0);
+        };
+        let query = format!("SELECT COUNT(*) FROM ({}) {}", union, EVENT_FILTER);
+        let (level, viewer_id) = filter.confidential.params();
+
+        let count = sqlx::query_scalar::<_, i64>(&query)
+            .bind(filter.student_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(level)
+            .bind(viewer_id)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(count)
+    }
+
+    /// Owner of a record that can be marked confidential; `None` when it has no owner
+    pub async fn get_owner(pool: &SqlitePool, event_type: &str, id: &str) -> AppResult<Option<String>> {
+        let (table, owner_column) = Self::confidential_table(event_type)?;
+
+        let query = format!("SELECT {} FROM {} WHERE id = ?", owner_column, table);
+        let owner = sqlx::query_scalar::<_, Option<String>>(&query)
+            .bind(id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("{} {}", event_type, id)))?;
+
+        Ok(owner)
+    }
+
+    /// Mark a counseling record as confidential or not
+    pub async fn set_confidential(pool: &SqlitePool, event_type: &str, id: &str, is_confidential: bool) -> AppResult<()> {
+        let (table, _) = Self::confidential_table(event_type)?;
+
+        let query = format!("UPDATE {} SET isConfidential = ?, updated_at = ? WHERE id = ?", table);
+        let result = sqlx::query(&query)
+            .bind(is_confidential)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("{} {}", event_type, id)));
+        }
+
+        Ok(())
+    }
+
+    /// Table and owner column of a record type that can be marked confidential
+    fn confidential_table(event_type: &str) -> AppResult<(&'static str, &'static str)> {
+        match event_type {
+            TIMELINE_COUNSELING_SESSION => Ok(("counseling_sessions", "counselorId")),
+            TIMELINE_MEETING_NOTE => Ok(("meeting_notes", "createdBy")),
+            TIMELINE_PARENT_MEETING => Ok(("parent_meetings", "createdBy")),
+            TIMELINE_HOME_VISIT => Ok(("home_visits", "createdBy")),
+            _ => Err(AppError::ValidationError(format!(
+                "{} records cannot be marked confidential",
+                event_type
+            ))),
+        }
+    }
+
+    fn union_for(event_types: &[&str]) -> Option<String> {
+        let parts: Vec<&str> = EVENT_SOURCES
+            .iter()
+            .filter(|(event_type, _)| event_types.contains(event_type))
+            .map(|(_, sql)| *sql)
+            .collect();
+
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join("\nUNION ALL\n"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    async fn setup() -> SqlitePool {
+        let pool = test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, name, email, passwordHash, role, institution) VALUES
+                ('u1', 'Ayşe', 'u1@okul.test', '', 'counselor', 'Okul'),
+                ('u2', 'Mehmet', 'u2@okul.test', '', 'counselor', 'Okul');
+            INSERT INTO students (id, name, surname, enrollmentDate) VALUES ('s1', 'Ali', 'Yılmaz', '2024-09-01');
+            INSERT INTO meeting_notes (id, studentId, date, type, note, isConfidential, createdBy) VALUES
+                ('n1', 's1', '2024-10-01', 'Bireysel', 'açık not', 0, 'u1'),
+                ('n2', 's1', '2024-10-02', 'Bireysel', 'gizli not', 1, 'u1'),
+                ('n3', 's1', '2024-10-03', 'Bireysel', 'sahipsiz gizli not', 1, NULL);
+            INSERT INTO parent_meetings (id, studentId, meetingDate, attendees, topics, isConfidential, createdBy) VALUES
+                ('p1', 's1', '2024-10-04', 'Anne', 'gizli görüşme', 1, 'u2');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    async fn visible_ids(pool: &SqlitePool, confidential: ConfidentialAccess) -> Vec<String> {
+        let filter = TimelineFilter {
+            student_id: "s1",
+            event_types: vec![TIMELINE_MEETING_NOTE, TIMELINE_PARENT_MEETING],
+            from: None,
+            to: None,
+            confidential,
+            limit: 50,
+            offset: 0,
+        };
+        let events = TimelineRepository::get_events(pool, &filter).await.unwrap();
+        assert_eq!(TimelineRepository::count_events(pool, &filter).await.unwrap(), events.len() as i64);
+
+        let mut ids: Vec<String> = events.into_iter().map(|e| e.sourceId).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_confidential_records_by_access() {
+        let pool = setup().await;
+
+        assert_eq!(visible_ids(&pool, ConfidentialAccess::None).await, vec!["n1"]);
+        assert_eq!(visible_ids(&pool, ConfidentialAccess::Owned("u1".to_string())).await, vec!["n1", "n2"]);
+        assert_eq!(visible_ids(&pool, ConfidentialAccess::Owned("u2".to_string())).await, vec!["n1", "p1"]);
+        assert_eq!(visible_ids(&pool, ConfidentialAccess::All).await, vec!["n1", "n2", "n3", "p1"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_owner() {
+        let pool = setup().await;
+
+        assert_eq!(TimelineRepository::get_owner(&pool, TIMELINE_MEETING_NOTE, "n1").await.unwrap().as_deref(), Some("u1"));
+        assert_eq!(TimelineRepository::get_owner(&pool, TIMELINE_MEETING_NOTE, "n3").await.unwrap(), None);
+        assert!(TimelineRepository::get_owner(&pool, TIMELINE_MEETING_NOTE, "none").await.is_err());
+        assert!(TimelineRepository::get_owner(&pool, TIMELINE_EXAM_RESULT, "n1").await.is_err());
+    }
+}
@@ -101,7 +101,7 @@ impl UserRepository {
     pub async fn get_session_by_token(pool: &SqlitePool, token: &str) -> AppResult<User> {
         let user = sqlx::query_as::<_, User>(
             r#"
-            SELECT u.* FROM users u
+            SELECT u.*, s.institutionId AS activeInstitutionId FROM users u
             INNER JOIN user_sessions s ON u.id = s.userId
             WHERE s.token = ? AND s.expiresAt > datetime('now')
             "#
@@ -54,11 +54,11 @@ pub fn validate_email(email: &str) -> Result<(), ValidationError> {
 /// Sanitize user input to prevent XSS
 pub fn sanitize_html(input: &str) -> String {
     input
+        .replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
         .replace('"', "&quot;")
         .replace('\'', "&#x27;")
-        .replace('&', "&amp;")
 }
 
 /// Check for path traversal attempts
@@ -0,0 +1,353 @@
+use chrono::{NaiveDate, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AcademicGoal, ExamResult, NotificationLog, Student, UpdateAcademicGoalRequest, User, EXAM_TYPES, GOAL_ACHIEVED,
+    GOAL_ACTIVE, GOAL_CANCELLED, GOAL_MISSED, GOAL_STATUSES,
+};
+use crate::repositories::{AcademicRepository, NotificationRepository, RiskAlertRepository, StudentRepository};
+use crate::services::attendance_service::local_today;
+use crate::services::notification_service::in_app_notification;
+
+pub struct AcademicGoalService;
+
+impl AcademicGoalService {
+    /// Save a new goal and count the student's existing exam results toward it
+    pub async fn create_goal(pool: &SqlitePool, goal: AcademicGoal) -> AppResult<AcademicGoal> {
+        validate_goal(&goal)?;
+
+        let goal = AcademicRepository::create_academic_goal(pool, goal).await?;
+        Self::track_student(pool, &goal.studentId).await?;
+
+        AcademicRepository::get_academic_goal_by_id(pool, &goal.id).await
+    }
+
+    pub async fn update_goal(pool: &SqlitePool, id: &str, req: UpdateAcademicGoalRequest) -> AppResult<AcademicGoal> {
+        let mut goal = AcademicRepository::get_academic_goal_by_id(pool, id).await?;
+
+        if let Some(title) = req.title {
+            goal.title = title;
+        }
+        if let Some(description) = req.description {
+            goal.description = non_empty(description);
+        }
+        if let Some(target_score) = req.targetScore {
+            goal.targetScore = Some(target_score);
+        }
+        if let Some(exam_type) = req.examType {
+            goal.examType = non_empty(exam_type);
+        }
+        if let Some(deadline) = req.deadline {
+            goal.deadline = non_empty(deadline);
+        }
+        if let Some(status) = req.status {
+            if status != goal.status {
+                goal.closedAt = (status != GOAL_ACTIVE).then(|| Utc::now().to_rfc3339());
+                goal.status = status;
+            }
+        }
+        validate_goal(&goal)?;
+
+        AcademicRepository::update_academic_goal(pool, &goal).await?;
+        Self::track_student(pool, &goal.studentId).await?;
+
+        AcademicRepository::get_academic_goal_by_id(pool, id).await
+    }
+
+    /// Delete a goal and return it
+    pub async fn delete_goal(pool: &SqlitePool, id: &str) -> AppResult<AcademicGoal> {
+        let goal = AcademicRepository::get_academic_goal_by_id(pool, id).await?;
+        AcademicRepository::delete_academic_goal(pool, id).await?;
+        Ok(goal)
+    }
+
+    /// Update the progress of the student's goals from their exam results and
+    /// mark goals achieved or missed, notifying the student's counselors.
+    pub async fn track_student(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<NotificationLog>> {
+        let goals = AcademicRepository::get_all_academic_goals_by_student(pool, student_id).await?;
+        if goals.is_empty() {
+            return Ok(Vec::new());
+        }
+        let results = AcademicRepository::get_exam_results_in_order(pool, student_id).await?;
+        let now = Utc::now();
+        let today = now.date_naive().format("%Y-%m-%d").to_string();
+        let now = now.to_rfc3339();
+
+        let mut notifications = Vec::new();
+        for goal in &goals {
+            let tracked = track_goal(goal, &results, &today, &now);
+            if tracked.currentScore == goal.currentScore
+                && tracked.lastExamResultId == goal.lastExamResultId
+                && tracked.status == goal.status
+            {
+                continue;
+            }
+
+            let tracked = AcademicRepository::update_academic_goal(pool, &tracked).await?;
+            if tracked.status != goal.status && (tracked.status == GOAL_ACHIEVED || tracked.status == GOAL_MISSED) {
+                info!("Academic goal {} of student {} is {}", tracked.id, student_id, tracked.status);
+                notifications.extend(Self::notify_counselors(pool, &tracked).await?);
+            }
+        }
+
+        Ok(notifications)
+    }
+
+    /// Mark active goals whose deadline has passed as missed
+    pub async fn close_overdue(pool: &SqlitePool) -> AppResult<Vec<NotificationLog>> {
+        let today = local_today().format("%Y-%m-%d").to_string();
+
+        let mut notifications = Vec::new();
+        for student_id in AcademicRepository::get_students_with_overdue_goals(pool, &today).await? {
+            notifications.extend(Self::track_student(pool, &student_id).await?);
+        }
+
+        Ok(notifications)
+    }
+
+    async fn notify_counselors(pool: &SqlitePool, goal: &AcademicGoal) -> AppResult<Vec<NotificationLog>> {
+        let student = StudentRepository::get_by_id(pool, &goal.studentId).await?;
+        let counselors = RiskAlertRepository::get_student_counselors(pool, &goal.studentId).await?;
+        let (subject, message, priority) = goal_message(&student, goal);
+        let metadata = serde_json::json!({
+            "source": "academic_goal",
+            "goalId": goal.id,
+            "status": goal.status,
+            "targetScore": goal.targetScore,
+            "currentScore": goal.currentScore,
+            "examResultId": goal.lastExamResultId,
+        });
+
+        // Without counselors the notification is left unaddressed for any counselor to pick up
+        let recipients: Vec<Option<&User>> = if counselors.is_empty() {
+            vec![None]
+        } else {
+            counselors.iter().map(Some).collect()
+        };
+
+        let mut notifications = Vec::new();
+        for recipient in recipients {
+            let notification = in_app_notification(
+                "PROGRESS_UPDATE",
+                recipient,
+                &goal.studentId,
+                &subject,
+                message.clone(),
+                priority,
+                metadata.clone(),
+            );
+            let log = NotificationRepository::create_log(pool, notification).await?;
+            notifications.push(log);
+        }
+
+        Ok(notifications)
+    }
+}
+
+/// The goal with its progress and status recalculated from the student's
+/// exam results (oldest first).
+///
+/// `currentScore` follows the latest result counted toward the goal. A goal
+/// with a target is achieved once a counted result reaches it, missed when
+/// its deadline passes first and active otherwise, so raising the target or
+/// moving the deadline reopens it. Goals without a target and cancelled
+/// goals keep their status.
+pub fn track_goal(goal: &AcademicGoal, results: &[ExamResult], today: &str, now: &str) -> AcademicGoal {
+    let mut tracked = goal.clone();
+    let Some(target) = goal.targetScore else {
+        return tracked;
+    };
+
+    let counted: Vec<(&ExamResult, f64)> = results
+        .iter()
+        .filter(|r| counts_toward(goal, r))
+        .filter_map(|r| goal_value(r).map(|value| (r, value)))
+        .collect();
+    if let Some((latest, value)) = counted.last() {
+        tracked.currentScore = Some(*value);
+        tracked.lastExamResultId = Some(latest.id.clone());
+    } else if goal.lastExamResultId.is_some() {
+        tracked.currentScore = None;
+        tracked.lastExamResultId = None;
+    }
+
+    if goal.status == GOAL_CANCELLED {
+        return tracked;
+    }
+    let status = if counted.iter().any(|(_, value)| *value >= target) {
+        GOAL_ACHIEVED
+    } else if goal.deadline.as_deref().is_some_and(|deadline| date_part(deadline) < today) {
+        GOAL_MISSED
+    } else {
+        GOAL_ACTIVE
+    };
+    if status != goal.status {
+        tracked.status = status.to_string();
+        tracked.closedAt = (status != GOAL_ACTIVE).then(|| now.to_string());
+    }
+
+    tracked
+}
+
+/// Whether an exam result counts toward a goal: of the goal's exam type (any
+/// type when the goal has none) and taken by the deadline
+pub fn counts_toward(goal: &AcademicGoal, result: &ExamResult) -> bool {
+    goal.examType.as_deref().is_none_or(|t| t == result.examType)
+        && goal.deadline.as_deref().is_none_or(|deadline| date_part(&result.examDate) <= date_part(deadline))
+}
+
+/// The value compared with a goal's target score: the result's total score.
+/// Results with only nets are not compared, as a net is not a score.
+pub fn goal_value(result: &ExamResult) -> Option<f64> {
+    result.totalScore
+}
+
+fn goal_message(student: &Student, goal: &AcademicGoal) -> (String, String, &'static str) {
+    let score = |value: Option<f64>| value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string());
+
+    if goal.status == GOAL_ACHIEVED {
+        (
+            "Akademik hedefe ulaşıldı".to_string(),
+            format!(
+                "{} {} \"{}\" hedefine ulaştı (hedef: {}, son sonuç: {}).",
+                student.name, student.surname, goal.title, score(goal.targetScore), score(goal.currentScore)
+            ),
+            "NORMAL",
+        )
+    } else {
+        (
+            "Akademik hedefe ulaşılamadı".to_string(),
+            format!(
+                "{} {} \"{}\" hedefine {} tarihine kadar ulaşamadı (hedef: {}, son sonuç: {}).",
+                student.name,
+                student.surname,
+                goal.title,
+                goal.deadline.as_deref().map(date_part).unwrap_or("-"),
+                score(goal.targetScore),
+                score(goal.currentScore)
+            ),
+            "HIGH",
+        )
+    }
+}
+
+fn validate_goal(goal: &AcademicGoal) -> AppResult<()> {
+    if goal.title.trim().is_empty() {
+        return Err(AppError::ValidationError("Goal title is required".to_string()));
+    }
+    if !GOAL_STATUSES.contains(&goal.status.as_str()) {
+        return Err(AppError::ValidationError(format!("Invalid goal status: {}", goal.status)));
+    }
+    if goal.targetScore.is_some_and(|target| target < 0.0) {
+        return Err(AppError::ValidationError("Target score cannot be negative".to_string()));
+    }
+    if let Some(exam_type) = &goal.examType {
+        if !EXAM_TYPES.contains(&exam_type.as_str()) {
+            return Err(AppError::ValidationError(format!("Unknown exam type: {}", exam_type)));
+        }
+    }
+    if let Some(deadline) = &goal.deadline {
+        if NaiveDate::parse_from_str(date_part(deadline), "%Y-%m-%d").is_err() {
+            return Err(AppError::ValidationError(format!("Invalid deadline: {}", deadline)));
+        }
+    }
+    Ok(())
+}
+
+fn date_part(value: &str) -> &str {
+    value.get(..10).unwrap_or(value)
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(target: Option<f64>, exam_type: Option<&str>, deadline: Option<&str>) -> AcademicGoal {
+        AcademicGoal {
+            id: "g".to_string(),
+            studentId: "s".to_string(),
+            title: "Hedef".to_string(),
+            description: None,
+            targetScore: target,
+            currentScore: None,
+            examType: exam_type.map(str::to_string),
+            deadline: deadline.map(str::to_string),
+            status: GOAL_ACTIVE.to_string(),
+            lastExamResultId: None,
+            closedAt: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn result(id: &str, exam_type: &str, date: &str, score: Option<f64>, net: f64) -> ExamResult {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "studentId": "s", "examType": exam_type, "examName": id, "examDate": date,
+            "totalScore": score, "totalNet": net, "isOfficial": false, "goalsMet": false,
+            "parentNotified": false, "created_at": "", "updated_at": "",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_progress_follows_latest_counted_result() {
+        let results = vec![
+            result("a", "TYT", "2025-01-10", Some(310.0), 60.0),
+            result("b", "DENEME", "2025-02-10", Some(340.0), 70.0),
+            result("c", "TYT", "2025-03-10", Some(330.0), 65.0),
+            result("e", "TYT", "2025-03-20", None, 72.0),
+            result("d", "TYT", "2025-07-01", Some(400.0), 90.0),
+        ];
+        let tracked = track_goal(&goal(Some(350.0), Some("TYT"), Some("2025-06-15")), &results, "2025-04-01", "now");
+
+        // Only scored TYT results by the deadline count; nets are not compared with a score
+        assert_eq!(tracked.currentScore, Some(330.0));
+        assert_eq!(tracked.lastExamResultId.as_deref(), Some("c"));
+        assert_eq!(tracked.status, GOAL_ACTIVE);
+        assert_eq!(tracked.closedAt, None);
+    }
+
+    #[test]
+    fn test_goal_achieved_missed_and_reopened() {
+        let results = vec![
+            result("a", "DENEME", "2025-01-10", Some(62.0), 40.0),
+            result("b", "DENEME", "2025-02-10", Some(55.0), 35.0),
+        ];
+
+        let achieved = track_goal(&goal(Some(60.0), None, None), &results, "2025-03-01", "now");
+        assert_eq!(achieved.status, GOAL_ACHIEVED);
+        assert_eq!(achieved.currentScore, Some(55.0));
+        assert_eq!(achieved.closedAt.as_deref(), Some("now"));
+
+        let missed = track_goal(&goal(Some(70.0), None, Some("2025-02-28")), &results, "2025-03-01", "now");
+        assert_eq!(missed.status, GOAL_MISSED);
+
+        // Raising the target reopens an achieved goal
+        let mut raised = achieved.clone();
+        raised.targetScore = Some(65.0);
+        let reopened = track_goal(&raised, &results, "2025-03-01", "later");
+        assert_eq!(reopened.status, GOAL_ACTIVE);
+        assert_eq!(reopened.closedAt, None);
+
+        let mut cancelled = goal(Some(60.0), None, None);
+        cancelled.status = GOAL_CANCELLED.to_string();
+        assert_eq!(track_goal(&cancelled, &results, "2025-03-01", "now").status, GOAL_CANCELLED);
+
+        // Goals without a target are tracked by hand
+        assert_eq!(track_goal(&goal(None, None, Some("2025-01-01")), &results, "2025-03-01", "now").status, GOAL_ACTIVE);
+    }
+
+    #[test]
+    fn test_validate_goal() {
+        assert!(validate_goal(&goal(Some(400.0), Some("TYT"), Some("2025-06-15"))).is_ok());
+        assert!(validate_goal(&goal(Some(-1.0), None, None)).is_err());
+        assert!(validate_goal(&goal(None, Some("XYZ"), None)).is_err());
+        assert!(validate_goal(&goal(None, None, Some("15.06.2025"))).is_err());
+    }
+}
@@ -0,0 +1,447 @@
+use std::sync::LazyLock;
+use chrono::NaiveDate;
+use log::info;
+use regex::Regex;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AttendanceImportResult, CreateAttendanceRequest, UnmatchedAttendanceRow, ATTENDANCE_EXCUSED,
+    ATTENDANCE_LATE, ATTENDANCE_LEAVE, ATTENDANCE_MEDICAL, ATTENDANCE_UNEXCUSED,
+};
+use crate::repositories::StudentRepository;
+use crate::services::attendance_service::AttendanceService;
+use crate::services::csv_import::{
+    detect_delimiter, normalize_header, parse_date, split_row, turkish_lowercase, NUMBER_HEADERS,
+};
+use crate::services::exam_import_service::read_first_sheet;
+
+pub const E_OKUL_SOURCE: &str = "E_OKUL";
+
+const NAME_HEADERS: &[&str] = &["adısoyadı", "adsoyad", "adısoyad", "öğrenciadısoyadı", "öğrenci"];
+const CLASS_HEADERS: &[&str] = &["sınıfı", "sınıf", "sınıfşube", "sınıfışubesi", "şube"];
+const STATUS_HEADERS: &[&str] = &["devamsızlıktürü", "özürdurumu", "devamsızlıkdurumu", "türü", "tür", "durum"];
+const DATE_HEADERS: &[&str] = &["tarih", "devamsızlıktarihi"];
+const DURATION_HEADERS: &[&str] = &["gün", "süre", "gündeğeri", "devamsızlıkgün"];
+const NOTES_HEADERS: &[&str] = &["açıklama", "not"];
+
+static DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d{1,2})[./-](\d{1,2})[./-](\d{4})").unwrap());
+
+/// One row of an e-Okul daily absence report
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedAbsenceRow {
+    pub line: usize,
+    pub school_number: Option<String>,
+    pub student_name: Option<String>,
+    pub class_name: Option<String>,
+    pub date: Option<NaiveDate>,
+    /// Date cell that could not be read
+    pub raw_date: Option<String>,
+    pub status: Option<String>,
+    pub raw_status: Option<String>,
+    /// Days absent; `None` when the duration cell could not be read
+    pub duration: Option<f64>,
+    pub raw_duration: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Default)]
+struct Columns {
+    number: Option<usize>,
+    name: Option<usize>,
+    class: Option<usize>,
+    status: Option<usize>,
+    date: Option<usize>,
+    duration: Option<usize>,
+    notes: Option<usize>,
+}
+
+pub struct AttendanceImportService;
+
+impl AttendanceImportService {
+    /// Parse the daily absence report exported from e-Okul as an Excel
+    /// workbook sent as a base64 data URL, or as CSV or tab separated text.
+    ///
+    /// The report date is read from a date column when there is one, otherwise
+    /// from the title lines above the header, otherwise `default_date` is used.
+    pub fn parse_daily_report(content: &str, default_date: Option<NaiveDate>) -> AppResult<Vec<ParsedAbsenceRow>> {
+        let sheet = if content.trim_start().starts_with("data:") {
+            read_first_sheet(content)?
+        } else {
+            text_rows(content)
+        };
+
+        let mut report_date = default_date;
+        let mut header_index = None;
+        for (index, cells) in sheet.iter().enumerate() {
+            if cells.iter().any(|cell| NUMBER_HEADERS.contains(&normalize_header(cell).as_str())) {
+                header_index = Some(index);
+                break;
+            }
+            if default_date.is_none() {
+                if let Some(date) = DATE_REGEX.find(&cells.join(" ")).and_then(|m| parse_date(m.as_str())) {
+                    report_date = Some(date);
+                }
+            }
+        }
+
+        let Some(header_index) = header_index else {
+            return Err(AppError::ValidationError(
+                "No school number column found in the e-Okul report".to_string(),
+            ));
+        };
+
+        let columns = Columns::detect(&sheet[header_index]);
+        let mut rows = Vec::new();
+
+        for (index, cells) in sheet.iter().enumerate().skip(header_index + 1) {
+            let cell = |column: Option<usize>| {
+                column
+                    .and_then(|i| cells.get(i))
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+
+            let school_number = cell(columns.number);
+            let student_name = cell(columns.name);
+            if school_number.is_none() && student_name.is_none() {
+                continue;
+            }
+
+            let raw_status = cell(columns.status);
+            let status = match &raw_status {
+                Some(raw) => normalize_status(raw),
+                None => Some(ATTENDANCE_UNEXCUSED.to_string()),
+            };
+
+            let raw_date = cell(columns.date);
+            let date = match &raw_date {
+                Some(value) => parse_date(value),
+                None => report_date,
+            };
+            let raw_duration = cell(columns.duration);
+            let duration = match &raw_duration {
+                Some(value) => parse_duration(value),
+                None => Some(1.0),
+            };
+
+            rows.push(ParsedAbsenceRow {
+                line: index + 1,
+                school_number,
+                student_name,
+                class_name: cell(columns.class),
+                raw_date: raw_date.filter(|_| date.is_none()),
+                date,
+                status,
+                raw_status,
+                raw_duration: raw_duration.filter(|_| duration.is_none()),
+                duration,
+                notes: cell(columns.notes),
+            });
+        }
+
+        Ok(rows)
+    }
+
+    /// Import an e-Okul daily absence report into `attendance_records`.
+    ///
+    /// Rows are matched to the institution's students by school number and
+    /// upserted per student and day in one transaction, so importing the same
+    /// report again updates rather than duplicates. Rows that cannot be matched
+    /// or have an unreadable date or duration are returned in `unmatched`.
+    pub async fn import_daily_report(
+        pool: &SqlitePool,
+        institution_id: &str,
+        content: &str,
+        date: Option<String>,
+        recorded_by: Option<String>,
+    ) -> AppResult<AttendanceImportResult> {
+        let default_date = match date.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+            Some(value) => Some(
+                parse_date(value)
+                    .ok_or_else(|| AppError::ValidationError(format!("Invalid report date: {}", value)))?,
+            ),
+            None => None,
+        };
+
+        let rows = Self::parse_daily_report(content, default_date)?;
+
+        let mut result = AttendanceImportResult {
+            dates: Vec::new(),
+            totalRows: rows.len(),
+            inserted: 0,
+            updated: 0,
+            unmatched: Vec::new(),
+        };
+        let mut records = Vec::new();
+
+        for row in rows {
+            let unmatched = |reason: &str| UnmatchedAttendanceRow {
+                line: row.line,
+                schoolNumber: row.school_number.clone(),
+                studentName: row.student_name.clone(),
+                className: row.class_name.clone(),
+                reason: reason.to_string(),
+            };
+
+            let Some(school_number) = &row.school_number else {
+                result.unmatched.push(unmatched("Missing school number"));
+                continue;
+            };
+            let Some(student) = StudentRepository::find_by_school_number(pool, institution_id, school_number).await? else {
+                result.unmatched.push(unmatched("No student with this school number"));
+                continue;
+            };
+            let Some(status) = row.status.clone() else {
+                let reason = format!("Unknown absence type: {}", row.raw_status.as_deref().unwrap_or_default());
+                result.unmatched.push(unmatched(&reason));
+                continue;
+            };
+            if let Some(raw_date) = &row.raw_date {
+                result.unmatched.push(unmatched(&format!("Invalid absence date: {}", raw_date)));
+                continue;
+            }
+            let Some(day) = row.date else {
+                result.unmatched.push(unmatched("Missing absence date"));
+                continue;
+            };
+            let Some(duration) = row.duration else {
+                let reason = format!("Invalid absence duration: {}", row.raw_duration.as_deref().unwrap_or_default());
+                result.unmatched.push(unmatched(&reason));
+                continue;
+            };
+
+            let request = CreateAttendanceRequest {
+                studentId: student.id.clone(),
+                date: day.format("%Y-%m-%d").to_string(),
+                status,
+                reason: row.raw_status.clone(),
+                notes: row.notes.clone(),
+                duration: Some(duration),
+                recordedBy: recorded_by.clone(),
+            };
+
+            match AttendanceService::prepare(pool, request, E_OKUL_SOURCE).await {
+                Ok(record) => records.push(record),
+                Err(AppError::ValidationError(message)) => result.unmatched.push(unmatched(&message)),
+                Err(e) => return Err(e),
+            }
+        }
+
+        for (record, inserted) in AttendanceService::upsert_records(pool, records).await? {
+            if inserted {
+                result.inserted += 1;
+            } else {
+                result.updated += 1;
+            }
+            if !result.dates.contains(&record.date) {
+                result.dates.push(record.date);
+            }
+        }
+
+        result.dates.sort();
+        info!(
+            "e-Okul attendance import: {} inserted, {} updated, {} unmatched",
+            result.inserted,
+            result.updated,
+            result.unmatched.len()
+        );
+
+        Ok(result)
+    }
+}
+
+impl Columns {
+    fn detect(header: &[String]) -> Self {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|cell| names.contains(&normalize_header(cell).as_str()))
+        };
+
+        Columns {
+            number: find(NUMBER_HEADERS),
+            name: find(NAME_HEADERS),
+            class: find(CLASS_HEADERS),
+            status: find(STATUS_HEADERS),
+            date: find(DATE_HEADERS),
+            duration: find(DURATION_HEADERS),
+            notes: find(NOTES_HEADERS),
+        }
+    }
+}
+
+/// Map an e-Okul absence type to an attendance status
+fn normalize_status(raw: &str) -> Option<String> {
+    let value = turkish_lowercase(raw);
+
+    let status = if value.contains("özürsüz") {
+        ATTENDANCE_UNEXCUSED
+    } else if value.contains("rapor") {
+        ATTENDANCE_MEDICAL
+    } else if value.contains("özürlü") {
+        ATTENDANCE_EXCUSED
+    } else if value.contains("izin") || value.contains("görev") {
+        ATTENDANCE_LEAVE
+    } else if value.contains("geç") {
+        ATTENDANCE_LATE
+    } else {
+        return None;
+    };
+
+    Some(status.to_string())
+}
+
+/// Absence length in days; e-Okul writes half days as `0,5` or "Yarım Gün"
+fn parse_duration(value: &str) -> Option<f64> {
+    if turkish_lowercase(value).contains("yarım") {
+        return Some(0.5);
+    }
+    value
+        .replace(',', ".")
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|days| *days > 0.0 && *days <= 1.0)
+}
+
+/// Cells of each line of a text report. Title lines are split on their own
+/// delimiter; the header line's delimiter is used from the header on.
+fn text_rows(content: &str) -> Vec<Vec<String>> {
+    let mut delimiter = None;
+
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(|line| match delimiter {
+            Some(delimiter) => split_row(line, delimiter),
+            None => {
+                let cells = split_row(line, detect_delimiter(line));
+                if cells.iter().any(|cell| NUMBER_HEADERS.contains(&normalize_header(cell).as_str())) {
+                    delimiter = Some(detect_delimiter(line));
+                }
+                cells
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_report_with_title_date() {
+        let report = "\u{feff}ATATÜRK ANADOLU LİSESİ\n\
+            07.10.2024 Tarihli Günlük Devamsızlık Listesi\n\
+            S.No;Okul No;Adı Soyadı;Sınıfı;Devamsızlık Türü;Gün\n\
+            1;123;Ali Yılmaz;9/A;Özürsüz;1\n\
+            2;456;\"Ayşe; Kaya\";10/B;Raporlu;0,5\n\
+            ;;;;;\n";
+
+        let rows = AttendanceImportService::parse_daily_report(report, None).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 10, 7);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].school_number.as_deref(), Some("123"));
+        assert_eq!(rows[0].status.as_deref(), Some(ATTENDANCE_UNEXCUSED));
+        assert_eq!(rows[0].date, date);
+        assert_eq!(rows[1].student_name.as_deref(), Some("Ayşe; Kaya"));
+        assert_eq!(rows[1].status.as_deref(), Some(ATTENDANCE_MEDICAL));
+        assert_eq!(rows[1].duration, Some(0.5));
+    }
+
+    #[test]
+    fn test_parse_report_with_date_column() {
+        let report = "Tarih\tÖğrenci No\tAd Soyad\tÖzür Durumu\n\
+            08.10.2024\t123\tAli Yılmaz\tÖzürlü\n\
+            \t456\tAyşe Kaya\tBilinmiyor\n";
+        let default_date = NaiveDate::from_ymd_opt(2024, 10, 1);
+
+        let rows = AttendanceImportService::parse_daily_report(report, default_date).unwrap();
+
+        assert_eq!(rows[0].date, NaiveDate::from_ymd_opt(2024, 10, 8));
+        assert_eq!(rows[0].status.as_deref(), Some(ATTENDANCE_EXCUSED));
+        assert_eq!(rows[1].date, default_date);
+        assert_eq!(rows[1].status, None);
+    }
+
+    #[test]
+    fn test_parse_excel_report() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        // Title rows with the report date, then a date cell for the first student only
+        let workbook = include_bytes!("../../tests/fixtures/eokul_absences.xlsx");
+        let content = format!("data:application/vnd.ms-excel;base64,{}", STANDARD.encode(workbook));
+
+        let rows = AttendanceImportService::parse_daily_report(&content, None).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].line, rows[0].school_number.as_deref()), (4, Some("123")));
+        assert_eq!(rows[0].date, NaiveDate::from_ymd_opt(2024, 10, 1));
+        assert_eq!(rows[0].status.as_deref(), Some(ATTENDANCE_UNEXCUSED));
+        assert_eq!(rows[1].date, NaiveDate::from_ymd_opt(2024, 10, 7));
+        assert_eq!(rows[1].status.as_deref(), Some(ATTENDANCE_MEDICAL));
+    }
+
+    #[test]
+    fn test_parse_report_with_unreadable_cells() {
+        let report = "Tarih;Okul No;Özür Durumu;Gün\n\
+            31.02.2024;123;Özürsüz;1\n\
+            08.10.2024;456;Özürsüz;3\n";
+
+        let rows = AttendanceImportService::parse_daily_report(report, NaiveDate::from_ymd_opt(2024, 10, 1)).unwrap();
+
+        assert_eq!(rows[0].date, None);
+        assert_eq!(rows[0].raw_date.as_deref(), Some("31.02.2024"));
+        assert_eq!(rows[1].duration, None);
+        assert_eq!(rows[1].raw_duration.as_deref(), Some("3"));
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_unreadable_rows() {
+        let pool = crate::database::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Atatürk Lisesi'), ('i2', 'Fen Lisesi');
+            INSERT INTO students (id, name, surname, schoolNumber, enrollmentDate, institutionId) VALUES
+                ('s1', 'Ali', 'Yılmaz', '123', '2024-09-01', 'i1'), ('s2', 'Ayşe', 'Kaya', '456', '2024-09-01', 'i1'),
+                ('s3', 'Can', 'Demir', '123', '2024-09-01', 'i2');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let report = "Tarih;Okul No;Özür Durumu;Gün\n\
+            31.02.2024;123;Özürsüz;1\n\
+            08.10.2024;456;Özürsüz;3\n\
+            08.10.2024;123;Özürsüz;0,5\n";
+
+        let result =
+            AttendanceImportService::import_daily_report(&pool, "i1", report, Some("01.10.2024".to_string()), None)
+                .await
+                .unwrap();
+
+        assert_eq!(result.inserted, 1);
+        let student_ids: Vec<String> = sqlx::query_scalar("SELECT studentId FROM attendance_records")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(student_ids, vec!["s1"]);
+        assert_eq!(result.dates, vec!["2024-10-08"]);
+        let reasons: Vec<&str> = result.unmatched.iter().map(|r| r.reason.as_str()).collect();
+        assert_eq!(reasons, vec!["Invalid absence date: 31.02.2024", "Invalid absence duration: 3"]);
+    }
+
+    #[test]
+    fn test_parse_report_without_header() {
+        assert!(AttendanceImportService::parse_daily_report("a;b;c\n1;2;3", None).is_err());
+    }
+
+    #[test]
+    fn test_normalize_status() {
+        assert_eq!(normalize_status("ÖZÜRSÜZ").as_deref(), Some(ATTENDANCE_UNEXCUSED));
+        assert_eq!(normalize_status("Sağlık Raporu").as_deref(), Some(ATTENDANCE_MEDICAL));
+        assert_eq!(normalize_status("Görevli / İzinli").as_deref(), Some(ATTENDANCE_LEAVE));
+        assert_eq!(normalize_status("Geç Gelme").as_deref(), Some(ATTENDANCE_LATE));
+    }
+}
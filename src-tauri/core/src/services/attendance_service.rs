@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+use chrono::{Datelike, Local, NaiveDate};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AttendanceRecord, AttendanceThresholds, CreateAttendanceRequest, NotificationLog, StudentAttendanceReport,
+    UpdateAttendanceRequest, ATTENDANCE_STATUSES,
+};
+use crate::repositories::{AttendanceRepository, NewAttendanceRecord, NotificationRepository, StudentRepository};
+use crate::services::notification_service::in_app_notification;
+
+pub const ATTENDANCE_NORMAL: &str = "NORMAL";
+pub const ATTENDANCE_WARNING: &str = "WARNING";
+pub const ATTENDANCE_FAILURE: &str = "FAILURE";
+
+pub struct AttendanceService;
+
+impl AttendanceService {
+    pub async fn create_record(pool: &SqlitePool, req: CreateAttendanceRequest) -> AppResult<AttendanceRecord> {
+        let record = Self::prepare(pool, req, "MANUAL").await?;
+        let student_id = record.student_id.clone();
+
+        let created = AttendanceRepository::create(pool, record).await?;
+        Self::check_thresholds(pool, &student_id, created.schoolYear.as_deref().unwrap_or_default()).await?;
+
+        Ok(created)
+    }
+
+    /// Create or overwrite the records for their students and days in one
+    /// transaction, as imports do, then check the thresholds of the students
+    pub async fn upsert_records(
+        pool: &SqlitePool,
+        records: Vec<NewAttendanceRecord>,
+    ) -> AppResult<Vec<(AttendanceRecord, bool)>> {
+        let saved = AttendanceRepository::upsert_many(pool, records).await?;
+
+        let mut checked = HashSet::new();
+        for (record, _) in &saved {
+            if let Some(school_year) = &record.schoolYear {
+                if checked.insert((record.studentId.clone(), school_year.clone())) {
+                    Self::check_thresholds(pool, &record.studentId, school_year).await?;
+                }
+            }
+        }
+
+        Ok(saved)
+    }
+
+    pub async fn update_record(
+        pool: &SqlitePool,
+        id: &str,
+        req: UpdateAttendanceRequest,
+    ) -> AppResult<AttendanceRecord> {
+        if let Some(status) = &req.status {
+            validate_status(status)?;
+        }
+        if let Some(duration) = req.duration {
+            validate_duration(duration)?;
+        }
+
+        let updated = AttendanceRepository::update(pool, id, req).await?;
+        if let Some(school_year) = &updated.schoolYear {
+            Self::check_thresholds(pool, &updated.studentId, school_year).await?;
+        }
+
+        Ok(updated)
+    }
+
+    /// Summaries for a school year (the current one when not given), per term and overall
+    pub async fn get_student_report(
+        pool: &SqlitePool,
+        student_id: &str,
+        school_year: Option<String>,
+    ) -> AppResult<StudentAttendanceReport> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+
+        let school_year = school_year.unwrap_or_else(|| school_year_and_term(local_today()).0);
+        let thresholds = AttendanceRepository::get_thresholds(pool).await?;
+        let year = AttendanceRepository::get_year_summary(pool, student_id, &school_year).await?;
+        let terms = AttendanceRepository::get_term_summaries(pool, student_id, &school_year).await?;
+
+        let unexcused_status = threshold_level(
+            year.unexcusedDays,
+            thresholds.unexcusedWarningDays,
+            thresholds.unexcusedFailureDays,
+        );
+        let total_status = threshold_level(
+            year.totalDays,
+            thresholds.totalWarningDays,
+            thresholds.totalFailureDays,
+        );
+
+        Ok(StudentAttendanceReport {
+            studentId: student_id.to_string(),
+            schoolYear: school_year,
+            year,
+            terms,
+            thresholds,
+            unexcusedStatus: unexcused_status.to_string(),
+            totalStatus: total_status.to_string(),
+        })
+    }
+
+    pub async fn update_thresholds(
+        pool: &SqlitePool,
+        thresholds: AttendanceThresholds,
+    ) -> AppResult<AttendanceThresholds> {
+        let limits = [
+            thresholds.unexcusedWarningDays,
+            thresholds.unexcusedFailureDays,
+            thresholds.totalWarningDays,
+            thresholds.totalFailureDays,
+        ];
+        if limits.iter().any(|days| *days <= 0.0) {
+            return Err(AppError::ValidationError("Thresholds must be positive".to_string()));
+        }
+        if thresholds.unexcusedWarningDays > thresholds.unexcusedFailureDays
+            || thresholds.totalWarningDays > thresholds.totalFailureDays
+        {
+            return Err(AppError::ValidationError(
+                "Warning threshold cannot exceed the failure threshold".to_string(),
+            ));
+        }
+
+        AttendanceRepository::update_thresholds(pool, &thresholds).await
+    }
+
+    /// Raise a RISK_ALERT notification for every threshold the student has
+    /// newly crossed in the school year. Each level is alerted once per year.
+    pub async fn check_thresholds(
+        pool: &SqlitePool,
+        student_id: &str,
+        school_year: &str,
+    ) -> AppResult<Vec<NotificationLog>> {
+        let thresholds = AttendanceRepository::get_thresholds(pool).await?;
+        let summary = AttendanceRepository::get_year_summary(pool, student_id, school_year).await?;
+
+        let checks = [
+            ("UNEXCUSED", "özürsüz", summary.unexcusedDays, thresholds.unexcusedWarningDays, thresholds.unexcusedFailureDays),
+            ("TOTAL", "toplam", summary.totalDays, thresholds.totalWarningDays, thresholds.totalFailureDays),
+        ];
+
+        let mut notifications = Vec::new();
+        let mut student = None;
+
+        for (kind, label, days, warning, failure) in checks {
+            for (level, limit) in [(ATTENDANCE_WARNING, warning), (ATTENDANCE_FAILURE, failure)] {
+                if days < limit {
+                    continue;
+                }
+
+                if student.is_none() {
+                    student = Some(StudentRepository::get_by_id(pool, student_id).await?);
+                }
+                let student = student.as_ref().unwrap();
+
+                let (subject, priority) = if level == ATTENDANCE_FAILURE {
+                    ("Devamsızlık sınırı aşıldı", "URGENT")
+                } else {
+                    ("Devamsızlık uyarı sınırına ulaşıldı", "HIGH")
+                };
+                let message = format!(
+                    "{} {} ({}) {} eğitim yılında {} gün {} devamsızlık yaptı (sınır: {} gün).",
+                    student.name, student.surname, student.class.as_deref().unwrap_or("-"), school_year, days, label, limit
+                );
+                let metadata = serde_json::json!({
+                    "source": "attendance",
+                    "schoolYear": school_year,
+                    "kind": kind,
+                    "level": level,
+                    "days": days,
+                    "threshold": limit,
+                });
+
+                let notification =
+                    in_app_notification("RISK_ALERT", None, student_id, subject, message, priority, metadata);
+
+                // The alert row, its notification and the link between them are written together
+                let mut tx = pool.begin().await?;
+                if !AttendanceRepository::record_alert(&mut *tx, student_id, school_year, kind, level, days).await? {
+                    continue;
+                }
+                let log = NotificationRepository::create_log(&mut *tx, notification).await?;
+                AttendanceRepository::set_alert_notification(&mut *tx, student_id, school_year, kind, level, &log.id)
+                    .await?;
+                tx.commit().await?;
+
+                info!("Attendance {} {} alert for student {}", kind, level, student_id);
+                notifications.push(log);
+            }
+        }
+
+        Ok(notifications)
+    }
+
+    pub(crate) async fn prepare(pool: &SqlitePool, req: CreateAttendanceRequest, source: &str) -> AppResult<NewAttendanceRecord> {
+        validate_status(&req.status)?;
+        let duration = req.duration.unwrap_or(1.0);
+        validate_duration(duration)?;
+
+        let date = NaiveDate::parse_from_str(req.date.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError(format!("Invalid attendance date: {}", req.date)))?;
+
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+
+        let (school_year, term) = school_year_and_term(date);
+
+        Ok(NewAttendanceRecord {
+            student_id: req.studentId,
+            date: date.format("%Y-%m-%d").to_string(),
+            status: req.status,
+            reason: req.reason,
+            notes: req.notes,
+            duration,
+            school_year,
+            term,
+            source: source.to_string(),
+            recorded_by: req.recordedBy,
+        })
+    }
+}
+
+/// Today's date on the local clock, which decides due dates, deadlines and
+/// the school year rather than the UTC date
+pub fn local_today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// School year (`"2024-2025"`) and term (`"1"` or `"2"`) a date falls in.
+///
+/// The year starts in September; the first term runs until the end of January.
+pub fn school_year_and_term(date: NaiveDate) -> (String, String) {
+    let start_year = if date.month() >= 9 { date.year() } else { date.year() - 1 };
+    let term = if date.month() >= 9 || date.month() == 1 { "1" } else { "2" };
+
+    (format!("{}-{}", start_year, start_year + 1), term.to_string())
+}
+
+fn threshold_level(days: f64, warning: f64, failure: f64) -> &'static str {
+    if days >= failure {
+        ATTENDANCE_FAILURE
+    } else if days >= warning {
+        ATTENDANCE_WARNING
+    } else {
+        ATTENDANCE_NORMAL
+    }
+}
+
+fn validate_status(status: &str) -> AppResult<()> {
+    if !ATTENDANCE_STATUSES.contains(&status) {
+        return Err(AppError::ValidationError(format!("Invalid attendance status: {}", status)));
+    }
+    Ok(())
+}
+
+fn validate_duration(duration: f64) -> AppResult<()> {
+    if !(duration > 0.0 && duration <= 1.0) {
+        return Err(AppError::ValidationError("Duration must be between 0 and 1 day".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_school_year_and_term() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(school_year_and_term(date("2024-09-16")), ("2024-2025".to_string(), "1".to_string()));
+        assert_eq!(school_year_and_term(date("2025-01-10")), ("2024-2025".to_string(), "1".to_string()));
+        assert_eq!(school_year_and_term(date("2025-03-03")), ("2024-2025".to_string(), "2".to_string()));
+    }
+
+    #[test]
+    fn test_threshold_level() {
+        assert_eq!(threshold_level(4.5, 5.0, 10.0), ATTENDANCE_NORMAL);
+        assert_eq!(threshold_level(5.0, 5.0, 10.0), ATTENDANCE_WARNING);
+        assert_eq!(threshold_level(12.0, 5.0, 10.0), ATTENDANCE_FAILURE);
+    }
+}
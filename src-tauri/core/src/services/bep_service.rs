@@ -0,0 +1,727 @@
+use std::path::Path;
+use chrono::{Duration, NaiveDate};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AssignBepTeacherRequest, AttachDocumentRequest, BepAccommodation, BepEvaluation, BepGoal, BepGoalProgress,
+    BepPlan, BepPlanDetail, BepResponsibleTeacher, ChangeBepPlanStatusRequest, CreateBepAccommodationRequest,
+    CreateBepEvaluationRequest, CreateBepGoalRequest, CreateBepPlanRequest, NotificationLog,
+    RecordBepGoalProgressRequest, Student, StudentDocument, UpdateBepGoalRequest, UpdateBepPlanRequest, User,
+    BEP_ACTIVE, BEP_CANCELLED, BEP_COMPLETED, BEP_DECISIONS, BEP_DECISION_CONTINUE, BEP_DECISION_END, BEP_DRAFT,
+    BEP_GOAL_COMPLETED, BEP_GOAL_IN_PROGRESS, BEP_GOAL_NOT_STARTED, BEP_STATUSES,
+};
+use crate::repositories::{
+    AcademicRepository, BepRepository, NotificationRepository, StudentRepository, UserRepository,
+};
+use crate::security::sanitize_html;
+use crate::services::attendance_service::local_today;
+use crate::services::document_service::DocumentService;
+use crate::services::notification_service::in_app_notification;
+
+pub const DEFAULT_REVIEW_INTERVAL_DAYS: i64 = 90;
+pub const DEFAULT_REVIEW_REMINDER_DAYS: i64 = 7;
+
+pub struct BepService;
+
+impl BepService {
+    pub async fn create_plan(pool: &SqlitePool, req: CreateBepPlanRequest) -> AppResult<BepPlanDetail> {
+        let start = parse_date(&req.startDate)?;
+        validate_optional_date(req.ramReportDate.as_deref())?;
+        validate_optional_date(req.nextReviewDate.as_deref())?;
+        validate_period(&req.startDate, req.endDate.as_deref())?;
+        let interval = validate_interval(req.reviewIntervalDays)?.unwrap_or(DEFAULT_REVIEW_INTERVAL_DAYS);
+
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+        if let Some(coordinator_id) = &req.coordinatorId {
+            UserRepository::get_by_id(pool, coordinator_id).await?;
+        }
+
+        let next_review = req
+            .nextReviewDate
+            .clone()
+            .unwrap_or_else(|| format_date(start + Duration::days(interval)));
+
+        let plan = BepRepository::create_plan(pool, &req, interval, Some(&next_review)).await?;
+        Self::get_detail(pool, &plan.id).await
+    }
+
+    pub async fn get_detail(pool: &SqlitePool, id: &str) -> AppResult<BepPlanDetail> {
+        let plan = BepRepository::get_plan_by_id(pool, id).await?;
+        let goals = BepRepository::get_goals(pool, id).await?;
+        let accommodations = BepRepository::get_accommodations(pool, id).await?;
+        let teachers = BepRepository::get_teachers(pool, id).await?;
+        let evaluations = BepRepository::get_evaluations(pool, id).await?;
+
+        Ok(BepPlanDetail {
+            plan,
+            goals,
+            accommodations,
+            teachers,
+            evaluations,
+        })
+    }
+
+    pub async fn update_plan(pool: &SqlitePool, id: &str, req: UpdateBepPlanRequest) -> AppResult<BepPlan> {
+        let current = BepRepository::get_plan_by_id(pool, id).await?;
+
+        let start_date = req.startDate.as_deref().unwrap_or(&current.startDate);
+        parse_date(start_date)?;
+        validate_period(start_date, req.endDate.as_deref().or(current.endDate.as_deref()))?;
+        validate_optional_date(req.ramReportDate.as_deref())?;
+        validate_optional_date(req.nextReviewDate.as_deref())?;
+        validate_interval(req.reviewIntervalDays)?;
+        if let Some(coordinator_id) = &req.coordinatorId {
+            UserRepository::get_by_id(pool, coordinator_id).await?;
+        }
+
+        BepRepository::update_plan(pool, id, req).await
+    }
+
+    /// Move a plan along Taslak → Aktif → Tamamlandı, or cancel it.
+    ///
+    /// Closing a plan clears its next review date so no further reminders are
+    /// sent; reactivating it schedules a review one interval from today.
+    pub async fn change_status(pool: &SqlitePool, id: &str, req: ChangeBepPlanStatusRequest) -> AppResult<BepPlan> {
+        let current = BepRepository::get_plan_by_id(pool, id).await?;
+
+        if !BEP_STATUSES.contains(&req.status.as_str()) {
+            return Err(AppError::ValidationError(format!("Invalid BEP status: {}", req.status)));
+        }
+        if !can_transition(&current.status, &req.status) {
+            return Err(AppError::ValidationError(format!(
+                "Invalid BEP status change: {} → {}",
+                current.status, req.status
+            )));
+        }
+
+        let next_review = if req.status == BEP_ACTIVE {
+            current.nextReviewDate.clone().or_else(|| {
+                Some(format_date(local_today() + Duration::days(current.reviewIntervalDays)))
+            })
+        } else {
+            None
+        };
+
+        BepRepository::set_review(pool, id, &req.status, next_review.as_deref()).await
+    }
+
+    pub async fn add_goal(pool: &SqlitePool, plan_id: &str, req: CreateBepGoalRequest) -> AppResult<BepGoal> {
+        if req.longTermGoal.trim().is_empty() {
+            return Err(AppError::ValidationError("Long-term goal is required".to_string()));
+        }
+        validate_optional_date(req.targetDate.as_deref())?;
+
+        BepRepository::get_plan_by_id(pool, plan_id).await?;
+        if let Some(subject_id) = &req.subjectId {
+            AcademicRepository::get_subject_by_id(pool, subject_id).await?;
+        }
+
+        BepRepository::create_goal(pool, plan_id, &req).await
+    }
+
+    pub async fn update_goal(pool: &SqlitePool, goal_id: &str, req: UpdateBepGoalRequest) -> AppResult<BepGoal> {
+        if req.longTermGoal.as_deref().map(|g| g.trim().is_empty()).unwrap_or(false) {
+            return Err(AppError::ValidationError("Long-term goal is required".to_string()));
+        }
+        validate_optional_date(req.targetDate.as_deref())?;
+        if let Some(subject_id) = &req.subjectId {
+            AcademicRepository::get_subject_by_id(pool, subject_id).await?;
+        }
+
+        BepRepository::update_goal(pool, goal_id, req).await
+    }
+
+    /// Record a goal's current progress; the goal's status follows from it
+    pub async fn record_goal_progress(
+        pool: &SqlitePool,
+        goal_id: &str,
+        req: RecordBepGoalProgressRequest,
+    ) -> AppResult<BepGoalProgress> {
+        validate_progress(req.progress)?;
+        BepRepository::get_goal_by_id(pool, goal_id).await?;
+
+        let mut tx = pool.begin().await?;
+        BepRepository::set_goal_progress(&mut *tx, goal_id, req.progress, goal_status(req.progress)).await?;
+        let entry = BepRepository::add_goal_progress(
+            &mut *tx,
+            goal_id,
+            None,
+            req.progress,
+            req.note.as_deref().filter(|n| !n.trim().is_empty()),
+            req.recordedBy.as_deref(),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(entry)
+    }
+
+    pub async fn add_accommodation(
+        pool: &SqlitePool,
+        plan_id: &str,
+        req: CreateBepAccommodationRequest,
+    ) -> AppResult<BepAccommodation> {
+        if req.description.trim().is_empty() {
+            return Err(AppError::ValidationError("Accommodation description is required".to_string()));
+        }
+
+        BepRepository::get_plan_by_id(pool, plan_id).await?;
+        if let Some(subject_id) = &req.subjectId {
+            AcademicRepository::get_subject_by_id(pool, subject_id).await?;
+        }
+
+        BepRepository::create_accommodation(pool, plan_id, &req).await
+    }
+
+    pub async fn assign_teacher(
+        pool: &SqlitePool,
+        plan_id: &str,
+        req: AssignBepTeacherRequest,
+    ) -> AppResult<BepResponsibleTeacher> {
+        BepRepository::get_plan_by_id(pool, plan_id).await?;
+
+        let user = UserRepository::get_by_id(pool, &req.userId).await?;
+        if !matches!(user.role.as_str(), "teacher" | "counselor" | "admin") {
+            return Err(AppError::ValidationError(format!("User {} cannot be responsible for a BEP", user.name)));
+        }
+        if let Some(subject_id) = &req.subjectId {
+            AcademicRepository::get_subject_by_id(pool, subject_id).await?;
+        }
+
+        BepRepository::assign_teacher(pool, plan_id, &req).await
+    }
+
+    /// Record a periodic evaluation of an active plan.
+    ///
+    /// Goal progress given with the evaluation is applied to the goals and
+    /// linked to it. The next review defaults to one interval after the
+    /// evaluation; a "Sonlandır" decision completes the plan instead.
+    pub async fn add_evaluation(
+        pool: &SqlitePool,
+        plan_id: &str,
+        req: CreateBepEvaluationRequest,
+    ) -> AppResult<BepEvaluation> {
+        if req.summary.trim().is_empty() {
+            return Err(AppError::ValidationError("Evaluation summary is required".to_string()));
+        }
+        let evaluation_date = parse_date(&req.evaluationDate)?;
+        validate_optional_date(req.nextReviewDate.as_deref())?;
+
+        let decision = req.decision.clone().unwrap_or_else(|| BEP_DECISION_CONTINUE.to_string());
+        if !BEP_DECISIONS.contains(&decision.as_str()) {
+            return Err(AppError::ValidationError(format!("Invalid BEP decision: {}", decision)));
+        }
+
+        let plan = BepRepository::get_plan_by_id(pool, plan_id).await?;
+        if plan.status != BEP_ACTIVE {
+            return Err(AppError::ValidationError(format!(
+                "Only active plans can be evaluated (status: {})",
+                plan.status
+            )));
+        }
+        if let Some(evaluator_id) = &req.evaluatorId {
+            UserRepository::get_by_id(pool, evaluator_id).await?;
+        }
+
+        let entries = req.goalProgress.clone().unwrap_or_default();
+        for entry in &entries {
+            validate_progress(entry.progress)?;
+            let goal = BepRepository::get_goal_by_id(pool, &entry.goalId).await?;
+            if goal.planId != plan.id {
+                return Err(AppError::ValidationError(format!("Goal {} belongs to another plan", entry.goalId)));
+            }
+        }
+
+        let (status, next_review) = if decision == BEP_DECISION_END {
+            (BEP_COMPLETED, None)
+        } else {
+            let next = req
+                .nextReviewDate
+                .clone()
+                .unwrap_or_else(|| format_date(evaluation_date + Duration::days(plan.reviewIntervalDays)));
+            (BEP_ACTIVE, Some(next))
+        };
+
+        let mut tx = pool.begin().await?;
+
+        let evaluation =
+            BepRepository::create_evaluation(&mut *tx, plan_id, &req, &decision, next_review.as_deref()).await?;
+
+        for entry in entries {
+            BepRepository::set_goal_progress(&mut *tx, &entry.goalId, entry.progress, goal_status(entry.progress))
+                .await?;
+            BepRepository::add_goal_progress(
+                &mut *tx,
+                &entry.goalId,
+                Some(&evaluation.id),
+                entry.progress,
+                entry.note.as_deref().filter(|n| !n.trim().is_empty()),
+                req.evaluatorId.as_deref(),
+            )
+            .await?;
+        }
+
+        BepRepository::set_review(&mut *tx, plan_id, status, next_review.as_deref()).await?;
+
+        tx.commit().await?;
+
+        Ok(evaluation)
+    }
+
+    /// Create reminders for active plans whose review date is within
+    /// `days_ahead` days or already past.
+    ///
+    /// The coordinator and every responsible teacher are notified. An upcoming
+    /// and an overdue reminder are each sent once per review date, so
+    /// rescheduling the review re-arms them.
+    pub async fn send_review_reminders(pool: &SqlitePool, days_ahead: i64) -> AppResult<Vec<NotificationLog>> {
+        let today = local_today();
+        let cutoff = format_date(today + Duration::days(days_ahead.max(0)));
+        let today = format_date(today);
+
+        let mut notifications = Vec::new();
+
+        for plan in BepRepository::get_active_due_by(pool, &cutoff).await? {
+            let Some(review_date) = plan.nextReviewDate.clone() else {
+                continue;
+            };
+            let overdue = review_date < today;
+            let kind = if overdue { "OVERDUE" } else { "UPCOMING" };
+
+            let student = StudentRepository::get_by_id(pool, &plan.studentId).await?;
+            let recipients = Self::reminder_recipients(pool, &plan).await?;
+
+            let (subject, priority) = if overdue {
+                ("BEP değerlendirme tarihi geçti", "URGENT")
+            } else {
+                ("BEP değerlendirme tarihi yaklaşıyor", "HIGH")
+            };
+            let message = format!(
+                "{} {} için BEP dönemsel değerlendirmesi {} tarihinde yapılmalı.",
+                student.name, student.surname, review_date
+            );
+            let metadata = serde_json::json!({
+                "source": "bep",
+                "planId": plan.id,
+                "kind": kind,
+                "reviewDate": review_date,
+            });
+
+            let mut tx = pool.begin().await?;
+
+            if !BepRepository::record_reminder(&mut *tx, &plan.id, kind, &review_date).await? {
+                continue;
+            }
+
+            let mut logs = Vec::new();
+            for recipient in recipients {
+                let notification = in_app_notification(
+                    "BEP_REVIEW_REMINDER",
+                    recipient.as_ref(),
+                    &plan.studentId,
+                    subject,
+                    message.clone(),
+                    priority,
+                    metadata.clone(),
+                );
+                logs.push(NotificationRepository::create_log(&mut *tx, notification).await?);
+            }
+
+            if let Some(first) = logs.first() {
+                BepRepository::set_reminder_notification(&mut *tx, &plan.id, kind, &review_date, &first.id).await?;
+            }
+
+            tx.commit().await?;
+            notifications.extend(logs);
+        }
+
+        if !notifications.is_empty() {
+            info!("Created {} BEP review reminders", notifications.len());
+        }
+
+        Ok(notifications)
+    }
+
+    /// The coordinator followed by each responsible teacher, once each;
+    /// `None` addresses the counselors when the plan has nobody assigned
+    async fn reminder_recipients(pool: &SqlitePool, plan: &BepPlan) -> AppResult<Vec<Option<User>>> {
+        let mut user_ids: Vec<String> = plan.coordinatorId.iter().cloned().collect();
+        for teacher in BepRepository::get_teachers(pool, &plan.id).await? {
+            if !user_ids.contains(&teacher.userId) {
+                user_ids.push(teacher.userId);
+            }
+        }
+
+        let mut recipients = Vec::new();
+        for user_id in user_ids {
+            if let Ok(user) = UserRepository::get_by_id(pool, &user_id).await {
+                recipients.push(Some(user));
+            }
+        }
+        if recipients.is_empty() {
+            recipients.push(None);
+        }
+
+        Ok(recipients)
+    }
+
+    /// Render the plan as a standalone HTML document ready for printing
+    pub async fn render_document(pool: &SqlitePool, plan_id: &str) -> AppResult<String> {
+        let detail = Self::get_detail(pool, plan_id).await?;
+        let student = StudentRepository::get_by_id(pool, &detail.plan.studentId).await?;
+
+        Ok(render_plan_document(&student, &detail))
+    }
+
+    /// Render the plan and store it with the student's documents
+    pub async fn save_document(
+        pool: &SqlitePool,
+        uploads_dir: &Path,
+        plan_id: &str,
+        uploaded_by: Option<String>,
+    ) -> AppResult<StudentDocument> {
+        let detail = Self::get_detail(pool, plan_id).await?;
+        let student = StudentRepository::get_by_id(pool, &detail.plan.studentId).await?;
+        let html = render_plan_document(&student, &detail);
+
+        let name = format!(
+            "BEP_{}_{}_{}.html",
+            file_name_part(&student.name),
+            file_name_part(&student.surname),
+            local_today().format("%Y-%m-%d")
+        );
+
+        DocumentService::attach(
+            pool,
+            uploads_dir,
+            AttachDocumentRequest {
+                studentId: student.id.clone(),
+                name,
+                mime_type: Some("text/html".to_string()),
+                category: Some("BEP".to_string()),
+                uploadedBy: uploaded_by,
+            },
+            html.into_bytes(),
+        )
+        .await
+    }
+}
+
+/// Whether a plan may move from one status to another
+pub fn can_transition(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        (BEP_DRAFT, BEP_ACTIVE)
+            | (BEP_DRAFT, BEP_CANCELLED)
+            | (BEP_ACTIVE, BEP_COMPLETED)
+            | (BEP_ACTIVE, BEP_CANCELLED)
+            | (BEP_COMPLETED, BEP_ACTIVE)
+    )
+}
+
+/// Goal status implied by its progress percentage
+pub fn goal_status(progress: i64) -> &'static str {
+    match progress {
+        0 => BEP_GOAL_NOT_STARTED,
+        100 => BEP_GOAL_COMPLETED,
+        _ => BEP_GOAL_IN_PROGRESS,
+    }
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn parse_date(value: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::ValidationError(format!("Invalid date: {}", value)))
+}
+
+fn validate_optional_date(value: Option<&str>) -> AppResult<()> {
+    if let Some(value) = value {
+        parse_date(value)?;
+    }
+    Ok(())
+}
+
+fn validate_period(start_date: &str, end_date: Option<&str>) -> AppResult<()> {
+    if let Some(end_date) = end_date {
+        if parse_date(end_date)? < parse_date(start_date)? {
+            return Err(AppError::ValidationError("End date cannot be before the start date".to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn validate_interval(days: Option<i64>) -> AppResult<Option<i64>> {
+    match days {
+        Some(value) if value <= 0 => Err(AppError::ValidationError("Review interval must be positive".to_string())),
+        _ => Ok(days),
+    }
+}
+
+fn validate_progress(progress: i64) -> AppResult<()> {
+    if !(0..=100).contains(&progress) {
+        return Err(AppError::ValidationError("Progress must be between 0 and 100".to_string()));
+    }
+    Ok(())
+}
+
+fn file_name_part(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn cell(value: Option<&str>) -> String {
+    value.map(sanitize_html).unwrap_or_else(|| "-".to_string())
+}
+
+fn render_plan_document(student: &Student, detail: &BepPlanDetail) -> String {
+    let plan = &detail.plan;
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"tr\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>BEP - {} {}</title>\n",
+        sanitize_html(&student.name),
+        sanitize_html(&student.surname)
+    ));
+    html.push_str(concat!(
+        "<style>\n",
+        "body { font-family: sans-serif; font-size: 11pt; margin: 2cm; }\n",
+        "h1 { font-size: 16pt; text-align: center; }\n",
+        "h2 { font-size: 13pt; margin-top: 1.5em; border-bottom: 1px solid #000; }\n",
+        "table { width: 100%; border-collapse: collapse; }\n",
+        "th, td { border: 1px solid #000; padding: 4px; text-align: left; vertical-align: top; }\n",
+        ".signatures td { height: 3em; }\n",
+        "@media print { body { margin: 0; } h2 { page-break-after: avoid; } tr { page-break-inside: avoid; } }\n",
+        "</style>\n</head>\n<body>\n",
+        "<h1>Bireyselleştirilmiş Eğitim Programı (BEP)</h1>\n",
+    ));
+
+    html.push_str("<h2>Öğrenci Bilgileri</h2>\n<table>\n");
+    html.push_str(&format!(
+        "<tr><th>Adı Soyadı</th><td>{} {}</td><th>Okul No</th><td>{}</td></tr>\n",
+        sanitize_html(&student.name),
+        sanitize_html(&student.surname),
+        cell(student.schoolNumber.as_deref())
+    ));
+    html.push_str(&format!(
+        "<tr><th>Sınıf</th><td>{}</td><th>Doğum Tarihi</th><td>{}</td></tr>\n",
+        cell(student.class.as_deref()),
+        cell(student.birthDate.as_deref())
+    ));
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>RAM Raporu ve Eğitsel Değerlendirme</h2>\n<table>\n");
+    html.push_str(&format!(
+        "<tr><th>Rapor No</th><td>{}</td><th>Rapor Tarihi</th><td>{}</td></tr>\n",
+        cell(plan.ramReportNumber.as_deref()),
+        cell(plan.ramReportDate.as_deref())
+    ));
+    html.push_str(&format!(
+        "<tr><th>Tanı</th><td colspan=\"3\">{}</td></tr>\n",
+        cell(plan.diagnosis.as_deref())
+    ));
+    html.push_str(&format!(
+        "<tr><th>Eğitsel Karar</th><td colspan=\"3\">{}</td></tr>\n",
+        cell(plan.educationalPlacement.as_deref())
+    ));
+    html.push_str(&format!(
+        "<tr><th>Rapor Özeti</th><td colspan=\"3\">{}</td></tr>\n",
+        cell(plan.ramReportSummary.as_deref())
+    ));
+    html.push_str(&format!(
+        "<tr><th>Destek Hizmetleri</th><td colspan=\"3\">{}</td></tr>\n",
+        cell(plan.supportServices.as_deref())
+    ));
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Plan Dönemi</h2>\n<table>\n");
+    html.push_str(&format!(
+        "<tr><th>Başlangıç</th><td>{}</td><th>Bitiş</th><td>{}</td></tr>\n",
+        sanitize_html(&plan.startDate),
+        cell(plan.endDate.as_deref())
+    ));
+    html.push_str(&format!(
+        "<tr><th>Durum</th><td>{}</td><th>Sonraki Değerlendirme</th><td>{}</td></tr>\n",
+        sanitize_html(&plan.status),
+        cell(plan.nextReviewDate.as_deref())
+    ));
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>BEP Geliştirme Birimi</h2>\n<table>\n");
+    html.push_str("<tr><th>Öğretmen</th><th>Ders</th><th>Sorumluluk</th></tr>\n");
+    for teacher in &detail.teachers {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            cell(teacher.teacherName.as_deref()),
+            cell(teacher.subjectName.as_deref()),
+            cell(teacher.responsibility.as_deref())
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Amaçlar</h2>\n<table>\n");
+    html.push_str(concat!(
+        "<tr><th>Ders / Alan</th><th>Uzun Dönemli Amaç</th><th>Kısa Dönemli Amaç</th>",
+        "<th>Ölçüt</th><th>Yöntem</th><th>Hedef Tarih</th><th>İlerleme</th></tr>\n",
+    ));
+    for goal in &detail.goals {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>%{} ({})</td></tr>\n",
+            cell(goal.subjectName.as_deref().or(goal.area.as_deref())),
+            sanitize_html(&goal.longTermGoal),
+            cell(goal.shortTermGoal.as_deref()),
+            cell(goal.successCriteria.as_deref()),
+            cell(goal.method.as_deref()),
+            cell(goal.targetDate.as_deref()),
+            goal.progress,
+            sanitize_html(&goal.status)
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Uyarlamalar</h2>\n<table>\n");
+    html.push_str("<tr><th>Tür</th><th>Ders</th><th>Açıklama</th></tr>\n");
+    for accommodation in &detail.accommodations {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            cell(accommodation.category.as_deref()),
+            cell(accommodation.subjectName.as_deref()),
+            sanitize_html(&accommodation.description)
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Dönemsel Değerlendirmeler</h2>\n<table>\n");
+    html.push_str("<tr><th>Tarih</th><th>Değerlendirme</th><th>Karar</th><th>Sonraki Değerlendirme</th></tr>\n");
+    for evaluation in &detail.evaluations {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            sanitize_html(&evaluation.evaluationDate),
+            sanitize_html(&evaluation.summary),
+            sanitize_html(&evaluation.decision),
+            cell(evaluation.nextReviewDate.as_deref())
+        ));
+    }
+    html.push_str("</table>\n");
+
+    if let Some(notes) = &plan.notes {
+        html.push_str(&format!("<h2>Notlar</h2>\n<p>{}</p>\n", sanitize_html(notes)));
+    }
+
+    html.push_str("<h2>İmzalar</h2>\n<table class=\"signatures\">\n");
+    html.push_str("<tr><th>Ad Soyad</th><th>Görev</th><th>İmza</th></tr>\n");
+    for teacher in &detail.teachers {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td></td></tr>\n",
+            cell(teacher.teacherName.as_deref()),
+            cell(teacher.responsibility.as_deref().or(teacher.subjectName.as_deref()))
+        ));
+    }
+    html.push_str("<tr><td></td><td>Veli</td><td></td></tr>\n");
+    html.push_str("<tr><td></td><td>Okul Müdürü</td><td></td></tr>\n");
+    html.push_str("</table>\n</body>\n</html>\n");
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(can_transition(BEP_DRAFT, BEP_ACTIVE));
+        assert!(can_transition(BEP_ACTIVE, BEP_COMPLETED));
+        assert!(can_transition(BEP_COMPLETED, BEP_ACTIVE));
+        assert!(!can_transition(BEP_DRAFT, BEP_COMPLETED));
+        assert!(!can_transition(BEP_CANCELLED, BEP_ACTIVE));
+    }
+
+    #[test]
+    fn test_goal_status() {
+        assert_eq!(goal_status(0), BEP_GOAL_NOT_STARTED);
+        assert_eq!(goal_status(40), BEP_GOAL_IN_PROGRESS);
+        assert_eq!(goal_status(100), BEP_GOAL_COMPLETED);
+    }
+
+    #[test]
+    fn test_plan_document_escapes_html() {
+        let student = Student {
+            id: "s1".to_string(),
+            name: "<script>alert(1)</script>".to_string(),
+            surname: "Yılmaz & Kaya".to_string(),
+            email: None,
+            phone: None,
+            birthDate: None,
+            address: None,
+            class: Some("9/A".to_string()),
+            enrollmentDate: "2024-09-01".to_string(),
+            status: "active".to_string(),
+            avatar: None,
+            parentContact: None,
+            notes: None,
+            gender: "K".to_string(),
+            risk: "Düşük".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            schoolNumber: None,
+            classId: None,
+            institutionId: None,
+        };
+        let detail = BepPlanDetail {
+            plan: BepPlan {
+                id: "p1".to_string(),
+                studentId: "s1".to_string(),
+                ramReportNumber: None,
+                ramReportDate: None,
+                ramReportSummary: None,
+                diagnosis: Some("<b>\"tanı\"</b>".to_string()),
+                educationalPlacement: None,
+                supportServices: None,
+                startDate: "2024-09-01".to_string(),
+                endDate: None,
+                status: BEP_ACTIVE.to_string(),
+                coordinatorId: None,
+                reviewIntervalDays: 90,
+                nextReviewDate: None,
+                notes: Some("<img src=x onerror='x'>".to_string()),
+                created_at: String::new(),
+                updated_at: String::new(),
+            },
+            goals: vec![BepGoal {
+                id: "g1".to_string(),
+                planId: "p1".to_string(),
+                subjectId: None,
+                subjectName: None,
+                area: Some("Okuma".to_string()),
+                longTermGoal: "</td><td>".to_string(),
+                shortTermGoal: None,
+                successCriteria: None,
+                method: None,
+                targetDate: None,
+                progress: 0,
+                status: BEP_GOAL_NOT_STARTED.to_string(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            }],
+            accommodations: Vec::new(),
+            teachers: Vec::new(),
+            evaluations: Vec::new(),
+        };
+
+        let html = render_plan_document(&student, &detail);
+
+        assert!(html.contains("<title>BEP - &lt;script&gt;alert(1)&lt;/script&gt; Yılmaz &amp; Kaya</title>"));
+        assert!(html.contains("&lt;b&gt;&quot;tanı&quot;&lt;/b&gt;"));
+        assert!(html.contains("<p>&lt;img src=x onerror=&#x27;x&#x27;&gt;</p>"));
+        assert!(html.contains("<td>&lt;/td&gt;&lt;td&gt;</td>"));
+        assert!(!html.contains("<script>") && !html.contains("<img") && !html.contains("<b>"));
+    }
+}
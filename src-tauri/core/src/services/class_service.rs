@@ -0,0 +1,435 @@
+use std::sync::LazyLock;
+use regex::Regex;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{ClassOverview, CreateClassRequest, SchoolClass, Student, UpdateClassRequest, User};
+use crate::repositories::{ClassRepository, InstitutionRepository, StudentRepository, UserRepository};
+use crate::services::attendance_service::{local_today, school_year_and_term};
+use crate::services::institution_service::{active_institution, can_access};
+
+const MAX_GRADE: i64 = 12;
+
+static CLASS_WORD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(sınıfı|sınıf|sinifi|sinif|şubesi|şube|subesi|sube)\b").unwrap());
+static CLASS_NAME_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{1,2})\s*[-/.]?\s*([a-zçğıöşü]{1,3})$").unwrap());
+static SECTION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-zçğıöşü]{1,3}$").unwrap());
+static SCHOOL_YEAR_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\d{4})-(\d{4})$").unwrap());
+
+pub struct ClassService;
+
+impl ClassService {
+    /// Classes of the viewer's current institution
+    pub async fn get_classes(pool: &SqlitePool, viewer: &User, school_year: Option<&str>) -> AppResult<Vec<SchoolClass>> {
+        let institution_id = active_institution(viewer)?;
+        ClassRepository::get_all(pool, Some(&institution_id), school_year).await
+    }
+
+    pub async fn get_class(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<SchoolClass> {
+        let class = ClassRepository::get_by_id(pool, id).await?;
+        require_class_access(pool, viewer, &class).await?;
+        Ok(class)
+    }
+
+    /// Class with its roster, risk distribution and exam averages
+    pub async fn get_overview(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<ClassOverview> {
+        let class = Self::get_class(pool, viewer, id).await?;
+        let students = ClassRepository::get_roster(pool, id).await?;
+        let risk_distribution = ClassRepository::get_risk_distribution(pool, id).await?;
+        let exam_averages = ClassRepository::get_exam_averages(pool, id).await?;
+
+        Ok(ClassOverview {
+            class,
+            students,
+            riskDistribution: risk_distribution,
+            examAverages: exam_averages,
+        })
+    }
+
+    pub async fn create_class(pool: &SqlitePool, viewer: &User, req: CreateClassRequest) -> AppResult<SchoolClass> {
+        require_manager(viewer)?;
+        let institution_id = active_institution(viewer)?;
+
+        let section = normalize_section(&req.section)?;
+        validate_grade(req.grade)?;
+        let school_year = match &req.schoolYear {
+            Some(year) => validate_school_year(year)?,
+            None => current_school_year(),
+        };
+
+        if ClassRepository::find(pool, Some(&institution_id), req.grade, &section, &school_year).await?.is_some() {
+            return Err(AppError::ValidationError(format!(
+                "Class {} already exists in {}",
+                class_name(req.grade, &section),
+                school_year
+            )));
+        }
+        let institution = Some(institution_id.as_str());
+        validate_staff(pool, institution, req.homeroomTeacherId.as_deref(), &["teacher", "counselor", "admin"]).await?;
+        validate_staff(pool, institution, req.counselorId.as_deref(), &["counselor", "admin"]).await?;
+
+        let name = class_name(req.grade, &section);
+        let req = CreateClassRequest { section, ..req };
+        ClassRepository::create(pool, Some(&institution_id), &school_year, &name, &req).await
+    }
+
+    /// Update a class; renaming it also renames the class of its students.
+    ///
+    /// An empty homeroom teacher or counselor id clears the assignment.
+    pub async fn update_class(
+        pool: &SqlitePool,
+        viewer: &User,
+        id: &str,
+        req: UpdateClassRequest,
+    ) -> AppResult<SchoolClass> {
+        require_manager(viewer)?;
+
+        let current = Self::get_class(pool, viewer, id).await?;
+        let grade = req.grade.unwrap_or(current.grade);
+        validate_grade(grade)?;
+        let section = match &req.section {
+            Some(section) => normalize_section(section)?,
+            None => current.section.clone(),
+        };
+
+        if grade != current.grade || section != current.section {
+            let existing = ClassRepository::find(
+                pool,
+                current.institutionId.as_deref(),
+                grade,
+                &section,
+                &current.schoolYear,
+            )
+            .await?;
+            if let Some(existing) = existing {
+                if existing.id != id {
+                    return Err(AppError::ValidationError(format!(
+                        "Class {} already exists in {}",
+                        class_name(grade, &section),
+                        current.schoolYear
+                    )));
+                }
+            }
+        }
+
+        let homeroom_teacher_id = match req.homeroomTeacherId {
+            Some(user_id) if user_id.is_empty() => None,
+            Some(user_id) => Some(user_id),
+            None => current.homeroomTeacherId,
+        };
+        let counselor_id = match req.counselorId {
+            Some(user_id) if user_id.is_empty() => None,
+            Some(user_id) => Some(user_id),
+            None => current.counselorId,
+        };
+        let institution = current.institutionId.as_deref();
+        validate_staff(pool, institution, homeroom_teacher_id.as_deref(), &["teacher", "counselor", "admin"]).await?;
+        validate_staff(pool, institution, counselor_id.as_deref(), &["counselor", "admin"]).await?;
+
+        ClassRepository::update(
+            pool,
+            id,
+            grade,
+            &section,
+            &class_name(grade, &section),
+            homeroom_teacher_id.as_deref(),
+            counselor_id.as_deref(),
+        )
+        .await
+    }
+
+    /// Delete a class; its students keep their class name but lose the link
+    pub async fn delete_class(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<()> {
+        require_manager(viewer)?;
+        Self::get_class(pool, viewer, id).await?;
+        ClassRepository::delete(pool, id).await
+    }
+
+    /// Link a student to a class of their own institution
+    pub async fn assign_student(pool: &SqlitePool, viewer: &User, student_id: &str, class_id: &str) -> AppResult<Student> {
+        require_manager(viewer)?;
+        let class = Self::get_class(pool, viewer, class_id).await?;
+        let student = StudentRepository::get_by_id(pool, student_id).await?;
+        if let Some(institution_id) = &student.institutionId {
+            if !can_access(pool, viewer, institution_id).await? {
+                return Err(AppError::Unauthorized);
+            }
+        }
+        if student.institutionId.is_some() && class.institutionId.is_some() && student.institutionId != class.institutionId {
+            return Err(AppError::ValidationError(format!(
+                "Class {} belongs to another institution",
+                class.name
+            )));
+        }
+        ClassRepository::set_student_class(pool, student_id, &class.id, &class.name).await?;
+        StudentRepository::get_by_id(pool, student_id).await
+    }
+
+    /// Link a student to the class named by their free-text class, creating
+    /// it in the current school year when needed.
+    ///
+    /// Names that can't be parsed leave the student without a class link.
+    pub async fn sync_student_class(pool: &SqlitePool, student_id: &str) -> AppResult<Student> {
+        let student = StudentRepository::get_by_id(pool, student_id).await?;
+
+        match student.class.as_deref().and_then(parse_class_name) {
+            Some((grade, section)) => {
+                let class = Self::find_or_create(pool, student.institutionId.as_deref(), grade, &section).await?;
+                ClassRepository::set_student_class(pool, student_id, &class.id, &class.name).await?;
+            }
+            None => {
+                ClassRepository::unlink_student(pool, student_id).await?;
+            }
+        }
+
+        StudentRepository::get_by_id(pool, student_id).await
+    }
+
+    /// Link students that only have a class name to class records and
+    /// rewrite their class names in the canonical "9/A" form.
+    ///
+    /// Returns the number of students linked.
+    pub async fn migrate_student_classes(pool: &SqlitePool) -> AppResult<usize> {
+        let students = ClassRepository::get_unlinked_students(pool).await?;
+        let mut linked = 0;
+
+        for student in students {
+            let Some((grade, section)) = student.class.as_deref().and_then(parse_class_name) else {
+                continue;
+            };
+
+            let class = Self::find_or_create(pool, student.institutionId.as_deref(), grade, &section).await?;
+            ClassRepository::set_student_class(pool, &student.id, &class.id, &class.name).await?;
+            linked += 1;
+        }
+
+        Ok(linked)
+    }
+
+    async fn find_or_create(
+        pool: &SqlitePool,
+        institution_id: Option<&str>,
+        grade: i64,
+        section: &str,
+    ) -> AppResult<SchoolClass> {
+        let school_year = current_school_year();
+
+        if let Some(class) = ClassRepository::find(pool, institution_id, grade, section, &school_year).await? {
+            return Ok(class);
+        }
+
+        let req = CreateClassRequest {
+            grade,
+            section: section.to_string(),
+            schoolYear: None,
+            homeroomTeacherId: None,
+            counselorId: None,
+        };
+        ClassRepository::create(pool, institution_id, &school_year, &class_name(grade, section), &req).await
+    }
+}
+
+/// Parse a free-text class name such as "9A", "9-a", "9/A", "10 B" or
+/// "9. Sınıf A Şubesi" into its grade and upper-case section.
+pub fn parse_class_name(raw: &str) -> Option<(i64, String)> {
+    let lowered = turkish_lowercase(raw.trim());
+    let without_words = CLASS_WORD_REGEX.replace_all(&lowered, " ");
+    let compact = without_words.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let captures = CLASS_NAME_REGEX.captures(&compact)?;
+
+    let grade: i64 = captures[1].parse().ok()?;
+    if grade > MAX_GRADE {
+        return None;
+    }
+
+    Some((grade, turkish_uppercase(&captures[2])))
+}
+
+pub fn class_name(grade: i64, section: &str) -> String {
+    format!("{}/{}", grade, section)
+}
+
+fn turkish_lowercase(value: &str) -> String {
+    value.replace('I', "ı").replace('İ', "i").to_lowercase()
+}
+
+fn turkish_uppercase(value: &str) -> String {
+    value.replace('i', "İ").replace('ı', "I").to_uppercase()
+}
+
+fn normalize_section(section: &str) -> AppResult<String> {
+    let lowered = turkish_lowercase(section.trim());
+    if !SECTION_REGEX.is_match(&lowered) {
+        return Err(AppError::ValidationError(format!("Invalid section: {}", section)));
+    }
+    Ok(turkish_uppercase(&lowered))
+}
+
+fn validate_grade(grade: i64) -> AppResult<()> {
+    if !(0..=MAX_GRADE).contains(&grade) {
+        return Err(AppError::ValidationError(format!("Invalid grade: {}", grade)));
+    }
+    Ok(())
+}
+
+fn validate_school_year(year: &str) -> AppResult<String> {
+    let valid = SCHOOL_YEAR_REGEX
+        .captures(year.trim())
+        .map(|c| c[2].parse::<i32>().unwrap_or(0) == c[1].parse::<i32>().unwrap_or(0) + 1)
+        .unwrap_or(false);
+
+    if !valid {
+        return Err(AppError::ValidationError(format!("Invalid school year: {} (expected 2024-2025)", year)));
+    }
+    Ok(year.trim().to_string())
+}
+
+fn current_school_year() -> String {
+    school_year_and_term(local_today()).0
+}
+
+async fn require_class_access(pool: &SqlitePool, viewer: &User, class: &SchoolClass) -> AppResult<()> {
+    if let Some(institution_id) = &class.institutionId {
+        if !can_access(pool, viewer, institution_id).await? {
+            return Err(AppError::Unauthorized);
+        }
+    }
+    Ok(())
+}
+
+fn require_manager(viewer: &User) -> AppResult<()> {
+    if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Check that a homeroom teacher or counselor has one of `roles` and is a
+/// member of the class's institution
+async fn validate_staff(
+    pool: &SqlitePool,
+    institution_id: Option<&str>,
+    user_id: Option<&str>,
+    roles: &[&str],
+) -> AppResult<()> {
+    let Some(user_id) = user_id else {
+        return Ok(());
+    };
+
+    let user = UserRepository::get_by_id(pool, user_id).await?;
+    if !roles.contains(&user.role.as_str()) {
+        return Err(AppError::ValidationError(format!(
+            "{} can't be assigned to a class as {}",
+            user.name, user.role
+        )));
+    }
+    if let Some(institution_id) = institution_id {
+        if !InstitutionRepository::is_member(pool, institution_id, user_id).await? {
+            return Err(AppError::ValidationError(format!(
+                "{} is not a member of the class's institution",
+                user.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_class_name() {
+        assert_eq!(parse_class_name("9A"), Some((9, "A".to_string())));
+        assert_eq!(parse_class_name("9-a"), Some((9, "A".to_string())));
+        assert_eq!(parse_class_name(" 10/B "), Some((10, "B".to_string())));
+        assert_eq!(parse_class_name("11 C"), Some((11, "C".to_string())));
+        assert_eq!(parse_class_name("12.D"), Some((12, "D".to_string())));
+        assert_eq!(parse_class_name("9. Sınıf A Şubesi"), Some((9, "A".to_string())));
+        assert_eq!(parse_class_name("9-i"), Some((9, "İ".to_string())));
+        assert_eq!(parse_class_name("9-I"), Some((9, "I".to_string())));
+        assert_eq!(parse_class_name("13/A"), None);
+        assert_eq!(parse_class_name("Mezun"), None);
+        assert_eq!(parse_class_name(""), None);
+    }
+
+    #[test]
+    fn test_validate_school_year() {
+        assert_eq!(validate_school_year("2024-2025").unwrap(), "2024-2025");
+        assert!(validate_school_year("2024-2026").is_err());
+        assert!(validate_school_year("2024").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assign_student_checks_role_and_institution() {
+        let pool = crate::database::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Atatürk Lisesi'), ('i2', 'Fen Lisesi');
+            INSERT INTO users (id, name, email, passwordHash, role, institution) VALUES
+                ('c1', 'Ayşe', 'c1@okul.test', '', 'counselor', 'Atatürk Lisesi'),
+                ('c2', 'Mehmet', 'c2@okul.test', '', 'counselor', 'Fen Lisesi'),
+                ('t1', 'Zeynep', 't1@okul.test', '', 'teacher', 'Atatürk Lisesi');
+            INSERT INTO user_institutions (userId, institutionId) VALUES ('c1', 'i1'), ('c2', 'i2'), ('t1', 'i1');
+            INSERT INTO classes (id, institutionId, grade, section, schoolYear, name) VALUES
+                ('k1', 'i1', 9, 'A', '2024-2025', '9/A');
+            INSERT INTO students (id, name, surname, enrollmentDate, institutionId) VALUES
+                ('s1', 'Ali', 'Yılmaz', '2024-09-01', 'i1');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let teacher = UserRepository::get_by_id(&pool, "t1").await.unwrap();
+        assert!(matches!(
+            ClassService::assign_student(&pool, &teacher, "s1", "k1").await,
+            Err(AppError::Unauthorized)
+        ));
+        let other = UserRepository::get_by_id(&pool, "c2").await.unwrap();
+        assert!(matches!(
+            ClassService::assign_student(&pool, &other, "s1", "k1").await,
+            Err(AppError::Unauthorized)
+        ));
+
+        let counselor = UserRepository::get_by_id(&pool, "c1").await.unwrap();
+        let student = ClassService::assign_student(&pool, &counselor, "s1", "k1").await.unwrap();
+        assert_eq!((student.classId.as_deref(), student.class.as_deref()), (Some("k1"), Some("9/A")));
+    }
+
+    #[tokio::test]
+    async fn test_staff_must_belong_to_the_institution() {
+        let pool = crate::database::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Atatürk Lisesi'), ('i2', 'Fen Lisesi');
+            INSERT INTO users (id, name, email, passwordHash, role, institution) VALUES
+                ('c1', 'Ayşe', 'c1@okul.test', '', 'counselor', 'Atatürk Lisesi'),
+                ('c2', 'Mehmet', 'c2@okul.test', '', 'counselor', 'Fen Lisesi'),
+                ('t1', 'Zeynep', 't1@okul.test', '', 'teacher', 'Atatürk Lisesi');
+            INSERT INTO user_institutions (userId, institutionId) VALUES ('c1', 'i1'), ('c2', 'i2'), ('t1', 'i1');
+            INSERT INTO classes (id, institutionId, grade, section, schoolYear, name) VALUES
+                ('k1', 'i1', 9, 'A', '2024-2025', '9/A');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let mut counselor = UserRepository::get_by_id(&pool, "c1").await.unwrap();
+        counselor.activeInstitutionId = Some("i1".to_string());
+
+        let assign = |homeroom: &str, counsel: &str| UpdateClassRequest {
+            grade: None,
+            section: None,
+            homeroomTeacherId: Some(homeroom.to_string()),
+            counselorId: Some(counsel.to_string()),
+        };
+        assert!(matches!(
+            ClassService::update_class(&pool, &counselor, "k1", assign("t1", "c2")).await,
+            Err(AppError::ValidationError(_))
+        ));
+
+        let class = ClassService::update_class(&pool, &counselor, "k1", assign("t1", "c1")).await.unwrap();
+        assert_eq!((class.homeroomTeacherId.as_deref(), class.counselorId.as_deref()), (Some("t1"), Some("c1")));
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::NaiveDate;
+
+/// Header names of the school number column in imported files
+pub(crate) const NUMBER_HEADERS: &[&str] = &["okulno", "öğrencino", "okulnumarası", "numara", "no"];
+
+/// The most frequent of `;`, tab and `,` in a line; `;` when none occurs
+pub(crate) fn detect_delimiter(line: &str) -> char {
+    [';', '\t', ',']
+        .into_iter()
+        .max_by_key(|d| line.matches(*d).count())
+        .filter(|d| line.contains(*d))
+        .unwrap_or(';')
+}
+
+/// Split a delimited line, honouring double-quoted cells
+pub(crate) fn split_row(line: &str, delimiter: char) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => cells.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    cells.push(current);
+
+    cells
+}
+
+pub(crate) fn turkish_lowercase(value: &str) -> String {
+    value.replace('İ', "i").replace('I', "ı").to_lowercase()
+}
+
+/// A header cell lowercased the Turkish way, without spaces or punctuation
+pub(crate) fn normalize_header(cell: &str) -> String {
+    turkish_lowercase(cell).chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+/// Column index of a CSV field: a 1-based column number or a header name
+pub(crate) fn resolve_column(column: &str, header: &[String]) -> Option<usize> {
+    let column = column.trim();
+    if let Ok(number) = column.parse::<usize>() {
+        return number.checked_sub(1);
+    }
+    let name = normalize_header(column);
+    header.iter().position(|cell| normalize_header(cell) == name)
+}
+
+/// A date as written in Turkish reports (`07.10.2024`, `07/10/2024`) or ISO
+pub(crate) fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    ["%d.%m.%Y", "%d/%m/%Y", "%d-%m-%Y", "%Y-%m-%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_row() {
+        assert_eq!(detect_delimiter("a;b;c"), ';');
+        assert_eq!(detect_delimiter("a\tb,c\td"), '\t');
+        assert_eq!(split_row("1;\"Kaya; Ayşe\";\"a \"\"b\"\"\"", ';'), vec!["1", "Kaya; Ayşe", "a \"b\""]);
+    }
+
+    #[test]
+    fn test_resolve_column() {
+        let header = vec!["Okul No".to_string(), "ADI SOYADI".to_string()];
+        assert_eq!(resolve_column("2", &header), Some(1));
+        assert_eq!(resolve_column("adı soyadı", &header), Some(1));
+        assert_eq!(resolve_column("0", &header), None);
+        assert_eq!(resolve_column("Sınıf", &header), None);
+        assert_eq!(normalize_header("İL / İLÇE"), "ililçe");
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CreateSubjectRequest, CreateTopicRequest, CurriculumCatalog, CurriculumImportResult, Subject, SubjectWithTopics,
+    Topic, UpdateSubjectRequest, UpdateTopicRequest, User, CURRICULUM_SOURCE_SCHOOL, EXAM_TYPES, TOPIC_DIFFICULTIES,
+};
+use crate::repositories::{AcademicRepository, CurriculumRepository};
+
+/// The MEB curriculum shipped with the application
+pub const MEB_CURRICULUM: &str = include_str!("../../seeds/meb_curriculum.json");
+
+const DEFAULT_ESTIMATED_HOURS: i32 = 1;
+
+pub struct CurriculumService;
+
+impl CurriculumService {
+    /// Subjects with their topics, optionally of one exam type
+    pub async fn get_catalog(
+        pool: &SqlitePool,
+        exam_type: Option<&str>,
+        include_archived: bool,
+    ) -> AppResult<Vec<SubjectWithTopics>> {
+        let mut catalog = Vec::new();
+        for subject in CurriculumRepository::get_subjects(pool, exam_type, include_archived).await? {
+            let topics = CurriculumRepository::get_topics(pool, &subject.id, include_archived).await?;
+            catalog.push(SubjectWithTopics { subject, topics });
+        }
+
+        Ok(catalog)
+    }
+
+    pub async fn create_subject(pool: &SqlitePool, req: CreateSubjectRequest) -> AppResult<Subject> {
+        let req = CreateSubjectRequest {
+            name: req.name.trim().to_string(),
+            code: req.code.and_then(non_empty),
+            description: req.description.and_then(non_empty),
+            color: req.color.and_then(non_empty),
+            examType: req.examType.and_then(non_empty),
+            examSubject: req.examSubject.and_then(non_empty),
+        };
+        validate_name("Subject", &req.name)?;
+        validate_exam_type(req.examType.as_deref())?;
+
+        CurriculumRepository::create_subject(pool, &req).await
+    }
+
+    pub async fn update_subject(pool: &SqlitePool, id: &str, req: UpdateSubjectRequest) -> AppResult<Subject> {
+        let mut subject = AcademicRepository::get_subject_by_id(pool, id).await?;
+
+        if let Some(name) = req.name {
+            subject.name = name.trim().to_string();
+        }
+        if let Some(code) = req.code {
+            subject.code = non_empty(code);
+        }
+        if let Some(description) = req.description {
+            subject.description = non_empty(description);
+        }
+        if let Some(color) = req.color {
+            subject.color = non_empty(color);
+        }
+        if let Some(exam_type) = req.examType {
+            subject.examType = non_empty(exam_type);
+        }
+        if let Some(exam_subject) = req.examSubject {
+            subject.examSubject = non_empty(exam_subject);
+        }
+        if let Some(sort_order) = req.sortOrder {
+            subject.sortOrder = sort_order;
+        }
+        if let Some(is_archived) = req.isArchived {
+            subject.isArchived = is_archived;
+        }
+        validate_name("Subject", &subject.name)?;
+        validate_exam_type(subject.examType.as_deref())?;
+
+        CurriculumRepository::update_subject(pool, &subject).await
+    }
+
+    /// Delete a school subject with its topics. Catalog subjects are archived
+    /// instead, so a later catalog version does not bring them back.
+    pub async fn delete_subject(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let mut subject = AcademicRepository::get_subject_by_id(pool, id).await?;
+        if subject.source == CURRICULUM_SOURCE_SCHOOL {
+            return CurriculumRepository::delete_subject(pool, id).await;
+        }
+
+        subject.isArchived = true;
+        CurriculumRepository::update_subject(pool, &subject).await?;
+        Ok(())
+    }
+
+    pub async fn create_topic(pool: &SqlitePool, req: CreateTopicRequest) -> AppResult<Topic> {
+        AcademicRepository::get_subject_by_id(pool, &req.subjectId).await?;
+
+        let req = CreateTopicRequest {
+            name: req.name.trim().to_string(),
+            description: req.description.and_then(non_empty),
+            difficulty: req.difficulty.and_then(non_empty),
+            ..req
+        };
+        let estimated_hours = req.estimatedHours.unwrap_or(DEFAULT_ESTIMATED_HOURS);
+        validate_name("Topic", &req.name)?;
+        validate_topic(req.difficulty.as_deref(), estimated_hours)?;
+
+        CurriculumRepository::create_topic(pool, &req, estimated_hours).await
+    }
+
+    pub async fn update_topic(pool: &SqlitePool, id: &str, req: UpdateTopicRequest) -> AppResult<Topic> {
+        let mut topic = CurriculumRepository::get_topic_by_id(pool, id).await?;
+
+        if let Some(name) = req.name {
+            topic.name = name.trim().to_string();
+        }
+        if let Some(description) = req.description {
+            topic.description = non_empty(description);
+        }
+        if let Some(difficulty) = req.difficulty {
+            topic.difficulty = non_empty(difficulty);
+        }
+        if let Some(estimated_hours) = req.estimatedHours {
+            topic.estimatedHours = estimated_hours;
+        }
+        if let Some(sort_order) = req.sortOrder {
+            topic.sortOrder = sort_order;
+        }
+        if let Some(is_archived) = req.isArchived {
+            topic.isArchived = is_archived;
+        }
+        validate_name("Topic", &topic.name)?;
+        validate_topic(topic.difficulty.as_deref(), topic.estimatedHours)?;
+
+        CurriculumRepository::update_topic(pool, &topic).await
+    }
+
+    /// Delete a school topic with the progress recorded on it. Catalog topics
+    /// are archived instead.
+    pub async fn delete_topic(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let mut topic = CurriculumRepository::get_topic_by_id(pool, id).await?;
+        if topic.source == CURRICULUM_SOURCE_SCHOOL {
+            return CurriculumRepository::delete_topic(pool, id).await;
+        }
+
+        topic.isArchived = true;
+        CurriculumRepository::update_topic(pool, &topic).await?;
+        Ok(())
+    }
+
+    /// Merge a curriculum catalog file. A catalog version older than the one
+    /// already imported is rejected; importing the same version again
+    /// re-applies it.
+    pub async fn import_catalog(pool: &SqlitePool, viewer: &User, content: &str) -> AppResult<CurriculumImportResult> {
+        if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+            return Err(AppError::Unauthorized);
+        }
+
+        let catalog = parse_catalog(content)?;
+        if let Some(installed) = CurriculumRepository::get_latest_version(pool, &catalog.catalog).await? {
+            if catalog.version < installed {
+                return Err(AppError::ValidationError(format!(
+                    "Catalog {} version {} is older than the installed version {}",
+                    catalog.catalog, catalog.version, installed
+                )));
+            }
+        }
+
+        let result = CurriculumRepository::merge_catalog(pool, &catalog, Some(&viewer.id)).await?;
+        info!("Imported curriculum catalog {} version {}", catalog.catalog, catalog.version);
+        Ok(result)
+    }
+
+    /// Merge the bundled MEB curriculum when it is newer than the installed
+    /// version; returns None when it is already installed.
+    pub async fn seed_meb_curriculum(pool: &SqlitePool) -> AppResult<Option<CurriculumImportResult>> {
+        let catalog = parse_catalog(MEB_CURRICULUM)?;
+        let installed = CurriculumRepository::get_latest_version(pool, &catalog.catalog).await?;
+        if installed.is_some_and(|version| version >= catalog.version) {
+            return Ok(None);
+        }
+
+        let result = CurriculumRepository::merge_catalog(pool, &catalog, None).await?;
+        info!(
+            "Seeded {} curriculum version {}: {} subjects and {} topics added",
+            catalog.catalog, catalog.version, result.subjectsAdded, result.topicsAdded
+        );
+        Ok(Some(result))
+    }
+}
+
+/// Read and check a curriculum catalog file
+pub fn parse_catalog(content: &str) -> AppResult<CurriculumCatalog> {
+    let catalog: CurriculumCatalog = serde_json::from_str(content)
+        .map_err(|e| AppError::ValidationError(format!("Invalid curriculum catalog: {}", e)))?;
+
+    let invalid = |message: String| Err(AppError::ValidationError(message));
+    if catalog.catalog.trim().is_empty() || catalog.catalog == CURRICULUM_SOURCE_SCHOOL {
+        return invalid(format!("Invalid catalog name: '{}'", catalog.catalog));
+    }
+    if catalog.version < 1 {
+        return invalid(format!("Invalid catalog version: {}", catalog.version));
+    }
+    if catalog.subjects.is_empty() {
+        return invalid("The catalog has no subjects".to_string());
+    }
+
+    let mut keys = HashSet::new();
+    for subject in &catalog.subjects {
+        if subject.key.trim().is_empty() || !keys.insert(subject.key.as_str()) {
+            return invalid(format!("Catalog keys must be unique and not empty: '{}'", subject.key));
+        }
+        validate_name("Subject", &subject.name)?;
+        validate_exam_type(subject.examType.as_deref())?;
+
+        for topic in &subject.topics {
+            if topic.key.trim().is_empty() || !keys.insert(topic.key.as_str()) {
+                return invalid(format!("Catalog keys must be unique and not empty: '{}'", topic.key));
+            }
+            validate_name("Topic", &topic.name)?;
+            validate_topic(topic.difficulty.as_deref(), topic.estimatedHours)?;
+        }
+    }
+
+    Ok(catalog)
+}
+
+fn validate_name(kind: &str, name: &str) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::ValidationError(format!("{} name is required", kind)));
+    }
+    Ok(())
+}
+
+fn validate_exam_type(exam_type: Option<&str>) -> AppResult<()> {
+    if let Some(exam_type) = exam_type {
+        if !EXAM_TYPES.contains(&exam_type) {
+            return Err(AppError::ValidationError(format!("Unknown exam type: {}", exam_type)));
+        }
+    }
+    Ok(())
+}
+
+fn validate_topic(difficulty: Option<&str>, estimated_hours: i32) -> AppResult<()> {
+    if let Some(difficulty) = difficulty {
+        if !TOPIC_DIFFICULTIES.contains(&difficulty) {
+            return Err(AppError::ValidationError(format!("Invalid topic difficulty: {}", difficulty)));
+        }
+    }
+    if estimated_hours < 1 {
+        return Err(AppError::ValidationError(format!("Invalid estimated hours: {}", estimated_hours)));
+    }
+    Ok(())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_meb_curriculum_is_valid() {
+        let catalog = parse_catalog(MEB_CURRICULUM).unwrap();
+        assert_eq!(catalog.catalog, "MEB");
+
+        for exam_type in ["LGS", "TYT", "AYT", "YDT"] {
+            assert!(catalog.subjects.iter().any(|s| s.examType.as_deref() == Some(exam_type)), "{}", exam_type);
+        }
+        assert!(catalog.subjects.iter().all(|s| !s.topics.is_empty() && s.examSubject.is_some()));
+    }
+
+    #[test]
+    fn test_parse_catalog_rejects_invalid_entries() {
+        let catalog = |topics: &str| {
+            format!(
+                r#"{{"catalog": "MEB", "version": 2, "name": "Test", "subjects": [
+                    {{"key": "S", "name": "Matematik", "examType": "TYT", "topics": [{}]}}
+                ]}}"#,
+                topics
+            )
+        };
+
+        assert!(parse_catalog(&catalog(r#"{"key": "T1", "name": "Kümeler", "estimatedHours": 4}"#)).is_ok());
+        // Duplicate key
+        assert!(parse_catalog(&catalog(r#"{"key": "S", "name": "Kümeler", "estimatedHours": 4}"#)).is_err());
+        assert!(parse_catalog(&catalog(r#"{"key": "T1", "name": "Kümeler", "difficulty": "ZOR", "estimatedHours": 4}"#)).is_err());
+        assert!(parse_catalog(&catalog(r#"{"key": "T1", "name": " ", "estimatedHours": 4}"#)).is_err());
+        assert!(parse_catalog(&catalog(r#"{"key": "T1", "name": "Kümeler", "estimatedHours": 0}"#)).is_err());
+        assert!(parse_catalog(&catalog(r#"{"key": "T1", "name": "Kümeler", "hours": 4}"#)).is_err());
+        assert!(parse_catalog(r#"{"catalog": "SCHOOL", "version": 1, "name": "x", "subjects": []}"#).is_err());
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::HashMap;
+use regex::Regex;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CreateCustomFieldRequest, CustomFieldDefinition, CustomFieldImportError, CustomFieldImportResult, Student,
+    StudentAttribute, StudentCustomData, StudentListQuery, TagCount, UpdateCustomFieldRequest, User,
+    CUSTOM_FIELD_DATE, CUSTOM_FIELD_ENUM, CUSTOM_FIELD_NUMBER, CUSTOM_FIELD_TYPES,
+};
+use crate::repositories::{CustomFieldRepository, StudentRepository};
+use crate::services::csv_import::{detect_delimiter, normalize_header, parse_date, split_row, NUMBER_HEADERS};
+
+const MAX_TAG_LENGTH: usize = 50;
+const TAG_HEADERS: &[&str] = &["etiketler", "etiket", "tags"];
+
+/// The standard columns of the student CSV export, ahead of the custom
+/// fields and tags; the value import skips them so an export can be
+/// edited and imported again
+pub const STUDENT_EXPORT_COLUMNS: &[&str] = &[
+    "ID", "Okul No", "Ad", "Soyad", "E-posta", "Telefon", "Doğum Tarihi", "Sınıf", "Kayıt Tarihi", "Durum",
+    "Risk Seviyesi", "Cinsiyet",
+];
+
+pub struct CustomFieldService;
+
+/// The checked values and tags of one import row, by field id
+struct ImportedRow<'a> {
+    student_id: String,
+    values: Vec<(&'a str, String)>,
+    tags: Vec<String>,
+}
+
+impl CustomFieldService {
+    pub async fn get_fields(pool: &SqlitePool, include_inactive: bool) -> AppResult<Vec<CustomFieldDefinition>> {
+        CustomFieldRepository::get_fields(pool, include_inactive).await
+    }
+
+    pub async fn create_field(
+        pool: &SqlitePool,
+        viewer: &User,
+        req: CreateCustomFieldRequest,
+    ) -> AppResult<CustomFieldDefinition> {
+        require_admin(viewer)?;
+
+        let key_regex = Regex::new(r"^[a-z][a-z0-9_]{0,49}$").unwrap();
+        if !key_regex.is_match(&req.key) {
+            return Err(AppError::ValidationError(format!(
+                "Invalid field key: {} (use lowercase letters, digits and _)",
+                req.key
+            )));
+        }
+        if req.label.trim().is_empty() {
+            return Err(AppError::ValidationError("Field label is required".to_string()));
+        }
+        if !CUSTOM_FIELD_TYPES.contains(&req.fieldType.as_str()) {
+            return Err(AppError::ValidationError(format!("Invalid field type: {}", req.fieldType)));
+        }
+        if CustomFieldRepository::find_field_by_key(pool, &req.key).await?.is_some() {
+            return Err(AppError::ValidationError(format!("Field key already exists: {}", req.key)));
+        }
+
+        let options = match (req.fieldType.as_str(), &req.options) {
+            (CUSTOM_FIELD_ENUM, Some(options)) => Some(encode_options(options)?),
+            (CUSTOM_FIELD_ENUM, None) => {
+                return Err(AppError::ValidationError("Enum fields need at least one option".to_string()));
+            }
+            (_, Some(_)) => {
+                return Err(AppError::ValidationError("Only enum fields have options".to_string()));
+            }
+            (_, None) => None,
+        };
+
+        CustomFieldRepository::create_field(pool, &req, options).await
+    }
+
+    /// Update a field's label, order, options or active flag.
+    ///
+    /// Options of an enum field can only be removed when no student has them.
+    pub async fn update_field(
+        pool: &SqlitePool,
+        viewer: &User,
+        id: &str,
+        req: UpdateCustomFieldRequest,
+    ) -> AppResult<CustomFieldDefinition> {
+        require_admin(viewer)?;
+
+        let field = CustomFieldRepository::get_field_by_id(pool, id).await?;
+        if req.label.as_deref().map(|l| l.trim().is_empty()).unwrap_or(false) {
+            return Err(AppError::ValidationError("Field label is required".to_string()));
+        }
+
+        let options = match &req.options {
+            Some(options) if field.fieldType == CUSTOM_FIELD_ENUM => {
+                let encoded = encode_options(options)?;
+                let in_use: Vec<String> = CustomFieldRepository::get_distinct_values(pool, id)
+                    .await?
+                    .into_iter()
+                    .filter(|value| !options.iter().any(|o| o.trim() == value))
+                    .collect();
+                if !in_use.is_empty() {
+                    return Err(AppError::ValidationError(format!(
+                        "Options still in use cannot be removed: {}",
+                        in_use.join(", ")
+                    )));
+                }
+                Some(encoded)
+            }
+            Some(_) => return Err(AppError::ValidationError("Only enum fields have options".to_string())),
+            None => None,
+        };
+
+        CustomFieldRepository::update_field(pool, id, &req, options).await
+    }
+
+    /// Delete a field together with every student's value for it
+    pub async fn delete_field(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<()> {
+        require_admin(viewer)?;
+        CustomFieldRepository::delete_field(pool, id).await
+    }
+
+    pub async fn get_student_data(pool: &SqlitePool, student_id: &str) -> AppResult<StudentCustomData> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+
+        Ok(StudentCustomData {
+            attributes: CustomFieldRepository::get_student_attributes(pool, student_id).await?,
+            tags: CustomFieldRepository::get_student_tags(pool, student_id).await?,
+        })
+    }
+
+    /// Set custom field values by field key; an empty or missing value clears the field.
+    ///
+    /// Every value is validated before any is written.
+    pub async fn set_student_attributes(
+        pool: &SqlitePool,
+        student_id: &str,
+        values: HashMap<String, Option<String>>,
+    ) -> AppResult<Vec<StudentAttribute>> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+
+        let mut changes = Vec::new();
+        for (key, value) in values {
+            let field = active_field(pool, &key).await?;
+            let value = match value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                Some(raw) => Some(normalize_value(&field, raw)?),
+                None => None,
+            };
+            changes.push((field.id, value));
+        }
+
+        let mut tx = pool.begin().await?;
+        for (field_id, value) in changes {
+            match value {
+                Some(value) => CustomFieldRepository::set_attribute(&mut *tx, student_id, &field_id, &value).await?,
+                None => CustomFieldRepository::delete_attribute(&mut *tx, student_id, &field_id).await?,
+            }
+        }
+        tx.commit().await?;
+
+        CustomFieldRepository::get_student_attributes(pool, student_id).await
+    }
+
+    /// Replace a student's tags
+    pub async fn set_student_tags(pool: &SqlitePool, student_id: &str, tags: Vec<String>) -> AppResult<Vec<String>> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+
+        let mut normalized: Vec<String> = Vec::new();
+        for tag in tags.iter().filter_map(|tag| normalize_tag(tag)) {
+            if tag.chars().count() > MAX_TAG_LENGTH {
+                return Err(AppError::ValidationError(format!(
+                    "Tag is longer than {} characters: {}",
+                    MAX_TAG_LENGTH, tag
+                )));
+            }
+            if !normalized.iter().any(|t| t.to_lowercase() == tag.to_lowercase()) {
+                normalized.push(tag);
+            }
+        }
+
+        CustomFieldRepository::replace_tags(pool, student_id, &normalized).await?;
+        CustomFieldRepository::get_student_tags(pool, student_id).await
+    }
+
+    /// How many students of the institution carry each tag
+    pub async fn get_tag_counts(pool: &SqlitePool, institution_id: &str) -> AppResult<Vec<TagCount>> {
+        CustomFieldRepository::get_tag_counts(pool, institution_id).await
+    }
+
+    /// The student list filtered by the basic columns, tags and custom fields.
+    ///
+    /// Attribute filter values are normalized like stored values, so a date
+    /// filter may be given as 01.09.2024 and a number filter as 2,5.
+    pub async fn list_students(pool: &SqlitePool, mut query: StudentListQuery) -> AppResult<Vec<Student>> {
+        for filter in query.attributes.iter_mut().flatten() {
+            let field = CustomFieldRepository::find_field_by_key(pool, &filter.key)
+                .await?
+                .ok_or_else(|| AppError::ValidationError(format!("Unknown custom field: {}", filter.key)))?;
+
+            for bound in [&mut filter.value, &mut filter.min, &mut filter.max] {
+                if let Some(raw) = bound.take().filter(|v| !v.trim().is_empty()) {
+                    *bound = Some(normalize_value(&field, raw.trim())?);
+                }
+            }
+        }
+
+        StudentRepository::list(pool, &query).await
+    }
+
+    /// Import custom field values and tags from a CSV or tab separated file.
+    ///
+    /// Rows are matched to the institution's students by school number, so
+    /// the header row needs a school number column; the other columns are
+    /// matched to active fields by key or label, and an "Etiketler" column
+    /// adds `;` or `|` separated tags. The standard columns of the student
+    /// export are ignored. Empty cells leave existing values alone.
+    /// Rows are checked first and the values are saved in one transaction.
+    pub async fn import_values(
+        pool: &SqlitePool,
+        institution_id: &str,
+        content: &str,
+    ) -> AppResult<CustomFieldImportResult> {
+        let lines: Vec<&str> = content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let Some(header_line) = lines.first() else {
+            return Err(AppError::ValidationError("The import file is empty".to_string()));
+        };
+
+        let delimiter = detect_delimiter(header_line);
+        let header = split_row(header_line, delimiter);
+        let fields = CustomFieldRepository::get_fields(pool, false).await?;
+
+        let mut number_column = None;
+        let mut tag_column = None;
+        let mut field_columns: Vec<(usize, &CustomFieldDefinition)> = Vec::new();
+
+        for (index, cell) in header.iter().enumerate() {
+            let normalized = normalize_header(cell);
+            if NUMBER_HEADERS.contains(&normalized.as_str()) {
+                number_column = Some(index);
+            } else if TAG_HEADERS.contains(&normalized.as_str()) {
+                tag_column = Some(index);
+            } else if STUDENT_EXPORT_COLUMNS.iter().any(|c| normalize_header(c) == normalized) {
+                continue;
+            } else if let Some(field) = fields
+                .iter()
+                .find(|f| f.key == cell.trim() || normalize_header(&f.label) == normalized)
+            {
+                field_columns.push((index, field));
+            } else if !normalized.is_empty() {
+                return Err(AppError::ValidationError(format!("Unknown column: {}", cell.trim())));
+            }
+        }
+
+        let Some(number_column) = number_column else {
+            return Err(AppError::ValidationError("No school number column found".to_string()));
+        };
+
+        let mut result = CustomFieldImportResult {
+            studentsUpdated: 0,
+            valuesImported: 0,
+            tagsImported: 0,
+            errors: Vec::new(),
+        };
+        let mut rows: Vec<ImportedRow> = Vec::new();
+
+        for (index, line) in lines.iter().enumerate().skip(1) {
+            let cells = split_row(line, delimiter);
+            let cell = |column: usize| cells.get(column).map(|v| v.trim()).filter(|v| !v.is_empty());
+            let line_number = index + 1;
+
+            let Some(school_number) = cell(number_column) else {
+                continue;
+            };
+            let mut error = |message: String| {
+                result.errors.push(CustomFieldImportError {
+                    line: line_number,
+                    schoolNumber: school_number.to_string(),
+                    message,
+                });
+            };
+
+            let Some(student) = StudentRepository::find_by_school_number(pool, institution_id, school_number).await? else {
+                error("No student with this school number".to_string());
+                continue;
+            };
+
+            let mut values = Vec::new();
+            for (column, field) in &field_columns {
+                let Some(raw) = cell(*column) else {
+                    continue;
+                };
+                match normalize_value(field, raw) {
+                    Ok(value) => values.push((field.id.as_str(), value)),
+                    Err(e) => error(format!("{}: {}", field.label, e)),
+                }
+            }
+
+            let mut tags = Vec::new();
+            if let Some(raw_tags) = tag_column.and_then(cell) {
+                for tag in raw_tags.split([';', '|']).filter_map(normalize_tag) {
+                    if tag.chars().count() > MAX_TAG_LENGTH {
+                        error(format!("Tag is longer than {} characters: {}", MAX_TAG_LENGTH, tag));
+                    } else {
+                        tags.push(tag);
+                    }
+                }
+            }
+
+            rows.push(ImportedRow { student_id: student.id, values, tags });
+        }
+
+        let mut tx = pool.begin().await?;
+        for row in rows {
+            for (field_id, value) in &row.values {
+                CustomFieldRepository::set_attribute(&mut *tx, &row.student_id, field_id, value).await?;
+            }
+            let mut added = 0;
+            for tag in &row.tags {
+                if CustomFieldRepository::add_tag(&mut *tx, &row.student_id, tag).await? {
+                    added += 1;
+                }
+            }
+
+            if row.values.len() + added > 0 {
+                result.studentsUpdated += 1;
+            }
+            result.valuesImported += row.values.len();
+            result.tagsImported += added;
+        }
+        tx.commit().await?;
+
+        Ok(result)
+    }
+}
+
+fn require_admin(viewer: &User) -> AppResult<()> {
+    if viewer.role != "admin" {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+async fn active_field(pool: &SqlitePool, key: &str) -> AppResult<CustomFieldDefinition> {
+    let field = CustomFieldRepository::find_field_by_key(pool, key)
+        .await?
+        .ok_or_else(|| AppError::ValidationError(format!("Unknown custom field: {}", key)))?;
+
+    if !field.isActive {
+        return Err(AppError::ValidationError(format!("Custom field is inactive: {}", field.label)));
+    }
+    Ok(field)
+}
+
+fn encode_options(options: &[String]) -> AppResult<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for option in options.iter().map(|o| o.trim()).filter(|o| !o.is_empty()) {
+        if !cleaned.iter().any(|c| c == option) {
+            cleaned.push(option.to_string());
+        }
+    }
+    if cleaned.is_empty() {
+        return Err(AppError::ValidationError("Enum fields need at least one option".to_string()));
+    }
+
+    Ok(serde_json::to_string(&cleaned)?)
+}
+
+fn field_options(field: &CustomFieldDefinition) -> Vec<String> {
+    field
+        .options
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default()
+}
+
+/// Validate a raw value against the field type and return it in stored form:
+/// numbers with a `.` decimal point, dates as YYYY-MM-DD and enum values
+/// spelled exactly like their option.
+pub fn normalize_value(field: &CustomFieldDefinition, raw: &str) -> AppResult<String> {
+    let raw = raw.trim();
+    let invalid = || AppError::ValidationError(format!("Invalid value for {}: {}", field.label, raw));
+
+    match field.fieldType.as_str() {
+        CUSTOM_FIELD_NUMBER => {
+            let number = raw.replace(',', ".").parse::<f64>().map_err(|_| invalid())?;
+            if !number.is_finite() {
+                return Err(invalid());
+            }
+            Ok(number.to_string())
+        }
+        CUSTOM_FIELD_DATE => parse_date(raw)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .ok_or_else(invalid),
+        CUSTOM_FIELD_ENUM => field_options(field)
+            .into_iter()
+            .find(|option| option.to_lowercase() == raw.to_lowercase())
+            .ok_or_else(invalid),
+        _ => Ok(raw.to_string()),
+    }
+}
+
+/// Trim a tag and collapse inner whitespace; empty tags are dropped
+fn normalize_tag(raw: &str) -> Option<String> {
+    let tag = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!tag.is_empty()).then_some(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(field_type: &str, options: Option<&str>) -> CustomFieldDefinition {
+        CustomFieldDefinition {
+            id: "f".to_string(),
+            key: "f".to_string(),
+            label: "Alan".to_string(),
+            fieldType: field_type.to_string(),
+            options: options.map(str::to_string),
+            sortOrder: 0,
+            isActive: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_value() {
+        let number = field(CUSTOM_FIELD_NUMBER, None);
+        assert_eq!(normalize_value(&number, "2,5").unwrap(), "2.5");
+        assert_eq!(normalize_value(&number, "12").unwrap(), "12");
+        assert!(normalize_value(&number, "on iki").is_err());
+
+        let date = field(CUSTOM_FIELD_DATE, None);
+        assert_eq!(normalize_value(&date, "01.09.2024").unwrap(), "2024-09-01");
+        assert!(normalize_value(&date, "2024-13-01").is_err());
+
+        let choice = field(CUSTOM_FIELD_ENUM, Some(r#"["Var","Yok"]"#));
+        assert_eq!(normalize_value(&choice, "var").unwrap(), "Var");
+        assert!(normalize_value(&choice, "Belki").is_err());
+    }
+
+    #[test]
+    fn test_normalize_tag() {
+        assert_eq!(normalize_tag("  servis   3 ").as_deref(), Some("servis 3"));
+        assert_eq!(normalize_tag("   "), None);
+    }
+
+    #[tokio::test]
+    async fn test_import_values() {
+        let pool = crate::database::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Atatürk Lisesi'), ('i2', 'Fen Lisesi');
+            INSERT INTO students (id, name, surname, enrollmentDate, schoolNumber, institutionId) VALUES
+                ('s1', 'Ali', 'Yılmaz', '2024-09-01', '101', 'i1'), ('s2', 'Ayşe', 'Kaya', '2024-09-01', '102', 'i1'),
+                ('s3', 'Can', 'Demir', '2024-09-01', '999', 'i2');
+            INSERT INTO custom_field_definitions (id, key, label, fieldType) VALUES ('f1', 'kardes', 'Kardeş Sayısı', 'number');
+            INSERT INTO student_tags (studentId, tag) VALUES ('s2', 'servis');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let content = "Okul No;Kardeş Sayısı;Etiketler\n101;2;burslu|servis\n102;iki;Servis\n999;1;\n";
+        let result = CustomFieldService::import_values(&pool, "i1", content).await.unwrap();
+
+        assert_eq!((result.studentsUpdated, result.valuesImported, result.tagsImported), (1, 1, 2));
+        let lines: Vec<usize> = result.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+        let data = CustomFieldService::get_student_data(&pool, "s1").await.unwrap();
+        assert_eq!(data.attributes[0].value, "2");
+        assert_eq!(data.tags, vec!["burslu", "servis"]);
+    }
+
+    #[tokio::test]
+    async fn test_import_exported_columns() {
+        let pool = crate::database::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Atatürk Lisesi');
+            INSERT INTO students (id, name, surname, enrollmentDate, schoolNumber, institutionId)
+            VALUES ('s1', 'Ali', 'Yılmaz', '2024-09-01', '101', 'i1');
+            INSERT INTO custom_field_definitions (id, key, label, fieldType) VALUES ('f1', 'kardes', 'Kardeş Sayısı', 'number');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let header = format!("{},Kardeş Sayısı,Etiketler", STUDENT_EXPORT_COLUMNS.join(","));
+        let row = "s1,101,Ali,Yılmaz,,,,9/A,2024-09-01,active,Düşük,K,3,burslu";
+        let result = CustomFieldService::import_values(&pool, "i1", &format!("{}\n{}\n", header, row))
+            .await
+            .unwrap();
+
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!((result.studentsUpdated, result.valuesImported, result.tagsImported), (1, 1, 1));
+        let data = CustomFieldService::get_student_data(&pool, "s1").await.unwrap();
+        assert_eq!(data.attributes[0].value, "3");
+        assert_eq!(data.tags, vec!["burslu"]);
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sqlx::SqlitePool;
+use crate::error::AppResult;
+use crate::models::{CategoryCount, DashboardStats, RISK_LEVELS};
+use crate::repositories::DashboardRepository;
+use crate::services::attendance_service::local_today;
+
+/// Days of absences the attendance hotspots look back over
+const HOTSPOT_DAYS: i64 = 14;
+const HOTSPOT_LIMIT: i64 = 5;
+/// Days ahead the upcoming reminders cover
+const UPCOMING_DAYS: i64 = 7;
+
+pub struct DashboardService;
+
+impl DashboardService {
+    /// Dashboard statistics for an institution, or all of them with `None`.
+    ///
+    /// Results are cached per institution until the next write to the data
+    /// they are computed from, or until the local day changes.
+    pub async fn get_stats(pool: &SqlitePool, institution_id: Option<&str>) -> AppResult<DashboardStats> {
+        let scope = institution_id.unwrap_or_default();
+        let today = local_today();
+        let date = format_date(today);
+
+        let version = DashboardRepository::get_data_version(pool).await?;
+        if let Some(cached) = DashboardRepository::get_cached(pool, scope, version, &date).await? {
+            if let Ok(stats) = serde_json::from_str(&cached) {
+                return Ok(stats);
+            }
+        }
+
+        let stats = Self::compute(pool, institution_id, today).await?;
+        DashboardRepository::save_cache(pool, scope, version, &date, &serde_json::to_string(&stats)?).await?;
+        Ok(stats)
+    }
+
+    async fn compute(pool: &SqlitePool, institution_id: Option<&str>, today: NaiveDate) -> AppResult<DashboardStats> {
+        let week_start = format_date(week_start(today));
+        let month_start = format_date(today.with_day(1).unwrap_or(today));
+        let date = format_date(today);
+
+        let by_risk = DashboardRepository::count_students_by_risk(pool, institution_id).await?;
+        let (new_alerts, open_alerts) = DashboardRepository::count_alerts(pool, institution_id, &week_start).await?;
+        let (sessions_this_week, sessions_this_month) =
+            DashboardRepository::count_sessions(pool, institution_id, &week_start, &month_start, &date).await?;
+
+        let hotspot_since = format_date(today - Duration::days(HOTSPOT_DAYS));
+        let upcoming_until = format_date(today + Duration::days(UPCOMING_DAYS));
+
+        Ok(DashboardStats {
+            totalStudents: by_risk.iter().map(|c| c.count).sum(),
+            riskDistribution: risk_distribution(&by_risk),
+            newAlertsThisWeek: new_alerts,
+            openAlerts: open_alerts,
+            sessionsThisWeek: sessions_this_week,
+            sessionsThisMonth: sessions_this_month,
+            overdueFollowUps: DashboardRepository::count_overdue_follow_ups(pool, institution_id, &date).await?,
+            pendingAiSuggestions: DashboardRepository::count_pending_suggestions(pool, institution_id).await?,
+            attendanceHotspots: DashboardRepository::get_attendance_hotspots(pool, institution_id, &hotspot_since, HOTSPOT_LIMIT)
+                .await?,
+            upcomingReminders: DashboardRepository::get_upcoming_reminders(pool, institution_id, &date, &upcoming_until)
+                .await?,
+            computedAt: Utc::now().to_rfc3339(),
+        })
+    }
+}
+
+/// Monday of the date's week
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Counts of every risk level in order, zero for levels without students
+fn risk_distribution(counts: &[CategoryCount]) -> Vec<CategoryCount> {
+    RISK_LEVELS
+        .iter()
+        .map(|level| CategoryCount {
+            category: level.to_string(),
+            count: counts.iter().find(|c| c.category == *level).map_or(0, |c| c.count),
+        })
+        .collect()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_start() {
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap();
+        assert_eq!(week_start(date("2024-10-16")), date("2024-10-14"));
+        assert_eq!(week_start(date("2024-10-14")), date("2024-10-14"));
+        assert_eq!(week_start(date("2024-10-20")), date("2024-10-14"));
+    }
+}
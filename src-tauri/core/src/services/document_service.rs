@@ -0,0 +1,378 @@
+use std::path::{Path, PathBuf};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use tokio::fs;
+use uuid::Uuid;
+use crate::error::{AppError, AppResult};
+use crate::models::{AttachDocumentRequest, StudentDocument};
+use crate::repositories::{DocumentRepository, StudentRepository};
+use crate::security;
+
+/// Student documents live on disk under `<uploads>/students/<studentId>/`,
+/// with their metadata in `student_documents`.
+pub struct DocumentService;
+
+impl DocumentService {
+    /// Store a file for a student and record its metadata.
+    ///
+    /// Attaching identical content to the same student twice returns the
+    /// existing document instead of writing a second copy.
+    pub async fn attach(
+        pool: &SqlitePool,
+        uploads_dir: &Path,
+        req: AttachDocumentRequest,
+        data: Vec<u8>,
+    ) -> AppResult<StudentDocument> {
+        security::validate_filename(&req.name)
+            .map_err(|e| AppError::ValidationError(e.to_string()))?;
+        if data.is_empty() {
+            return Err(AppError::ValidationError("Document is empty".to_string()));
+        }
+
+        // Ensures the student exists before its id is used as a directory name
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+
+        let sha256 = sha256_hex(&data);
+        if let Some(existing) = DocumentRepository::find_by_hash(pool, &req.studentId, &sha256).await? {
+            return Ok(existing);
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let mime_type = req
+            .mime_type
+            .filter(|m| !m.trim().is_empty())
+            .unwrap_or_else(|| guess_mime_type(&req.name).to_string());
+        let storage_path = relative_storage_path(&req.studentId, &id, extension_of(&req.name));
+
+        write_file(uploads_dir, &storage_path, &data).await?;
+
+        let document = StudentDocument {
+            id,
+            studentId: req.studentId,
+            name: req.name,
+            mime_type,
+            category: req.category,
+            storagePath: Some(storage_path.clone()),
+            size: Some(data.len() as i64),
+            sha256: Some(sha256),
+            uploadedBy: req.uploadedBy,
+            dataUrl: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+
+        match DocumentRepository::create(pool, document).await {
+            Ok(created) => Ok(created),
+            Err(e) => {
+                // Do not leave an orphaned file behind when the insert fails
+                fs::remove_file(resolve_path(uploads_dir, &storage_path)?).await.ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Remove a document's metadata, then its file once no other document
+    /// refers to the same content
+    pub async fn detach(pool: &SqlitePool, uploads_dir: &Path, id: &str) -> AppResult<()> {
+        let document = DocumentRepository::get_by_id(pool, id).await?;
+        DocumentRepository::delete(pool, id).await?;
+
+        if let Some(sha256) = &document.sha256 {
+            if DocumentRepository::is_hash_referenced(pool, sha256).await? {
+                return Ok(());
+            }
+        }
+        if let Some(storage_path) = &document.storagePath {
+            let path = resolve_path(uploads_dir, storage_path)?;
+            if path.exists() {
+                fs::remove_file(&path).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read a document's content, falling back to the legacy inline payload
+    pub async fn read(pool: &SqlitePool, uploads_dir: &Path, id: &str) -> AppResult<(StudentDocument, Vec<u8>)> {
+        let document = DocumentRepository::get_by_id(pool, id).await?;
+
+        let data = match (&document.storagePath, &document.dataUrl) {
+            (Some(storage_path), _) => fs::read(resolve_path(uploads_dir, storage_path)?).await?,
+            (None, Some(data_url)) => decode_data_url(data_url)?.1,
+            (None, None) => {
+                return Err(AppError::FileError(format!("Document {} has no content", id)));
+            }
+        };
+
+        Ok((document, data))
+    }
+
+    /// Where a document's file is stored on disk
+    pub async fn file_path(pool: &SqlitePool, uploads_dir: &Path, id: &str) -> AppResult<PathBuf> {
+        let document = DocumentRepository::get_by_id(pool, id).await?;
+        let storage_path = document
+            .storagePath
+            .ok_or_else(|| AppError::FileError(format!("Document {} is not stored as a file", id)))?;
+
+        resolve_path(uploads_dir, &storage_path)
+    }
+
+    /// Move documents still stored as inline `dataUrl` blobs onto disk.
+    ///
+    /// Safe to run on every startup; rows that fail to decode are logged and
+    /// left in place so no data is lost.
+    pub async fn migrate_legacy_documents(pool: &SqlitePool, uploads_dir: &Path) -> AppResult<usize> {
+        let legacy = DocumentRepository::get_legacy_inline(pool).await?;
+        let mut migrated = 0;
+
+        for document in legacy {
+            let Some(data_url) = &document.dataUrl else { continue };
+
+            let (mime_type, data) = match decode_data_url(data_url) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    warn!("Skipping legacy document {}: {}", document.id, e);
+                    continue;
+                }
+            };
+            let mime_type = mime_type.unwrap_or_else(|| {
+                if document.mime_type.contains('/') {
+                    document.mime_type.clone()
+                } else {
+                    guess_mime_type(&document.name).to_string()
+                }
+            });
+
+            let storage_path = relative_storage_path(&document.studentId, &document.id, extension_of(&document.name));
+            write_file(uploads_dir, &storage_path, &data).await?;
+
+            DocumentRepository::mark_stored(
+                pool,
+                &document.id,
+                &mime_type,
+                &storage_path,
+                data.len() as i64,
+                &sha256_hex(&data),
+            )
+            .await?;
+
+            migrated += 1;
+        }
+
+        if migrated > 0 {
+            info!("Migrated {} legacy documents to file storage", migrated);
+        }
+
+        Ok(migrated)
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+fn extension_of(name: &str) -> Option<&str> {
+    name.rsplit_once('.')
+        .map(|(_, ext)| ext)
+        .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+fn relative_storage_path(student_id: &str, document_id: &str, extension: Option<&str>) -> String {
+    match extension {
+        Some(ext) => format!("students/{}/{}.{}", student_id, document_id, ext.to_lowercase()),
+        None => format!("students/{}/{}", student_id, document_id),
+    }
+}
+
+/// Resolve a stored relative path, refusing anything that escapes the uploads directory
+fn resolve_path(uploads_dir: &Path, storage_path: &str) -> AppResult<PathBuf> {
+    if storage_path.contains("..") || storage_path.starts_with('/') || storage_path.starts_with('\\') {
+        return Err(AppError::FileError("Invalid file path: path traversal detected".to_string()));
+    }
+
+    let path = uploads_dir.join(storage_path);
+    if !path.starts_with(uploads_dir) {
+        return Err(AppError::FileError("Invalid file path: path traversal detected".to_string()));
+    }
+
+    Ok(path)
+}
+
+async fn write_file(uploads_dir: &Path, storage_path: &str, data: &[u8]) -> AppResult<()> {
+    let path = resolve_path(uploads_dir, storage_path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    fs::write(&path, data).await?;
+    Ok(())
+}
+
+/// Split a `data:<mime>;base64,<payload>` URL into its MIME type and bytes
+pub(crate) fn decode_data_url(data_url: &str) -> AppResult<(Option<String>, Vec<u8>)> {
+    let Some(rest) = data_url.strip_prefix("data:") else {
+        return Err(AppError::SerializationError("Not a data URL".to_string()));
+    };
+    let (header, payload) = rest
+        .split_once(',')
+        .ok_or_else(|| AppError::SerializationError("Malformed data URL".to_string()))?;
+
+    let mime_type = header
+        .split(';')
+        .next()
+        .filter(|m| !m.is_empty())
+        .map(|m| m.to_string());
+
+    let data = if header.ends_with(";base64") {
+        STANDARD
+            .decode(payload.trim())
+            .map_err(|e| AppError::SerializationError(e.to_string()))?
+    } else {
+        payload.as_bytes().to_vec()
+    };
+
+    Ok((mime_type, data))
+}
+
+fn guess_mime_type(name: &str) -> &'static str {
+    match extension_of(name).map(|e| e.to_lowercase()).as_deref() {
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("txt") => "text/plain",
+        Some("csv") => "text/csv",
+        Some("doc") => "application/msword",
+        Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        Some("xls") => "application/vnd.ms-excel",
+        Some("xlsx") => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    async fn setup() -> (SqlitePool, PathBuf) {
+        let pool = test_pool().await;
+        sqlx::query("INSERT INTO students (id, name, surname, enrollmentDate) VALUES ('s1', 'Ali', 'Veli', '2024-09-01')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let uploads_dir = std::env::temp_dir().join(format!("rehber360_documents_{}", Uuid::new_v4()));
+        (pool, uploads_dir)
+    }
+
+    fn request(name: &str) -> AttachDocumentRequest {
+        AttachDocumentRequest {
+            studentId: "s1".to_string(),
+            name: name.to_string(),
+            mime_type: None,
+            category: None,
+            uploadedBy: None,
+        }
+    }
+
+    #[test]
+    fn test_decode_data_url() {
+        let (mime_type, data) = decode_data_url("data:text/plain;base64,bWVyaGFiYQ==").unwrap();
+        assert_eq!((mime_type.as_deref(), data.as_slice()), (Some("text/plain"), b"merhaba".as_slice()));
+
+        let (mime_type, data) = decode_data_url("data:,merhaba").unwrap();
+        assert_eq!((mime_type, data.as_slice()), (None, b"merhaba".as_slice()));
+
+        assert!(decode_data_url("bWVyaGFiYQ==").is_err());
+        assert!(decode_data_url("data:text/plain;base64").is_err());
+        assert!(decode_data_url("data:text/plain;base64,%%%").is_err());
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let uploads_dir = Path::new("/data/uploads");
+        assert_eq!(
+            resolve_path(uploads_dir, "students/s1/a.pdf").unwrap(),
+            PathBuf::from("/data/uploads/students/s1/a.pdf")
+        );
+        assert!(resolve_path(uploads_dir, "../secret").is_err());
+        assert!(resolve_path(uploads_dir, "students/../../secret").is_err());
+        assert!(resolve_path(uploads_dir, "/etc/passwd").is_err());
+        assert!(resolve_path(uploads_dir, "\\windows").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_attach_deduplicates_identical_content() {
+        let (pool, uploads_dir) = setup().await;
+
+        let first = DocumentService::attach(&pool, &uploads_dir, request("rapor.pdf"), b"icerik".to_vec()).await.unwrap();
+        let second = DocumentService::attach(&pool, &uploads_dir, request("kopya.pdf"), b"icerik".to_vec()).await.unwrap();
+        let other = DocumentService::attach(&pool, &uploads_dir, request("diger.pdf"), b"baska".to_vec()).await.unwrap();
+
+        assert_eq!(second.id, first.id);
+        assert_ne!(other.id, first.id);
+        assert_eq!(first.mime_type, "application/pdf");
+        assert_eq!(DocumentRepository::get_by_student(&pool, "s1").await.unwrap().len(), 2);
+
+        let path = DocumentService::file_path(&pool, &uploads_dir, &first.id).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"icerik");
+
+        DocumentService::detach(&pool, &uploads_dir, &first.id).await.unwrap();
+        assert!(!path.exists());
+        std::fs::remove_dir_all(&uploads_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_detach_keeps_shared_file() {
+        let (pool, uploads_dir) = setup().await;
+        let first = DocumentService::attach(&pool, &uploads_dir, request("rapor.pdf"), b"icerik".to_vec()).await.unwrap();
+        sqlx::query(
+            "INSERT INTO student_documents (id, studentId, name, type, storagePath, sha256) VALUES ('d2', 's1', 'ek.pdf', 'application/pdf', ?, ?)"
+        )
+        .bind(&first.storagePath)
+        .bind(&first.sha256)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let path = DocumentService::file_path(&pool, &uploads_dir, &first.id).await.unwrap();
+
+        DocumentService::detach(&pool, &uploads_dir, &first.id).await.unwrap();
+        assert!(path.exists());
+        assert!(DocumentRepository::get_by_id(&pool, &first.id).await.is_err());
+
+        DocumentService::detach(&pool, &uploads_dir, "d2").await.unwrap();
+        assert!(!path.exists());
+        std::fs::remove_dir_all(&uploads_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_documents() {
+        let (pool, uploads_dir) = setup().await;
+        sqlx::query(
+            r#"
+            INSERT INTO student_documents (id, studentId, name, type, dataUrl) VALUES
+                ('d1', 's1', 'not.txt', 'txt', 'data:text/plain;base64,bWVyaGFiYQ=='),
+                ('d2', 's1', 'bozuk.txt', 'txt', 'not a data url')
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(DocumentService::migrate_legacy_documents(&pool, &uploads_dir).await.unwrap(), 1);
+
+        let migrated = DocumentRepository::get_by_id(&pool, "d1").await.unwrap();
+        assert_eq!(migrated.storagePath.as_deref(), Some("students/s1/d1.txt"));
+        assert_eq!(migrated.mime_type, "text/plain");
+        assert!(migrated.dataUrl.is_none());
+        let (_, data) = DocumentService::read(&pool, &uploads_dir, "d1").await.unwrap();
+        assert_eq!(data, b"merhaba");
+
+        // The undecodable row is left in place, and a second run has nothing to do
+        assert!(DocumentRepository::get_by_id(&pool, "d2").await.unwrap().dataUrl.is_some());
+        assert_eq!(DocumentService::migrate_legacy_documents(&pool, &uploads_dir).await.unwrap(), 0);
+        std::fs::remove_dir_all(&uploads_dir).ok();
+    }
+}
@@ -0,0 +1,750 @@
+use std::collections::HashSet;
+use std::io::Cursor;
+use calamine::{open_workbook_auto_from_rs, Data, Reader};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CreateExamImportLayoutRequest, ExamAnswerKey, ExamImportLayout, ExamImportResult, ExamLayoutConfig,
+    ExamLayoutField, ExamResult, ExamScoreCalculation, ExamSubjectCount, ExamSubjectResult,
+    ImportExamResultsRequest, Student, UnmatchedExamRow, UpdateExamImportLayoutRequest, EXAM_LAYOUT_CSV,
+    EXAM_LAYOUT_EXCEL, EXAM_LAYOUT_FIXED_WIDTH, EXAM_LAYOUT_FORMATS, EXAM_TYPES,
+};
+use crate::repositories::{AcademicRepository, ExamImportRepository, StudentRepository};
+use crate::services::csv_import::{detect_delimiter, parse_date, resolve_column, split_row};
+use crate::services::document_service::decode_data_url;
+use crate::services::exam_score_service::{apply_calculation, calculate, has_official_rules, summarize, ExamScoreService};
+
+/// One student's row of an optical reader or publisher result file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedExamRow {
+    pub line: usize,
+    pub school_number: Option<String>,
+    pub student_name: Option<String>,
+    pub booklet: Option<String>,
+    /// Answer string of each layout subject, in layout order
+    pub answers: Vec<(String, String)>,
+}
+
+/// A layout field with its CSV or Excel column resolved to an index
+struct FieldSource {
+    column: Option<usize>,
+    start: Option<usize>,
+    length: Option<usize>,
+}
+
+pub struct ExamImportService;
+
+impl ExamImportService {
+    pub async fn create_layout(pool: &SqlitePool, req: CreateExamImportLayoutRequest) -> AppResult<ExamImportLayout> {
+        let exam_type = req.examType.unwrap_or_else(|| "DENEME".to_string());
+        let config = parse_layout_config(&req.format, req.config)?;
+
+        let layout = ExamImportLayout {
+            id: String::new(),
+            publisher: req.publisher.trim().to_string(),
+            name: req.name.trim().to_string(),
+            format: req.format,
+            examType: exam_type,
+            config: serde_json::to_string(&config)?,
+            notes: req.notes,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        validate_layout(&layout)?;
+
+        ExamImportRepository::create_layout(pool, &layout).await
+    }
+
+    pub async fn update_layout(
+        pool: &SqlitePool,
+        id: &str,
+        req: UpdateExamImportLayoutRequest,
+    ) -> AppResult<ExamImportLayout> {
+        let mut layout = ExamImportRepository::get_layout(pool, id).await?;
+
+        if let Some(publisher) = req.publisher {
+            layout.publisher = publisher.trim().to_string();
+        }
+        if let Some(name) = req.name {
+            layout.name = name.trim().to_string();
+        }
+        if let Some(format) = req.format {
+            layout.format = format;
+        }
+        if let Some(exam_type) = req.examType {
+            layout.examType = exam_type;
+        }
+        if req.notes.is_some() {
+            layout.notes = req.notes;
+        }
+
+        let config = match req.config {
+            Some(value) => value,
+            None => serde_json::from_str(&layout.config)?,
+        };
+        layout.config = serde_json::to_string(&parse_layout_config(&layout.format, config)?)?;
+        validate_layout(&layout)?;
+
+        ExamImportRepository::update_layout(pool, &layout).await
+    }
+
+    /// Split a result file into student rows using the layout.
+    ///
+    /// Number, name and booklet values are trimmed; answer strings are kept
+    /// as they are because optical readers write unmarked questions as spaces.
+    pub fn parse_result_file(format: &str, config: &ExamLayoutConfig, content: &str) -> AppResult<Vec<ParsedExamRow>> {
+        let tabular = format != EXAM_LAYOUT_FIXED_WIDTH;
+
+        // Each row as its line and its cells; fixed-width fields are cut from
+        // the line, the others read from the cells
+        let records: Vec<(String, Vec<String>)> = if format == EXAM_LAYOUT_EXCEL {
+            read_first_sheet(content)?.into_iter().map(|cells| (String::new(), cells)).collect()
+        } else {
+            let lines: Vec<&str> = content.trim_start_matches('\u{feff}').lines().collect();
+            let data_start = config.headerRows.min(lines.len());
+            let delimiter = lines[data_start..]
+                .iter()
+                .chain(lines[..data_start].iter().rev())
+                .find(|line| !line.trim().is_empty())
+                .map(|line| detect_delimiter(line))
+                .unwrap_or(';');
+
+            lines
+                .iter()
+                .map(|line| {
+                    let cells = if tabular { split_row(line, delimiter) } else { Vec::new() };
+                    (line.to_string(), cells)
+                })
+                .collect()
+        };
+
+        let data_start = config.headerRows.min(records.len());
+        let header = match data_start.checked_sub(1) {
+            Some(index) if tabular => records[index].1.clone(),
+            _ => Vec::new(),
+        };
+
+        let resolve = |field: &ExamLayoutField| -> AppResult<FieldSource> {
+            let column = match (&field.column, tabular) {
+                (Some(column), true) => Some(resolve_column(column, &header).ok_or_else(|| {
+                    AppError::ValidationError(format!("Column not found in the result file: {}", column))
+                })?),
+                _ => None,
+            };
+            Ok(FieldSource { column, start: field.start, length: field.length })
+        };
+
+        let number = resolve(&config.schoolNumber)?;
+        let name = config.studentName.as_ref().map(&resolve).transpose()?;
+        let booklet = config.booklet.as_ref().map(&resolve).transpose()?;
+        let subjects = config
+            .subjects
+            .iter()
+            .map(|s| Ok((s.subject.clone(), resolve(&s.answers)?)))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        let mut rows = Vec::new();
+
+        for (index, (line, cells)) in records.iter().enumerate().skip(data_start) {
+            if line.trim().is_empty() && cells.iter().all(|cell| cell.trim().is_empty()) {
+                continue;
+            }
+            let trimmed = |source: &FieldSource| {
+                extract(source, line, cells)
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+
+            let school_number = trimmed(&number);
+            let student_name = name.as_ref().and_then(trimmed);
+            if school_number.is_none() && student_name.is_none() {
+                continue;
+            }
+
+            rows.push(ParsedExamRow {
+                line: index + 1,
+                school_number,
+                student_name,
+                booklet: booklet.as_ref().and_then(trimmed),
+                answers: subjects
+                    .iter()
+                    .map(|(subject, source)| (subject.clone(), extract(source, line, cells).unwrap_or_default()))
+                    .collect(),
+            });
+        }
+
+        Ok(rows)
+    }
+
+    /// Import a practice exam's result file into `exam_results`.
+    ///
+    /// Rows are matched to the institution's students by school number and
+    /// scored against the answer key of their booklet; LGS, TYT, AYT and YDT
+    /// results get their nets and score under the official rules. Results are
+    /// inserted in one transaction, replacing the students' earlier results of
+    /// the same exam. Rows that cannot be matched or scored are returned in
+    /// `unmatched`.
+    pub async fn import_results(
+        pool: &SqlitePool,
+        institution_id: &str,
+        req: ImportExamResultsRequest,
+    ) -> AppResult<ExamImportResult> {
+        let layout = ExamImportRepository::get_layout(pool, &req.layoutId).await?;
+        let config: ExamLayoutConfig = serde_json::from_str(&layout.config)?;
+
+        let exam_name = req.examName.trim().to_string();
+        if exam_name.is_empty() {
+            return Err(AppError::ValidationError("Exam name is required".to_string()));
+        }
+        let exam_date = parse_date(&req.examDate)
+            .ok_or_else(|| AppError::ValidationError(format!("Invalid exam date: {}", req.examDate)))?
+            .format("%Y-%m-%d")
+            .to_string();
+        let exam_type = req.examType.unwrap_or_else(|| layout.examType.clone());
+        if !EXAM_TYPES.contains(&exam_type.as_str()) {
+            return Err(AppError::ValidationError(format!("Invalid exam type: {}", exam_type)));
+        }
+        validate_answer_keys(&config, &req.answerKeys)?;
+
+        // Check the layout's subjects and the score type against the official rules up front
+        let official = has_official_rules(&exam_type);
+        if official {
+            let counts = config
+                .subjects
+                .iter()
+                .map(|s| ExamSubjectCount { subject: s.subject.clone(), correct: 0, wrong: 0 })
+                .collect::<Vec<_>>();
+            calculate(&exam_type, req.scoreType.as_deref(), &counts, None)?;
+        }
+
+        let rows = Self::parse_result_file(&layout.format, &config, &req.content)?;
+
+        let mut result = ExamImportResult {
+            totalRows: rows.len(),
+            inserted: 0,
+            updated: 0,
+            studentIds: Vec::new(),
+            unmatched: Vec::new(),
+        };
+        let mut results = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+
+        for row in rows {
+            let unmatched = |reason: &str| UnmatchedExamRow {
+                line: row.line,
+                schoolNumber: row.school_number.clone(),
+                studentName: row.student_name.clone(),
+                reason: reason.to_string(),
+            };
+
+            let Some(school_number) = &row.school_number else {
+                result.unmatched.push(unmatched("Missing school number"));
+                continue;
+            };
+            let Some(student) = find_student(pool, institution_id, school_number).await? else {
+                result.unmatched.push(unmatched("No student with this school number"));
+                continue;
+            };
+            if !seen.insert(student.id.clone()) {
+                result.unmatched.push(unmatched("Student appears more than once in the file"));
+                continue;
+            }
+            let Some(key) = find_answer_key(&req.answerKeys, row.booklet.as_deref()) else {
+                let reason = format!("No answer key for booklet {}", row.booklet.as_deref().unwrap_or("-"));
+                result.unmatched.push(unmatched(&reason));
+                continue;
+            };
+
+            let subjects = row
+                .answers
+                .iter()
+                .map(|(subject, answers)| score_answers(subject, answers, &key.answers[subject], config.wrongsPerCorrect))
+                .collect::<Vec<_>>();
+
+            let calculation = if official {
+                let counts = subjects
+                    .iter()
+                    .map(|s| ExamSubjectCount { subject: s.subject.clone(), correct: s.correct, wrong: s.wrong })
+                    .collect::<Vec<_>>();
+                let calculated = ExamScoreService::calculate_for_student(
+                    pool,
+                    &student.id,
+                    &exam_type,
+                    &exam_date,
+                    req.scoreType.as_deref(),
+                    &counts,
+                )
+                .await;
+                match calculated {
+                    Ok(calculation) => calculation,
+                    Err(AppError::ValidationError(message)) => {
+                        result.unmatched.push(unmatched(&message));
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                }
+            } else {
+                summarize(&exam_type, None, subjects)
+            };
+
+            results.push(exam_result(
+                &student.id,
+                &exam_type,
+                &exam_name,
+                &exam_date,
+                &layout.publisher,
+                &calculation,
+            )?);
+            result.studentIds.push(student.id);
+        }
+
+        let (inserted, updated) = AcademicRepository::import_exam_results(pool, &results).await?;
+        result.inserted = inserted;
+        result.updated = updated;
+
+        info!(
+            "Exam import '{}' ({}): {} inserted, {} updated, {} unmatched",
+            exam_name,
+            layout.publisher,
+            result.inserted,
+            result.updated,
+            result.unmatched.len()
+        );
+
+        Ok(result)
+    }
+}
+
+pub fn parse_layout_config(format: &str, value: serde_json::Value) -> AppResult<ExamLayoutConfig> {
+    let config: ExamLayoutConfig = serde_json::from_value(value)
+        .map_err(|e| AppError::ValidationError(format!("Invalid exam import layout: {}", e)))?;
+    validate_layout_config(format, &config)?;
+    Ok(config)
+}
+
+fn validate_layout(layout: &ExamImportLayout) -> AppResult<()> {
+    if layout.publisher.is_empty() || layout.name.is_empty() {
+        return Err(AppError::ValidationError("Publisher and layout name are required".to_string()));
+    }
+    if !EXAM_TYPES.contains(&layout.examType.as_str()) {
+        return Err(AppError::ValidationError(format!("Invalid exam type: {}", layout.examType)));
+    }
+    Ok(())
+}
+
+pub fn validate_layout_config(format: &str, config: &ExamLayoutConfig) -> AppResult<()> {
+    let invalid = |message: String| Err(AppError::ValidationError(message));
+
+    if !EXAM_LAYOUT_FORMATS.contains(&format) {
+        return invalid(format!("Invalid exam import format: {}", format));
+    }
+
+    let mut fields = vec![("schoolNumber", &config.schoolNumber)];
+    fields.extend(config.studentName.iter().map(|f| ("studentName", f)));
+    fields.extend(config.booklet.iter().map(|f| ("booklet", f)));
+    fields.extend(config.subjects.iter().map(|s| (s.subject.as_str(), &s.answers)));
+
+    for (name, field) in fields {
+        if field.start == Some(0) || field.length == Some(0) {
+            return invalid(format!("Positions of {} start at 1 and cannot be empty", name));
+        }
+        if format == EXAM_LAYOUT_FIXED_WIDTH && (field.start.is_none() || field.length.is_none()) {
+            return invalid(format!("Fixed-width field {} needs a start and length", name));
+        }
+        if format == EXAM_LAYOUT_FIXED_WIDTH && field.column.is_some() {
+            return invalid(format!("Fixed-width field {} cannot have a column", name));
+        }
+        if format == EXAM_LAYOUT_CSV || format == EXAM_LAYOUT_EXCEL {
+            let Some(column) = field.column.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+                return invalid(format!("{} field {} needs a column", format, name));
+            };
+            if column.parse::<usize>().is_err() && config.headerRows == 0 {
+                return invalid(format!("Column {} is named but the layout has no header row", column));
+            }
+        }
+    }
+
+    if config.subjects.is_empty() {
+        return invalid("The layout needs at least one subject".to_string());
+    }
+    let mut subjects = HashSet::new();
+    for subject in &config.subjects {
+        if subject.subject.trim().is_empty() || !subjects.insert(subject.subject.as_str()) {
+            return invalid(format!("Subject names must be unique and not empty: '{}'", subject.subject));
+        }
+    }
+    if config.wrongsPerCorrect.is_some_and(|n| !n.is_finite() || n <= 0.0) {
+        return invalid("Wrong answers per cancelled correct answer must be greater than zero".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_answer_keys(config: &ExamLayoutConfig, keys: &[ExamAnswerKey]) -> AppResult<()> {
+    if keys.is_empty() {
+        return Err(AppError::ValidationError("An answer key is required".to_string()));
+    }
+
+    for key in keys {
+        let booklet = key.booklet.as_deref().unwrap_or("-");
+        if let Some(subject) = key.answers.keys().find(|s| !config.subjects.iter().any(|l| &l.subject == *s)) {
+            return Err(AppError::ValidationError(format!(
+                "Answer key of booklet {} has a subject not in the layout: {}",
+                booklet, subject
+            )));
+        }
+        if let Some(subject) = config.subjects.iter().find(|s| !key.answers.contains_key(&s.subject)) {
+            return Err(AppError::ValidationError(format!(
+                "Answer key of booklet {} has no answers for {}",
+                booklet, subject.subject
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// The answer key of a booklet; a key without a booklet applies to every
+/// booklet, and a single key to rows without one
+pub fn find_answer_key<'a>(keys: &'a [ExamAnswerKey], booklet: Option<&str>) -> Option<&'a ExamAnswerKey> {
+    let normalize = |value: Option<&str>| value.map(|b| b.trim().to_uppercase()).filter(|b| !b.is_empty());
+    let booklet = normalize(booklet);
+
+    keys.iter()
+        .find(|key| normalize(key.booklet.as_deref()) == booklet)
+        .or_else(|| keys.iter().find(|key| normalize(key.booklet.as_deref()).is_none()))
+        .or_else(|| if booklet.is_none() && keys.len() == 1 { keys.first() } else { None })
+}
+
+/// Score one subject's answers against the key.
+///
+/// Blank positions in the key (cancelled questions) are not scored. Unmarked
+/// questions are empty; anything else that differs from the key, including
+/// double marks, is wrong.
+pub fn score_answers(subject: &str, answers: &str, key: &str, wrongs_per_correct: Option<f64>) -> ExamSubjectResult {
+    let given: Vec<Option<char>> = answers.chars().map(normalize_answer).collect();
+    let (mut correct, mut wrong, mut empty) = (0, 0, 0);
+
+    for (index, expected) in key.chars().map(normalize_answer).enumerate() {
+        let Some(expected) = expected else {
+            continue;
+        };
+        match given.get(index).copied().flatten() {
+            None => empty += 1,
+            Some(answer) if answer == expected => correct += 1,
+            Some(_) => wrong += 1,
+        }
+    }
+
+    let penalty = wrongs_per_correct.map_or(0.0, |n| wrong as f64 / n);
+
+    ExamSubjectResult {
+        subject: subject.to_string(),
+        correct,
+        wrong,
+        empty,
+        net: round2(correct as f64 - penalty),
+    }
+}
+
+fn normalize_answer(mark: char) -> Option<char> {
+    if mark.is_whitespace() || matches!(mark, '-' | '_' | '.') {
+        None
+    } else {
+        Some(mark.to_ascii_uppercase())
+    }
+}
+
+/// Find the institution's student by the school number in a result file,
+/// which optical readers often pad with zeros
+pub(crate) async fn find_student(
+    pool: &SqlitePool,
+    institution_id: &str,
+    school_number: &str,
+) -> AppResult<Option<Student>> {
+    let student = StudentRepository::find_by_school_number(pool, institution_id, school_number).await?;
+    let unpadded = school_number.trim_start_matches('0');
+    if student.is_none() && !unpadded.is_empty() && unpadded != school_number {
+        return StudentRepository::find_by_school_number(pool, institution_id, unpadded).await;
+    }
+    Ok(student)
+}
+
+/// Rows of the first sheet of a workbook sent as a base64 data URL, padded
+/// so that they start at cell A1 like the sheet does. Date cells are written
+/// as YYYY-MM-DD.
+pub(crate) fn read_first_sheet(content: &str) -> AppResult<Vec<Vec<String>>> {
+    let (_, data) = decode_data_url(content.trim())?;
+    let unreadable = |e: calamine::Error| AppError::ValidationError(format!("Cannot read the Excel file: {}", e));
+
+    let mut workbook = open_workbook_auto_from_rs(Cursor::new(data)).map_err(unreadable)?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| AppError::ValidationError("The Excel file has no sheets".to_string()))?
+        .map_err(unreadable)?;
+
+    let (first_row, first_column) = range.start().unwrap_or((0, 0));
+    let mut rows = vec![Vec::new(); first_row as usize];
+    rows.extend(range.rows().map(|cells| {
+        std::iter::repeat_n(String::new(), first_column as usize)
+            .chain(cells.iter().map(cell_text))
+            .collect()
+    }));
+
+    Ok(rows)
+}
+
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::DateTime(value) if value.is_datetime() => {
+            let (year, month, day, ..) = value.to_ymd_hms_milli();
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        }
+        other => other.to_string(),
+    }
+}
+
+fn extract(source: &FieldSource, line: &str, cells: &[String]) -> Option<String> {
+    let value = match source.column {
+        Some(index) => cells.get(index)?.as_str(),
+        None => line,
+    };
+
+    match source.start {
+        Some(start) => {
+            let chars = value.chars().skip(start - 1);
+            Some(match source.length {
+                Some(length) => chars.take(length).collect(),
+                None => chars.collect(),
+            })
+        }
+        None => Some(value.to_string()),
+    }
+}
+
+fn exam_result(
+    student_id: &str,
+    exam_type: &str,
+    exam_name: &str,
+    exam_date: &str,
+    publisher: &str,
+    calculation: &ExamScoreCalculation,
+) -> AppResult<ExamResult> {
+    let mut result = ExamResult {
+        id: String::new(),
+        studentId: student_id.to_string(),
+        examType: exam_type.to_string(),
+        examName: exam_name.to_string(),
+        examDate: exam_date.to_string(),
+        examProvider: Some(publisher.to_string()),
+        totalScore: None,
+        percentileRank: None,
+        turkishScore: None,
+        mathScore: None,
+        scienceScore: None,
+        socialScore: None,
+        foreignLanguageScore: None,
+        turkishNet: None,
+        mathNet: None,
+        scienceNet: None,
+        socialNet: None,
+        foreignLanguageNet: None,
+        totalNet: None,
+        correctAnswers: None,
+        wrongAnswers: None,
+        emptyAnswers: None,
+        totalQuestions: None,
+        subjectBreakdown: None,
+        topicAnalysis: None,
+        strengthAreas: None,
+        weaknessAreas: None,
+        improvementSuggestions: None,
+        comparedToGoal: None,
+        comparedToPrevious: None,
+        comparedToClassAverage: None,
+        schoolRank: None,
+        classRank: None,
+        isOfficial: false,
+        certificateUrl: None,
+        answerKeyUrl: None,
+        detailedReportUrl: None,
+        goalsMet: false,
+        parentNotified: false,
+        counselorNotes: None,
+        actionPlan: None,
+        notes: None,
+        scoreType: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    apply_calculation(&mut result, calculation)?;
+
+    Ok(result)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::models::ExamLayoutSubject;
+
+    fn field(column: Option<&str>, start: Option<usize>, length: Option<usize>) -> ExamLayoutField {
+        ExamLayoutField { column: column.map(str::to_string), start, length }
+    }
+
+    #[test]
+    fn test_parse_fixed_width_file() {
+        let config: ExamLayoutConfig = serde_json::from_value(serde_json::json!({
+            "schoolNumber": { "start": 1, "length": 5 },
+            "studentName": { "start": 6, "length": 10 },
+            "booklet": { "start": 16, "length": 1 },
+            "subjects": [
+                { "subject": "turkish", "answers": { "start": 17, "length": 5 } },
+                { "subject": "math", "answers": { "start": 22, "length": 5 } }
+            ]
+        }))
+        .unwrap();
+        validate_layout_config(EXAM_LAYOUT_FIXED_WIDTH, &config).unwrap();
+
+        let content = "00123ALİ YILMAZAABCDEAB DE\n\n00456AYŞE KAYA B A  C\n";
+        let rows = ExamImportService::parse_result_file(EXAM_LAYOUT_FIXED_WIDTH, &config, content).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 1);
+        assert_eq!(rows[0].school_number.as_deref(), Some("00123"));
+        assert_eq!(rows[0].student_name.as_deref(), Some("ALİ YILMAZ"));
+        assert_eq!(rows[0].booklet.as_deref(), Some("A"));
+        assert_eq!(rows[0].answers[1], ("math".to_string(), "AB DE".to_string()));
+        assert_eq!(rows[1].line, 3);
+        assert_eq!(rows[1].answers[0].1, " A  C");
+        assert_eq!(rows[1].answers[1].1, "");
+    }
+
+    #[test]
+    fn test_parse_csv_file_with_shared_answer_column() {
+        let config = ExamLayoutConfig {
+            headerRows: 2,
+            schoolNumber: field(Some("Öğrenci No"), None, None),
+            studentName: Some(field(Some("2"), None, None)),
+            booklet: Some(field(Some("KİTAPÇIK"), None, None)),
+            subjects: vec![
+                ExamLayoutSubject { subject: "turkish".to_string(), answers: field(Some("Cevaplar"), Some(1), Some(3)) },
+                ExamLayoutSubject { subject: "science".to_string(), answers: field(Some("Cevaplar"), Some(4), None) },
+            ],
+            wrongsPerCorrect: Some(3.0),
+        };
+        validate_layout_config(EXAM_LAYOUT_CSV, &config).unwrap();
+
+        let content = "Deneme Sınavı 1\nÖğrenci No;Ad Soyad;Kitapçık;Cevaplar\n123;Ali Yılmaz;B;ABC DA\n";
+        let rows = ExamImportService::parse_result_file(EXAM_LAYOUT_CSV, &config, content).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].student_name.as_deref(), Some("Ali Yılmaz"));
+        assert_eq!(rows[0].booklet.as_deref(), Some("B"));
+        assert_eq!(rows[0].answers[0].1, "ABC");
+        assert_eq!(rows[0].answers[1].1, " DA");
+
+        let missing = ExamLayoutConfig { schoolNumber: field(Some("Numara"), None, None), ..config };
+        assert!(ExamImportService::parse_result_file(EXAM_LAYOUT_CSV, &missing, content).is_err());
+    }
+
+    #[test]
+    fn test_parse_excel_file() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let config = ExamLayoutConfig {
+            headerRows: 2,
+            schoolNumber: field(Some("No"), None, None),
+            studentName: Some(field(Some("3"), None, None)),
+            booklet: None,
+            subjects: vec![ExamLayoutSubject { subject: "math".to_string(), answers: field(Some("Cevap"), None, None) }],
+            wrongsPerCorrect: None,
+        };
+        validate_layout_config(EXAM_LAYOUT_EXCEL, &config).unwrap();
+
+        // The sheet's cells start at B2: the header row, then two students
+        let workbook = include_bytes!("../../tests/fixtures/exam_results.xlsx");
+        let content = format!("data:application/vnd.ms-excel;base64,{}", STANDARD.encode(workbook));
+        let rows = ExamImportService::parse_result_file(EXAM_LAYOUT_EXCEL, &config, &content).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 3);
+        assert_eq!(rows[0].school_number.as_deref(), Some("1234"));
+        assert_eq!(rows[0].student_name.as_deref(), Some("Ali"));
+        assert_eq!(rows[1].answers[0], ("math".to_string(), "AB D".to_string()));
+
+        assert!(ExamImportService::parse_result_file(EXAM_LAYOUT_EXCEL, &config, "data:;base64,bm90IGV4Y2Vs").is_err());
+    }
+
+    #[test]
+    fn test_score_answers() {
+        let tyt = score_answers("math", "ABCD*b E", "ABCEABCD", Some(4.0));
+        assert_eq!((tyt.correct, tyt.wrong, tyt.empty), (4, 3, 1));
+        assert_eq!(tyt.net, 3.25);
+
+        let lgs = score_answers("turkish", "ABD", "AB-C", Some(3.0));
+        assert_eq!((lgs.correct, lgs.wrong, lgs.empty), (2, 0, 1));
+        assert_eq!(lgs.net, 2.0);
+
+        let penalty = score_answers("science", "BBCA", "ABCD", Some(3.0));
+        assert_eq!(penalty.net, 1.33);
+
+        let no_penalty = score_answers("social", "BBCA", "ABCD", None);
+        assert_eq!(no_penalty.net, 2.0);
+    }
+
+    #[test]
+    fn test_find_answer_key() {
+        let key = |booklet: Option<&str>| ExamAnswerKey {
+            booklet: booklet.map(str::to_string),
+            answers: HashMap::new(),
+        };
+        let keys = vec![key(Some("A")), key(Some("B"))];
+
+        assert_eq!(find_answer_key(&keys, Some(" b ")).unwrap().booklet.as_deref(), Some("B"));
+        assert!(find_answer_key(&keys, Some("C")).is_none());
+        assert!(find_answer_key(&keys, None).is_none());
+
+        let single = vec![key(Some("A"))];
+        assert!(find_answer_key(&single, None).is_some());
+        assert!(find_answer_key(&single, Some("B")).is_none());
+
+        let shared = vec![key(None)];
+        assert!(find_answer_key(&shared, Some("B")).is_some());
+    }
+
+    #[test]
+    fn test_layout_validation() {
+        let config = |value: serde_json::Value| parse_layout_config(EXAM_LAYOUT_FIXED_WIDTH, value);
+
+        assert!(config(serde_json::json!({
+            "schoolNumber": { "start": 1 },
+            "subjects": [{ "subject": "math", "answers": { "start": 6, "length": 20 } }]
+        }))
+        .is_err());
+        assert!(config(serde_json::json!({
+            "schoolNumber": { "start": 1, "length": 5 },
+            "subjects": []
+        }))
+        .is_err());
+        assert!(config(serde_json::json!({
+            "schoolNumber": { "start": 1, "length": 5 },
+            "subjects": [{ "subject": "math", "answers": { "start": 6, "length": 20 } }],
+            "penalty": 4
+        }))
+        .is_err());
+        assert!(parse_layout_config(
+            EXAM_LAYOUT_CSV,
+            serde_json::json!({
+                "schoolNumber": { "column": "No" },
+                "subjects": [{ "subject": "math", "answers": { "column": "Matematik" } }]
+            })
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,396 @@
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{ClassExamResultRow, ClassExamResultTable, ExamGroupAverage, ExamRankingEntry, ExamRankingSummary, User};
+use crate::repositories::AcademicRepository;
+use crate::services::class_service::ClassService;
+use crate::services::institution_service::active_institution;
+
+/// Group name of students without an institution
+const DEFAULT_SCHOOL_NAME: &str = "Okul";
+
+pub struct ExamRankingService;
+
+impl ExamRankingService {
+    /// Rank an exam's results within their class and school and save the ranks,
+    /// percentile ranks and differences from the class average.
+    ///
+    /// Each exam type of the exam is ranked separately. Official results keep
+    /// the percentile rank reported by the exam provider.
+    pub async fn rank_exam(pool: &SqlitePool, exam_name: &str, exam_date: &str) -> AppResult<ExamRankingSummary> {
+        let (entries, updated) = rank_entries(pool, exam_name, exam_date).await?;
+        Ok(summary(exam_name, exam_date, &entries, updated))
+    }
+
+    /// Rank an exam like `rank_exam`; the summary covers only the viewer's
+    /// institution unless they are a district admin.
+    pub async fn rank_exam_for(
+        pool: &SqlitePool,
+        viewer: &User,
+        exam_name: &str,
+        exam_date: &str,
+    ) -> AppResult<ExamRankingSummary> {
+        let institution_id = if viewer.isDistrictAdmin { None } else { Some(active_institution(viewer)?) };
+
+        let (mut entries, updated) = rank_entries(pool, exam_name, exam_date).await?;
+        if institution_id.is_some() {
+            entries.retain(|e| e.institutionId == institution_id);
+        }
+        Ok(summary(exam_name, exam_date, &entries, updated))
+    }
+
+    /// A class's ranked results in one exam with the class and school averages
+    pub async fn get_class_results(
+        pool: &SqlitePool,
+        viewer: &User,
+        class_id: &str,
+        exam_name: &str,
+        exam_date: &str,
+    ) -> AppResult<ClassExamResultTable> {
+        let class = ClassService::get_class(pool, viewer, class_id).await?;
+
+        let mut entries = AcademicRepository::get_exam_ranking_entries(pool, exam_name, exam_date).await?;
+        apply_rankings(&mut entries);
+
+        let mut rows: Vec<ClassExamResultRow> = entries
+            .iter()
+            .filter(|e| e.classKey.as_deref() == Some(class_id))
+            .map(result_row)
+            .collect();
+        rows.sort_by(|a, b| {
+            a.examType
+                .cmp(&b.examType)
+                .then(a.classRank.unwrap_or(i32::MAX).cmp(&b.classRank.unwrap_or(i32::MAX)))
+        });
+
+        let class_averages = class_averages(&entries)
+            .into_iter()
+            .filter(|a| a.groupId.as_deref() == Some(class_id))
+            .collect();
+        let school_averages = school_averages(&entries)
+            .into_iter()
+            .filter(|a| a.groupId == class.institutionId)
+            .collect();
+
+        Ok(ClassExamResultTable {
+            class,
+            examName: exam_name.to_string(),
+            examDate: exam_date.to_string(),
+            rows,
+            classAverages: class_averages,
+            schoolAverages: school_averages,
+        })
+    }
+}
+
+/// Rank an exam and save the changed ranks; returns the ranked results and the number changed
+async fn rank_entries(pool: &SqlitePool, exam_name: &str, exam_date: &str) -> AppResult<(Vec<ExamRankingEntry>, usize)> {
+    let stored = AcademicRepository::get_exam_ranking_entries(pool, exam_name, exam_date).await?;
+    if stored.is_empty() {
+        return Err(AppError::NotFound(format!("Exam {} ({})", exam_name, exam_date)));
+    }
+
+    let mut entries = stored.clone();
+    apply_rankings(&mut entries);
+
+    let changed: Vec<ExamRankingEntry> = entries
+        .iter()
+        .zip(&stored)
+        .filter(|(entry, before)| {
+            entry.classRank != before.classRank
+                || entry.schoolRank != before.schoolRank
+                || entry.percentileRank != before.percentileRank
+                || entry.comparedToClassAverage != before.comparedToClassAverage
+        })
+        .map(|(entry, _)| entry.clone())
+        .collect();
+    if !changed.is_empty() {
+        AcademicRepository::update_exam_rankings(pool, &changed).await?;
+    }
+
+    info!("Ranked {} results of exam {} ({}), {} changed", entries.len(), exam_name, exam_date, changed.len());
+    Ok((entries, changed.len()))
+}
+
+fn summary(exam_name: &str, exam_date: &str, entries: &[ExamRankingEntry], updated: usize) -> ExamRankingSummary {
+    ExamRankingSummary {
+        examName: exam_name.to_string(),
+        examDate: exam_date.to_string(),
+        resultCount: entries.len(),
+        updated,
+        schools: school_averages(entries),
+        classes: class_averages(entries),
+    }
+}
+
+/// Fill the class and school ranks of one exam's results.
+///
+/// Results of an exam type are ranked by total score when all of them have
+/// one, otherwise by total net. Results without that value, or without a
+/// class for the class rank, are left unranked.
+pub fn apply_rankings(entries: &mut [ExamRankingEntry]) {
+    let all: Vec<usize> = (0..entries.len()).collect();
+
+    for (_, indices) in group_by(entries, &all, |e| e.examType.clone()) {
+        let by_score = indices.iter().all(|&i| entries[i].totalScore.is_some());
+        let value = |e: &ExamRankingEntry| if by_score { e.totalScore } else { e.totalNet };
+
+        for &i in &indices {
+            let entry = &mut entries[i];
+            entry.schoolRank = None;
+            entry.classRank = None;
+            entry.comparedToClassAverage = None;
+            if !entry.isOfficial {
+                entry.percentileRank = None;
+            }
+        }
+
+        let ranked: Vec<usize> = indices.iter().copied().filter(|&i| value(&entries[i]).is_some()).collect();
+
+        for (_, school) in group_by(entries, &ranked, |e| e.institutionId.clone()) {
+            let values: Vec<f64> = school.iter().filter_map(|&i| value(&entries[i])).collect();
+            let ranks = competition_ranks(&values);
+            let percentiles = percentile_ranks(&values);
+            for (position, &i) in school.iter().enumerate() {
+                entries[i].schoolRank = Some(ranks[position]);
+                if !entries[i].isOfficial {
+                    entries[i].percentileRank = Some(percentiles[position]);
+                }
+            }
+        }
+
+        let in_class: Vec<usize> = ranked.iter().copied().filter(|&i| entries[i].classKey.is_some()).collect();
+        for (_, class) in group_by(entries, &in_class, |e| (e.institutionId.clone(), e.classKey.clone())) {
+            let values: Vec<f64> = class.iter().filter_map(|&i| value(&entries[i])).collect();
+            let ranks = competition_ranks(&values);
+            let average = values.iter().sum::<f64>() / values.len() as f64;
+            for (position, &i) in class.iter().enumerate() {
+                entries[i].classRank = Some(ranks[position]);
+                entries[i].comparedToClassAverage = Some(round2(values[position] - average));
+            }
+        }
+    }
+}
+
+/// Ranks from the highest value; tied values share a rank and the next rank is skipped
+pub fn competition_ranks(values: &[f64]) -> Vec<i32> {
+    values
+        .iter()
+        .map(|value| 1 + values.iter().filter(|other| *other > value).count() as i32)
+        .collect()
+}
+
+/// Percent of values below each value, counting ties (itself included) as half
+pub fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    let count = values.len() as f64;
+    values
+        .iter()
+        .map(|value| {
+            let below = values.iter().filter(|other| *other < value).count() as f64;
+            let equal = values.iter().filter(|other| *other == value).count() as f64;
+            round2((below + equal / 2.0) / count * 100.0)
+        })
+        .collect()
+}
+
+/// Averages of each school per exam type
+pub fn school_averages(entries: &[ExamRankingEntry]) -> Vec<ExamGroupAverage> {
+    let all: Vec<usize> = (0..entries.len()).collect();
+    group_by(entries, &all, |e| (e.examType.clone(), e.institutionId.clone()))
+        .into_iter()
+        .map(|((exam_type, institution_id), members)| {
+            let name = entries[members[0]]
+                .institutionName
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SCHOOL_NAME.to_string());
+            group_average(entries, &members, exam_type, institution_id, name)
+        })
+        .collect()
+}
+
+/// Averages of each class per exam type; students without a class are left out
+pub fn class_averages(entries: &[ExamRankingEntry]) -> Vec<ExamGroupAverage> {
+    let in_class: Vec<usize> = (0..entries.len()).filter(|&i| entries[i].classKey.is_some()).collect();
+    group_by(entries, &in_class, |e| (e.examType.clone(), e.institutionId.clone(), e.classKey.clone()))
+        .into_iter()
+        .map(|((exam_type, _, class_key), members)| {
+            let name = entries[members[0]].className.clone().unwrap_or_default();
+            group_average(entries, &members, exam_type, class_key, name)
+        })
+        .collect()
+}
+
+fn group_average(
+    entries: &[ExamRankingEntry],
+    members: &[usize],
+    exam_type: String,
+    group_id: Option<String>,
+    name: String,
+) -> ExamGroupAverage {
+    let average = |value: fn(&ExamRankingEntry) -> Option<f64>| {
+        let values: Vec<f64> = members.iter().filter_map(|&i| value(&entries[i])).collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(round2(values.iter().sum::<f64>() / values.len() as f64))
+        }
+    };
+
+    ExamGroupAverage {
+        examType: exam_type,
+        groupId: group_id,
+        name,
+        studentCount: members.len(),
+        averageScore: average(|e| e.totalScore),
+        averageNet: average(|e| e.totalNet),
+        averageTurkishNet: average(|e| e.turkishNet),
+        averageMathNet: average(|e| e.mathNet),
+        averageScienceNet: average(|e| e.scienceNet),
+        averageSocialNet: average(|e| e.socialNet),
+        averageForeignLanguageNet: average(|e| e.foreignLanguageNet),
+    }
+}
+
+/// Group entry indices by a key, keeping the order in which keys first appear
+fn group_by<K: PartialEq>(
+    entries: &[ExamRankingEntry],
+    indices: &[usize],
+    key: impl Fn(&ExamRankingEntry) -> K,
+) -> Vec<(K, Vec<usize>)> {
+    let mut groups: Vec<(K, Vec<usize>)> = Vec::new();
+    for &i in indices {
+        let k = key(&entries[i]);
+        match groups.iter_mut().find(|(existing, _)| *existing == k) {
+            Some((_, members)) => members.push(i),
+            None => groups.push((k, vec![i])),
+        }
+    }
+    groups
+}
+
+fn result_row(entry: &ExamRankingEntry) -> ClassExamResultRow {
+    ClassExamResultRow {
+        examResultId: entry.id.clone(),
+        studentId: entry.studentId.clone(),
+        studentName: entry.studentName.clone(),
+        schoolNumber: entry.schoolNumber.clone(),
+        examType: entry.examType.clone(),
+        totalScore: entry.totalScore,
+        totalNet: entry.totalNet,
+        turkishNet: entry.turkishNet,
+        mathNet: entry.mathNet,
+        scienceNet: entry.scienceNet,
+        socialNet: entry.socialNet,
+        foreignLanguageNet: entry.foreignLanguageNet,
+        classRank: entry.classRank,
+        schoolRank: entry.schoolRank,
+        percentileRank: entry.percentileRank,
+        comparedToClassAverage: entry.comparedToClassAverage,
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, school: &str, class: Option<&str>, net: Option<f64>) -> ExamRankingEntry {
+        ExamRankingEntry {
+            id: id.to_string(),
+            studentId: id.to_string(),
+            studentName: id.to_string(),
+            schoolNumber: None,
+            institutionId: Some(school.to_string()),
+            institutionName: Some(school.to_string()),
+            classKey: class.map(str::to_string),
+            className: class.map(str::to_string),
+            examType: "TYT".to_string(),
+            isOfficial: false,
+            totalScore: None,
+            totalNet: net,
+            turkishNet: None,
+            mathNet: None,
+            scienceNet: None,
+            socialNet: None,
+            foreignLanguageNet: None,
+            percentileRank: None,
+            comparedToClassAverage: None,
+            schoolRank: None,
+            classRank: None,
+        }
+    }
+
+    #[test]
+    fn test_competition_and_percentile_ranks() {
+        assert_eq!(competition_ranks(&[50.0, 70.0, 50.0, 30.0]), vec![2, 1, 2, 4]);
+        assert_eq!(percentile_ranks(&[50.0, 70.0, 50.0, 30.0]), vec![50.0, 87.5, 50.0, 12.5]);
+        assert_eq!(percentile_ranks(&[42.0]), vec![50.0]);
+    }
+
+    #[test]
+    fn test_apply_rankings() {
+        let mut entries = vec![
+            entry("a", "s1", Some("9A"), Some(60.0)),
+            entry("b", "s1", Some("9A"), Some(40.0)),
+            entry("c", "s1", Some("9B"), Some(50.0)),
+            entry("d", "s1", None, Some(70.0)),
+            entry("e", "s1", Some("9A"), None),
+            entry("f", "s2", Some("9A"), Some(10.0)),
+        ];
+        entries[3].isOfficial = true;
+        entries[3].percentileRank = Some(99.1);
+        entries[4].classRank = Some(1);
+
+        apply_rankings(&mut entries);
+
+        let ranks: Vec<_> = entries.iter().map(|e| (e.schoolRank, e.classRank)).collect();
+        assert_eq!(
+            ranks,
+            vec![(Some(2), Some(1)), (Some(4), Some(2)), (Some(3), Some(1)), (Some(1), None), (None, None), (Some(1), Some(1))]
+        );
+        assert_eq!(entries[0].comparedToClassAverage, Some(10.0));
+        assert_eq!(entries[1].comparedToClassAverage, Some(-10.0));
+        assert_eq!(entries[0].percentileRank, Some(62.5));
+        // Official results keep the provider's percentile
+        assert_eq!(entries[3].percentileRank, Some(99.1));
+        assert_eq!(entries[4].percentileRank, None);
+    }
+
+    #[test]
+    fn test_ranks_by_score_only_when_every_result_has_one() {
+        let mut entries = vec![entry("a", "s1", Some("9A"), Some(60.0)), entry("b", "s1", Some("9A"), Some(50.0))];
+        entries[1].totalScore = Some(400.0);
+        apply_rankings(&mut entries);
+        assert_eq!(entries[0].schoolRank, Some(1));
+
+        entries[0].totalScore = Some(350.0);
+        apply_rankings(&mut entries);
+        assert_eq!(entries[0].schoolRank, Some(2));
+        assert_eq!(entries[0].comparedToClassAverage, Some(-25.0));
+    }
+
+    #[test]
+    fn test_group_averages() {
+        let mut entries = vec![
+            entry("a", "s1", Some("9A"), Some(60.0)),
+            entry("b", "s1", Some("9B"), Some(40.0)),
+            entry("c", "s1", Some("9A"), None),
+        ];
+        entries[2].mathNet = Some(12.5);
+
+        let schools = school_averages(&entries);
+        assert_eq!(schools.len(), 1);
+        assert_eq!(schools[0].studentCount, 3);
+        assert_eq!(schools[0].averageNet, Some(50.0));
+
+        let classes = class_averages(&entries);
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].groupId.as_deref(), Some("9A"));
+        assert_eq!(classes[0].averageNet, Some(60.0));
+        assert_eq!(classes[0].averageMathNet, Some(12.5));
+        assert_eq!(classes[0].averageScore, None);
+    }
+}
@@ -0,0 +1,674 @@
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    ExamResult, ExamScoreCalculation, ExamSubjectCount, ExamSubjectResult, EXAM_SUBJECT_BIOLOGY,
+    EXAM_SUBJECT_CHEMISTRY, EXAM_SUBJECT_FOREIGN_LANGUAGE, EXAM_SUBJECT_GEOGRAPHY_1, EXAM_SUBJECT_GEOGRAPHY_2,
+    EXAM_SUBJECT_HISTORY, EXAM_SUBJECT_HISTORY_1, EXAM_SUBJECT_HISTORY_2, EXAM_SUBJECT_LITERATURE,
+    EXAM_SUBJECT_MATH, EXAM_SUBJECT_PHILOSOPHY, EXAM_SUBJECT_PHYSICS, EXAM_SUBJECT_RELIGION,
+    EXAM_SUBJECT_SCIENCE, EXAM_SUBJECT_SOCIAL, EXAM_SUBJECT_TURKISH, EXAM_TYPE_AYT, EXAM_TYPE_LGS,
+    EXAM_TYPE_TYT, EXAM_TYPE_YDT, SCORE_TYPES, SCORE_TYPE_DIL, SCORE_TYPE_EA, SCORE_TYPE_SAY, SCORE_TYPE_SOZ,
+};
+use crate::repositories::AcademicRepository;
+
+/// Question count of each test, as given in MEB's "Sınavla Öğrenci Alacak
+/// Ortaöğretim Kurumlarına İlişkin Merkezi Sınav Başvuru ve Uygulama
+/// Kılavuzu" for LGS and ÖSYM's "Yükseköğretim Kurumları Sınavı (YKS)
+/// Kılavuzu" for the TYT, AYT and YDT sessions
+const LGS_TESTS: &[(&str, i32)] = &[
+    (EXAM_SUBJECT_TURKISH, 20),
+    (EXAM_SUBJECT_MATH, 20),
+    (EXAM_SUBJECT_SCIENCE, 20),
+    (EXAM_SUBJECT_HISTORY, 10),
+    (EXAM_SUBJECT_RELIGION, 10),
+    (EXAM_SUBJECT_FOREIGN_LANGUAGE, 10),
+];
+const TYT_TESTS: &[(&str, i32)] = &[
+    (EXAM_SUBJECT_TURKISH, 40),
+    (EXAM_SUBJECT_SOCIAL, 20),
+    (EXAM_SUBJECT_MATH, 40),
+    (EXAM_SUBJECT_SCIENCE, 20),
+];
+const AYT_TESTS: &[(&str, i32)] = &[
+    (EXAM_SUBJECT_MATH, 40),
+    (EXAM_SUBJECT_PHYSICS, 14),
+    (EXAM_SUBJECT_CHEMISTRY, 13),
+    (EXAM_SUBJECT_BIOLOGY, 13),
+    (EXAM_SUBJECT_LITERATURE, 24),
+    (EXAM_SUBJECT_HISTORY_1, 10),
+    (EXAM_SUBJECT_GEOGRAPHY_1, 6),
+    (EXAM_SUBJECT_HISTORY_2, 11),
+    (EXAM_SUBJECT_GEOGRAPHY_2, 11),
+    (EXAM_SUBJECT_PHILOSOPHY, 12),
+    (EXAM_SUBJECT_RELIGION, 6),
+];
+const YDT_TESTS: &[(&str, i32)] = &[(EXAM_SUBJECT_FOREIGN_LANGUAGE, 80)];
+
+/// LGS subject weights from the weight table of MEB's Merkezi Sınav
+/// Kılavuzu; the weighted net is scaled onto the base..500 range
+const LGS_WEIGHTS: &[(&str, f64)] = &[
+    (EXAM_SUBJECT_TURKISH, 4.0),
+    (EXAM_SUBJECT_MATH, 4.0),
+    (EXAM_SUBJECT_SCIENCE, 4.0),
+    (EXAM_SUBJECT_HISTORY, 1.0),
+    (EXAM_SUBJECT_RELIGION, 1.0),
+    (EXAM_SUBJECT_FOREIGN_LANGUAGE, 1.0),
+];
+/// Score of a paper without any net. MEB does not publish it: it is the
+/// score of a blank paper in the 2018 LGS results, which practice exam
+/// publishers use as the floor of their LGS score estimates
+const LGS_BASE_SCORE: f64 = 194.752082;
+/// Highest central exam score, per MEB's Merkezi Sınav Kılavuzu
+const LGS_MAX_SCORE: f64 = 500.0;
+
+/// Points per net of the TYT score.
+///
+/// ÖSYM's YKS Kılavuzu weighs the TYT tests 33% (Türkçe), 17% (Sosyal
+/// Bilimler), 33% (Temel Matematik) and 17% (Fen Bilimleri); each coefficient
+/// is its test's share of the 400 points above the 100 base points, divided
+/// by the test's question count. ÖSYM standardizes raw scores against all
+/// candidates, so scores from these coefficients are estimates
+const TYT_COEFFICIENTS: &[(&str, f64)] = &[
+    (EXAM_SUBJECT_TURKISH, 3.3),
+    (EXAM_SUBJECT_SOCIAL, 3.4),
+    (EXAM_SUBJECT_MATH, 3.3),
+    (EXAM_SUBJECT_SCIENCE, 3.4),
+];
+/// Points per TYT net in the SAY, EA, SÖZ and DİL scores, where the YKS
+/// Kılavuzu gives the TYT 40% with the same split between its tests
+const TYT_PLACEMENT_COEFFICIENTS: &[(&str, f64)] = &[
+    (EXAM_SUBJECT_TURKISH, 1.32),
+    (EXAM_SUBJECT_SOCIAL, 1.36),
+    (EXAM_SUBJECT_MATH, 1.32),
+    (EXAM_SUBJECT_SCIENCE, 1.36),
+];
+/// Points per AYT or YDT net of each score type, from the 60% share the YKS
+/// Kılavuzu gives them: SAY weighs Matematik 30% and Fizik, Kimya and
+/// Biyoloji 10% each; EA Matematik 30%, Türk Dili ve Edebiyatı 18%,
+/// Tarih-1 7% and Coğrafya-1 5%; SÖZ Türk Dili ve Edebiyatı 18%, Tarih-1 7%,
+/// Coğrafya-1 5%, Tarih-2 8%, Coğrafya-2 8%, Felsefe Grubu 9% and Din
+/// Kültürü 5%; DİL the YDT 60%
+const SAY_COEFFICIENTS: &[(&str, f64)] = &[
+    (EXAM_SUBJECT_MATH, 3.0),
+    (EXAM_SUBJECT_PHYSICS, 2.85),
+    (EXAM_SUBJECT_CHEMISTRY, 3.07),
+    (EXAM_SUBJECT_BIOLOGY, 3.07),
+];
+const EA_COEFFICIENTS: &[(&str, f64)] = &[
+    (EXAM_SUBJECT_MATH, 3.0),
+    (EXAM_SUBJECT_LITERATURE, 3.0),
+    (EXAM_SUBJECT_HISTORY_1, 2.8),
+    (EXAM_SUBJECT_GEOGRAPHY_1, 3.33),
+];
+const SOZ_COEFFICIENTS: &[(&str, f64)] = &[
+    (EXAM_SUBJECT_LITERATURE, 3.0),
+    (EXAM_SUBJECT_HISTORY_1, 2.8),
+    (EXAM_SUBJECT_GEOGRAPHY_1, 3.33),
+    (EXAM_SUBJECT_HISTORY_2, 2.91),
+    (EXAM_SUBJECT_GEOGRAPHY_2, 2.91),
+    (EXAM_SUBJECT_PHILOSOPHY, 3.0),
+    (EXAM_SUBJECT_RELIGION, 3.33),
+];
+const DIL_COEFFICIENTS: &[(&str, f64)] = &[(EXAM_SUBJECT_FOREIGN_LANGUAGE, 3.0)];
+
+/// Raw score a student needs in Turkish or math for a TYT score, and in one
+/// test of the score type for an AYT or YDT score (YKS Kılavuzu)
+const MIN_TEST_NET: f64 = 0.5;
+/// TYT score needed for SAY, EA, SÖZ and DİL scores to be calculated (YKS Kılavuzu)
+const MIN_TYT_SCORE_FOR_PLACEMENT: f64 = 150.0;
+
+pub struct ExamScoreService;
+
+impl ExamScoreService {
+    /// Calculate a student's exam; AYT and YDT scores add the student's TYT of the same date
+    pub async fn calculate_for_student(
+        pool: &SqlitePool,
+        student_id: &str,
+        exam_type: &str,
+        exam_date: &str,
+        score_type: Option<&str>,
+        counts: &[ExamSubjectCount],
+    ) -> AppResult<ExamScoreCalculation> {
+        let tyt = match exam_type {
+            EXAM_TYPE_AYT | EXAM_TYPE_YDT => {
+                AcademicRepository::get_exam_results_by_type(pool, student_id, EXAM_TYPE_TYT)
+                    .await?
+                    .into_iter()
+                    .find(|r| r.examDate == exam_date)
+                    .map(|r| tyt_calculation(&r))
+            }
+            _ => None,
+        };
+
+        calculate(exam_type, score_type, counts, tyt.as_ref())
+    }
+
+    /// Recalculate a stored result from the given counts, or from its subject breakdown
+    pub async fn score_result(
+        pool: &SqlitePool,
+        id: &str,
+        score_type: Option<String>,
+        counts: Option<Vec<ExamSubjectCount>>,
+    ) -> AppResult<ExamResult> {
+        let mut result = AcademicRepository::get_exam_result_by_id(pool, id).await?;
+
+        let counts = match counts {
+            Some(counts) => counts,
+            None => {
+                let breakdown = result.subjectBreakdown.as_deref().ok_or_else(|| {
+                    AppError::ValidationError("The exam result has no subject counts to calculate from".to_string())
+                })?;
+                serde_json::from_str::<Vec<ExamSubjectResult>>(breakdown)?
+                    .into_iter()
+                    .map(|s| ExamSubjectCount { subject: s.subject, correct: s.correct, wrong: s.wrong })
+                    .collect()
+            }
+        };
+        let score_type = score_type.or_else(|| result.scoreType.clone());
+
+        let calculation = Self::calculate_for_student(
+            pool,
+            &result.studentId,
+            &result.examType,
+            &result.examDate,
+            score_type.as_deref(),
+            &counts,
+        )
+        .await?;
+        apply_calculation(&mut result, &calculation)?;
+
+        AcademicRepository::update_exam_scores(pool, id, &result).await?;
+        AcademicRepository::get_exam_result_by_id(pool, id).await
+    }
+}
+
+fn tests(exam_type: &str) -> Option<&'static [(&'static str, i32)]> {
+    match exam_type {
+        EXAM_TYPE_LGS => Some(LGS_TESTS),
+        EXAM_TYPE_TYT => Some(TYT_TESTS),
+        EXAM_TYPE_AYT => Some(AYT_TESTS),
+        EXAM_TYPE_YDT => Some(YDT_TESTS),
+        _ => None,
+    }
+}
+
+/// Whether nets and scores of the exam type follow official rules
+pub fn has_official_rules(exam_type: &str) -> bool {
+    tests(exam_type).is_some()
+}
+
+/// Wrong answers that cancel one correct answer: 3 in LGS, 4 in YKS sessions
+pub fn wrongs_per_correct(exam_type: &str) -> f64 {
+    if exam_type == EXAM_TYPE_LGS {
+        3.0
+    } else {
+        4.0
+    }
+}
+
+fn resolve_score_type(exam_type: &str, score_type: Option<&str>) -> AppResult<Option<String>> {
+    let invalid = |message: String| Err(AppError::ValidationError(message));
+
+    if let Some(value) = score_type {
+        if !SCORE_TYPES.contains(&value) {
+            return invalid(format!("Invalid score type: {}", value));
+        }
+    }
+
+    match (exam_type, score_type) {
+        (EXAM_TYPE_AYT, None) => invalid("AYT results need a score type (SAY, EA or SÖZ)".to_string()),
+        (EXAM_TYPE_AYT, Some(SCORE_TYPE_DIL)) => invalid("DİL scores are calculated from YDT results".to_string()),
+        (EXAM_TYPE_YDT, None | Some(SCORE_TYPE_DIL)) => Ok(Some(SCORE_TYPE_DIL.to_string())),
+        (EXAM_TYPE_AYT, Some(value)) => Ok(Some(value.to_string())),
+        (_, None) => Ok(None),
+        (_, Some(value)) => invalid(format!("{} results have no {} score", exam_type, value)),
+    }
+}
+
+/// Calculate nets and the score of an exam under its official rules.
+///
+/// LGS scores put the weighted net on MEB's scale with a full paper at 500;
+/// MEB standardizes against all candidates, so this is an estimate. TYT and
+/// the SAY, EA, SÖZ and DİL scores use ÖSYM's coefficients and take 100 base
+/// points; the latter need the TYT nets of the same session.
+pub fn calculate(
+    exam_type: &str,
+    score_type: Option<&str>,
+    counts: &[ExamSubjectCount],
+    tyt: Option<&ExamScoreCalculation>,
+) -> AppResult<ExamScoreCalculation> {
+    let tests = tests(exam_type)
+        .ok_or_else(|| AppError::ValidationError(format!("No official scoring rules for {} exams", exam_type)))?;
+    let score_type = resolve_score_type(exam_type, score_type)?;
+    let penalty = wrongs_per_correct(exam_type);
+
+    let mut subjects: Vec<ExamSubjectResult> = Vec::new();
+    for count in counts {
+        let Some((_, questions)) = tests.iter().find(|(subject, _)| *subject == count.subject) else {
+            return Err(AppError::ValidationError(format!("{} is not a test of {}", count.subject, exam_type)));
+        };
+        if subjects.iter().any(|s| s.subject == count.subject) {
+            return Err(AppError::ValidationError(format!("{} is counted twice", count.subject)));
+        }
+        if count.correct < 0 || count.wrong < 0 || count.correct + count.wrong > *questions {
+            return Err(AppError::ValidationError(format!(
+                "The {} test of {} has {} questions",
+                count.subject, exam_type, questions
+            )));
+        }
+
+        subjects.push(ExamSubjectResult {
+            subject: count.subject.clone(),
+            correct: count.correct,
+            wrong: count.wrong,
+            empty: questions - count.correct - count.wrong,
+            net: round2(count.correct as f64 - count.wrong as f64 / penalty),
+        });
+    }
+
+    let mut calculation = summarize(exam_type, score_type, subjects);
+    calculation.totalScore = match exam_type {
+        EXAM_TYPE_LGS => Some(lgs_score(&calculation)),
+        EXAM_TYPE_TYT => tyt_score(&calculation),
+        _ => tyt.and_then(|tyt| placement_score(tyt, &calculation)),
+    };
+
+    Ok(calculation)
+}
+
+/// Add subject nets up into the net columns of `exam_results`
+pub fn summarize(exam_type: &str, score_type: Option<String>, subjects: Vec<ExamSubjectResult>) -> ExamScoreCalculation {
+    let column = |name: &str| {
+        let nets: Vec<f64> = subjects
+            .iter()
+            .filter(|s| net_column(&s.subject) == Some(name))
+            .map(|s| s.net)
+            .collect();
+        (!nets.is_empty()).then(|| round2(nets.iter().sum()))
+    };
+
+    ExamScoreCalculation {
+        examType: exam_type.to_string(),
+        scoreType: score_type,
+        turkishNet: column(EXAM_SUBJECT_TURKISH),
+        mathNet: column(EXAM_SUBJECT_MATH),
+        scienceNet: column(EXAM_SUBJECT_SCIENCE),
+        socialNet: column(EXAM_SUBJECT_SOCIAL),
+        foreignLanguageNet: column(EXAM_SUBJECT_FOREIGN_LANGUAGE),
+        totalNet: round2(subjects.iter().map(|s| s.net).sum()),
+        totalScore: None,
+        subjects,
+    }
+}
+
+/// Fill an exam result's nets, answer counts and score from a calculation.
+///
+/// A score that could not be calculated leaves the stored score in place.
+pub fn apply_calculation(result: &mut ExamResult, calculation: &ExamScoreCalculation) -> AppResult<()> {
+    let subjects = &calculation.subjects;
+
+    result.turkishNet = calculation.turkishNet;
+    result.mathNet = calculation.mathNet;
+    result.scienceNet = calculation.scienceNet;
+    result.socialNet = calculation.socialNet;
+    result.foreignLanguageNet = calculation.foreignLanguageNet;
+    result.totalNet = Some(calculation.totalNet);
+    result.correctAnswers = Some(subjects.iter().map(|s| s.correct).sum());
+    result.wrongAnswers = Some(subjects.iter().map(|s| s.wrong).sum());
+    result.emptyAnswers = Some(subjects.iter().map(|s| s.empty).sum());
+    result.totalQuestions = Some(subjects.iter().map(|s| s.correct + s.wrong + s.empty).sum());
+    result.subjectBreakdown = Some(serde_json::to_string(subjects)?);
+    result.scoreType = calculation.scoreType.clone();
+    if calculation.totalScore.is_some() {
+        result.totalScore = calculation.totalScore;
+    }
+
+    Ok(())
+}
+
+fn net_column(subject: &str) -> Option<&'static str> {
+    match subject {
+        EXAM_SUBJECT_TURKISH | EXAM_SUBJECT_LITERATURE => Some(EXAM_SUBJECT_TURKISH),
+        EXAM_SUBJECT_MATH => Some(EXAM_SUBJECT_MATH),
+        EXAM_SUBJECT_SCIENCE | EXAM_SUBJECT_PHYSICS | EXAM_SUBJECT_CHEMISTRY | EXAM_SUBJECT_BIOLOGY => {
+            Some(EXAM_SUBJECT_SCIENCE)
+        }
+        EXAM_SUBJECT_SOCIAL
+        | EXAM_SUBJECT_HISTORY
+        | EXAM_SUBJECT_RELIGION
+        | EXAM_SUBJECT_HISTORY_1
+        | EXAM_SUBJECT_GEOGRAPHY_1
+        | EXAM_SUBJECT_HISTORY_2
+        | EXAM_SUBJECT_GEOGRAPHY_2
+        | EXAM_SUBJECT_PHILOSOPHY => Some(EXAM_SUBJECT_SOCIAL),
+        EXAM_SUBJECT_FOREIGN_LANGUAGE => Some(EXAM_SUBJECT_FOREIGN_LANGUAGE),
+        _ => None,
+    }
+}
+
+fn subject_net(calculation: &ExamScoreCalculation, subject: &str) -> f64 {
+    calculation.subjects.iter().find(|s| s.subject == subject).map_or(0.0, |s| s.net)
+}
+
+fn weighted(calculation: &ExamScoreCalculation, coefficients: &[(&str, f64)]) -> f64 {
+    coefficients
+        .iter()
+        .map(|(subject, coefficient)| subject_net(calculation, subject) * coefficient)
+        .sum()
+}
+
+fn lgs_score(calculation: &ExamScoreCalculation) -> f64 {
+    let full: f64 = LGS_TESTS
+        .iter()
+        .zip(LGS_WEIGHTS)
+        .map(|((_, questions), (_, weight))| *questions as f64 * weight)
+        .sum();
+
+    round3(LGS_BASE_SCORE + weighted(calculation, LGS_WEIGHTS) * (LGS_MAX_SCORE - LGS_BASE_SCORE) / full)
+}
+
+fn tyt_score(calculation: &ExamScoreCalculation) -> Option<f64> {
+    let counted = [EXAM_SUBJECT_TURKISH, EXAM_SUBJECT_MATH]
+        .iter()
+        .any(|subject| subject_net(calculation, subject) >= MIN_TEST_NET);
+
+    counted.then(|| round3(100.0 + weighted(calculation, TYT_COEFFICIENTS)))
+}
+
+fn placement_score(tyt: &ExamScoreCalculation, calculation: &ExamScoreCalculation) -> Option<f64> {
+    let coefficients = match calculation.scoreType.as_deref()? {
+        SCORE_TYPE_SAY => SAY_COEFFICIENTS,
+        SCORE_TYPE_EA => EA_COEFFICIENTS,
+        SCORE_TYPE_SOZ => SOZ_COEFFICIENTS,
+        _ => DIL_COEFFICIENTS,
+    };
+
+    if tyt_score(tyt)? < MIN_TYT_SCORE_FOR_PLACEMENT {
+        return None;
+    }
+    if !coefficients.iter().any(|(subject, _)| subject_net(calculation, subject) >= MIN_TEST_NET) {
+        return None;
+    }
+
+    Some(round3(
+        100.0 + weighted(tyt, TYT_PLACEMENT_COEFFICIENTS) + weighted(calculation, coefficients),
+    ))
+}
+
+/// A stored TYT result as a calculation, from its subject nets
+fn tyt_calculation(result: &ExamResult) -> ExamScoreCalculation {
+    let subjects = [
+        (EXAM_SUBJECT_TURKISH, result.turkishNet),
+        (EXAM_SUBJECT_SOCIAL, result.socialNet),
+        (EXAM_SUBJECT_MATH, result.mathNet),
+        (EXAM_SUBJECT_SCIENCE, result.scienceNet),
+    ]
+    .into_iter()
+    .map(|(subject, net)| ExamSubjectResult {
+        subject: subject.to_string(),
+        correct: 0,
+        wrong: 0,
+        empty: 0,
+        net: net.unwrap_or(0.0),
+    })
+    .collect();
+
+    summarize(EXAM_TYPE_TYT, None, subjects)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(values: &[(&str, i32, i32)]) -> Vec<ExamSubjectCount> {
+        values
+            .iter()
+            .map(|(subject, correct, wrong)| ExamSubjectCount {
+                subject: subject.to_string(),
+                correct: *correct,
+                wrong: *wrong,
+            })
+            .collect()
+    }
+
+    fn full(tests: &[(&str, i32)]) -> Vec<ExamSubjectCount> {
+        counts(&tests.iter().map(|(subject, questions)| (*subject, *questions, 0)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_lgs_score() {
+        let perfect = calculate(EXAM_TYPE_LGS, None, &full(LGS_TESTS), None).unwrap();
+        assert_eq!(perfect.totalNet, 90.0);
+        assert_eq!(perfect.totalScore, Some(500.0));
+
+        let blank = calculate(EXAM_TYPE_LGS, None, &[], None).unwrap();
+        assert_eq!(blank.totalScore, Some(194.752));
+
+        // Türkçe 17/3, Matematik 13/3, Fen 16/3, İnkılap 8/0, Din 10/0, İngilizce 9/0
+        let result = calculate(
+            EXAM_TYPE_LGS,
+            None,
+            &counts(&[
+                (EXAM_SUBJECT_TURKISH, 17, 3),
+                (EXAM_SUBJECT_MATH, 13, 3),
+                (EXAM_SUBJECT_SCIENCE, 16, 3),
+                (EXAM_SUBJECT_HISTORY, 8, 0),
+                (EXAM_SUBJECT_RELIGION, 10, 0),
+                (EXAM_SUBJECT_FOREIGN_LANGUAGE, 9, 0),
+            ]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.turkishNet, Some(16.0));
+        assert_eq!(result.socialNet, Some(18.0));
+        assert_eq!(result.subjects[0].empty, 0);
+        assert_eq!(result.totalNet, 70.0);
+        assert_eq!(result.totalScore, Some(419.731));
+    }
+
+    #[test]
+    fn test_tyt_score() {
+        let perfect = calculate(EXAM_TYPE_TYT, None, &full(TYT_TESTS), None).unwrap();
+        assert_eq!(perfect.totalScore, Some(500.0));
+
+        let result = calculate(
+            EXAM_TYPE_TYT,
+            None,
+            &counts(&[
+                (EXAM_SUBJECT_TURKISH, 30, 8),
+                (EXAM_SUBJECT_SOCIAL, 12, 4),
+                (EXAM_SUBJECT_MATH, 25, 8),
+                (EXAM_SUBJECT_SCIENCE, 10, 4),
+            ]),
+            None,
+        )
+        .unwrap();
+        assert_eq!((result.turkishNet, result.mathNet), (Some(28.0), Some(23.0)));
+        assert_eq!(result.subjects[0].empty, 2);
+        assert_eq!(result.totalNet, 71.0);
+        assert_eq!(result.totalScore, Some(336.3));
+
+        // No score without half a net in Turkish or math
+        let social_only = calculate(EXAM_TYPE_TYT, None, &counts(&[(EXAM_SUBJECT_SOCIAL, 10, 0)]), None).unwrap();
+        assert_eq!(social_only.totalScore, None);
+    }
+
+    #[test]
+    fn test_placement_scores() {
+        let tyt = calculate(
+            EXAM_TYPE_TYT,
+            None,
+            &counts(&[
+                (EXAM_SUBJECT_TURKISH, 30, 8),
+                (EXAM_SUBJECT_SOCIAL, 12, 4),
+                (EXAM_SUBJECT_MATH, 25, 8),
+                (EXAM_SUBJECT_SCIENCE, 10, 4),
+            ]),
+            None,
+        )
+        .unwrap();
+        let ayt = counts(&[
+            (EXAM_SUBJECT_MATH, 22, 8),
+            (EXAM_SUBJECT_PHYSICS, 8, 4),
+            (EXAM_SUBJECT_CHEMISTRY, 6, 0),
+            (EXAM_SUBJECT_BIOLOGY, 9, 4),
+        ]);
+
+        let say = calculate(EXAM_TYPE_AYT, Some(SCORE_TYPE_SAY), &ayt, Some(&tyt)).unwrap();
+        assert_eq!(say.scienceNet, Some(21.0));
+        assert_eq!(say.totalScore, Some(317.45));
+
+        // Without the TYT, or with one under 150, there is no placement score
+        assert_eq!(calculate(EXAM_TYPE_AYT, Some(SCORE_TYPE_SAY), &ayt, None).unwrap().totalScore, None);
+        let weak_tyt = calculate(EXAM_TYPE_TYT, None, &counts(&[(EXAM_SUBJECT_TURKISH, 10, 0)]), None).unwrap();
+        assert_eq!(calculate(EXAM_TYPE_AYT, Some(SCORE_TYPE_SAY), &ayt, Some(&weak_tyt)).unwrap().totalScore, None);
+
+        // Sayısal tests do not count towards SÖZ
+        assert_eq!(calculate(EXAM_TYPE_AYT, Some(SCORE_TYPE_SOZ), &ayt, Some(&tyt)).unwrap().totalScore, None);
+
+        let ydt = calculate(EXAM_TYPE_YDT, None, &counts(&[(EXAM_SUBJECT_FOREIGN_LANGUAGE, 60, 8)]), Some(&tyt)).unwrap();
+        assert_eq!(ydt.scoreType.as_deref(), Some(SCORE_TYPE_DIL));
+        assert_eq!(ydt.totalScore, Some(368.52));
+    }
+
+    /// Score of a paper with the given tests full and the others blank
+    fn full_score(
+        exam_type: &str,
+        score_type: Option<&str>,
+        subjects: &[&str],
+        tyt: Option<&ExamScoreCalculation>,
+    ) -> f64 {
+        let paper: Vec<(&str, i32)> =
+            tests(exam_type).unwrap().iter().copied().filter(|(s, _)| subjects.contains(s)).collect();
+        calculate(exam_type, score_type, &full(&paper), tyt).unwrap().totalScore.unwrap()
+    }
+
+    /// Check the share of the points above the base score each full test
+    /// earns on top of the `with` tests, or of `floor` without them
+    fn assert_shares(
+        exam_type: &str,
+        score_type: Option<&str>,
+        tyt: Option<&ExamScoreCalculation>,
+        floor: f64,
+        with: &[&str],
+        published: &[(&str, f64)],
+    ) {
+        let span = if exam_type == EXAM_TYPE_LGS { LGS_MAX_SCORE - LGS_BASE_SCORE } else { 400.0 };
+        let floor = if with.is_empty() { floor } else { full_score(exam_type, score_type, with, tyt) };
+
+        for (subject, percent) in published {
+            let subjects: Vec<&str> = with.iter().copied().chain([*subject]).collect();
+            let actual = (full_score(exam_type, score_type, &subjects, tyt) - floor) / span * 100.0;
+            assert!(
+                (actual - percent).abs() < 0.05,
+                "{} {:?} {}: {} instead of {}",
+                exam_type,
+                score_type,
+                subject,
+                actual,
+                percent
+            );
+        }
+    }
+
+    /// Each full test earns the share of the score the MEB and ÖSYM guides give it
+    #[test]
+    fn test_published_weights() {
+        // LGS weights 4, 4, 4, 1, 1, 1 over 20, 20, 20, 10, 10, 10 questions: 80/270 and 10/270
+        assert_shares(
+            EXAM_TYPE_LGS,
+            None,
+            None,
+            LGS_BASE_SCORE,
+            &[],
+            &[
+                (EXAM_SUBJECT_TURKISH, 29.63),
+                (EXAM_SUBJECT_SCIENCE, 29.63),
+                (EXAM_SUBJECT_HISTORY, 3.7),
+                (EXAM_SUBJECT_FOREIGN_LANGUAGE, 3.7),
+            ],
+        );
+
+        let tyt_shares = [(EXAM_SUBJECT_TURKISH, 33.0), (EXAM_SUBJECT_MATH, 33.0)];
+        assert_shares(EXAM_TYPE_TYT, None, None, 100.0, &[], &tyt_shares);
+        // Sosyal and Fen count only once Türkçe or Temel Matematik does
+        assert_shares(
+            EXAM_TYPE_TYT,
+            None,
+            None,
+            100.0,
+            &[EXAM_SUBJECT_TURKISH],
+            &[(EXAM_SUBJECT_SOCIAL, 17.0), (EXAM_SUBJECT_SCIENCE, 17.0)],
+        );
+
+        // A full TYT Türkçe test alone carries 33% of the 40% TYT share into placement scores
+        let tyt = calculate(EXAM_TYPE_TYT, None, &full(&TYT_TESTS[..1]), None).unwrap();
+        let floor = 100.0 + 400.0 * 0.40 * 0.33;
+        assert_shares(
+            EXAM_TYPE_AYT,
+            Some(SCORE_TYPE_SAY),
+            Some(&tyt),
+            floor,
+            &[],
+            &[
+                (EXAM_SUBJECT_MATH, 30.0),
+                (EXAM_SUBJECT_PHYSICS, 10.0),
+                (EXAM_SUBJECT_CHEMISTRY, 10.0),
+                (EXAM_SUBJECT_BIOLOGY, 10.0),
+            ],
+        );
+        assert_shares(
+            EXAM_TYPE_AYT,
+            Some(SCORE_TYPE_EA),
+            Some(&tyt),
+            floor,
+            &[],
+            &[
+                (EXAM_SUBJECT_MATH, 30.0),
+                (EXAM_SUBJECT_LITERATURE, 18.0),
+                (EXAM_SUBJECT_HISTORY_1, 7.0),
+                (EXAM_SUBJECT_GEOGRAPHY_1, 5.0),
+            ],
+        );
+        assert_shares(
+            EXAM_TYPE_AYT,
+            Some(SCORE_TYPE_SOZ),
+            Some(&tyt),
+            floor,
+            &[],
+            &[
+                (EXAM_SUBJECT_LITERATURE, 18.0),
+                (EXAM_SUBJECT_HISTORY_1, 7.0),
+                (EXAM_SUBJECT_GEOGRAPHY_1, 5.0),
+                (EXAM_SUBJECT_HISTORY_2, 8.0),
+                (EXAM_SUBJECT_GEOGRAPHY_2, 8.0),
+                (EXAM_SUBJECT_PHILOSOPHY, 9.0),
+                (EXAM_SUBJECT_RELIGION, 5.0),
+            ],
+        );
+        assert_shares(EXAM_TYPE_YDT, None, Some(&tyt), floor, &[], &[(EXAM_SUBJECT_FOREIGN_LANGUAGE, 60.0)]);
+    }
+
+    #[test]
+    fn test_invalid_counts() {
+        assert!(calculate("DENEME", None, &[], None).is_err());
+        assert!(calculate(EXAM_TYPE_AYT, None, &[], None).is_err());
+        assert!(calculate(EXAM_TYPE_TYT, Some(SCORE_TYPE_SAY), &[], None).is_err());
+        assert!(calculate(EXAM_TYPE_TYT, None, &counts(&[(EXAM_SUBJECT_PHYSICS, 5, 0)]), None).is_err());
+        assert!(calculate(EXAM_TYPE_LGS, None, &counts(&[(EXAM_SUBJECT_HISTORY, 8, 3)]), None).is_err());
+        assert!(calculate(
+            EXAM_TYPE_LGS,
+            None,
+            &counts(&[(EXAM_SUBJECT_MATH, 8, 3), (EXAM_SUBJECT_MATH, 8, 3)]),
+            None
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,796 @@
+use std::collections::HashMap;
+use chrono::{Duration, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AiSuggestion, CreateAiSuggestionRequest, CreateStudyAssignmentRequest, ExamTopicImportResult, ExamTopicReportColumns,
+    ExamTopicResult, ExamTopicResultInput, ExamTopicResultSet, ImportExamTopicReportRequest, StudentExamTopicResult,
+    StudyAssignment, Subject, SubjectWithTopics, Topic, TopicWeaknessAnalysis, UnmatchedExamRow, User, WeakTopic,
+    ASSIGNMENT_COMPLETED, EXAM_TYPE_AYT, EXAM_TYPE_LGS, EXAM_TYPE_TYT, EXAM_TYPE_YDT, EXAM_TYPE_YKS,
+};
+use crate::repositories::{
+    AcademicRepository, AiSuggestionRepository, ExamTopicRepository, StudentRepository, StudyRepository,
+};
+use crate::services::attendance_service::local_today;
+use crate::services::csv_import::{detect_delimiter, normalize_header, parse_date, resolve_column, split_row};
+use crate::services::curriculum_service::CurriculumService;
+use crate::services::exam_import_service::find_student;
+use crate::services::study_service::{covered_exam_types, StudyService};
+
+/// Success rate under which a topic counts as weak in an exam
+pub const WEAK_SUCCESS_RATE: f64 = 50.0;
+/// Success rate from which a topic is listed among the strengths of an exam
+pub const STRONG_SUCCESS_RATE: f64 = 80.0;
+/// Exams a topic must be weak in to count as a recurring weakness
+pub const RECURRING_WEAK_EXAMS: usize = 2;
+/// Latest exams with topic results taken into the weakness analysis
+pub const ANALYSIS_EXAM_WINDOW: usize = 10;
+
+pub const EXAM_TOPIC_SOURCE: &str = "EXAM_TOPIC_ANALYSIS";
+const SUGGESTION_EXPIRY_DAYS: i64 = 30;
+const DEFAULT_ASSIGNMENT_DAYS: i64 = 7;
+/// Topics named in a counseling suggestion
+const SUGGESTION_TOPICS: usize = 5;
+
+const SCHOOL_NUMBER_HEADERS: &[&str] = &["Öğrenci No", "Okul No", "Okul Numarası", "Numara", "No"];
+const SUBJECT_HEADERS: &[&str] = &["Ders", "Ders Adı", "Test"];
+const TOPIC_HEADERS: &[&str] = &["Konu", "Konu Adı", "Kazanım"];
+const QUESTION_COUNT_HEADERS: &[&str] = &["Soru", "Soru Sayısı", "Soru Adedi"];
+const CORRECT_HEADERS: &[&str] = &["Doğru", "D"];
+const WRONG_HEADERS: &[&str] = &["Yanlış", "Y"];
+const EMPTY_HEADERS: &[&str] = &["Boş", "B"];
+
+/// One student's line of a topic report
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicReportRow {
+    pub line: usize,
+    pub school_number: String,
+    pub subject: String,
+    pub topic: String,
+    pub question_count: Option<i32>,
+    pub correct: i32,
+    pub wrong: i32,
+    pub empty: Option<i32>,
+}
+
+pub struct ExamTopicService;
+
+impl ExamTopicService {
+    pub async fn get_topic_results(pool: &SqlitePool, exam_result_id: &str) -> AppResult<Vec<ExamTopicResult>> {
+        AcademicRepository::get_exam_result_by_id(pool, exam_result_id).await?;
+        ExamTopicRepository::get_by_exam_result(pool, exam_result_id).await
+    }
+
+    /// Replace the topic results of an exam result with topics entered by hand
+    pub async fn save_topic_results(
+        pool: &SqlitePool,
+        exam_result_id: &str,
+        inputs: Vec<ExamTopicResultInput>,
+    ) -> AppResult<Vec<ExamTopicResult>> {
+        let result = AcademicRepository::get_exam_result_by_id(pool, exam_result_id).await?;
+        let catalog = Self::load_catalog(pool, &result.examType).await?;
+
+        let (results, _) = build_topic_results(&result.studentId, &result.id, &inputs, &catalog)?;
+        let (strength, weakness) = topic_areas(&results);
+        ExamTopicRepository::replace_topic_results(pool, &[ExamTopicResultSet {
+            examResultId: result.id.clone(),
+            results,
+            strengthAreas: strength,
+            weaknessAreas: weakness,
+        }])
+        .await?;
+
+        ExamTopicRepository::get_by_exam_result(pool, exam_result_id).await
+    }
+
+    /// Import a publisher's topic report for an exam whose results are already
+    /// imported. Rows are matched to the institution's students by school number
+    /// and each student's topic results are replaced in one transaction;
+    /// rows of unknown students or students without a result of the exam are
+    /// returned in `unmatched`.
+    pub async fn import_topic_report(
+        pool: &SqlitePool,
+        institution_id: &str,
+        req: ImportExamTopicReportRequest,
+    ) -> AppResult<ExamTopicImportResult> {
+        let exam_name = req.examName.trim().to_string();
+        if exam_name.is_empty() {
+            return Err(AppError::ValidationError("Exam name is required".to_string()));
+        }
+        let exam_date = parse_date(&req.examDate)
+            .ok_or_else(|| AppError::ValidationError(format!("Invalid exam date: {}", req.examDate)))?
+            .format("%Y-%m-%d")
+            .to_string();
+
+        let (rows, unmatched) = parse_topic_report(&req.content, &req.columns.unwrap_or_default())?;
+        let mut result = ExamTopicImportResult {
+            totalRows: rows.len() + unmatched.len(),
+            examResults: 0,
+            topicResults: 0,
+            studentIds: Vec::new(),
+            unlinkedTopics: Vec::new(),
+            unmatched,
+        };
+
+        let mut students: Vec<(String, Vec<TopicReportRow>)> = Vec::new();
+        for row in rows {
+            match students.iter_mut().find(|(number, _)| *number == row.school_number) {
+                Some((_, student_rows)) => student_rows.push(row),
+                None => students.push((row.school_number.clone(), vec![row])),
+            }
+        }
+
+        let mut catalogs: HashMap<String, Vec<SubjectWithTopics>> = HashMap::new();
+        let mut sets = Vec::new();
+        for (school_number, rows) in students {
+            let unmatched = |reason: &str| UnmatchedExamRow {
+                line: rows[0].line,
+                schoolNumber: Some(school_number.clone()),
+                studentName: None,
+                reason: reason.to_string(),
+            };
+
+            let Some(student) = find_student(pool, institution_id, &school_number).await? else {
+                result.unmatched.push(unmatched("No student with this school number"));
+                continue;
+            };
+            let Some(exam_result) = AcademicRepository::find_exam_result(pool, &student.id, &exam_name, &exam_date).await?
+            else {
+                result.unmatched.push(unmatched("No result of this exam for the student"));
+                continue;
+            };
+
+            if !catalogs.contains_key(&exam_result.examType) {
+                let catalog = Self::load_catalog(pool, &exam_result.examType).await?;
+                catalogs.insert(exam_result.examType.clone(), catalog);
+            }
+            let inputs: Vec<ExamTopicResultInput> = rows
+                .iter()
+                .map(|row| ExamTopicResultInput {
+                    topicId: None,
+                    subjectName: row.subject.clone(),
+                    topicName: row.topic.clone(),
+                    questionCount: row.question_count,
+                    correct: row.correct,
+                    wrong: row.wrong,
+                    empty: row.empty,
+                })
+                .collect();
+
+            let (results, unlinked) =
+                match build_topic_results(&student.id, &exam_result.id, &inputs, &catalogs[&exam_result.examType]) {
+                    Ok(built) => built,
+                    Err(AppError::ValidationError(message)) => {
+                        result.unmatched.push(unmatched(&message));
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+            for name in unlinked {
+                if !result.unlinkedTopics.contains(&name) {
+                    result.unlinkedTopics.push(name);
+                }
+            }
+
+            let (strength, weakness) = topic_areas(&results);
+            sets.push(ExamTopicResultSet {
+                examResultId: exam_result.id,
+                results,
+                strengthAreas: strength,
+                weaknessAreas: weakness,
+            });
+            result.studentIds.push(student.id);
+        }
+
+        result.topicResults = ExamTopicRepository::replace_topic_results(pool, &sets).await?;
+        result.examResults = sets.len();
+
+        info!(
+            "Topic report import '{}': {} exam results, {} topic results, {} unmatched, {} unlinked topics",
+            exam_name,
+            result.examResults,
+            result.topicResults,
+            result.unmatched.len(),
+            result.unlinkedTopics.len()
+        );
+
+        Ok(result)
+    }
+
+    /// The topics a student keeps missing across their latest exams
+    pub async fn analyze_student(pool: &SqlitePool, student_id: &str) -> AppResult<TopicWeaknessAnalysis> {
+        let results = ExamTopicRepository::get_by_student(pool, student_id).await?;
+        let (exam_count, weak_topics) = weak_topics(&results);
+
+        Ok(TopicWeaknessAnalysis {
+            studentId: student_id.to_string(),
+            examCount: exam_count,
+            weakTopics: weak_topics,
+        })
+    }
+
+    /// Assign the student's recurring weak topics that have no open assignment,
+    /// due in a week unless a due date is given
+    pub async fn assign_weak_topics(
+        pool: &SqlitePool,
+        viewer: &User,
+        student_id: &str,
+        due_date: Option<&str>,
+    ) -> AppResult<Vec<StudyAssignment>> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+        let due_date = match due_date {
+            Some(due_date) => due_date.to_string(),
+            None => (local_today() + Duration::days(DEFAULT_ASSIGNMENT_DAYS)).format("%Y-%m-%d").to_string(),
+        };
+
+        let analysis = Self::analyze_student(pool, student_id).await?;
+        let open: Vec<String> = StudyRepository::get_assignments_by_student(pool, student_id, None)
+            .await?
+            .into_iter()
+            .filter(|detail| detail.assignment.status != ASSIGNMENT_COMPLETED)
+            .map(|detail| detail.assignment.topicId)
+            .collect();
+
+        let mut assignments = Vec::new();
+        for topic in analysis.weakTopics.iter().filter(|topic| topic.isRecurring) {
+            let Some(topic_id) = &topic.topicId else {
+                continue;
+            };
+            if open.contains(topic_id) {
+                continue;
+            }
+
+            let request = CreateStudyAssignmentRequest {
+                studentId: student_id.to_string(),
+                topicId: topic_id.clone(),
+                dueDate: due_date.clone(),
+                notes: Some(format!(
+                    "Denemelerde tekrar eden eksik konu: {} sınavın {}'inde zayıf, başarı %{:.0}",
+                    topic.examCount, topic.weakExamCount, topic.successRate
+                )),
+            };
+            assignments.push(StudyService::create_assignment(pool, viewer, request).await?);
+        }
+
+        if !assignments.is_empty() {
+            info!("Assigned {} weak topics to student {}", assignments.len(), student_id);
+        }
+        Ok(assignments)
+    }
+
+    /// Queue a counseling suggestion when the student has recurring weak
+    /// topics, once per latest exam with topic results
+    pub async fn refresh_student(pool: &SqlitePool, student_id: &str) -> AppResult<Option<AiSuggestion>> {
+        let results = ExamTopicRepository::get_by_student(pool, student_id).await?;
+        let Some(latest) = results.first() else {
+            return Ok(None);
+        };
+        let (exam_count, weak) = weak_topics(&results);
+        let recurring: Vec<&WeakTopic> = weak.iter().filter(|topic| topic.isRecurring).collect();
+        if recurring.is_empty()
+            || AiSuggestionRepository::exists_for_source(pool, EXAM_TOPIC_SOURCE, &latest.result.examResultId).await?
+        {
+            return Ok(None);
+        }
+
+        let listed: Vec<String> = recurring
+            .iter()
+            .take(SUGGESTION_TOPICS)
+            .map(|topic| format!("{} - {} (%{:.0})", topic.subjectName, topic.topicName, topic.successRate))
+            .collect();
+        let request = CreateAiSuggestionRequest {
+            studentId: student_id.to_string(),
+            suggestionType: "ACADEMIC_INSIGHT".to_string(),
+            source: EXAM_TOPIC_SOURCE.to_string(),
+            sourceId: Some(latest.result.examResultId.clone()),
+            priority: if recurring.len() >= 3 { "HIGH" } else { "MEDIUM" }.to_string(),
+            title: format!("Tekrar eden eksik konular: {} konu", recurring.len()),
+            description: format!(
+                "Son {} sınavda birden fazla kez zayıf kalınan konular: {}. Bu konular için çalışma ödevi verilmesi ve akademik destek görüşmesi planlanabilir.",
+                exam_count,
+                listed.join(", ")
+            ),
+            reasoning: Some(format!(
+                "En az {} sınavda başarı %{:.0} altında kaldı",
+                RECURRING_WEAK_EXAMS, WEAK_SUCCESS_RATE
+            )),
+            confidence: None,
+            proposedChanges: None,
+            currentValues: None,
+            aiModel: None,
+            aiVersion: None,
+            analysisData: Some(serde_json::to_string(&recurring)?),
+            expiresAt: (Utc::now() + Duration::days(SUGGESTION_EXPIRY_DAYS)).to_rfc3339(),
+        };
+        let suggestion = AiSuggestionRepository::create(pool, request).await?;
+
+        info!("Recurring weak topics flagged for student {}", student_id);
+        Ok(Some(suggestion))
+    }
+
+    /// Subjects and topics an exam type asks, archived ones included so older
+    /// reports still link; all of them for exam types without a curriculum
+    async fn load_catalog(pool: &SqlitePool, exam_type: &str) -> AppResult<Vec<SubjectWithTopics>> {
+        let curriculum_types = [EXAM_TYPE_LGS, EXAM_TYPE_YKS, EXAM_TYPE_TYT, EXAM_TYPE_AYT, EXAM_TYPE_YDT];
+        if !curriculum_types.contains(&exam_type) {
+            return CurriculumService::get_catalog(pool, None, true).await;
+        }
+
+        let mut catalog = Vec::new();
+        for covered in covered_exam_types(exam_type) {
+            catalog.extend(CurriculumService::get_catalog(pool, Some(covered), true).await?);
+        }
+        Ok(catalog)
+    }
+}
+
+/// Read a topic report: a header line, then one line per student and topic
+pub fn parse_topic_report(
+    content: &str,
+    columns: &ExamTopicReportColumns,
+) -> AppResult<(Vec<TopicReportRow>, Vec<UnmatchedExamRow>)> {
+    let lines: Vec<&str> = content.trim_start_matches('\u{feff}').lines().collect();
+    let Some(header_index) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return Err(AppError::ValidationError("The topic report is empty".to_string()));
+    };
+    let delimiter = detect_delimiter(lines[header_index]);
+    let header = split_row(lines[header_index], delimiter);
+
+    let column = |given: &Option<String>, defaults: &[&str], required: bool| -> AppResult<Option<usize>> {
+        let found = match given {
+            Some(given) => resolve_column(given, &header),
+            None => defaults.iter().find_map(|name| resolve_column(name, &header)),
+        };
+        match found {
+            None if required || given.is_some() => Err(AppError::ValidationError(format!(
+                "Column not found in the topic report: {}",
+                given.as_deref().unwrap_or(defaults[0])
+            ))),
+            found => Ok(found),
+        }
+    };
+    let number = column(&columns.schoolNumber, SCHOOL_NUMBER_HEADERS, true)?;
+    let subject = column(&columns.subject, SUBJECT_HEADERS, false)?;
+    let topic = column(&columns.topic, TOPIC_HEADERS, true)?;
+    let question_count = column(&columns.questionCount, QUESTION_COUNT_HEADERS, false)?;
+    let correct = column(&columns.correct, CORRECT_HEADERS, true)?;
+    let wrong = column(&columns.wrong, WRONG_HEADERS, true)?;
+    let empty = column(&columns.empty, EMPTY_HEADERS, false)?;
+
+    let mut rows = Vec::new();
+    let mut unmatched = Vec::new();
+    for (index, line) in lines.iter().enumerate().skip(header_index + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = split_row(line, delimiter);
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|index| cells.get(index))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let count = |column: Option<usize>| cell(column).map(|value| value.parse::<i32>());
+
+        let school_number = cell(number);
+        let topic_name = cell(topic);
+        let failed = |reason: &str| UnmatchedExamRow {
+            line: index + 1,
+            schoolNumber: school_number.clone(),
+            studentName: None,
+            reason: reason.to_string(),
+        };
+        let (Some(school_number), Some(topic_name)) = (school_number.clone(), topic_name) else {
+            unmatched.push(failed("Missing school number or topic"));
+            continue;
+        };
+
+        let counts = (count(question_count).transpose(), count(correct).transpose(), count(wrong).transpose(), count(empty).transpose());
+        let (Ok(question_count), Ok(correct), Ok(wrong), Ok(empty)) = counts else {
+            unmatched.push(failed("Invalid question counts"));
+            continue;
+        };
+
+        rows.push(TopicReportRow {
+            line: index + 1,
+            school_number,
+            subject: cell(subject).unwrap_or_default(),
+            topic: topic_name,
+            question_count,
+            correct: correct.unwrap_or(0),
+            wrong: wrong.unwrap_or(0),
+            empty,
+        });
+    }
+
+    Ok((rows, unmatched))
+}
+
+/// Turn topic lines into topic results of one exam result, linking them to
+/// the curriculum and adding up lines of the same topic. Returns the results
+/// and the names of topics that could not be linked.
+pub fn build_topic_results(
+    student_id: &str,
+    exam_result_id: &str,
+    inputs: &[ExamTopicResultInput],
+    catalog: &[SubjectWithTopics],
+) -> AppResult<(Vec<ExamTopicResult>, Vec<String>)> {
+    let mut results: Vec<ExamTopicResult> = Vec::new();
+    let mut unlinked = Vec::new();
+
+    for input in inputs {
+        let (question_count, correct, wrong, empty) = topic_counts(input)?;
+
+        let (subject, topic) = match &input.topicId {
+            Some(topic_id) => {
+                let found = catalog
+                    .iter()
+                    .find_map(|s| s.topics.iter().find(|t| &t.id == topic_id).map(|t| (&s.subject, t)))
+                    .ok_or_else(|| AppError::ValidationError(format!("Topic {} is not asked in this exam", topic_id)))?;
+                (Some(found.0), Some(found.1))
+            }
+            None if input.topicName.trim().is_empty() => {
+                return Err(AppError::ValidationError("Topic name is required".to_string()));
+            }
+            None => link_topic(&input.subjectName, &input.topicName, catalog),
+        };
+        if topic.is_none() {
+            let name = match input.subjectName.trim() {
+                "" => input.topicName.trim().to_string(),
+                subject => format!("{} - {}", subject, input.topicName.trim()),
+            };
+            if !unlinked.contains(&name) {
+                unlinked.push(name);
+            }
+        }
+
+        let subject_name = subject.map_or_else(|| input.subjectName.trim().to_string(), |s| s.name.clone());
+        let topic_name = topic.map_or_else(|| input.topicName.trim().to_string(), |t| t.name.clone());
+        let existing = results.iter_mut().find(|r| match topic {
+            Some(topic) => r.topicId.as_deref() == Some(topic.id.as_str()),
+            None => {
+                r.topicId.is_none()
+                    && normalize_header(&r.subjectName) == normalize_header(&subject_name)
+                    && normalize_header(&r.topicName) == normalize_header(&topic_name)
+            }
+        });
+
+        match existing {
+            Some(result) => {
+                result.questionCount += question_count;
+                result.correct += correct;
+                result.wrong += wrong;
+                result.empty += empty;
+            }
+            None => results.push(ExamTopicResult {
+                id: String::new(),
+                examResultId: exam_result_id.to_string(),
+                studentId: student_id.to_string(),
+                subjectId: subject.map(|s| s.id.clone()),
+                topicId: topic.map(|t| t.id.clone()),
+                subjectName: subject_name,
+                topicName: topic_name,
+                questionCount: question_count,
+                correct,
+                wrong,
+                empty,
+                successRate: 0.0,
+                created_at: String::new(),
+            }),
+        }
+    }
+
+    for result in &mut results {
+        result.successRate = success_rate(result.correct, result.questionCount);
+    }
+    Ok((results, unlinked))
+}
+
+/// Find the curriculum subject and topic a report names. The topic is looked
+/// up in the named subject first, then anywhere when only one topic has the name.
+pub fn link_topic<'a>(
+    subject_name: &str,
+    topic_name: &str,
+    catalog: &'a [SubjectWithTopics],
+) -> (Option<&'a Subject>, Option<&'a Topic>) {
+    let subject_key = normalize_header(subject_name);
+    let topic_key = normalize_header(topic_name);
+    let named = |topic: &&Topic| normalize_header(&topic.name) == topic_key;
+
+    let subjects: Vec<&SubjectWithTopics> = catalog
+        .iter()
+        .filter(|s| {
+            !subject_key.is_empty()
+                && (normalize_header(&s.subject.name) == subject_key
+                    || s.subject.code.as_deref().is_some_and(|code| normalize_header(code) == subject_key))
+        })
+        .collect();
+    if let Some((subject, topic)) = subjects.iter().find_map(|s| s.topics.iter().find(named).map(|t| (&s.subject, t))) {
+        return (Some(subject), Some(topic));
+    }
+
+    let matches: Vec<(&Subject, &Topic)> = catalog
+        .iter()
+        .flat_map(|s| s.topics.iter().filter(named).map(move |t| (&s.subject, t)))
+        .collect();
+    match matches.as_slice() {
+        [(subject, topic)] => (Some(subject), Some(topic)),
+        _ => (subjects.first().map(|s| &s.subject), None),
+    }
+}
+
+/// Question, correct, wrong and empty counts of a topic line
+pub fn topic_counts(input: &ExamTopicResultInput) -> AppResult<(i32, i32, i32, i32)> {
+    let invalid = || {
+        Err(AppError::ValidationError(format!(
+            "Invalid question counts for topic {}",
+            input.topicName.trim()
+        )))
+    };
+    if input.correct < 0 || input.wrong < 0 || input.empty.is_some_and(|empty| empty < 0) {
+        return invalid();
+    }
+
+    let answered = input.correct + input.wrong;
+    let (question_count, empty) = match (input.questionCount, input.empty) {
+        (Some(count), Some(empty)) => (count, empty),
+        (Some(count), None) => (count, count - answered),
+        (None, empty) => (answered + empty.unwrap_or(0), empty.unwrap_or(0)),
+    };
+    if question_count < 1 || empty < 0 || answered + empty > question_count {
+        return invalid();
+    }
+    Ok((question_count, input.correct, input.wrong, empty))
+}
+
+/// Strength and weakness summaries of an exam's topic results, for the free
+/// text `strengthAreas` and `weaknessAreas` of the result
+pub fn topic_areas(results: &[ExamTopicResult]) -> (Option<String>, Option<String>) {
+    let list = |mut topics: Vec<&ExamTopicResult>, best_first: bool| {
+        topics.sort_by(|a, b| {
+            let order = a.successRate.total_cmp(&b.successRate);
+            if best_first { order.reverse() } else { order }.then_with(|| a.topicName.cmp(&b.topicName))
+        });
+        let names: Vec<String> = topics
+            .iter()
+            .map(|topic| format!("{} (%{:.0})", topic.topicName, topic.successRate))
+            .collect();
+        (!names.is_empty()).then(|| names.join(", "))
+    };
+
+    (
+        list(results.iter().filter(|r| r.successRate >= STRONG_SUCCESS_RATE).collect(), true),
+        list(results.iter().filter(|r| r.successRate < WEAK_SUCCESS_RATE).collect(), false),
+    )
+}
+
+/// Weak topics of the latest exams (results ordered latest exam first), with
+/// the number of exams looked at. A topic is weak when its overall success
+/// rate is under the threshold or it was weak in several exams.
+pub fn weak_topics(results: &[StudentExamTopicResult]) -> (usize, Vec<WeakTopic>) {
+    let mut exams: Vec<&str> = Vec::new();
+    let mut topics: Vec<(String, WeakTopic)> = Vec::new();
+
+    for entry in results {
+        let result = &entry.result;
+        if !exams.contains(&result.examResultId.as_str()) {
+            if exams.len() == ANALYSIS_EXAM_WINDOW {
+                break;
+            }
+            exams.push(&result.examResultId);
+        }
+
+        let key = result.topicId.clone().unwrap_or_else(|| {
+            format!("{}|{}", normalize_header(&result.subjectName), normalize_header(&result.topicName))
+        });
+        let weak_in_exam = usize::from(result.successRate < WEAK_SUCCESS_RATE);
+        match topics.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, topic)) => {
+                topic.examCount += 1;
+                topic.weakExamCount += weak_in_exam;
+                topic.questionCount += result.questionCount;
+                topic.correct += result.correct;
+                topic.wrong += result.wrong;
+                topic.empty += result.empty;
+            }
+            None => topics.push((key, WeakTopic {
+                topicId: result.topicId.clone(),
+                subjectId: result.subjectId.clone(),
+                subjectName: result.subjectName.clone(),
+                topicName: result.topicName.clone(),
+                examCount: 1,
+                weakExamCount: weak_in_exam,
+                questionCount: result.questionCount,
+                correct: result.correct,
+                wrong: result.wrong,
+                empty: result.empty,
+                successRate: 0.0,
+                latestSuccessRate: result.successRate,
+                lastExamDate: entry.examDate.clone(),
+                isRecurring: false,
+            })),
+        }
+    }
+
+    let mut weak: Vec<WeakTopic> = topics
+        .into_iter()
+        .map(|(_, mut topic)| {
+            topic.successRate = success_rate(topic.correct, topic.questionCount);
+            topic.isRecurring = topic.weakExamCount >= RECURRING_WEAK_EXAMS;
+            topic
+        })
+        .filter(|topic| topic.isRecurring || topic.successRate < WEAK_SUCCESS_RATE)
+        .collect();
+    weak.sort_by(|a, b| {
+        b.isRecurring
+            .cmp(&a.isRecurring)
+            .then_with(|| a.successRate.total_cmp(&b.successRate))
+            .then_with(|| b.questionCount.cmp(&a.questionCount))
+            .then_with(|| a.topicName.cmp(&b.topicName))
+    });
+
+    (exams.len(), weak)
+}
+
+fn success_rate(correct: i32, question_count: i32) -> f64 {
+    if question_count <= 0 {
+        return 0.0;
+    }
+    (correct as f64 / question_count as f64 * 1000.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subject(id: &str, name: &str, code: &str, topics: &[(&str, &str)]) -> SubjectWithTopics {
+        let subject: Subject = serde_json::from_value(serde_json::json!({
+            "id": id, "name": name, "code": code, "source": "MEB", "sortOrder": 0, "isArchived": false, "created_at": ""
+        }))
+        .unwrap();
+        let topics = topics
+            .iter()
+            .map(|(topic_id, topic_name)| {
+                serde_json::from_value(serde_json::json!({
+                    "id": topic_id, "subjectId": id, "name": topic_name, "estimatedHours": 4, "source": "MEB",
+                    "sortOrder": 0, "isArchived": false, "created_at": ""
+                }))
+                .unwrap()
+            })
+            .collect();
+        SubjectWithTopics { subject, topics }
+    }
+
+    fn input(subject: &str, topic: &str, question_count: Option<i32>, correct: i32, wrong: i32) -> ExamTopicResultInput {
+        ExamTopicResultInput {
+            topicId: None,
+            subjectName: subject.to_string(),
+            topicName: topic.to_string(),
+            questionCount: question_count,
+            correct,
+            wrong,
+            empty: None,
+        }
+    }
+
+    fn entry(exam: &str, date: &str, topic: &str, question_count: i32, correct: i32) -> StudentExamTopicResult {
+        StudentExamTopicResult {
+            result: ExamTopicResult {
+                id: String::new(),
+                examResultId: exam.to_string(),
+                studentId: "s".to_string(),
+                subjectId: None,
+                topicId: Some(topic.to_string()),
+                subjectName: "Matematik".to_string(),
+                topicName: topic.to_string(),
+                questionCount: question_count,
+                correct,
+                wrong: question_count - correct,
+                empty: 0,
+                successRate: success_rate(correct, question_count),
+                created_at: String::new(),
+            },
+            examName: exam.to_string(),
+            examDate: date.to_string(),
+            examType: "TYT".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_link_topic_by_subject_and_unique_name() {
+        let catalog = vec![
+            subject("tm", "Matematik", "TYT-MAT", &[("t1", "Problemler"), ("t2", "Fonksiyonlar")]),
+            subject("am", "Matematik", "AYT-MAT", &[("a1", "Fonksiyonlar"), ("a2", "Türev")]),
+            subject("tf", "Fizik", "TYT-FIZ", &[("f1", "Optik")]),
+        ];
+
+        assert_eq!(link_topic("MATEMATİK", "problemler", &catalog).1.map(|t| t.id.as_str()), Some("t1"));
+        assert_eq!(link_topic("AYT-MAT", "Fonksiyonlar", &catalog).1.map(|t| t.id.as_str()), Some("a1"));
+        // Unique names link without a subject; shared ones do not
+        assert_eq!(link_topic("", "Türev", &catalog).1.map(|t| t.id.as_str()), Some("a2"));
+        assert!(link_topic("", "Fonksiyonlar", &catalog).1.is_none());
+        let (subject, topic) = link_topic("Fizik", "Dalgalar", &catalog);
+        assert_eq!((subject.map(|s| s.id.as_str()), topic.is_none()), (Some("tf"), true));
+    }
+
+    #[test]
+    fn test_build_topic_results_merges_and_checks_counts() {
+        let catalog = vec![subject("tm", "Matematik", "TYT-MAT", &[("t1", "Problemler")])];
+        let inputs = vec![
+            input("Matematik", "Problemler", Some(6), 2, 3),
+            input("Matematik", "PROBLEMLER", None, 2, 0),
+            input("Matematik", "Olasılık", Some(2), 2, 0),
+        ];
+
+        let (results, unlinked) = build_topic_results("s", "e", &inputs, &catalog).unwrap();
+        assert_eq!(results.len(), 2);
+        let problems = &results[0];
+        assert_eq!(problems.topicId.as_deref(), Some("t1"));
+        assert_eq!((problems.questionCount, problems.correct, problems.wrong, problems.empty), (8, 4, 3, 1));
+        assert_eq!(problems.successRate, 50.0);
+        assert_eq!(unlinked, vec!["Matematik - Olasılık"]);
+
+        assert!(build_topic_results("s", "e", &[input("Matematik", "Problemler", Some(3), 2, 2)], &catalog).is_err());
+        assert!(build_topic_results("s", "e", &[input("Matematik", " ", Some(3), 1, 1)], &catalog).is_err());
+        assert!(build_topic_results("s", "e", &[input("Matematik", "Problemler", Some(3), -1, 1)], &catalog).is_err());
+
+        let (strength, weakness) = topic_areas(&results);
+        assert_eq!(strength.as_deref(), Some("Olasılık (%100)"));
+        assert_eq!(weakness, None);
+    }
+
+    #[test]
+    fn test_parse_topic_report() {
+        let content = "\u{feff}Öğrenci No;Ad Soyad;Ders;Konu;Soru;D;Y;B\n\
+                       0012;Ali Veli;Matematik;Problemler;6;2;3;1\n\
+                       0012;Ali Veli;Türkçe;Paragraf;;5;1;0\n\
+                       ;Ayşe;Matematik;Problemler;6;2;3;1\n\
+                       0013;Ayşe;Matematik;Problemler;6;iki;3;1\n";
+
+        let (rows, unmatched) = parse_topic_report(content, &ExamTopicReportColumns::default()).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], TopicReportRow {
+            line: 2,
+            school_number: "0012".to_string(),
+            subject: "Matematik".to_string(),
+            topic: "Problemler".to_string(),
+            question_count: Some(6),
+            correct: 2,
+            wrong: 3,
+            empty: Some(1),
+        });
+        assert_eq!((rows[1].question_count, rows[1].empty), (None, Some(0)));
+        assert_eq!(unmatched.iter().map(|u| u.line).collect::<Vec<_>>(), vec![4, 5]);
+
+        let columns = ExamTopicReportColumns { topic: Some("Kazanım Adı".to_string()), ..Default::default() };
+        assert!(parse_topic_report(content, &columns).is_err());
+        assert!(parse_topic_report("No;Ders;Konu\n1;Matematik;Problemler", &ExamTopicReportColumns::default()).is_err());
+    }
+
+    #[test]
+    fn test_weak_topics_across_exams() {
+        let results = vec![
+            entry("e3", "2024-03-01", "Problemler", 10, 3),
+            entry("e3", "2024-03-01", "Olasılık", 4, 1),
+            entry("e3", "2024-03-01", "Kümeler", 4, 4),
+            entry("e2", "2024-02-01", "Problemler", 10, 4),
+            entry("e2", "2024-02-01", "Olasılık", 4, 2),
+            entry("e1", "2024-01-01", "Problemler", 10, 9),
+        ];
+
+        let (exam_count, weak) = weak_topics(&results);
+        assert_eq!(exam_count, 3);
+        assert_eq!(weak.len(), 2);
+        assert_eq!(weak[0].topicName, "Problemler");
+        assert!(weak[0].isRecurring);
+        assert_eq!((weak[0].examCount, weak[0].weakExamCount, weak[0].successRate), (3, 2, 53.3));
+        assert_eq!((weak[0].latestSuccessRate, weak[0].lastExamDate.as_str()), (30.0, "2024-03-01"));
+        assert!(!weak[1].isRecurring);
+        assert_eq!((weak[1].topicName.as_str(), weak[1].successRate), ("Olasılık", 37.5));
+
+        let many: Vec<_> = (0..12).map(|i| entry(&format!("e{}", i), "2024-01-01", "Problemler", 10, 1)).collect();
+        let (exam_count, weak) = weak_topics(&many);
+        assert_eq!((exam_count, weak[0].examCount), (ANALYSIS_EXAM_WINDOW, ANALYSIS_EXAM_WINDOW));
+    }
+}
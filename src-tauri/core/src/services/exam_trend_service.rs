@@ -0,0 +1,370 @@
+use chrono::{Duration, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::AppResult;
+use crate::models::{
+    AcademicGoal, CreateAiSuggestionRequest, ExamGoalComparison, ExamNetChange, ExamPreviousComparison, ExamResult,
+    ExamSubjectTrend, ExamTrendPoint, ExamTypeTrend, EXAM_SUBJECT_FOREIGN_LANGUAGE, EXAM_SUBJECT_MATH,
+    EXAM_SUBJECT_SCIENCE, EXAM_SUBJECT_SOCIAL, EXAM_SUBJECT_TURKISH, EXAM_TREND_TOTAL, TREND_DECLINING,
+    GOAL_CANCELLED, TREND_IMPROVING, TREND_STABLE,
+};
+use crate::repositories::{AcademicRepository, AiSuggestionRepository};
+use crate::services::academic_goal_service::{counts_toward, goal_value};
+
+/// Exams in a moving average, and before the latest exam when looking for a sharp decline
+pub const TREND_WINDOW: usize = 3;
+/// Net change per exam under which a trend counts as stable
+pub const STABLE_SLOPE: f64 = 0.5;
+/// A sharp decline is a fall of at least this percent and this many nets
+/// below the average of the preceding exams
+pub const SHARP_DECLINE_PERCENT: f64 = 20.0;
+pub const SHARP_DECLINE_MIN_NET: f64 = 2.0;
+
+pub const EXAM_TREND_SOURCE: &str = "EXAM_TREND";
+const SUGGESTION_EXPIRY_DAYS: i64 = 30;
+
+const NET_SERIES: &[&str] = &[
+    EXAM_SUBJECT_TURKISH,
+    EXAM_SUBJECT_MATH,
+    EXAM_SUBJECT_SCIENCE,
+    EXAM_SUBJECT_SOCIAL,
+    EXAM_SUBJECT_FOREIGN_LANGUAGE,
+    EXAM_TREND_TOTAL,
+];
+
+pub struct ExamTrendService;
+
+impl ExamTrendService {
+    /// Net trends of a student's exams, per exam type
+    pub async fn get_trends(pool: &SqlitePool, student_id: &str, exam_type: Option<&str>) -> AppResult<Vec<ExamTypeTrend>> {
+        let results = AcademicRepository::get_exam_results_in_order(pool, student_id).await?;
+
+        Ok(build_trends(&results, exam_type))
+    }
+
+    /// Fill `comparedToPrevious` and `comparedToGoal` of the student's exam results
+    /// and queue an AI suggestion when the latest exam of a type fell sharply.
+    ///
+    /// Results are compared with the previous exam of the same type and with the
+    /// student's goal they count toward.
+    pub async fn refresh_student(pool: &SqlitePool, student_id: &str) -> AppResult<()> {
+        let results = AcademicRepository::get_exam_results_in_order(pool, student_id).await?;
+        let goals = AcademicRepository::get_all_academic_goals_by_student(pool, student_id).await?;
+
+        for (index, result) in results.iter().enumerate() {
+            let previous = results[..index].iter().rev().find(|r| r.examType == result.examType);
+            let to_previous = previous
+                .map(|p| serde_json::to_string(&compare_with_previous(result, p)))
+                .transpose()?;
+
+            let to_goal = goal_for(&goals, result).and_then(|goal| compare_with_goal(result, goal));
+            let goals_met = to_goal.as_ref().map_or(result.goalsMet, |c| c.met);
+            let to_goal = to_goal.map(|c| serde_json::to_string(&c)).transpose()?;
+
+            if to_previous != result.comparedToPrevious || to_goal != result.comparedToGoal || goals_met != result.goalsMet {
+                AcademicRepository::update_exam_comparisons(
+                    pool,
+                    &result.id,
+                    to_previous.as_deref(),
+                    to_goal.as_deref(),
+                    goals_met,
+                )
+                .await?;
+            }
+        }
+
+        for trend in build_trends(&results, None) {
+            Self::flag_sharp_decline(pool, student_id, &trend).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn flag_sharp_decline(pool: &SqlitePool, student_id: &str, trend: &ExamTypeTrend) -> AppResult<()> {
+        let Some(total) = trend.subjects.iter().find(|s| s.subject == EXAM_TREND_TOTAL) else {
+            return Ok(());
+        };
+        let (Some(percent), Some(latest)) = (total.sharpDeclinePercent, total.points.last()) else {
+            return Ok(());
+        };
+        if AiSuggestionRepository::exists_for_source(pool, EXAM_TREND_SOURCE, &latest.examId).await? {
+            return Ok(());
+        }
+
+        let subjects: Vec<String> = trend
+            .subjects
+            .iter()
+            .filter(|s| s.subject != EXAM_TREND_TOTAL && s.sharpDeclinePercent.is_some())
+            .map(|s| format!("{} (%{:.0})", subject_label(&s.subject), s.sharpDeclinePercent.unwrap_or_default()))
+            .collect();
+        let reasoning = if subjects.is_empty() {
+            None
+        } else {
+            Some(format!("Düşüş görülen dersler: {}", subjects.join(", ")))
+        };
+
+        let request = CreateAiSuggestionRequest {
+            studentId: student_id.to_string(),
+            suggestionType: "ACADEMIC_INSIGHT".to_string(),
+            source: EXAM_TREND_SOURCE.to_string(),
+            sourceId: Some(latest.examId.clone()),
+            priority: "HIGH".to_string(),
+            title: format!("{} netlerinde sert düşüş: {}", trend.examType, latest.examName),
+            description: format!(
+                "Toplam net {:.2}, önceki sınavların ortalamasının %{:.0} altında. Akademik destek görüşmesi planlanabilir.",
+                latest.net, percent
+            ),
+            reasoning,
+            confidence: None,
+            proposedChanges: None,
+            currentValues: None,
+            aiModel: None,
+            aiVersion: None,
+            analysisData: Some(serde_json::to_string(trend)?),
+            expiresAt: (Utc::now() + Duration::days(SUGGESTION_EXPIRY_DAYS)).to_rfc3339(),
+        };
+        AiSuggestionRepository::create(pool, request).await?;
+
+        info!("Sharp {} net decline flagged for student {}", trend.examType, student_id);
+        Ok(())
+    }
+}
+
+/// Group results (oldest first) by exam type and compute the trend of each net series
+pub fn build_trends(results: &[ExamResult], exam_type: Option<&str>) -> Vec<ExamTypeTrend> {
+    let mut types: Vec<&str> = Vec::new();
+    for result in results {
+        if !types.contains(&result.examType.as_str()) && exam_type.is_none_or(|t| t == result.examType) {
+            types.push(&result.examType);
+        }
+    }
+
+    types
+        .into_iter()
+        .map(|exam_type| {
+            let exams: Vec<&ExamResult> = results.iter().filter(|r| r.examType == exam_type).collect();
+            let subjects = NET_SERIES
+                .iter()
+                .filter_map(|subject| subject_trend(subject, &exams))
+                .collect();
+
+            ExamTypeTrend {
+                examType: exam_type.to_string(),
+                examCount: exams.len(),
+                subjects,
+            }
+        })
+        .collect()
+}
+
+fn subject_trend(subject: &str, exams: &[&ExamResult]) -> Option<ExamSubjectTrend> {
+    let exams: Vec<(&ExamResult, f64)> = exams
+        .iter()
+        .filter_map(|exam| series_net(exam, subject).map(|net| (*exam, net)))
+        .collect();
+    if exams.is_empty() {
+        return None;
+    }
+
+    let nets: Vec<f64> = exams.iter().map(|(_, net)| *net).collect();
+    let averages = moving_averages(&nets, TREND_WINDOW);
+    let slope = linear_slope(&nets);
+
+    Some(ExamSubjectTrend {
+        subject: subject.to_string(),
+        points: exams
+            .iter()
+            .zip(averages)
+            .map(|((exam, net), average)| ExamTrendPoint {
+                examId: exam.id.clone(),
+                examName: exam.examName.clone(),
+                examDate: exam.examDate.clone(),
+                net: *net,
+                movingAverage: average,
+            })
+            .collect(),
+        slope,
+        volatility: volatility(&nets),
+        direction: direction(slope).to_string(),
+        sharpDeclinePercent: sharp_decline_percent(&nets, TREND_WINDOW),
+    })
+}
+
+fn series_net(result: &ExamResult, subject: &str) -> Option<f64> {
+    match subject {
+        EXAM_SUBJECT_TURKISH => result.turkishNet,
+        EXAM_SUBJECT_MATH => result.mathNet,
+        EXAM_SUBJECT_SCIENCE => result.scienceNet,
+        EXAM_SUBJECT_SOCIAL => result.socialNet,
+        EXAM_SUBJECT_FOREIGN_LANGUAGE => result.foreignLanguageNet,
+        _ => result.totalNet,
+    }
+}
+
+fn subject_label(subject: &str) -> &'static str {
+    match subject {
+        EXAM_SUBJECT_TURKISH => "Türkçe",
+        EXAM_SUBJECT_MATH => "Matematik",
+        EXAM_SUBJECT_SCIENCE => "Fen",
+        EXAM_SUBJECT_SOCIAL => "Sosyal",
+        EXAM_SUBJECT_FOREIGN_LANGUAGE => "Yabancı dil",
+        _ => "Toplam",
+    }
+}
+
+/// Average of each value and up to `window - 1` values before it
+pub fn moving_averages(values: &[f64], window: usize) -> Vec<f64> {
+    (0..values.len())
+        .map(|i| {
+            let slice = &values[(i + 1).saturating_sub(window.max(1))..=i];
+            round2(slice.iter().sum::<f64>() / slice.len() as f64)
+        })
+        .collect()
+}
+
+/// Least-squares slope of the values against their position
+pub fn linear_slope(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (numerator, denominator) = values.iter().enumerate().fold((0.0, 0.0), |(num, den), (i, y)| {
+        let dx = i as f64 - mean_x;
+        (num + dx * (y - mean_y), den + dx * dx)
+    });
+
+    Some(round2(numerator / denominator))
+}
+
+/// Population standard deviation of the values
+pub fn volatility(values: &[f64]) -> Option<f64> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+    Some(round2(variance.sqrt()))
+}
+
+pub fn direction(slope: Option<f64>) -> &'static str {
+    match slope {
+        Some(s) if s >= STABLE_SLOPE => TREND_IMPROVING,
+        Some(s) if s <= -STABLE_SLOPE => TREND_DECLINING,
+        _ => TREND_STABLE,
+    }
+}
+
+/// Percent the last value fell below the average of up to `window` values
+/// before it, when the fall is sharp
+pub fn sharp_decline_percent(values: &[f64], window: usize) -> Option<f64> {
+    let (latest, before) = values.split_last()?;
+    let before = &before[before.len().saturating_sub(window)..];
+    if before.is_empty() {
+        return None;
+    }
+
+    let baseline = before.iter().sum::<f64>() / before.len() as f64;
+    let fall = baseline - latest;
+    if baseline <= 0.0 || fall < SHARP_DECLINE_MIN_NET {
+        return None;
+    }
+
+    let percent = fall / baseline * 100.0;
+    (percent >= SHARP_DECLINE_PERCENT).then(|| (percent * 10.0).round() / 10.0)
+}
+
+fn compare_with_previous(result: &ExamResult, previous: &ExamResult) -> ExamPreviousComparison {
+    let change = |current: Option<f64>, before: Option<f64>| current.zip(before).map(|(c, b)| round2(c - b));
+
+    ExamPreviousComparison {
+        previousExamId: previous.id.clone(),
+        previousExamName: previous.examName.clone(),
+        previousExamDate: previous.examDate.clone(),
+        totalNetChange: change(result.totalNet, previous.totalNet),
+        totalScoreChange: change(result.totalScore, previous.totalScore),
+        subjectChanges: NET_SERIES
+            .iter()
+            .filter(|subject| **subject != EXAM_TREND_TOTAL)
+            .filter_map(|subject| {
+                change(series_net(result, subject), series_net(previous, subject)).map(|change| ExamNetChange {
+                    subject: subject.to_string(),
+                    change,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// The goal with a target the result counts toward, preferring one for the
+/// result's exam type over one without a type; cancelled goals are skipped
+fn goal_for<'a>(goals: &'a [AcademicGoal], result: &ExamResult) -> Option<&'a AcademicGoal> {
+    let with_target = || {
+        goals
+            .iter()
+            .filter(|g| g.targetScore.is_some() && g.status != GOAL_CANCELLED && counts_toward(g, result))
+    };
+
+    with_target()
+        .find(|g| g.examType.is_some())
+        .or_else(|| with_target().find(|g| g.examType.is_none()))
+}
+
+fn compare_with_goal(result: &ExamResult, goal: &AcademicGoal) -> Option<ExamGoalComparison> {
+    let target = goal.targetScore?;
+    let achieved = goal_value(result)?;
+
+    Some(ExamGoalComparison {
+        goalId: goal.id.clone(),
+        goalTitle: goal.title.clone(),
+        targetScore: target,
+        achieved,
+        difference: round2(achieved - target),
+        met: achieved >= target,
+    })
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_averages() {
+        assert_eq!(moving_averages(&[10.0, 20.0, 30.0, 40.0], 3), vec![10.0, 15.0, 20.0, 30.0]);
+        assert!(moving_averages(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn test_slope_and_volatility() {
+        assert_eq!(linear_slope(&[50.0, 52.0, 54.0, 56.0]), Some(2.0));
+        assert_eq!(linear_slope(&[60.0, 55.0, 58.0, 49.0]), Some(-3.0));
+        assert_eq!(linear_slope(&[60.0]), None);
+
+        assert_eq!(volatility(&[40.0, 40.0, 40.0]), Some(0.0));
+        assert_eq!(volatility(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), Some(2.0));
+
+        assert_eq!(direction(Some(2.0)), TREND_IMPROVING);
+        assert_eq!(direction(Some(-0.2)), TREND_STABLE);
+        assert_eq!(direction(Some(-3.0)), TREND_DECLINING);
+        assert_eq!(direction(None), TREND_STABLE);
+    }
+
+    #[test]
+    fn test_sharp_decline() {
+        // Average of the last three before the latest is 60; 45 is 25% below
+        assert_eq!(sharp_decline_percent(&[30.0, 58.0, 60.0, 62.0, 45.0], 3), Some(25.0));
+        assert_eq!(sharp_decline_percent(&[60.0, 62.0, 58.0, 55.0], 3), None);
+        // 25% but less than two nets
+        assert_eq!(sharp_decline_percent(&[6.0, 4.5], 3), None);
+        assert_eq!(sharp_decline_percent(&[60.0], 3), None);
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{CreateFamilyRequest, Family, FamilyUnit, SiblingSuggestion, Student};
+use crate::repositories::{FamilyRepository, StudentRepository};
+
+const SHARED_GUARDIAN_WEIGHT: f64 = 0.7;
+const SAME_ADDRESS_WEIGHT: f64 = 0.5;
+const SAME_SURNAME_WEIGHT: f64 = 0.2;
+const MAX_SUGGESTIONS: usize = 10;
+
+pub struct FamilyService;
+
+impl FamilyService {
+    pub async fn create_family(pool: &SqlitePool, req: CreateFamilyRequest) -> AppResult<FamilyUnit> {
+        if req.name.trim().is_empty() {
+            return Err(AppError::ValidationError("Family name is required".to_string()));
+        }
+
+        for student_id in &req.studentIds {
+            StudentRepository::get_by_id(pool, student_id).await?;
+        }
+
+        let family = FamilyRepository::create_with_members(pool, req.name.trim(), req.notes, &req.studentIds).await?;
+
+        Self::get_family_unit(pool, &family.id).await
+    }
+
+    pub async fn get_family_unit(pool: &SqlitePool, family_id: &str) -> AppResult<FamilyUnit> {
+        let family = FamilyRepository::get_by_id(pool, family_id).await?;
+        let members = FamilyRepository::get_member_summaries(pool, family_id).await?;
+        let shared_guardians = FamilyRepository::get_shared_guardians(pool, family_id).await?;
+
+        Ok(FamilyUnit {
+            family,
+            members,
+            sharedGuardians: shared_guardians,
+        })
+    }
+
+    pub async fn get_student_family_unit(pool: &SqlitePool, student_id: &str) -> AppResult<Option<FamilyUnit>> {
+        match FamilyRepository::get_by_student(pool, student_id).await? {
+            Some(family) => Ok(Some(Self::get_family_unit(pool, &family.id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Add a student to a family; a family left empty by the move is removed
+    pub async fn add_member(pool: &SqlitePool, family_id: &str, student_id: &str) -> AppResult<()> {
+        let previous = FamilyRepository::get_by_student(pool, student_id).await?;
+        FamilyRepository::add_member(pool, family_id, student_id).await?;
+
+        if let Some(previous) = previous.filter(|f| f.id != family_id) {
+            Self::remove_if_empty(pool, &previous.id).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn remove_member(pool: &SqlitePool, family_id: &str, student_id: &str) -> AppResult<()> {
+        FamilyRepository::remove_member(pool, family_id, student_id).await?;
+        Self::remove_if_empty(pool, family_id).await
+    }
+
+    /// Record two students as siblings, merging their families if both already have one
+    pub async fn link_siblings(pool: &SqlitePool, student_id: &str, sibling_id: &str) -> AppResult<FamilyUnit> {
+        if student_id == sibling_id {
+            return Err(AppError::ValidationError("A student cannot be their own sibling".to_string()));
+        }
+
+        let student = StudentRepository::get_by_id(pool, student_id).await?;
+        StudentRepository::get_by_id(pool, sibling_id).await?;
+
+        let family = FamilyRepository::get_by_student(pool, student_id).await?;
+        let sibling_family = FamilyRepository::get_by_student(pool, sibling_id).await?;
+
+        let mut tx = pool.begin().await?;
+        let family: Family = match (family, sibling_family) {
+            (Some(family), Some(other)) => {
+                if family.id != other.id {
+                    FamilyRepository::move_members(&mut *tx, &other.id, &family.id).await?;
+                    FamilyRepository::delete(&mut *tx, &other.id).await?;
+                }
+                family
+            }
+            (Some(family), None) => {
+                FamilyRepository::add_member(&mut *tx, &family.id, sibling_id).await?;
+                family
+            }
+            (None, Some(other)) => {
+                FamilyRepository::add_member(&mut *tx, &other.id, student_id).await?;
+                other
+            }
+            (None, None) => {
+                let family = FamilyRepository::create(&mut *tx, &format!("{} Ailesi", student.surname), None).await?;
+                FamilyRepository::add_member(&mut *tx, &family.id, student_id).await?;
+                FamilyRepository::add_member(&mut *tx, &family.id, sibling_id).await?;
+                family
+            }
+        };
+        tx.commit().await?;
+
+        Self::get_family_unit(pool, &family.id).await
+    }
+
+    /// Suggest likely siblings from shared guardians or the same address; the
+    /// same surname only raises the score of a student found by another signal.
+    ///
+    /// Students already in the same family and previously dismissed pairs are
+    /// left out. Scores add up per matching signal and are capped at 1.0, and
+    /// only the best suggestions are returned.
+    pub async fn suggest_siblings(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<SiblingSuggestion>> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+
+        let mut excluded = FamilyRepository::get_dismissed_for(pool, student_id).await?;
+        if let Some(family) = FamilyRepository::get_by_student(pool, student_id).await? {
+            let members = FamilyRepository::get_members(pool, &family.id).await?;
+            excluded.extend(members.into_iter().map(|m| m.id));
+        }
+
+        let signals = [
+            (FamilyRepository::get_students_sharing_guardian(pool, student_id).await?, SHARED_GUARDIAN_WEIGHT, "Ortak veli"),
+            (FamilyRepository::get_students_at_address(pool, student_id).await?, SAME_ADDRESS_WEIGHT, "Aynı adres"),
+        ];
+
+        let mut candidates: HashMap<String, SiblingSuggestion> = HashMap::new();
+        for (students, weight, reason) in signals {
+            for candidate in students {
+                if excluded.contains(&candidate.id) {
+                    continue;
+                }
+                let entry = candidates
+                    .entry(candidate.id.clone())
+                    .or_insert_with(|| SiblingSuggestion {
+                        student: candidate,
+                        score: 0.0,
+                        reasons: Vec::new(),
+                    });
+                entry.score = (entry.score + weight).min(1.0);
+                entry.reasons.push(reason.to_string());
+            }
+        }
+
+        for candidate in FamilyRepository::get_students_with_surname(pool, student_id).await? {
+            if let Some(entry) = candidates.get_mut(&candidate.id) {
+                entry.score = (entry.score + SAME_SURNAME_WEIGHT).min(1.0);
+                entry.reasons.push("Aynı soyadı".to_string());
+            }
+        }
+
+        let mut suggestions: Vec<SiblingSuggestion> = candidates.into_values().collect();
+        suggestions.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.student.name.cmp(&b.student.name))
+        });
+        suggestions.truncate(MAX_SUGGESTIONS);
+
+        Ok(suggestions)
+    }
+
+    pub async fn dismiss_suggestion(
+        pool: &SqlitePool,
+        student_id: &str,
+        other_student_id: &str,
+        dismissed_by: Option<String>,
+    ) -> AppResult<()> {
+        FamilyRepository::dismiss_suggestion(pool, student_id, other_student_id, dismissed_by).await
+    }
+
+    pub async fn get_siblings(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<Student>> {
+        let Some(family) = FamilyRepository::get_by_student(pool, student_id).await? else {
+            return Ok(Vec::new());
+        };
+
+        let members = FamilyRepository::get_members(pool, &family.id).await?;
+
+        Ok(members.into_iter().filter(|m| m.id != student_id).collect())
+    }
+
+    async fn remove_if_empty(pool: &SqlitePool, family_id: &str) -> AppResult<()> {
+        if FamilyRepository::count_members(pool, family_id).await? == 0 {
+            FamilyRepository::delete(pool, family_id).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    async fn setup() -> SqlitePool {
+        let pool = test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Okul 1'), ('i2', 'Okul 2');
+            INSERT INTO students (id, name, surname, address, institutionId, enrollmentDate) VALUES
+                ('s1', 'Ali', 'Yılmaz', 'Çiçek Sok. 5', 'i1', '2024-09-01'),
+                ('s2', 'Ayşe', 'Yılmaz', 'Çiçek Sok. 5', 'i1', '2024-09-01'),
+                ('s3', 'Can', 'Yılmaz', NULL, 'i1', '2024-09-01'),
+                ('s4', 'Deniz', 'Kaya', 'Çiçek Sok. 5', 'i1', '2024-09-01'),
+                ('s5', 'Ece', 'Yılmaz', 'Çiçek Sok. 5', 'i2', '2024-09-01');
+            INSERT INTO guardians (id, name) VALUES ('g1', 'Fatma Yılmaz'), ('g2', 'Mehmet Yılmaz');
+            INSERT INTO student_guardians (studentId, guardianId) VALUES ('s1', 'g1'), ('s2', 'g1'), ('s1', 'g2');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn request(student_ids: &[&str]) -> CreateFamilyRequest {
+        CreateFamilyRequest {
+            name: "Yılmaz Ailesi".to_string(),
+            notes: None,
+            studentIds: student_ids.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    async fn family_count(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM families").fetch_one(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_family_with_unknown_student_creates_nothing() {
+        let pool = setup().await;
+
+        assert!(FamilyService::create_family(&pool, request(&["s1", "missing"])).await.is_err());
+        assert_eq!(family_count(&pool).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_family_removes_emptied_family() {
+        let pool = setup().await;
+        let first = FamilyService::create_family(&pool, request(&["s1"])).await.unwrap();
+        let other = FamilyService::create_family(&pool, request(&["s4"])).await.unwrap();
+
+        let unit = FamilyService::create_family(&pool, request(&["s1", "s2"])).await.unwrap();
+
+        assert_eq!(unit.members.len(), 2);
+        assert!(FamilyRepository::get_by_id(&pool, &first.family.id).await.is_err());
+        assert!(FamilyRepository::get_by_id(&pool, &other.family.id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_shared_guardians_need_two_members() {
+        let pool = setup().await;
+
+        let unit = FamilyService::create_family(&pool, request(&["s1", "s2"])).await.unwrap();
+
+        let ids: Vec<&str> = unit.sharedGuardians.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(ids, vec!["g1"]);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_siblings() {
+        let pool = setup().await;
+
+        let suggestions = FamilyService::suggest_siblings(&pool, "s1").await.unwrap();
+        let scores: Vec<(&str, f64)> = suggestions.iter().map(|s| (s.student.id.as_str(), s.score)).collect();
+
+        // s3 only shares the surname and s5 is in another institution
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].0, "s2");
+        assert!((scores[0].1 - 1.0).abs() < f64::EPSILON);
+        assert_eq!(scores[1].0, "s4");
+        assert!((scores[1].1 - SAME_ADDRESS_WEIGHT).abs() < f64::EPSILON);
+        assert_eq!(suggestions[0].reasons, vec!["Ortak veli", "Aynı adres", "Aynı soyadı"]);
+    }
+
+    #[tokio::test]
+    async fn test_suggest_siblings_skips_family_and_dismissed() {
+        let pool = setup().await;
+        FamilyService::create_family(&pool, request(&["s1", "s2"])).await.unwrap();
+        FamilyService::dismiss_suggestion(&pool, "s1", "s4", None).await.unwrap();
+
+        assert!(FamilyService::suggest_siblings(&pool, "s1").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+use std::sync::LazyLock;
+use log::info;
+use regex::Regex;
+use serde::Serialize;
+use sqlx::{Sqlite, SqlitePool, Transaction};
+use crate::error::AppResult;
+use crate::models::{CreateGuardianRequest, Guardian, LinkGuardianRequest, Student};
+use crate::repositories::GuardianRepository;
+
+/// Guardian details recovered from a free-text `parentContact` value
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ParsedGuardian {
+    pub name: Option<String>,
+    pub relationship: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
+
+const RELATIONSHIP_KEYWORDS: &[(&str, &str)] = &[
+    ("anneanne", "Büyükanne"),
+    ("babaanne", "Büyükanne"),
+    ("nine", "Büyükanne"),
+    ("dede", "Büyükbaba"),
+    ("anne", "Anne"),
+    ("annesi", "Anne"),
+    ("baba", "Baba"),
+    ("babası", "Baba"),
+    ("vasi", "Vasi"),
+    ("vasisi", "Vasi"),
+    ("abla", "Kardeş"),
+    ("ağabey", "Kardeş"),
+    ("abi", "Kardeş"),
+    ("kardeş", "Kardeş"),
+    ("teyze", "Akraba"),
+    ("hala", "Akraba"),
+    ("dayı", "Akraba"),
+    ("amca", "Akraba"),
+    ("veli", "Veli"),
+    ("velisi", "Veli"),
+];
+
+static PHONE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+?90[\s.-]?)?0?[\s.-]?\(?[2-5]\d{2}\)?[\s.-]?\d{3}[\s.-]?\d{2}[\s.-]?\d{2}").unwrap()
+});
+static EMAIL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+
+pub struct GuardianService;
+
+impl GuardianService {
+    /// Parse a free-text parent contact such as
+    /// `"Anne: Ayşe Yılmaz 0555 123 45 67, Baba Mehmet 0532 765 43 21"`.
+    ///
+    /// Each comma, semicolon, slash or line separated part yields at most one
+    /// guardian; parts with no usable name, phone or email are dropped.
+    pub fn parse_parent_contact(raw: &str) -> Vec<ParsedGuardian> {
+        raw.split([',', ';', '/', '\n', '|'])
+            .filter_map(|part| {
+                let mut rest = part.to_string();
+
+                let email = EMAIL_REGEX.find(&rest).map(|m| m.as_str().to_lowercase());
+                rest = EMAIL_REGEX.replace_all(&rest, " ").to_string();
+
+                let phone = PHONE_REGEX
+                    .find(&rest)
+                    .and_then(|m| normalize_phone(m.as_str()));
+                rest = PHONE_REGEX.replace_all(&rest, " ").to_string();
+
+                let mut relationship = None;
+                let mut name_words = Vec::new();
+                for word in rest.split(|c: char| !c.is_alphabetic()) {
+                    if word.is_empty() {
+                        continue;
+                    }
+                    let lowered = turkish_lowercase(word);
+                    match RELATIONSHIP_KEYWORDS.iter().find(|(keyword, _)| *keyword == lowered) {
+                        Some((_, label)) => {
+                            relationship.get_or_insert_with(|| label.to_string());
+                        }
+                        None => name_words.push(word),
+                    }
+                }
+
+                let name = Some(name_words.join(" ")).filter(|n| n.chars().count() >= 2);
+
+                if name.is_none() && phone.is_none() && email.is_none() {
+                    return None;
+                }
+
+                Some(ParsedGuardian { name, relationship, phone, email })
+            })
+            .collect()
+    }
+
+    /// Create guardians for students that only have a free-text `parentContact`.
+    ///
+    /// Guardians are matched on phone, then email, so siblings whose contact
+    /// text mentions the same number end up sharing one guardian record.
+    /// Students that already have linked guardians are left untouched, and
+    /// everything is written in one transaction.
+    pub async fn migrate_parent_contacts(pool: &SqlitePool) -> AppResult<usize> {
+        let students = sqlx::query_as::<_, Student>(
+            r#"
+            SELECT * FROM students s
+            WHERE s.parentContact IS NOT NULL AND TRIM(s.parentContact) != ''
+              AND NOT EXISTS (SELECT 1 FROM student_guardians sg WHERE sg.studentId = s.id)
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut migrated = 0;
+        let mut tx = pool.begin().await?;
+
+        for student in students {
+            let parsed = Self::parse_parent_contact(student.parentContact.as_deref().unwrap_or_default());
+            if parsed.is_empty() {
+                continue;
+            }
+
+            for (index, contact) in parsed.into_iter().enumerate() {
+                let guardian = Self::find_or_create(&mut tx, &student, &contact).await?;
+                let link = LinkGuardianRequest {
+                    studentId: student.id.clone(),
+                    guardianId: guardian.id,
+                    relationship: contact.relationship.unwrap_or_else(|| "Veli".to_string()),
+                    isPrimary: Some(index == 0),
+                    hasCustody: None,
+                    livesWithStudent: None,
+                    contactRestricted: None,
+                    restrictionNotes: None,
+                };
+                GuardianRepository::upsert_link(&mut *tx, &link).await?;
+            }
+
+            migrated += 1;
+        }
+
+        tx.commit().await?;
+
+        if migrated > 0 {
+            info!("Created guardian records for {} students from parentContact", migrated);
+        }
+
+        Ok(migrated)
+    }
+
+    async fn find_or_create(
+        tx: &mut Transaction<'_, Sqlite>,
+        student: &Student,
+        contact: &ParsedGuardian,
+    ) -> AppResult<Guardian> {
+        if let Some(phone) = &contact.phone {
+            if let Some(existing) = GuardianRepository::find_by_phone(&mut **tx, phone).await? {
+                return Ok(existing);
+            }
+        }
+        if let Some(email) = &contact.email {
+            if let Some(existing) = GuardianRepository::find_by_email(&mut **tx, email).await? {
+                return Ok(existing);
+            }
+        }
+
+        let name = contact.name.clone().unwrap_or_else(|| {
+            format!(
+                "{} {} ({})",
+                student.name,
+                student.surname,
+                contact.relationship.as_deref().unwrap_or("Veli")
+            )
+        });
+
+        GuardianRepository::create(&mut **tx, CreateGuardianRequest {
+            name,
+            phone: contact.phone.clone(),
+            secondaryPhone: None,
+            email: contact.email.clone(),
+            address: student.address.clone(),
+            occupation: None,
+            preferredLanguage: None,
+            notes: student.parentContact.clone().map(|raw| format!("parentContact: {}", raw)),
+        })
+        .await
+    }
+}
+
+/// Normalize a Turkish phone number to the `0XXXXXXXXXX` form
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    let national = match digits.len() {
+        12 if digits.starts_with("90") => &digits[2..],
+        11 if digits.starts_with('0') => &digits[1..],
+        10 => &digits[..],
+        _ => return None,
+    };
+
+    Some(format!("0{}", national))
+}
+
+fn turkish_lowercase(word: &str) -> String {
+    word.replace('İ', "i").replace('I', "ı").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_contact() {
+        let parsed = GuardianService::parse_parent_contact("Anne: Ayşe Yılmaz 0555 123 45 67");
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name.as_deref(), Some("Ayşe Yılmaz"));
+        assert_eq!(parsed[0].relationship.as_deref(), Some("Anne"));
+        assert_eq!(parsed[0].phone.as_deref(), Some("05551234567"));
+    }
+
+    #[test]
+    fn test_parse_multiple_contacts() {
+        let parsed = GuardianService::parse_parent_contact(
+            "Mehmet Kaya (BABA) +90 532 765 43 21; Fatma Kaya annesi fatma@example.com"
+        );
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].relationship.as_deref(), Some("Baba"));
+        assert_eq!(parsed[0].phone.as_deref(), Some("05327654321"));
+        assert_eq!(parsed[1].name.as_deref(), Some("Fatma Kaya"));
+        assert_eq!(parsed[1].email.as_deref(), Some("fatma@example.com"));
+    }
+
+    #[test]
+    fn test_parse_phone_only() {
+        let parsed = GuardianService::parse_parent_contact("05551234567");
+        assert_eq!(parsed.len(), 1);
+        assert!(parsed[0].name.is_none());
+        assert_eq!(parsed[0].phone.as_deref(), Some("05551234567"));
+    }
+
+    #[test]
+    fn test_parse_empty_contact() {
+        assert!(GuardianService::parse_parent_contact("  -  ").is_empty());
+    }
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(normalize_phone("+90 (555) 123-45-67").as_deref(), Some("05551234567"));
+        assert_eq!(normalize_phone("5551234567").as_deref(), Some("05551234567"));
+        assert_eq!(normalize_phone("12345"), None);
+    }
+}
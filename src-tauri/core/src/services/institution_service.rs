@@ -0,0 +1,202 @@
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{CreateInstitutionRequest, Institution, InstitutionSummary, Student, UpdateInstitutionRequest, User};
+use crate::repositories::{InstitutionRepository, StudentRepository, UserRepository};
+
+const DEFAULT_INSTITUTION_NAME: &str = "Okul";
+
+pub struct InstitutionService;
+
+impl InstitutionService {
+    /// Institutions the user can switch to; district admins see all of them
+    pub async fn get_institutions(pool: &SqlitePool, viewer: &User) -> AppResult<Vec<Institution>> {
+        if viewer.isDistrictAdmin {
+            return InstitutionRepository::get_all(pool).await;
+        }
+        InstitutionRepository::get_for_user(pool, &viewer.id).await
+    }
+
+    pub async fn create_institution(
+        pool: &SqlitePool,
+        viewer: &User,
+        req: CreateInstitutionRequest,
+    ) -> AppResult<Institution> {
+        require_district_admin(viewer)?;
+
+        if req.name.trim().is_empty() {
+            return Err(AppError::ValidationError("Institution name is required".to_string()));
+        }
+        if InstitutionRepository::find_by_name(pool, &req.name).await?.is_some() {
+            return Err(AppError::ValidationError(format!("Institution already exists: {}", req.name.trim())));
+        }
+
+        let institution = InstitutionRepository::create(pool, &req).await?;
+        InstitutionRepository::add_member(pool, &institution.id, &viewer.id).await?;
+        Ok(institution)
+    }
+
+    pub async fn update_institution(
+        pool: &SqlitePool,
+        viewer: &User,
+        id: &str,
+        req: UpdateInstitutionRequest,
+    ) -> AppResult<Institution> {
+        require_district_admin(viewer)?;
+
+        if let Some(name) = &req.name {
+            if name.trim().is_empty() {
+                return Err(AppError::ValidationError("Institution name is required".to_string()));
+            }
+            if let Some(existing) = InstitutionRepository::find_by_name(pool, name).await? {
+                if existing.id != id {
+                    return Err(AppError::ValidationError(format!("Institution already exists: {}", name.trim())));
+                }
+            }
+        }
+
+        InstitutionRepository::update(pool, id, &req).await
+    }
+
+    pub async fn get_members(pool: &SqlitePool, viewer: &User, institution_id: &str) -> AppResult<Vec<User>> {
+        require_access(pool, viewer, institution_id).await?;
+        InstitutionRepository::get_members(pool, institution_id).await
+    }
+
+    /// Add a user to an institution; district admins or admins of that institution only
+    pub async fn add_member(pool: &SqlitePool, viewer: &User, institution_id: &str, user_id: &str) -> AppResult<()> {
+        require_institution_admin(pool, viewer, institution_id).await?;
+        UserRepository::get_by_id(pool, user_id).await?;
+        InstitutionRepository::add_member(pool, institution_id, user_id).await?;
+        Ok(())
+    }
+
+    pub async fn remove_member(pool: &SqlitePool, viewer: &User, institution_id: &str, user_id: &str) -> AppResult<()> {
+        require_institution_admin(pool, viewer, institution_id).await?;
+        InstitutionRepository::remove_member(pool, institution_id, user_id).await
+    }
+
+    /// Grant or revoke district admin rights; district admins only
+    pub async fn set_district_admin(pool: &SqlitePool, viewer: &User, user_id: &str, is_district_admin: bool) -> AppResult<()> {
+        require_district_admin(viewer)?;
+        InstitutionRepository::set_district_admin(pool, user_id, is_district_admin).await
+    }
+
+    /// Pick the institution a new session works in: the one named on the
+    /// user, else their first membership, else the first one for district admins.
+    pub async fn start_session(pool: &SqlitePool, token: &str, user: &User) -> AppResult<Option<Institution>> {
+        let mut institutions = Self::get_institutions(pool, user).await?;
+        let position = institutions
+            .iter()
+            .position(|i| i.name == user.institution.trim())
+            .unwrap_or(0);
+
+        let institution = (!institutions.is_empty()).then(|| institutions.swap_remove(position));
+        InstitutionRepository::set_session_institution(pool, token, institution.as_ref().map(|i| i.id.as_str())).await?;
+        Ok(institution)
+    }
+
+    /// Switch the session to another institution the user belongs to
+    pub async fn switch_institution(pool: &SqlitePool, token: &str, institution_id: &str) -> AppResult<User> {
+        let viewer = UserRepository::get_session_by_token(pool, token).await?;
+        require_access(pool, &viewer, institution_id).await?;
+
+        let institution = InstitutionRepository::get_by_id(pool, institution_id).await?;
+        if !institution.isActive {
+            return Err(AppError::ValidationError(format!("Institution {} is inactive", institution.name)));
+        }
+
+        InstitutionRepository::set_session_institution(pool, token, Some(institution_id)).await?;
+        UserRepository::get_session_by_token(pool, token).await
+    }
+
+    pub async fn get_summary(pool: &SqlitePool, viewer: &User) -> AppResult<InstitutionSummary> {
+        let institution_id = active_institution(viewer)?;
+        InstitutionRepository::get_summary(pool, &institution_id).await
+    }
+
+    /// Summaries of every active institution, for district admins only
+    pub async fn get_district_report(pool: &SqlitePool, viewer: &User) -> AppResult<Vec<InstitutionSummary>> {
+        require_district_admin(viewer)?;
+        InstitutionRepository::get_all_summaries(pool).await
+    }
+
+    /// Create institutions from the free-text `users.institution` values,
+    /// link users to them and move records from before tenancy into the
+    /// oldest institution.
+    ///
+    /// Returns the number of records moved.
+    pub async fn migrate_institutions(pool: &SqlitePool) -> AppResult<u64> {
+        for name in InstitutionRepository::get_unregistered_names(pool).await? {
+            let req = CreateInstitutionRequest { name, code: None, district: None };
+            InstitutionRepository::create(pool, &req).await?;
+        }
+        InstitutionRepository::link_users_by_name(pool).await?;
+
+        if InstitutionRepository::count_unscoped(pool).await? == 0 {
+            return Ok(0);
+        }
+
+        let institution = match InstitutionRepository::get_default(pool).await? {
+            Some(institution) => institution,
+            None => {
+                let req = CreateInstitutionRequest {
+                    name: DEFAULT_INSTITUTION_NAME.to_string(),
+                    code: None,
+                    district: None,
+                };
+                InstitutionRepository::create(pool, &req).await?
+            }
+        };
+
+        InstitutionRepository::assign_unscoped(pool, &institution.id).await
+    }
+}
+
+/// The institution the viewer's session works in
+pub fn active_institution(viewer: &User) -> AppResult<String> {
+    viewer
+        .activeInstitutionId
+        .clone()
+        .ok_or_else(|| AppError::ValidationError("No institution selected for this session".to_string()))
+}
+
+/// Whether the user may work in the institution
+pub async fn can_access(pool: &SqlitePool, viewer: &User, institution_id: &str) -> AppResult<bool> {
+    if viewer.isDistrictAdmin {
+        return Ok(true);
+    }
+    InstitutionRepository::is_member(pool, institution_id, &viewer.id).await
+}
+
+/// The student, if the viewer may work in their institution
+pub async fn accessible_student(pool: &SqlitePool, viewer: &User, student_id: &str) -> AppResult<Student> {
+    let student = StudentRepository::get_by_id(pool, student_id).await?;
+    if let Some(institution_id) = &student.institutionId {
+        require_access(pool, viewer, institution_id).await?;
+    }
+    Ok(student)
+}
+
+async fn require_access(pool: &SqlitePool, viewer: &User, institution_id: &str) -> AppResult<()> {
+    if !can_access(pool, viewer, institution_id).await? {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+async fn require_institution_admin(pool: &SqlitePool, viewer: &User, institution_id: &str) -> AppResult<()> {
+    if viewer.isDistrictAdmin {
+        return Ok(());
+    }
+    if viewer.role != "admin" || !InstitutionRepository::is_member(pool, institution_id, &viewer.id).await? {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_district_admin(viewer: &User) -> AppResult<()> {
+    if !viewer.isDistrictAdmin {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use chrono::{Duration, NaiveDate, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AddInterventionNoteRequest, ChangeInterventionStatusRequest, CreateInterventionRequest, Intervention,
+    InterventionDetail, InterventionProgressNote, NotificationLog, UpdateInterventionRequest, User,
+    INTERVENTION_COMPLETED, INTERVENTION_IN_PROGRESS, INTERVENTION_PLANNED, INTERVENTION_PRIORITIES,
+};
+use crate::repositories::{
+    AcademicRepository, CounselingRepository, InterventionRepository, StudentRepository,
+    UserRepository,
+};
+use crate::services::attendance_service::local_today;
+use crate::services::notification_service::in_app_notification;
+
+pub const DEFAULT_REMINDER_DAYS: i64 = 3;
+
+pub struct InterventionService;
+
+impl InterventionService {
+    pub async fn create(pool: &SqlitePool, req: CreateInterventionRequest) -> AppResult<InterventionDetail> {
+        if req.title.trim().is_empty() {
+            return Err(AppError::ValidationError("Intervention title is required".to_string()));
+        }
+        validate_date(req.date.as_deref())?;
+        validate_schedule(req.startDate.as_deref(), req.targetDate.as_deref())?;
+        validate_priority(req.priority.as_deref())?;
+
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+        if let Some(owner_id) = &req.ownerId {
+            UserRepository::get_by_id(pool, owner_id).await?;
+        }
+
+        for session_id in req.sessionIds.iter().flatten() {
+            check_session(pool, &req.studentId, session_id).await?;
+        }
+        for incident_id in req.incidentIds.iter().flatten() {
+            check_incident(pool, &req.studentId, incident_id).await?;
+        }
+
+        let mut tx = pool.begin().await?;
+        let today = local_today().format("%Y-%m-%d").to_string();
+        let intervention = InterventionRepository::create(&mut *tx, &req, INTERVENTION_PLANNED, &today).await?;
+        for session_id in req.sessionIds.iter().flatten() {
+            InterventionRepository::link_session(&mut *tx, &intervention.id, session_id).await?;
+        }
+        for incident_id in req.incidentIds.iter().flatten() {
+            InterventionRepository::link_incident(&mut *tx, &intervention.id, incident_id).await?;
+        }
+        tx.commit().await?;
+
+        Self::get_detail(pool, &intervention.id).await
+    }
+
+    pub async fn get_detail(pool: &SqlitePool, id: &str) -> AppResult<InterventionDetail> {
+        let intervention = InterventionRepository::get_by_id(pool, id).await?;
+        let sessions = InterventionRepository::get_sessions(pool, id).await?;
+        let incidents = InterventionRepository::get_incidents(pool, id).await?;
+        let notes = InterventionRepository::get_notes(pool, id).await?;
+
+        Ok(InterventionDetail {
+            intervention,
+            sessions,
+            incidents,
+            notes,
+        })
+    }
+
+    pub async fn update(pool: &SqlitePool, id: &str, req: UpdateInterventionRequest) -> AppResult<Intervention> {
+        let current = InterventionRepository::get_by_id(pool, id).await?;
+
+        if req.title.as_deref().map(|t| t.trim().is_empty()).unwrap_or(false) {
+            return Err(AppError::ValidationError("Intervention title is required".to_string()));
+        }
+        validate_schedule(
+            req.startDate.as_deref().or(current.startDate.as_deref()),
+            req.targetDate.as_deref().or(current.targetDate.as_deref()),
+        )?;
+        validate_priority(req.priority.as_deref())?;
+        if let Some(owner_id) = &req.ownerId {
+            UserRepository::get_by_id(pool, owner_id).await?;
+        }
+
+        InterventionRepository::update(pool, id, req).await
+    }
+
+    /// Move an intervention along Planlandı → Devam → Tamamlandı.
+    ///
+    /// Every change is recorded as a progress note by the viewer. A completed
+    /// intervention can be reopened by moving it back to Devam.
+    pub async fn change_status(
+        pool: &SqlitePool,
+        viewer: &User,
+        id: &str,
+        req: ChangeInterventionStatusRequest,
+    ) -> AppResult<Intervention> {
+        let current = InterventionRepository::get_by_id(pool, id).await?;
+
+        if !can_transition(&current.status, &req.status) {
+            return Err(AppError::ValidationError(format!(
+                "Invalid intervention status change: {} → {}",
+                current.status, req.status
+            )));
+        }
+
+        let completed_at = (req.status == INTERVENTION_COMPLETED).then(|| Utc::now().to_rfc3339());
+        let note = req
+            .note
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| format!("Durum değişti: {} → {}", current.status, req.status));
+
+        let mut tx = pool.begin().await?;
+        let today = local_today().format("%Y-%m-%d").to_string();
+        let updated =
+            InterventionRepository::set_status(&mut *tx, id, &req.status, completed_at, req.outcome, &today).await?;
+        InterventionRepository::add_note(
+            &mut *tx,
+            id,
+            Some(&viewer.id),
+            &note,
+            None,
+            Some((&current.status, &req.status)),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    /// Add a progress note written by the viewer
+    pub async fn add_note(
+        pool: &SqlitePool,
+        viewer: &User,
+        id: &str,
+        req: AddInterventionNoteRequest,
+    ) -> AppResult<InterventionProgressNote> {
+        if req.note.trim().is_empty() {
+            return Err(AppError::ValidationError("Progress note is required".to_string()));
+        }
+        if let Some(progress) = req.progress {
+            if !(0..=100).contains(&progress) {
+                return Err(AppError::ValidationError("Progress must be between 0 and 100".to_string()));
+            }
+        }
+
+        InterventionRepository::get_by_id(pool, id).await?;
+        InterventionRepository::add_note(pool, id, Some(&viewer.id), req.note.trim(), req.progress, None).await
+    }
+
+    /// Link a counseling session the intervention's student took part in
+    pub async fn link_session(pool: &SqlitePool, id: &str, session_id: &str) -> AppResult<()> {
+        let intervention = InterventionRepository::get_by_id(pool, id).await?;
+        check_session(pool, &intervention.studentId, session_id).await?;
+
+        InterventionRepository::link_session(pool, id, session_id).await
+    }
+
+    /// Link a behavior incident recorded for the intervention's student
+    pub async fn link_incident(pool: &SqlitePool, id: &str, incident_id: &str) -> AppResult<()> {
+        let intervention = InterventionRepository::get_by_id(pool, id).await?;
+        check_incident(pool, &intervention.studentId, incident_id).await?;
+
+        InterventionRepository::link_incident(pool, id, incident_id).await
+    }
+
+    /// Create INTERVENTION_REMINDER notifications for open interventions whose
+    /// target date is within `days_ahead` days or already past.
+    ///
+    /// An upcoming and an overdue reminder are each sent once per target date,
+    /// so moving the target date re-arms them.
+    pub async fn send_due_reminders(pool: &SqlitePool, days_ahead: i64) -> AppResult<Vec<NotificationLog>> {
+        let today = local_today();
+        let cutoff = (today + Duration::days(days_ahead.max(0))).format("%Y-%m-%d").to_string();
+        let today = today.format("%Y-%m-%d").to_string();
+
+        let mut notifications = Vec::new();
+
+        for intervention in InterventionRepository::get_open_due_by(pool, &cutoff).await? {
+            let Some(target_date) = intervention.targetDate.clone() else {
+                continue;
+            };
+            let overdue = target_date < today;
+            let kind = if overdue { "OVERDUE" } else { "UPCOMING" };
+
+            if InterventionRepository::has_reminder(pool, &intervention.id, kind, &target_date).await? {
+                continue;
+            }
+
+            let student = StudentRepository::get_by_id(pool, &intervention.studentId).await?;
+            let owner = match &intervention.ownerId {
+                Some(owner_id) => UserRepository::get_by_id(pool, owner_id).await.ok(),
+                None => None,
+            };
+
+            let (subject, priority) = if overdue {
+                ("Müdahale hedef tarihi geçti", "URGENT")
+            } else {
+                ("Müdahale hedef tarihi yaklaşıyor", "HIGH")
+            };
+            let message = format!(
+                "{} {} için \"{}\" müdahalesinin hedef tarihi {} (durum: {}).",
+                student.name, student.surname, intervention.title, target_date, intervention.status
+            );
+            let metadata = serde_json::json!({
+                "source": "intervention",
+                "kind": kind,
+                "targetDate": target_date,
+                "status": intervention.status,
+            });
+
+            let notification = NotificationLog {
+                interventionId: Some(intervention.id.clone()),
+                ..in_app_notification(
+                    "INTERVENTION_REMINDER",
+                    owner.as_ref(),
+                    &intervention.studentId,
+                    subject,
+                    message,
+                    priority,
+                    metadata,
+                )
+            };
+
+            let created =
+                InterventionRepository::create_reminder(pool, &intervention.id, kind, &target_date, notification).await?;
+            notifications.extend(created);
+        }
+
+        if !notifications.is_empty() {
+            info!("Created {} intervention reminders", notifications.len());
+        }
+
+        Ok(notifications)
+    }
+}
+
+/// Whether an intervention may move from one status to another
+pub fn can_transition(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        (INTERVENTION_PLANNED, INTERVENTION_IN_PROGRESS)
+            | (INTERVENTION_IN_PROGRESS, INTERVENTION_PLANNED)
+            | (INTERVENTION_IN_PROGRESS, INTERVENTION_COMPLETED)
+            | (INTERVENTION_COMPLETED, INTERVENTION_IN_PROGRESS)
+    )
+}
+
+/// Fail unless the counseling session exists and the student took part in it
+async fn check_session(pool: &SqlitePool, student_id: &str, session_id: &str) -> AppResult<()> {
+    CounselingRepository::get_session_by_id(pool, session_id).await?;
+
+    if !CounselingRepository::session_has_student(pool, session_id, student_id).await? {
+        return Err(AppError::ValidationError(format!(
+            "Session {} does not include the intervention's student",
+            session_id
+        )));
+    }
+    Ok(())
+}
+
+/// Fail unless the behavior incident was recorded for the student
+async fn check_incident(pool: &SqlitePool, student_id: &str, incident_id: &str) -> AppResult<()> {
+    let incident = AcademicRepository::get_behavior_incident_by_id(pool, incident_id).await?;
+
+    if incident.studentId != student_id {
+        return Err(AppError::ValidationError(format!(
+            "Incident {} belongs to another student",
+            incident_id
+        )));
+    }
+    Ok(())
+}
+
+fn validate_date(date: Option<&str>) -> AppResult<Option<NaiveDate>> {
+    match date {
+        Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| AppError::ValidationError(format!("Invalid date: {}", value))),
+        None => Ok(None),
+    }
+}
+
+fn validate_schedule(start_date: Option<&str>, target_date: Option<&str>) -> AppResult<()> {
+    let start = validate_date(start_date)?;
+    let target = validate_date(target_date)?;
+
+    if let (Some(start), Some(target)) = (start, target) {
+        if target < start {
+            return Err(AppError::ValidationError("Target date cannot be before the start date".to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn validate_priority(priority: Option<&str>) -> AppResult<()> {
+    match priority {
+        Some(value) if !INTERVENTION_PRIORITIES.contains(&value) => {
+            Err(AppError::ValidationError(format!("Invalid priority: {}", value)))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_transitions() {
+        assert!(can_transition(INTERVENTION_PLANNED, INTERVENTION_IN_PROGRESS));
+        assert!(can_transition(INTERVENTION_IN_PROGRESS, INTERVENTION_COMPLETED));
+        assert!(can_transition(INTERVENTION_COMPLETED, INTERVENTION_IN_PROGRESS));
+        assert!(!can_transition(INTERVENTION_PLANNED, INTERVENTION_COMPLETED));
+        assert!(!can_transition(INTERVENTION_COMPLETED, INTERVENTION_PLANNED));
+        assert!(!can_transition(INTERVENTION_PLANNED, INTERVENTION_PLANNED));
+    }
+
+    #[test]
+    fn test_validate_schedule() {
+        assert!(validate_schedule(Some("2024-10-01"), Some("2024-11-01")).is_ok());
+        assert!(validate_schedule(Some("2024-11-01"), Some("2024-10-01")).is_err());
+        assert!(validate_schedule(None, Some("01.10.2024")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_due_reminders_once() {
+        let pool = crate::database::test_pool().await;
+        let target_date = (local_today() - Duration::days(1)).format("%Y-%m-%d").to_string();
+        sqlx::query("INSERT INTO students (id, name, surname, enrollmentDate) VALUES ('s1', 'Ali', 'Yılmaz', '2024-09-01')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO interventions (id, studentId, date, title, status, targetDate) \
+             VALUES ('i1', 's1', '2024-09-01', 'Okuma planı', 'Devam', ?)"
+        )
+        .bind(&target_date)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let sent = InterventionService::send_due_reminders(&pool, DEFAULT_REMINDER_DAYS).await.unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(InterventionService::send_due_reminders(&pool, DEFAULT_REMINDER_DAYS).await.unwrap().is_empty());
+
+        let linked: Option<String> = sqlx::query_scalar("SELECT notificationId FROM intervention_reminders WHERE interventionId = 'i1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(linked.as_deref(), Some(sent[0].id.as_str()));
+    }
+}
@@ -1,2 +1,29 @@
 pub mod ai_service;
 pub mod config_service;
+pub mod document_service;
+pub mod guardian_service;
+pub mod family_service;
+pub mod attendance_service;
+pub mod attendance_import_service;
+pub mod csv_import;
+pub mod intervention_service;
+pub mod student_profile_service;
+pub mod timeline_service;
+pub mod bep_service;
+pub mod custom_field_service;
+pub mod class_service;
+pub mod institution_service;
+pub mod risk_service;
+pub mod risk_alert_service;
+pub mod dashboard_service;
+pub mod exam_import_service;
+pub mod exam_score_service;
+pub mod exam_trend_service;
+pub mod exam_ranking_service;
+pub mod academic_goal_service;
+pub mod curriculum_service;
+pub mod study_service;
+pub mod exam_topic_service;
+pub mod placement_service;
+pub mod scheduler_service;
+pub mod notification_service;
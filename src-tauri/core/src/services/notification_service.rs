@@ -0,0 +1,49 @@
+use crate::models::{NotificationLog, User};
+
+/// Recipient type of a notification addressed to a user with this role
+pub(crate) fn recipient_type_for_role(role: &str) -> &'static str {
+    match role {
+        "admin" => "ADMIN",
+        "teacher" => "TEACHER",
+        _ => "COUNSELOR",
+    }
+}
+
+/// A pending in-app notification about a student, ready to be logged.
+///
+/// Without a recipient it is left unaddressed for any counselor to pick up.
+/// Alert and intervention links are set by the caller where they apply.
+pub(crate) fn in_app_notification(
+    notification_type: &str,
+    recipient: Option<&User>,
+    student_id: &str,
+    subject: &str,
+    message: String,
+    priority: &str,
+    metadata: serde_json::Value,
+) -> NotificationLog {
+    NotificationLog {
+        id: String::new(),
+        recipientType: recipient.map(|user| recipient_type_for_role(&user.role)).unwrap_or("COUNSELOR").to_string(),
+        recipientId: recipient.map(|user| user.id.clone()),
+        recipientName: recipient.map(|user| user.name.clone()),
+        recipientContact: recipient.map(|user| user.email.clone()),
+        notificationType: notification_type.to_string(),
+        channel: "IN_APP".to_string(),
+        subject: Some(subject.to_string()),
+        message,
+        studentId: Some(student_id.to_string()),
+        alertId: None,
+        interventionId: None,
+        status: "PENDING".to_string(),
+        priority: priority.to_string(),
+        metadata: Some(metadata.to_string()),
+        templateId: None,
+        sentAt: None,
+        deliveredAt: None,
+        readAt: None,
+        failureReason: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    }
+}
@@ -0,0 +1,519 @@
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    ExamResult, ImportPlacementTableRequest, PlacementEstimate, PlacementEstimateRequest, PlacementImportResult,
+    PlacementOption, PlacementProgram, PlacementScore, PlacementTable, PlacementTableColumns, SkippedPlacementRow,
+    User, EXAM_TYPE_AYT, EXAM_TYPE_LGS, EXAM_TYPE_TYT, EXAM_TYPE_YDT, EXAM_TYPE_YKS, PLACEMENT_BORDERLINE,
+    PLACEMENT_EXAM_TYPES, PLACEMENT_REACH, PLACEMENT_REACHABLE, SCORE_TYPES, SCORE_TYPE_DIL, SCORE_TYPE_SOZ,
+};
+use crate::repositories::{AcademicRepository, PlacementRepository, StudentRepository};
+use crate::services::csv_import::{detect_delimiter, normalize_header, resolve_column, split_row};
+
+/// Points around a base score within which a program is borderline
+pub const YKS_BORDERLINE_MARGIN: f64 = 10.0;
+pub const LGS_BORDERLINE_MARGIN: f64 = 5.0;
+/// Reach programs are listed up to this many borderline margins above the student's score
+const REACH_MARGINS: f64 = 3.0;
+/// School points (OBP) per diploma grade point in YKS placement scores: the
+/// grade times 5, weighted by 0.12
+const DIPLOMA_POINTS_PER_GRADE: f64 = 0.6;
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+const CODE_HEADERS: &[&str] = &["Program Kodu", "Okul Kodu", "Kurum Kodu", "Kod"];
+const INSTITUTION_HEADERS: &[&str] = &["Üniversite Adı", "Üniversite", "Okul Adı", "Okul", "Kurum Adı"];
+const PROGRAM_HEADERS: &[&str] = &["Program Adı", "Program", "Bölüm", "Alan"];
+const CITY_HEADERS: &[&str] = &["İl", "Şehir", "İl Adı"];
+const SCHOOL_TYPE_HEADERS: &[&str] = &["Üniversite Türü", "Okul Türü", "Kurum Türü", "Tür"];
+const SCORE_TYPE_HEADERS: &[&str] = &["Puan Türü", "Puan Tipi"];
+const QUOTA_HEADERS: &[&str] = &["Kontenjan", "Genel Kontenjan"];
+const BASE_SCORE_HEADERS: &[&str] = &["Taban Puan", "Taban Puanı", "En Küçük Puan", "Taban"];
+const BASE_RANK_HEADERS: &[&str] = &["Başarı Sırası", "Taban Başarı Sırası", "En Küçük Başarı Sırası"];
+const PERCENTILE_HEADERS: &[&str] = &["Yüzdelik Dilim", "Yüzdelik", "Taban Yüzdelik Dilim"];
+
+pub struct PlacementService;
+
+impl PlacementService {
+    pub async fn get_tables(pool: &SqlitePool) -> AppResult<Vec<PlacementTable>> {
+        PlacementRepository::get_tables(pool).await
+    }
+
+    /// Import a year's base-score table of an exam, replacing the programs of
+    /// a table already imported for that year
+    pub async fn import_table(
+        pool: &SqlitePool,
+        viewer: &User,
+        req: ImportPlacementTableRequest,
+    ) -> AppResult<PlacementImportResult> {
+        if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+            return Err(AppError::Unauthorized);
+        }
+
+        let exam_type = validate_exam_type(&req.examType)?;
+        if !(2000..=2100).contains(&req.year) {
+            return Err(AppError::ValidationError(format!("Invalid year: {}", req.year)));
+        }
+
+        let columns = req.columns.unwrap_or_default();
+        let (programs, skipped) = parse_placement_table(&exam_type, &req.content, &columns)?;
+        if programs.is_empty() {
+            return Err(AppError::ValidationError("The base-score table has no programs".to_string()));
+        }
+
+        let name = req
+            .name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("{} {} taban puanları", req.year, exam_type));
+        let table = PlacementRepository::replace_table(pool, &exam_type, req.year, &name, &programs, Some(&viewer.id)).await?;
+        info!("Imported placement table {} with {} programs", table.name, programs.len());
+
+        Ok(PlacementImportResult {
+            table,
+            totalRows: programs.len() + skipped.len(),
+            programs: programs.len(),
+            skipped,
+        })
+    }
+
+    pub async fn delete_table(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<()> {
+        if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+            return Err(AppError::Unauthorized);
+        }
+        PlacementRepository::delete_table(pool, id).await
+    }
+
+    /// Programs of an imported table the student's latest scores reach, are
+    /// borderline for or fall somewhat short of
+    pub async fn estimate(pool: &SqlitePool, req: PlacementEstimateRequest) -> AppResult<PlacementEstimate> {
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+        let results = AcademicRepository::get_exam_results_by_student(pool, &req.studentId).await?;
+
+        let exam_type = match req.examType.as_deref() {
+            Some(exam_type) => validate_exam_type(exam_type)?,
+            None => latest_exam_type(&results)
+                .ok_or_else(|| AppError::ValidationError("The student has no scored YKS or LGS result".to_string()))?
+                .to_string(),
+        };
+        if let Some(grade) = req.diplomaGrade {
+            if !(50.0..=100.0).contains(&grade) {
+                return Err(AppError::ValidationError(format!("Invalid diploma grade: {}", grade)));
+            }
+        }
+
+        let mut scores = placement_scores(&results, &exam_type, req.diplomaGrade);
+        if let Some(score_type) = req.scoreType.as_deref() {
+            let score_type = normalize_score_type(score_type)
+                .ok_or_else(|| AppError::ValidationError(format!("Invalid score type: {}", score_type)))?;
+            scores.retain(|score| score.scoreType == score_type);
+        }
+        if scores.is_empty() {
+            return Err(AppError::ValidationError(format!("The student has no scored {} result", exam_type)));
+        }
+
+        let table = PlacementRepository::find_table(pool, &exam_type, req.year)
+            .await?
+            .ok_or_else(|| match req.year {
+                Some(year) => AppError::ValidationError(format!("No {} base-score table imported for {}", exam_type, year)),
+                None => AppError::ValidationError(format!("No {} base-score table imported", exam_type)),
+            })?;
+
+        let score_types: Vec<String> = scores.iter().map(|score| score.scoreType.clone()).collect();
+        let city = req.city.as_deref().map(normalize_header).filter(|city| !city.is_empty());
+        let school_type = req.schoolType.as_deref().map(normalize_header).filter(|kind| !kind.is_empty());
+        let programs: Vec<PlacementProgram> = PlacementRepository::get_programs(pool, &table.id, &score_types)
+            .await?
+            .into_iter()
+            .filter(|program| matches_filter(program.city.as_deref(), city.as_deref()))
+            .filter(|program| matches_filter(program.schoolType.as_deref(), school_type.as_deref()))
+            .collect();
+
+        let limit = req.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let (reachable, borderline, reach) = estimate_programs(&exam_type, &scores, programs, limit);
+
+        Ok(PlacementEstimate {
+            studentId: req.studentId,
+            examType: exam_type,
+            year: table.year,
+            tableName: table.name,
+            scores,
+            reachable,
+            borderline,
+            reach,
+        })
+    }
+}
+
+fn validate_exam_type(exam_type: &str) -> AppResult<String> {
+    let exam_type = exam_type.trim().to_uppercase();
+    if !PLACEMENT_EXAM_TYPES.contains(&exam_type.as_str()) {
+        return Err(AppError::ValidationError(format!("No base-score tables for exam type: {}", exam_type)));
+    }
+    Ok(exam_type)
+}
+
+fn matches_filter(value: Option<&str>, filter: Option<&str>) -> bool {
+    match filter {
+        Some(filter) => value.is_some_and(|value| normalize_header(value) == filter),
+        None => true,
+    }
+}
+
+/// SAY, EA, SÖZ, DİL, TYT or LGS however the table writes it
+pub fn normalize_score_type(value: &str) -> Option<String> {
+    let normalized = normalize_header(value);
+    let score_type = match normalized.as_str() {
+        "soz" => SCORE_TYPE_SOZ,
+        "dıl" => SCORE_TYPE_DIL,
+        "tyt" => EXAM_TYPE_TYT,
+        "lgs" => EXAM_TYPE_LGS,
+        other => *SCORE_TYPES.iter().find(|score_type| normalize_header(score_type) == other)?,
+    };
+    Some(score_type.to_string())
+}
+
+/// The exam with base-score tables a result prepares for
+fn placement_exam_type(exam_type: &str) -> Option<&'static str> {
+    match exam_type {
+        EXAM_TYPE_LGS => Some(EXAM_TYPE_LGS),
+        EXAM_TYPE_YKS | EXAM_TYPE_TYT | EXAM_TYPE_AYT | EXAM_TYPE_YDT => Some(EXAM_TYPE_YKS),
+        _ => None,
+    }
+}
+
+/// Score type a result's total score counts for: TYT results have the TYT
+/// score, AYT, YDT and YKS results the score type they were calculated for
+fn result_score_type(result: &ExamResult) -> Option<String> {
+    match result.examType.as_str() {
+        EXAM_TYPE_LGS => Some(EXAM_TYPE_LGS.to_string()),
+        EXAM_TYPE_TYT => Some(EXAM_TYPE_TYT.to_string()),
+        EXAM_TYPE_YDT => Some(SCORE_TYPE_DIL.to_string()),
+        EXAM_TYPE_AYT | EXAM_TYPE_YKS => result.scoreType.as_deref().and_then(normalize_score_type),
+        _ => None,
+    }
+}
+
+/// YKS or LGS, whichever the student's latest scored result prepares for
+pub fn latest_exam_type(results: &[ExamResult]) -> Option<&'static str> {
+    results
+        .iter()
+        .filter(|result| result.totalScore.is_some())
+        .filter_map(|result| placement_exam_type(&result.examType).map(|exam_type| (result, exam_type)))
+        .max_by(|(a, _), (b, _)| a.examDate.cmp(&b.examDate))
+        .map(|(_, exam_type)| exam_type)
+}
+
+/// The student's latest score of each score type of an exam; YKS scores get
+/// the diploma grade's school points
+pub fn placement_scores(results: &[ExamResult], exam_type: &str, diploma_grade: Option<f64>) -> Vec<PlacementScore> {
+    let mut scored: Vec<&ExamResult> = results
+        .iter()
+        .filter(|result| result.totalScore.is_some() && placement_exam_type(&result.examType) == Some(exam_type))
+        .collect();
+    scored.sort_by(|a, b| b.examDate.cmp(&a.examDate));
+
+    let school_points = match (exam_type, diploma_grade) {
+        (EXAM_TYPE_YKS, Some(grade)) => grade * DIPLOMA_POINTS_PER_GRADE,
+        _ => 0.0,
+    };
+
+    let mut scores: Vec<PlacementScore> = Vec::new();
+    for result in scored {
+        let (Some(score_type), Some(score)) = (result_score_type(result), result.totalScore) else {
+            continue;
+        };
+        if scores.iter().any(|existing| existing.scoreType == score_type) {
+            continue;
+        }
+        scores.push(PlacementScore {
+            scoreType: score_type,
+            examScore: score,
+            score: round3(score + school_points),
+            percentileRank: result.percentileRank,
+            examResultId: result.id.clone(),
+            examName: result.examName.clone(),
+            examDate: result.examDate.clone(),
+        });
+    }
+
+    scores
+}
+
+/// Category of a program with `base_score` for a student with `score`, or
+/// None when the program is out of reach
+pub fn placement_category(exam_type: &str, score: f64, base_score: f64) -> Option<&'static str> {
+    let margin = if exam_type == EXAM_TYPE_LGS { LGS_BORDERLINE_MARGIN } else { YKS_BORDERLINE_MARGIN };
+    let difference = score - base_score;
+
+    if difference >= margin {
+        Some(PLACEMENT_REACHABLE)
+    } else if difference >= -margin {
+        Some(PLACEMENT_BORDERLINE)
+    } else if difference >= -margin * REACH_MARGINS {
+        Some(PLACEMENT_REACH)
+    } else {
+        None
+    }
+}
+
+/// Sort programs into reachable, borderline and reach lists of at most
+/// `limit` programs, each against the student's score of its score type
+pub fn estimate_programs(
+    exam_type: &str,
+    scores: &[PlacementScore],
+    programs: Vec<PlacementProgram>,
+    limit: usize,
+) -> (Vec<PlacementOption>, Vec<PlacementOption>, Vec<PlacementOption>) {
+    let mut reachable = Vec::new();
+    let mut borderline = Vec::new();
+    let mut reach = Vec::new();
+
+    for program in programs {
+        let Some(score) = scores.iter().find(|score| score.scoreType == program.scoreType) else {
+            continue;
+        };
+        let Some(category) = placement_category(exam_type, score.score, program.baseScore) else {
+            continue;
+        };
+        let option = PlacementOption {
+            studentScore: score.score,
+            margin: round3(score.score - program.baseScore),
+            category: category.to_string(),
+            program,
+        };
+        match category {
+            PLACEMENT_REACHABLE => reachable.push(option),
+            PLACEMENT_BORDERLINE => borderline.push(option),
+            _ => reach.push(option),
+        }
+    }
+
+    reachable.sort_by(|a, b| a.margin.total_cmp(&b.margin));
+    borderline.sort_by(|a, b| a.margin.abs().total_cmp(&b.margin.abs()));
+    reach.sort_by(|a, b| b.margin.total_cmp(&a.margin));
+    for list in [&mut reachable, &mut borderline, &mut reach] {
+        list.truncate(limit);
+    }
+
+    (reachable, borderline, reach)
+}
+
+/// Read a base-score table. Programs without a base score (not filled that
+/// year) and rows that cannot be read are returned as skipped.
+pub fn parse_placement_table(
+    exam_type: &str,
+    content: &str,
+    columns: &PlacementTableColumns,
+) -> AppResult<(Vec<PlacementProgram>, Vec<SkippedPlacementRow>)> {
+    let lines: Vec<&str> = content.trim_start_matches('\u{feff}').lines().collect();
+    let Some(header_index) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return Err(AppError::ValidationError("The base-score table is empty".to_string()));
+    };
+    let delimiter = detect_delimiter(lines[header_index]);
+    let header = split_row(lines[header_index], delimiter);
+
+    let column = |given: &Option<String>, defaults: &[&str], required: bool| -> AppResult<Option<usize>> {
+        let found = match given {
+            Some(given) => resolve_column(given, &header),
+            None => defaults.iter().find_map(|name| resolve_column(name, &header)),
+        };
+        match found {
+            None if required || given.is_some() => Err(AppError::ValidationError(format!(
+                "Column not found in the base-score table: {}",
+                given.as_deref().unwrap_or(defaults[0])
+            ))),
+            found => Ok(found),
+        }
+    };
+    let is_yks = exam_type == EXAM_TYPE_YKS;
+    let code = column(&columns.code, CODE_HEADERS, false)?;
+    let institution = column(&columns.institution, INSTITUTION_HEADERS, true)?;
+    let program = column(&columns.program, PROGRAM_HEADERS, is_yks)?;
+    let city = column(&columns.city, CITY_HEADERS, false)?;
+    let school_type = column(&columns.schoolType, SCHOOL_TYPE_HEADERS, false)?;
+    let score_type = column(&columns.scoreType, SCORE_TYPE_HEADERS, is_yks)?;
+    let quota = column(&columns.quota, QUOTA_HEADERS, false)?;
+    let base_score = column(&columns.baseScore, BASE_SCORE_HEADERS, true)?;
+    let base_rank = column(&columns.baseRank, BASE_RANK_HEADERS, false)?;
+    let percentile = column(&columns.basePercentile, PERCENTILE_HEADERS, false)?;
+
+    let mut programs = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, line) in lines.iter().enumerate().skip(header_index + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = split_row(line, delimiter);
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|index| cells.get(index))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let mut skip = |reason: String| skipped.push(SkippedPlacementRow { line: index + 1, reason });
+
+        let Some(institution_name) = cell(institution) else {
+            skip("Missing university or school".to_string());
+            continue;
+        };
+        let program_score_type = if is_yks {
+            let raw = cell(score_type).unwrap_or_default();
+            match normalize_score_type(&raw).filter(|score_type| score_type != EXAM_TYPE_LGS) {
+                Some(score_type) => score_type,
+                None => {
+                    skip(format!("Unknown score type: '{}'", raw));
+                    continue;
+                }
+            }
+        } else {
+            EXAM_TYPE_LGS.to_string()
+        };
+        let Some(score) = cell(base_score).as_deref().and_then(parse_decimal) else {
+            skip("No base score".to_string());
+            continue;
+        };
+
+        programs.push(PlacementProgram {
+            id: String::new(),
+            tableId: String::new(),
+            code: cell(code),
+            institutionName: institution_name,
+            programName: cell(program),
+            city: cell(city),
+            schoolType: cell(school_type),
+            scoreType: program_score_type,
+            quota: cell(quota).as_deref().and_then(parse_count).and_then(|quota| i32::try_from(quota).ok()),
+            baseScore: score,
+            baseRank: cell(base_rank).as_deref().and_then(parse_count),
+            basePercentile: cell(percentile).as_deref().and_then(parse_decimal),
+        });
+    }
+
+    Ok((programs, skipped))
+}
+
+/// A published decimal such as `452,12345` or `452.12345`; a comma is the
+/// decimal separator when present, dots before it grouping thousands
+pub fn parse_decimal(value: &str) -> Option<f64> {
+    let value = value.trim().trim_end_matches('%').trim();
+    let value = if value.contains(',') {
+        value.replace('.', "").replace(',', ".")
+    } else {
+        value.to_string()
+    };
+    value.parse::<f64>().ok().filter(|number| number.is_finite() && *number > 0.0)
+}
+
+/// A published whole number such as `12.345`
+fn parse_count(value: &str) -> Option<i64> {
+    let digits: String = value.chars().filter(|c| !matches!(c, '.' | ',' | ' ')).collect();
+    digits.parse::<i64>().ok().filter(|number| *number >= 0)
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(exam_type: &str, score_type: Option<&str>, date: &str, score: Option<f64>) -> ExamResult {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("{}-{}", exam_type, date), "studentId": "s1", "examType": exam_type,
+            "examName": exam_type, "examDate": date, "totalScore": score, "scoreType": score_type,
+            "isOfficial": false, "goalsMet": false, "parentNotified": false, "created_at": "", "updated_at": ""
+        }))
+        .unwrap()
+    }
+
+    fn program(score_type: &str, base_score: f64) -> PlacementProgram {
+        PlacementProgram {
+            id: format!("{}-{}", score_type, base_score),
+            tableId: "t".to_string(),
+            code: None,
+            institutionName: "Üniversite".to_string(),
+            programName: None,
+            city: None,
+            schoolType: None,
+            scoreType: score_type.to_string(),
+            quota: None,
+            baseScore: base_score,
+            baseRank: None,
+            basePercentile: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_placement_table() {
+        let content = "Program Kodu;Üniversite Adı;Program Adı;İl;Üniversite Türü;Puan Türü;Kontenjan;Taban Puan;Başarı Sırası\n\
+            101110001;ANKARA ÜNİVERSİTESİ;Hukuk;ANKARA;Devlet;EA;410;452,12345;12.345\n\
+            101110002;ANKARA ÜNİVERSİTESİ;Fizik;ANKARA;Devlet;say;60;Dolmadı;\n\
+            101110003;ANKARA ÜNİVERSİTESİ;Tarih;ANKARA;Devlet;soz;80;380.5;45000\n\
+            101110004;ANKARA ÜNİVERSİTESİ;Bilinmeyen;ANKARA;Devlet;XYZ;10;300;1\n";
+        let (programs, skipped) = parse_placement_table(EXAM_TYPE_YKS, content, &PlacementTableColumns::default()).unwrap();
+
+        assert_eq!(programs.len(), 2);
+        assert_eq!(programs[0].scoreType, "EA");
+        assert_eq!(programs[0].baseScore, 452.12345);
+        assert_eq!((programs[0].baseRank, programs[0].quota), (Some(12345), Some(410)));
+        assert_eq!(programs[1].scoreType, "SÖZ");
+        assert_eq!(programs[1].baseScore, 380.5);
+        let reasons: Vec<_> = skipped.iter().map(|row| (row.line, row.reason.as_str())).collect();
+        assert_eq!(reasons, vec![(3, "No base score"), (5, "Unknown score type: 'XYZ'")]);
+
+        let lgs = "Okul Adı,Okul Türü,İl,Kontenjan,Taban Puan,Yüzdelik Dilim\n\
+            \"Ankara Fen Lisesi\",Fen Lisesi,Ankara,150,\"495,5\",\"0,08\"\n";
+        let (programs, _) = parse_placement_table(EXAM_TYPE_LGS, lgs, &PlacementTableColumns::default()).unwrap();
+        assert_eq!(programs[0].scoreType, "LGS");
+        assert_eq!((programs[0].baseScore, programs[0].basePercentile), (495.5, Some(0.08)));
+
+        let missing = parse_placement_table(EXAM_TYPE_YKS, "Üniversite Adı;Taban Puan\nA;300", &PlacementTableColumns::default());
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_placement_scores() {
+        let results = vec![
+            result(EXAM_TYPE_TYT, None, "2024-03-01", Some(380.0)),
+            result(EXAM_TYPE_TYT, None, "2024-04-01", Some(400.0)),
+            result(EXAM_TYPE_AYT, Some("SAY"), "2024-04-01", Some(420.5)),
+            result(EXAM_TYPE_AYT, Some("EA"), "2024-02-01", None),
+            result(EXAM_TYPE_LGS, None, "2023-06-01", Some(480.0)),
+        ];
+
+        assert_eq!(latest_exam_type(&results), Some(EXAM_TYPE_YKS));
+        let scores = placement_scores(&results, EXAM_TYPE_YKS, Some(90.0));
+        let summary: Vec<_> = scores.iter().map(|s| (s.scoreType.as_str(), s.examScore, s.score)).collect();
+        assert_eq!(summary, vec![("TYT", 400.0, 454.0), ("SAY", 420.5, 474.5)]);
+
+        let lgs = placement_scores(&results, EXAM_TYPE_LGS, Some(90.0));
+        assert_eq!((lgs.len(), lgs[0].score), (1, 480.0));
+    }
+
+    #[test]
+    fn test_estimate_programs() {
+        assert_eq!(placement_category(EXAM_TYPE_YKS, 450.0, 440.0), Some(PLACEMENT_REACHABLE));
+        assert_eq!(placement_category(EXAM_TYPE_YKS, 450.0, 445.0), Some(PLACEMENT_BORDERLINE));
+        assert_eq!(placement_category(EXAM_TYPE_YKS, 450.0, 470.0), Some(PLACEMENT_REACH));
+        assert_eq!(placement_category(EXAM_TYPE_YKS, 450.0, 481.0), None);
+        assert_eq!(placement_category(EXAM_TYPE_LGS, 450.0, 444.0), Some(PLACEMENT_REACHABLE));
+
+        let scores = placement_scores(&[result(EXAM_TYPE_AYT, Some("SAY"), "2024-04-01", Some(450.0))], EXAM_TYPE_YKS, None);
+        let programs = vec![
+            program("SAY", 300.0),
+            program("SAY", 430.0),
+            program("SAY", 452.0),
+            program("SAY", 447.0),
+            program("SAY", 470.0),
+            program("SAY", 500.0),
+            program("EA", 400.0),
+        ];
+        let (reachable, borderline, reach) = estimate_programs(EXAM_TYPE_YKS, &scores, programs, 1);
+
+        assert_eq!((reachable.len(), reachable[0].program.baseScore), (1, 430.0));
+        assert_eq!((borderline[0].program.baseScore, borderline[0].margin), (452.0, -2.0));
+        assert_eq!((reach[0].program.baseScore, reach[0].category.as_str()), (470.0, "reach"));
+    }
+}
@@ -0,0 +1,346 @@
+use chrono::{Duration, Local, NaiveTime, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    NotificationLog, NotificationPreference, RiskAlert, RiskAlertSettings, Student, User,
+    RISK_HIGH, RISK_LEVELS,
+};
+use crate::repositories::{NotificationRepository, RiskAlertRepository, UserRepository};
+use crate::services::institution_service::{accessible_student, active_institution};
+use crate::services::notification_service::{in_app_notification, recipient_type_for_role};
+
+const RECIPIENT_ALERT: &str = "ALERT";
+const RECIPIENT_ESCALATION: &str = "ESCALATION";
+
+pub struct RiskAlertService;
+
+impl RiskAlertService {
+    /// React to a change of a student's risk level.
+    ///
+    /// A rise opens an alert for the student's counselors and admins whose
+    /// preferences cover the new level; a fall resolves open alerts above it.
+    pub async fn record_transition(
+        pool: &SqlitePool,
+        student: &Student,
+        new_level: &str,
+        score: f64,
+    ) -> AppResult<Option<RiskAlert>> {
+        let previous_rank = level_rank(&student.risk);
+        let new_rank = level_rank(new_level);
+
+        if new_rank < previous_rank {
+            RiskAlertRepository::resolve_alerts(pool, &student.id, &RISK_LEVELS[new_rank + 1..]).await?;
+            return Ok(None);
+        }
+        if new_rank == previous_rank {
+            return Ok(None);
+        }
+
+        let mut recipients = RiskAlertRepository::get_student_counselors(pool, &student.id).await?;
+        let addressed_counselors = !recipients.is_empty();
+        for admin in RiskAlertRepository::get_student_admins(pool, &student.id).await? {
+            if !recipients.iter().any(|user| user.id == admin.id) {
+                recipients.push(admin);
+            }
+        }
+
+        let mut recipient_ids = Vec::new();
+        for user in &recipients {
+            let preference = preference_for(pool, user).await?;
+            if wants_risk_alert(preference.as_ref(), new_level) {
+                recipient_ids.push(Some(user.id.as_str()));
+            }
+        }
+        if !addressed_counselors && new_level == RISK_HIGH {
+            recipient_ids.push(None);
+        }
+
+        let mut tx = pool.begin().await?;
+        let alert_id =
+            RiskAlertRepository::create_alert(&mut *tx, &student.id, Some(&student.risk), new_level, Some(score)).await?;
+        for user_id in recipient_ids {
+            RiskAlertRepository::add_recipient(&mut *tx, &alert_id, user_id, RECIPIENT_ALERT).await?;
+        }
+        tx.commit().await?;
+        let alert = RiskAlertRepository::get_alert(pool, &alert_id).await?;
+
+        info!("Risk of student {} rose from {} to {}", student.id, student.risk, new_level);
+        Self::deliver_pending(pool, Local::now().time()).await?;
+        Ok(Some(alert))
+    }
+
+    /// Escalate overdue alerts and send notifications held back by quiet hours.
+    ///
+    /// Returns the notifications created.
+    pub async fn process_alerts(pool: &SqlitePool) -> AppResult<Vec<NotificationLog>> {
+        let settings = RiskAlertRepository::get_settings(pool).await?;
+        let before = (Utc::now() - Duration::hours(settings.escalationHours)).to_rfc3339();
+
+        for alert in RiskAlertRepository::get_overdue_alerts(pool, RISK_HIGH, &before).await? {
+            let admins = RiskAlertRepository::get_student_admins(pool, &alert.studentId).await?;
+
+            let mut tx = pool.begin().await?;
+            for admin in &admins {
+                RiskAlertRepository::add_recipient(&mut *tx, &alert.id, Some(&admin.id), RECIPIENT_ESCALATION).await?;
+            }
+            RiskAlertRepository::mark_escalated(&mut *tx, &alert.id).await?;
+            tx.commit().await?;
+            info!("Risk alert {} escalated to admins", alert.id);
+        }
+
+        Self::deliver_pending(pool, Local::now().time()).await
+    }
+
+    /// Alerts of the viewer's institution, newest first
+    pub async fn get_alerts(
+        pool: &SqlitePool,
+        viewer: &User,
+        status: Option<&str>,
+        student_id: Option<&str>,
+    ) -> AppResult<Vec<RiskAlert>> {
+        require_counselor(viewer)?;
+        let institution_id = active_institution(viewer)?;
+        RiskAlertRepository::get_alerts(pool, status, student_id, Some(&institution_id)).await
+    }
+
+    /// Acknowledge an alert, which stops its escalation and marks its notifications read
+    pub async fn acknowledge(pool: &SqlitePool, viewer: &User, alert_id: &str) -> AppResult<RiskAlert> {
+        require_counselor(viewer)?;
+        let alert = RiskAlertRepository::get_alert(pool, alert_id).await?;
+        accessible_student(pool, viewer, &alert.studentId).await?;
+
+        if !RiskAlertRepository::acknowledge(pool, alert_id, &viewer.id).await? {
+            return Err(AppError::ValidationError(format!("Risk alert is already {}", alert.status)));
+        }
+        RiskAlertRepository::mark_notifications_read(pool, alert_id).await?;
+
+        RiskAlertRepository::get_alert(pool, alert_id).await
+    }
+
+    pub async fn get_settings(pool: &SqlitePool) -> AppResult<RiskAlertSettings> {
+        RiskAlertRepository::get_settings(pool).await
+    }
+
+    pub async fn update_settings(
+        pool: &SqlitePool,
+        viewer: &User,
+        settings: RiskAlertSettings,
+    ) -> AppResult<RiskAlertSettings> {
+        if viewer.role != "admin" {
+            return Err(AppError::Unauthorized);
+        }
+        if settings.escalationHours < 1 {
+            return Err(AppError::ValidationError("Escalation time must be at least one hour".to_string()));
+        }
+
+        RiskAlertRepository::update_settings(pool, &settings).await
+    }
+
+    /// Create the notifications of recipients who are outside their quiet hours at `now`
+    async fn deliver_pending(pool: &SqlitePool, now: NaiveTime) -> AppResult<Vec<NotificationLog>> {
+        let mut notifications = Vec::new();
+
+        for recipient in RiskAlertRepository::get_pending_recipients(pool).await? {
+            let user = match &recipient.userId {
+                Some(user_id) => Some(UserRepository::get_by_id(pool, user_id).await?),
+                None => None,
+            };
+            if let Some(user) = &user {
+                let preference = preference_for(pool, user).await?;
+                if let Some(preference) = &preference {
+                    if in_quiet_hours(preference.quietHoursStart.as_deref(), preference.quietHoursEnd.as_deref(), now) {
+                        continue;
+                    }
+                }
+            }
+
+            let alert = RiskAlertRepository::get_alert(pool, &recipient.alertId).await?;
+            let escalation = recipient.kind == RECIPIENT_ESCALATION;
+            let student_name = alert.studentName.clone().unwrap_or_default();
+
+            let (subject, message) = if escalation {
+                (
+                    "Risk uyarısı yanıtlanmadı".to_string(),
+                    format!(
+                        "{} için {} risk uyarısı {} tarihinden beri onaylanmadı.",
+                        student_name, alert.newLevel, alert.created_at.get(..10).unwrap_or(&alert.created_at)
+                    ),
+                )
+            } else {
+                (
+                    format!("Risk düzeyi yükseldi: {}", alert.newLevel),
+                    format!(
+                        "{} adlı öğrencinin risk düzeyi {} iken {} oldu (puan: {}).",
+                        student_name,
+                        alert.previousLevel.as_deref().unwrap_or("-"),
+                        alert.newLevel,
+                        alert.score.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
+                    ),
+                )
+            };
+            let priority = match (alert.newLevel == RISK_HIGH, escalation) {
+                (true, true) => "URGENT",
+                (true, false) => "HIGH",
+                _ => "NORMAL",
+            };
+            let metadata = serde_json::json!({
+                "source": "risk",
+                "kind": recipient.kind,
+                "previousLevel": alert.previousLevel,
+                "newLevel": alert.newLevel,
+                "score": alert.score,
+            });
+
+            let notification = NotificationLog {
+                alertId: Some(alert.id.clone()),
+                ..in_app_notification("RISK_ALERT", user.as_ref(), &alert.studentId, &subject, message, priority, metadata)
+            };
+            let mut tx = pool.begin().await?;
+            let log = NotificationRepository::create_log(&mut *tx, notification).await?;
+            RiskAlertRepository::set_recipient_notification(&mut *tx, &recipient.id, &log.id).await?;
+            tx.commit().await?;
+
+            notifications.push(log);
+        }
+
+        Ok(notifications)
+    }
+}
+
+async fn preference_for(pool: &SqlitePool, user: &User) -> AppResult<Option<NotificationPreference>> {
+    match NotificationRepository::get_preferences_by_user(pool, &user.id, recipient_type_for_role(&user.role)).await {
+        Ok(preference) => Ok(Some(preference)),
+        Err(AppError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether a user wants an in-app alert for a rise to `level`.
+///
+/// Without preferences, or without `riskLevels` in them, only Yüksek is
+/// alerted. `riskLevels` and `alertTypes` may be JSON arrays or comma
+/// separated lists and are matched case-insensitively.
+pub fn wants_risk_alert(preference: Option<&NotificationPreference>, level: &str) -> bool {
+    let Some(preference) = preference else {
+        return level == RISK_HIGH;
+    };
+    if !preference.inAppEnabled {
+        return false;
+    }
+
+    let alert_types = parse_list(preference.alertTypes.as_deref());
+    if !alert_types.is_empty() && !alert_types.iter().any(|t| t == "risk_alert") {
+        return false;
+    }
+
+    let levels = parse_list(preference.riskLevels.as_deref());
+    if levels.is_empty() {
+        return level == RISK_HIGH;
+    }
+    levels.contains(&level.to_lowercase())
+}
+
+/// Whether `now` falls in the quiet hours `start`–`end` (HH:MM), which may span midnight
+pub fn in_quiet_hours(start: Option<&str>, end: Option<&str>, now: NaiveTime) -> bool {
+    let (Some(start), Some(end)) = (start.and_then(parse_time), end.and_then(parse_time)) else {
+        return false;
+    };
+
+    if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value.trim(), "%H:%M:%S"))
+        .ok()
+}
+
+fn parse_list(value: Option<&str>) -> Vec<String> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Vec::new();
+    };
+
+    let items: Vec<String> = serde_json::from_str(value)
+        .unwrap_or_else(|_| value.split(',').map(str::to_string).collect());
+    items
+        .iter()
+        .map(|item| item.trim().to_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn level_rank(level: &str) -> usize {
+    RISK_LEVELS.iter().position(|l| *l == level).unwrap_or(0)
+}
+
+fn require_counselor(viewer: &User) -> AppResult<()> {
+    if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveTime {
+        parse_time(value).unwrap()
+    }
+
+    fn preference(risk_levels: Option<&str>, alert_types: Option<&str>) -> NotificationPreference {
+        NotificationPreference {
+            id: "p".to_string(),
+            userId: Some("u".to_string()),
+            parentId: None,
+            studentId: None,
+            userType: "COUNSELOR".to_string(),
+            emailEnabled: true,
+            smsEnabled: false,
+            pushEnabled: true,
+            inAppEnabled: true,
+            emailAddress: None,
+            phoneNumber: None,
+            alertTypes: alert_types.map(str::to_string),
+            riskLevels: risk_levels.map(str::to_string),
+            quietHoursStart: None,
+            quietHoursEnd: None,
+            weeklyDigest: false,
+            monthlyReport: false,
+            language: "tr".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_in_quiet_hours() {
+        assert!(in_quiet_hours(Some("22:00"), Some("07:00"), time("23:30")));
+        assert!(in_quiet_hours(Some("22:00"), Some("07:00"), time("06:59")));
+        assert!(!in_quiet_hours(Some("22:00"), Some("07:00"), time("07:00")));
+        assert!(in_quiet_hours(Some("12:00"), Some("13:00"), time("12:30")));
+        assert!(!in_quiet_hours(Some("12:00"), Some("13:00"), time("13:30")));
+        assert!(!in_quiet_hours(None, Some("07:00"), time("03:00")));
+        assert!(!in_quiet_hours(Some("gece"), Some("07:00"), time("03:00")));
+    }
+
+    #[test]
+    fn test_wants_risk_alert() {
+        assert!(wants_risk_alert(None, "Yüksek"));
+        assert!(!wants_risk_alert(None, "Orta"));
+        assert!(wants_risk_alert(Some(&preference(Some(r#"["ORTA","YÜKSEK"]"#), None)), "Orta"));
+        assert!(wants_risk_alert(Some(&preference(Some("orta, yüksek"), None)), "Yüksek"));
+        assert!(!wants_risk_alert(Some(&preference(Some("Yüksek"), None)), "Orta"));
+        assert!(!wants_risk_alert(Some(&preference(None, Some("INTERVENTION_REMINDER"))), "Yüksek"));
+        assert!(wants_risk_alert(Some(&preference(None, Some("RISK_ALERT,WEEKLY_DIGEST"))), "Yüksek"));
+
+        let mut muted = preference(None, None);
+        muted.inAppEnabled = false;
+        assert!(!wants_risk_alert(Some(&muted), "Yüksek"));
+    }
+}
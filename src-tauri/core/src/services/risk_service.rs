@@ -0,0 +1,761 @@
+use std::collections::HashMap;
+use chrono::{Duration, NaiveDate, Utc};
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    CategoryCount, CreateRiskRuleSetRequest, RiskAssessment, RiskFactorContribution, RiskPreviewChange,
+    RiskRulePreview, RiskRuleSet, RiskRules, RiskScore, SetRiskOverrideRequest, Student, User,
+    RISK_FACTOR_ATTENDANCE, RISK_FACTOR_BEHAVIOR, RISK_FACTOR_EXAM_DECLINE, RISK_FACTOR_FOLLOW_UPS,
+    RISK_FACTOR_SURVEY_FLAGS, RISK_HIGH, RISK_LEVELS, RISK_LOW, RISK_MEDIUM,
+};
+use crate::repositories::{AttendanceRepository, RiskRepository, StudentRepository};
+use crate::services::attendance_service::{local_today, school_year_and_term};
+use crate::services::institution_service::{active_institution, can_access};
+use crate::services::risk_alert_service::RiskAlertService;
+
+/// Scores older than this are refreshed by the scheduler
+pub const RISK_RECALCULATION_HOURS: i64 = 24;
+
+const RISK_FACTORS: &[&str] = &[
+    RISK_FACTOR_ATTENDANCE,
+    RISK_FACTOR_BEHAVIOR,
+    RISK_FACTOR_EXAM_DECLINE,
+    RISK_FACTOR_FOLLOW_UPS,
+    RISK_FACTOR_SURVEY_FLAGS,
+];
+
+/// Everything the risk score is computed from, gathered for one student
+#[derive(Debug, Clone, Default)]
+pub struct RiskInputs {
+    pub unexcused_days: f64,
+    pub total_days: f64,
+    pub unexcused_failure_days: f64,
+    pub total_failure_days: f64,
+    pub incident_intensities: Vec<Option<String>>,
+    /// (examType, examDate, totalNet), oldest first
+    pub exam_nets: Vec<(String, String, f64)>,
+    pub open_follow_ups: i64,
+    pub overdue_follow_ups: i64,
+    pub survey_flags: i64,
+}
+
+pub struct RiskService;
+
+impl RiskService {
+    /// Current assessment of a student, computing it when it was never calculated
+    pub async fn get_assessment(pool: &SqlitePool, student_id: &str) -> AppResult<RiskAssessment> {
+        match RiskRepository::get_score(pool, student_id).await? {
+            Some(score) => Self::assessment(pool, score).await,
+            None => Self::recalculate_student(pool, student_id).await,
+        }
+    }
+
+    /// Compute and store a student's score, then write the level in effect to `students.risk`
+    pub async fn recalculate_student(pool: &SqlitePool, student_id: &str) -> AppResult<RiskAssessment> {
+        let student = StudentRepository::get_by_id(pool, student_id).await?;
+        let (rules, version) = Self::active_rules(pool, student.institutionId.as_deref()).await?;
+        Self::recalculate_with(pool, &rules, version, &student).await
+    }
+
+    /// Rules of the institution's active rule set and its version, or the
+    /// built-in rules; students without an institution always use the built-in rules
+    pub async fn active_rules(pool: &SqlitePool, institution_id: Option<&str>) -> AppResult<(RiskRules, Option<i64>)> {
+        let rule_set = match institution_id {
+            Some(institution_id) => RiskRepository::get_active_rule_set(pool, institution_id).await?,
+            None => None,
+        };
+
+        match rule_set {
+            Some(rule_set) => Ok((serde_json::from_str(&rule_set.rules)?, Some(rule_set.version))),
+            None => Ok((RiskRules::default(), None)),
+        }
+    }
+
+    async fn recalculate_with(
+        pool: &SqlitePool,
+        rules: &RiskRules,
+        version: Option<i64>,
+        student: &Student,
+    ) -> AppResult<RiskAssessment> {
+        let inputs = gather_inputs(pool, rules, &student.id).await?;
+        let (score, level, factors) = evaluate(rules, &inputs);
+
+        let factors = serde_json::to_string(&factors)?;
+        let saved = RiskRepository::save_score(pool, &student.id, score, level, &factors, version).await?;
+        let assessment = Self::assessment(pool, saved).await?;
+        if RiskRepository::set_student_risk(pool, &student.id, &assessment.level).await? {
+            RiskAlertService::record_transition(pool, student, &assessment.level, assessment.score).await?;
+        }
+
+        Ok(assessment)
+    }
+
+    /// Recalculate the students, each with the rules of their institution
+    async fn recalculate_many(pool: &SqlitePool, student_ids: &[String]) -> AppResult<()> {
+        let mut rules_by_institution: HashMap<Option<String>, (RiskRules, Option<i64>)> = HashMap::new();
+
+        for student_id in student_ids {
+            let student = StudentRepository::get_by_id(pool, student_id).await?;
+            if !rules_by_institution.contains_key(&student.institutionId) {
+                let rules = Self::active_rules(pool, student.institutionId.as_deref()).await?;
+                rules_by_institution.insert(student.institutionId.clone(), rules);
+            }
+
+            let (rules, version) = &rules_by_institution[&student.institutionId];
+            Self::recalculate_with(pool, rules, *version, &student).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Recalculate every active student; returns the number recalculated
+    pub async fn recalculate_all(pool: &SqlitePool, viewer: &User) -> AppResult<usize> {
+        require_counselor(viewer)?;
+        Self::recalculate_stale(pool, 0).await
+    }
+
+    /// Recalculate active students whose score is older than `max_age_hours`;
+    /// 0 recalculates all of them. Returns the number recalculated.
+    pub async fn recalculate_stale(pool: &SqlitePool, max_age_hours: i64) -> AppResult<usize> {
+        let before = (Utc::now() - Duration::hours(max_age_hours)).to_rfc3339();
+        let student_ids = RiskRepository::get_stale_student_ids(pool, None, &before).await?;
+        Self::recalculate_many(pool, &student_ids).await?;
+
+        Ok(student_ids.len())
+    }
+
+    /// Recalculate the students of a counseling session, after its follow-ups change
+    pub async fn recalculate_session_students(pool: &SqlitePool, session_id: &str) -> AppResult<()> {
+        let student_ids = RiskRepository::get_session_student_ids(pool, session_id).await?;
+        Self::recalculate_many(pool, &student_ids).await
+    }
+
+    /// Rule sets of the viewer's institution, newest first
+    pub async fn get_rule_sets(pool: &SqlitePool, viewer: &User) -> AppResult<Vec<RiskRuleSet>> {
+        require_admin(viewer)?;
+        let institution_id = active_institution(viewer)?;
+        RiskRepository::get_rule_sets(pool, &institution_id).await
+    }
+
+    /// Save rules as a new, inactive version of the viewer's institution
+    pub async fn create_rule_set(
+        pool: &SqlitePool,
+        viewer: &User,
+        req: CreateRiskRuleSetRequest,
+    ) -> AppResult<RiskRuleSet> {
+        require_admin(viewer)?;
+        let institution_id = active_institution(viewer)?;
+
+        if req.name.trim().is_empty() {
+            return Err(AppError::ValidationError("Rule set name is required".to_string()));
+        }
+        let rules = parse_rules(req.rules)?;
+
+        RiskRepository::create_rule_set(
+            pool,
+            &institution_id,
+            req.name.trim(),
+            &serde_json::to_string(&rules)?,
+            req.notes.as_deref(),
+            &viewer.id,
+        )
+        .await
+    }
+
+    /// Score every active student of the rule set's institution with it without
+    /// saving anything, listing the students whose computed level would change.
+    pub async fn preview_rule_set(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<RiskRulePreview> {
+        require_admin(viewer)?;
+
+        let rule_set = accessible_rule_set(pool, viewer, id).await?;
+        let rules: RiskRules = serde_json::from_str(&rule_set.rules)?;
+        let today = local_today().format("%Y-%m-%d").to_string();
+        let students = RiskRepository::get_preview_students(pool, &rule_set.institutionId, &today).await?;
+
+        let mut current_levels = Vec::new();
+        let mut new_levels = Vec::new();
+        let mut changes = Vec::new();
+
+        for (student_id, student_name, current_score, current_level, overridden) in students {
+            let inputs = gather_inputs(pool, &rules, &student_id).await?;
+            let (score, level, _) = evaluate(&rules, &inputs);
+
+            if let Some(current) = &current_level {
+                current_levels.push(current.clone());
+            }
+            new_levels.push(level.to_string());
+
+            if current_level.as_deref() != Some(level) {
+                changes.push(RiskPreviewChange {
+                    studentId: student_id,
+                    studentName: student_name,
+                    currentScore: current_score,
+                    currentLevel: current_level,
+                    newScore: score,
+                    newLevel: level.to_string(),
+                    overridden,
+                });
+            }
+        }
+
+        Ok(RiskRulePreview {
+            ruleSetId: rule_set.id,
+            version: rule_set.version,
+            evaluatedCount: new_levels.len(),
+            currentLevelCounts: level_counts(&current_levels),
+            newLevelCounts: level_counts(&new_levels),
+            changes,
+        })
+    }
+
+    /// Activate a rule set of the viewer's institution, or the built-in rules
+    /// with `None`, and recalculate the institution's active students with it.
+    /// Returns the number recalculated.
+    pub async fn activate_rule_set(pool: &SqlitePool, viewer: &User, id: Option<&str>) -> AppResult<usize> {
+        require_admin(viewer)?;
+        let institution_id = active_institution(viewer)?;
+        RiskRepository::activate_rule_set(pool, &institution_id, id).await?;
+
+        let now = Utc::now().to_rfc3339();
+        let student_ids = RiskRepository::get_stale_student_ids(pool, Some(&institution_id), &now).await?;
+        Self::recalculate_many(pool, &student_ids).await?;
+
+        Ok(student_ids.len())
+    }
+
+    pub async fn delete_rule_set(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<()> {
+        require_admin(viewer)?;
+        accessible_rule_set(pool, viewer, id).await?;
+        RiskRepository::delete_rule_set(pool, id).await
+    }
+
+    /// Set the student's risk level by hand; it stays until cleared or `expiresAt` passes
+    pub async fn set_override(
+        pool: &SqlitePool,
+        viewer: &User,
+        student_id: &str,
+        req: SetRiskOverrideRequest,
+    ) -> AppResult<RiskAssessment> {
+        require_counselor(viewer)?;
+        StudentRepository::get_by_id(pool, student_id).await?;
+
+        if !RISK_LEVELS.contains(&req.level.as_str()) {
+            return Err(AppError::ValidationError(format!("Invalid risk level: {}", req.level)));
+        }
+        if req.reason.trim().is_empty() {
+            return Err(AppError::ValidationError("A reason is required to override the risk level".to_string()));
+        }
+        if let Some(expires_at) = &req.expiresAt {
+            let date = NaiveDate::parse_from_str(expires_at, "%Y-%m-%d")
+                .map_err(|_| AppError::ValidationError(format!("Invalid date: {}", expires_at)))?;
+            if date < local_today() {
+                return Err(AppError::ValidationError("Override expiry date is in the past".to_string()));
+            }
+        }
+
+        RiskRepository::set_override(
+            pool,
+            student_id,
+            &req.level,
+            req.reason.trim(),
+            &viewer.id,
+            req.expiresAt.as_deref(),
+        )
+        .await?;
+
+        Self::recalculate_student(pool, student_id).await
+    }
+
+    pub async fn clear_override(pool: &SqlitePool, viewer: &User, student_id: &str) -> AppResult<RiskAssessment> {
+        require_counselor(viewer)?;
+        RiskRepository::delete_override(pool, student_id).await?;
+        Self::recalculate_student(pool, student_id).await
+    }
+
+    /// Mark the answers of a survey question that raise a risk flag; an empty list clears them
+    pub async fn set_question_risk_answers(
+        pool: &SqlitePool,
+        viewer: &User,
+        question_id: &str,
+        answers: Vec<String>,
+    ) -> AppResult<()> {
+        require_counselor(viewer)?;
+
+        let mut cleaned: Vec<String> = Vec::new();
+        for answer in answers {
+            let answer = answer.trim().to_string();
+            if !answer.is_empty() && !cleaned.iter().any(|a| a.to_lowercase() == answer.to_lowercase()) {
+                cleaned.push(answer);
+            }
+        }
+
+        let risk_answers = if cleaned.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&cleaned)?)
+        };
+        RiskRepository::set_question_risk_answers(pool, question_id, risk_answers.as_deref()).await
+    }
+
+    async fn assessment(pool: &SqlitePool, score: RiskScore) -> AppResult<RiskAssessment> {
+        let today = local_today().format("%Y-%m-%d").to_string();
+        let risk_override = RiskRepository::get_active_override(pool, &score.studentId, &today).await?;
+        let factors: Vec<RiskFactorContribution> = serde_json::from_str(&score.factors)?;
+
+        Ok(RiskAssessment {
+            level: risk_override.as_ref().map(|o| o.level.clone()).unwrap_or_else(|| score.level.clone()),
+            studentId: score.studentId,
+            score: score.score,
+            computedLevel: score.level,
+            factors,
+            riskOverride: risk_override,
+            calculatedAt: score.calculatedAt,
+            ruleVersion: score.ruleVersion,
+        })
+    }
+}
+
+async fn gather_inputs(pool: &SqlitePool, rules: &RiskRules, student_id: &str) -> AppResult<RiskInputs> {
+    let today = local_today();
+    let since = (today - Duration::days(rules.lookbackDays)).format("%Y-%m-%d").to_string();
+    let today = today.format("%Y-%m-%d").to_string();
+
+    let school_year = school_year_and_term(local_today()).0;
+    let attendance = AttendanceRepository::get_year_summary(pool, student_id, &school_year).await?;
+    let thresholds = AttendanceRepository::get_thresholds(pool).await?;
+
+    let (open_follow_ups, overdue_follow_ups) = RiskRepository::count_open_follow_ups(pool, student_id, &today).await?;
+    let answers = RiskRepository::get_survey_answers(pool, student_id, &since).await?;
+    let questions = RiskRepository::get_flagging_questions(pool).await?;
+
+    Ok(RiskInputs {
+        unexcused_days: attendance.unexcusedDays,
+        total_days: attendance.totalDays,
+        unexcused_failure_days: thresholds.unexcusedFailureDays,
+        total_failure_days: thresholds.totalFailureDays,
+        incident_intensities: RiskRepository::get_incident_intensities(pool, student_id, &since).await?,
+        exam_nets: RiskRepository::get_exam_nets(pool, student_id).await?,
+        open_follow_ups,
+        overdue_follow_ups,
+        survey_flags: count_survey_flags(&answers, &questions),
+    })
+}
+
+/// Score a student: the sum of each factor's weight times the share of it
+/// reached, with the level from the rules' thresholds.
+pub fn evaluate(rules: &RiskRules, inputs: &RiskInputs) -> (f64, &'static str, Vec<RiskFactorContribution>) {
+    let mut factors = Vec::new();
+
+    let attendance_ratio = ratio(inputs.unexcused_days, inputs.unexcused_failure_days)
+        .max(ratio(inputs.total_days, inputs.total_failure_days));
+    factors.push(contribution(
+        RISK_FACTOR_ATTENDANCE,
+        "Devamsızlık",
+        rules.attendanceWeight,
+        attendance_ratio,
+        format!("Özürsüz {} gün, toplam {} gün", inputs.unexcused_days, inputs.total_days),
+    ));
+
+    let behavior_points: f64 = inputs
+        .incident_intensities
+        .iter()
+        .map(|intensity| intensity_points(rules, intensity.as_deref()))
+        .sum();
+    factors.push(contribution(
+        RISK_FACTOR_BEHAVIOR,
+        "Davranış olayları",
+        rules.behaviorWeight,
+        ratio(behavior_points, rules.behaviorMaxPoints),
+        format!(
+            "Son {} günde {} olay, {} puan",
+            rules.lookbackDays,
+            inputs.incident_intensities.len(),
+            behavior_points
+        ),
+    ));
+
+    let decline = exam_decline_percent(&inputs.exam_nets, rules.examWindow);
+    factors.push(contribution(
+        RISK_FACTOR_EXAM_DECLINE,
+        "Sınav netlerinde düşüş",
+        rules.examDeclineWeight,
+        ratio(decline.unwrap_or(0.0), rules.examDeclineMaxPercent),
+        match decline {
+            Some(decline) => format!("Son sınavlarda netler %{:.1} düştü", decline),
+            None => "Karşılaştırılacak düşüş yok".to_string(),
+        },
+    ));
+
+    let on_time = (inputs.open_follow_ups - inputs.overdue_follow_ups).max(0) as f64;
+    let follow_up_points = on_time + inputs.overdue_follow_ups as f64 * rules.overdueFollowUpPoints;
+    factors.push(contribution(
+        RISK_FACTOR_FOLLOW_UPS,
+        "Açık takipler",
+        rules.followUpWeight,
+        ratio(follow_up_points, rules.followUpMaxPoints),
+        format!("{} açık takip, {} gecikmiş", inputs.open_follow_ups, inputs.overdue_follow_ups),
+    ));
+
+    factors.push(contribution(
+        RISK_FACTOR_SURVEY_FLAGS,
+        "Anket işaretleri",
+        rules.surveyFlagWeight,
+        ratio(inputs.survey_flags as f64, rules.surveyFlagMax),
+        format!("Son {} günde {} riskli anket yanıtı", rules.lookbackDays, inputs.survey_flags),
+    ));
+
+    let score = round1(factors.iter().map(|f| f.points).sum());
+    (score, risk_level(rules, score, &factors), factors)
+}
+
+/// Level of a score by the thresholds, raised by any level rule whose factor reached its ratio
+pub fn risk_level(rules: &RiskRules, score: f64, factors: &[RiskFactorContribution]) -> &'static str {
+    let by_score = if score >= rules.highThreshold {
+        RISK_HIGH
+    } else if score >= rules.mediumThreshold {
+        RISK_MEDIUM
+    } else {
+        RISK_LOW
+    };
+
+    rules
+        .levelRules
+        .iter()
+        .filter(|rule| factors.iter().any(|f| f.factor == rule.factor && f.ratio >= rule.minRatio))
+        .filter_map(|rule| RISK_LEVELS.iter().copied().find(|level| *level == rule.level))
+        .fold(by_score, |level, raised| if level_rank(raised) > level_rank(level) { raised } else { level })
+}
+
+fn level_rank(level: &str) -> usize {
+    RISK_LEVELS.iter().position(|l| *l == level).unwrap_or(0)
+}
+
+/// Read rules from JSON, filling omitted keys with the defaults, and check them
+pub fn parse_rules(value: serde_json::Value) -> AppResult<RiskRules> {
+    let rules: RiskRules = serde_json::from_value(value)
+        .map_err(|e| AppError::ValidationError(format!("Invalid risk rules: {}", e)))?;
+    validate_rules(&rules)?;
+    Ok(rules)
+}
+
+pub fn validate_rules(rules: &RiskRules) -> AppResult<()> {
+    let invalid = |message: String| Err(AppError::ValidationError(message));
+
+    let weights = [
+        rules.attendanceWeight,
+        rules.behaviorWeight,
+        rules.examDeclineWeight,
+        rules.followUpWeight,
+        rules.surveyFlagWeight,
+        rules.lowIntensityPoints,
+        rules.mediumIntensityPoints,
+        rules.highIntensityPoints,
+        rules.overdueFollowUpPoints,
+    ];
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return invalid("Weights and points cannot be negative".to_string());
+    }
+
+    let maximums = [
+        rules.behaviorMaxPoints,
+        rules.examDeclineMaxPercent,
+        rules.followUpMaxPoints,
+        rules.surveyFlagMax,
+    ];
+    if maximums.iter().any(|m| !m.is_finite() || *m <= 0.0) {
+        return invalid("Factor maximums must be greater than zero".to_string());
+    }
+
+    if rules.lookbackDays < 1 || rules.examWindow < 1 {
+        return invalid("lookbackDays and examWindow must be at least 1".to_string());
+    }
+    if !(rules.mediumThreshold > 0.0 && rules.mediumThreshold < rules.highThreshold) {
+        return invalid("mediumThreshold must be above zero and below highThreshold".to_string());
+    }
+
+    for rule in &rules.levelRules {
+        if !RISK_FACTORS.contains(&rule.factor.as_str()) {
+            return invalid(format!("Unknown risk factor: {}", rule.factor));
+        }
+        if !RISK_LEVELS.contains(&rule.level.as_str()) {
+            return invalid(format!("Invalid risk level: {}", rule.level));
+        }
+        if !(rule.minRatio > 0.0 && rule.minRatio <= 1.0) {
+            return invalid(format!("minRatio of {} must be between 0 and 1", rule.factor));
+        }
+    }
+
+    Ok(())
+}
+
+fn level_counts(levels: &[String]) -> Vec<CategoryCount> {
+    RISK_LEVELS
+        .iter()
+        .map(|level| CategoryCount {
+            category: level.to_string(),
+            count: levels.iter().filter(|l| l == level).count() as i64,
+        })
+        .collect()
+}
+
+/// Largest drop, in percent, of the average net of the last `window` exams
+/// against the `window` exams before them, per exam type.
+///
+/// Types with fewer than two exams are skipped; `None` when nothing declined.
+pub fn exam_decline_percent(exam_nets: &[(String, String, f64)], window: usize) -> Option<f64> {
+    let mut by_type: HashMap<&str, Vec<f64>> = HashMap::new();
+    for (exam_type, _, net) in exam_nets {
+        by_type.entry(exam_type.as_str()).or_default().push(*net);
+    }
+
+    by_type
+        .values()
+        .filter_map(|nets| {
+            let size = window.min(nets.len() / 2);
+            if size == 0 {
+                return None;
+            }
+            let recent = &nets[nets.len() - size..];
+            let previous = &nets[nets.len() - 2 * size..nets.len() - size];
+            let previous_average = previous.iter().sum::<f64>() / size as f64;
+            let recent_average = recent.iter().sum::<f64>() / size as f64;
+
+            (previous_average > 0.0 && recent_average < previous_average)
+                .then(|| (previous_average - recent_average) / previous_average * 100.0)
+        })
+        .fold(None, |max: Option<f64>, decline| Some(max.map_or(decline, |m| m.max(decline))))
+}
+
+/// Count answers matching the flagged answers of their question.
+///
+/// `answers` are (templateId, responseData JSON object keyed by question id);
+/// `questions` are (questionId, templateId, riskAnswers JSON array).
+pub fn count_survey_flags(answers: &[(String, String)], questions: &[(String, String, String)]) -> i64 {
+    let mut flags = 0;
+
+    for (template_id, response_data) in answers {
+        let Ok(serde_json::Value::Object(response)) = serde_json::from_str::<serde_json::Value>(response_data) else {
+            continue;
+        };
+
+        for (question_id, _, risk_answers) in questions.iter().filter(|(_, t, _)| t == template_id) {
+            let Some(answer) = response.get(question_id) else {
+                continue;
+            };
+            let risk_answers: Vec<String> = serde_json::from_str(risk_answers).unwrap_or_default();
+            if answer_matches(answer, &risk_answers) {
+                flags += 1;
+            }
+        }
+    }
+
+    flags
+}
+
+fn answer_matches(answer: &serde_json::Value, risk_answers: &[String]) -> bool {
+    let matches = |value: &str| risk_answers.iter().any(|r| r.trim().to_lowercase() == value.trim().to_lowercase());
+
+    match answer {
+        serde_json::Value::String(value) => matches(value),
+        serde_json::Value::Number(value) => matches(&value.to_string()),
+        serde_json::Value::Bool(value) => matches(&value.to_string()),
+        serde_json::Value::Array(values) => values.iter().any(|v| answer_matches(v, risk_answers)),
+        _ => false,
+    }
+}
+
+fn intensity_points(rules: &RiskRules, intensity: Option<&str>) -> f64 {
+    match intensity.map(|i| i.trim().to_uppercase()).as_deref() {
+        Some("YÜKSEK") => rules.highIntensityPoints,
+        Some("ORTA") => rules.mediumIntensityPoints,
+        _ => rules.lowIntensityPoints,
+    }
+}
+
+fn contribution(factor: &str, label: &str, weight: f64, ratio: f64, detail: String) -> RiskFactorContribution {
+    RiskFactorContribution {
+        factor: factor.to_string(),
+        label: label.to_string(),
+        weight,
+        ratio: round1(ratio * 100.0) / 100.0,
+        points: round1(weight * ratio),
+        detail,
+    }
+}
+
+fn ratio(value: f64, max: f64) -> f64 {
+    if max <= 0.0 {
+        return 0.0;
+    }
+    (value / max).clamp(0.0, 1.0)
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn require_admin(viewer: &User) -> AppResult<()> {
+    if viewer.role != "admin" {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_counselor(viewer: &User) -> AppResult<()> {
+    if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(())
+}
+
+async fn accessible_rule_set(pool: &SqlitePool, viewer: &User, id: &str) -> AppResult<RiskRuleSet> {
+    let rule_set = RiskRepository::get_rule_set(pool, id).await?;
+    if !can_access(pool, viewer, &rule_set.institutionId).await? {
+        return Err(AppError::Unauthorized);
+    }
+    Ok(rule_set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exam(exam_type: &str, date: &str, net: f64) -> (String, String, f64) {
+        (exam_type.to_string(), date.to_string(), net)
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let rules = RiskRules::default();
+        let quiet = RiskInputs { unexcused_failure_days: 10.0, total_failure_days: 30.0, ..Default::default() };
+        let (score, level, factors) = evaluate(&rules, &quiet);
+        assert_eq!(score, 0.0);
+        assert_eq!(level, RISK_LOW);
+        assert_eq!(factors.len(), 5);
+
+        let troubled = RiskInputs {
+            unexcused_days: 5.0,
+            total_days: 6.0,
+            unexcused_failure_days: 10.0,
+            total_failure_days: 30.0,
+            incident_intensities: vec![Some("YÜKSEK".to_string()), Some("orta".to_string()), None],
+            exam_nets: vec![exam("TYT", "2024-10-01", 80.0), exam("TYT", "2024-11-01", 60.0)],
+            open_follow_ups: 2,
+            overdue_follow_ups: 1,
+            survey_flags: 3,
+        };
+        let (score, level, factors) = evaluate(&rules, &troubled);
+        // attendance 15 + behavior 25 + exam 20 + follow-ups 7.5 + survey 15
+        assert_eq!(factors[0].points, 15.0);
+        assert_eq!(factors[1].points, 25.0);
+        assert_eq!(factors[2].points, 20.0);
+        assert_eq!(factors[3].points, 7.5);
+        assert_eq!(score, 82.5);
+        assert_eq!(level, RISK_HIGH);
+        assert_eq!(risk_level(&rules, 30.0, &factors), RISK_MEDIUM);
+    }
+
+    #[test]
+    fn test_level_rules() {
+        let rules = parse_rules(serde_json::json!({
+            "highThreshold": 80,
+            "levelRules": [{ "factor": "BEHAVIOR", "minRatio": 1.0, "level": "Yüksek" }]
+        }))
+        .unwrap();
+        assert_eq!(rules.attendanceWeight, 30.0);
+
+        let inputs = RiskInputs {
+            incident_intensities: vec![Some("YÜKSEK".to_string()); 2],
+            ..Default::default()
+        };
+        let (score, level, _) = evaluate(&rules, &inputs);
+        assert_eq!(score, 25.0);
+        assert_eq!(level, RISK_HIGH);
+
+        assert!(parse_rules(serde_json::json!({ "attendanceWeigth": 10 })).is_err());
+        assert!(parse_rules(serde_json::json!({ "mediumThreshold": 70, "highThreshold": 60 })).is_err());
+        assert!(parse_rules(serde_json::json!({ "behaviorWeight": -5 })).is_err());
+        assert!(parse_rules(serde_json::json!({
+            "levelRules": [{ "factor": "GRADES", "minRatio": 0.5, "level": "Orta" }]
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_exam_decline_percent() {
+        let nets = vec![
+            exam("TYT", "2024-09-01", 50.0),
+            exam("AYT", "2024-09-15", 40.0),
+            exam("TYT", "2024-10-01", 50.0),
+            exam("TYT", "2024-11-01", 40.0),
+            exam("TYT", "2024-12-01", 40.0),
+            exam("AYT", "2024-12-15", 38.0),
+        ];
+        let decline = exam_decline_percent(&nets, 3).unwrap();
+        assert!((decline - 20.0).abs() < 1e-9);
+
+        let rising = vec![exam("TYT", "2024-09-01", 40.0), exam("TYT", "2024-10-01", 50.0)];
+        assert_eq!(exam_decline_percent(&rising, 3), None);
+        assert_eq!(exam_decline_percent(&rising[..1], 3), None);
+    }
+
+    #[test]
+    fn test_count_survey_flags() {
+        let questions = vec![
+            ("q1".to_string(), "t1".to_string(), r#"["Hiçbir zaman","Nadiren"]"#.to_string()),
+            ("q2".to_string(), "t1".to_string(), r#"["1"]"#.to_string()),
+            ("q3".to_string(), "t2".to_string(), r#"["Evet"]"#.to_string()),
+        ];
+        let answers = vec![
+            ("t1".to_string(), r#"{"q1":"nadiren","q2":1,"q3":"Evet"}"#.to_string()),
+            ("t2".to_string(), r#"{"q3":["Hayır","Evet"]}"#.to_string()),
+            ("t1".to_string(), "not json".to_string()),
+        ];
+        assert_eq!(count_survey_flags(&answers, &questions), 3);
+    }
+
+    fn admin(institution_id: &str) -> User {
+        User {
+            id: "a1".to_string(),
+            name: "Admin".to_string(),
+            email: "a1@okul.test".to_string(),
+            passwordHash: String::new(),
+            role: "admin".to_string(),
+            institution: "Okul".to_string(),
+            isActive: true,
+            isDistrictAdmin: false,
+            activeInstitutionId: Some(institution_id.to_string()),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rule_sets_are_per_institution() {
+        let pool = crate::database::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, name, email, passwordHash, role, institution) VALUES
+                ('a1', 'Admin', 'a1@okul.test', '', 'admin', 'Okul');
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Okul 1'), ('i2', 'Okul 2');
+            INSERT INTO user_institutions (userId, institutionId) VALUES ('a1', 'i1');
+            INSERT INTO students (id, name, surname, enrollmentDate, institutionId) VALUES
+                ('s1', 'Ali', 'Yılmaz', '2024-09-01', 'i1'),
+                ('s2', 'Ayşe', 'Kaya', '2024-09-01', 'i2');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let request = CreateRiskRuleSetRequest { name: "Kurallar".to_string(), rules: serde_json::json!({}), notes: None };
+        let rule_set = RiskService::create_rule_set(&pool, &admin("i1"), request).await.unwrap();
+        assert_eq!(rule_set.institutionId, "i1");
+
+        assert!(RiskService::activate_rule_set(&pool, &admin("i2"), Some(&rule_set.id)).await.is_err());
+        assert_eq!(RiskService::activate_rule_set(&pool, &admin("i1"), Some(&rule_set.id)).await.unwrap(), 1);
+
+        assert_eq!(RiskService::get_assessment(&pool, "s1").await.unwrap().ruleVersion, Some(1));
+        assert_eq!(RiskService::get_assessment(&pool, "s2").await.unwrap().ruleVersion, None);
+    }
+}
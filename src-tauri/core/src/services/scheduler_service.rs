@@ -0,0 +1,62 @@
+use std::time::Duration;
+use log::{error, info};
+use sqlx::SqlitePool;
+use crate::services::academic_goal_service::AcademicGoalService;
+use crate::services::bep_service::{BepService, DEFAULT_REVIEW_REMINDER_DAYS};
+use crate::services::intervention_service::{InterventionService, DEFAULT_REMINDER_DAYS};
+use crate::services::risk_alert_service::RiskAlertService;
+use crate::services::risk_service::{RiskService, RISK_RECALCULATION_HOURS};
+use crate::services::study_service::StudyService;
+
+/// How often time-based jobs run while the application is open
+pub const SCHEDULER_INTERVAL_MINUTES: u64 = 15;
+
+pub struct SchedulerService;
+
+impl SchedulerService {
+    /// Run the time-based jobs now and then every `SCHEDULER_INTERVAL_MINUTES`
+    /// for as long as the application runs
+    pub async fn run(pool: SqlitePool) {
+        let mut interval = tokio::time::interval(Duration::from_secs(SCHEDULER_INTERVAL_MINUTES * 60));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        info!("Scheduler started, running jobs every {} minutes", SCHEDULER_INTERVAL_MINUTES);
+        loop {
+            interval.tick().await;
+            Self::run_jobs(&pool).await;
+        }
+    }
+
+    /// Run each job once; a failing job is logged and does not stop the others
+    pub async fn run_jobs(pool: &SqlitePool) {
+        // Remind owners of interventions whose target date is near or past
+        if let Err(e) = InterventionService::send_due_reminders(pool, DEFAULT_REMINDER_DAYS).await {
+            error!("Failed to create intervention reminders: {}", e);
+        }
+
+        // Remind BEP teams of periodic evaluations that are near or past
+        if let Err(e) = BepService::send_review_reminders(pool, DEFAULT_REVIEW_REMINDER_DAYS).await {
+            error!("Failed to create BEP review reminders: {}", e);
+        }
+
+        // Refresh risk scores that were not recalculated recently
+        if let Err(e) = RiskService::recalculate_stale(pool, RISK_RECALCULATION_HOURS).await {
+            error!("Failed to recalculate risk scores: {}", e);
+        }
+
+        // Mark active academic goals whose deadline has passed as missed
+        if let Err(e) = AcademicGoalService::close_overdue(pool).await {
+            error!("Failed to close overdue academic goals: {}", e);
+        }
+
+        // Mark pending study assignments past their due date as overdue
+        if let Err(e) = StudyService::mark_overdue(pool).await {
+            error!("Failed to mark overdue study assignments: {}", e);
+        }
+
+        // Escalate unacknowledged risk alerts and send those held back by quiet hours
+        if let Err(e) = RiskAlertService::process_alerts(pool).await {
+            error!("Failed to process risk alerts: {}", e);
+        }
+    }
+}
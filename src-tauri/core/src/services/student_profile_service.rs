@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use sqlx::SqlitePool;
+use crate::error::AppResult;
+use crate::models::{
+    BehaviorIncident, CategoryCount, CounselingSession, StudentProfile, User, TIMELINE_AI_SUGGESTION,
+    TIMELINE_BEHAVIOR_INCIDENT, TIMELINE_COUNSELING_SESSION, TIMELINE_INTERVENTION,
+};
+use crate::repositories::{
+    AcademicRepository, AiSuggestionRepository, AttendanceRepository, GuardianRepository, StudentProfileRepository,
+};
+use crate::services::attendance_service::{local_today, school_year_and_term};
+use crate::services::institution_service::accessible_student;
+use crate::services::timeline_service::{confidential_access, visible_event_types};
+
+const RECENT_LIMIT: i64 = 5;
+
+pub struct StudentProfileService;
+
+impl StudentProfileService {
+    /// Compose the full student profile: the student, their guardians, summary
+    /// counts and the most recent records of each kind. Attendance totals are
+    /// for the current school year.
+    ///
+    /// Sections follow the viewer's timeline access: records of event types
+    /// the role may not see are left empty, confidential sessions are shown to
+    /// admins and their counselor, and teachers get sessions without content.
+    pub async fn get_profile(pool: &SqlitePool, viewer: &User, student_id: &str) -> AppResult<StudentProfile> {
+        let visible = visible_event_types(&viewer.role)?;
+        let access = confidential_access(viewer);
+        let student = accessible_student(pool, viewer, student_id).await?;
+        let (school_year, _) = school_year_and_term(local_today());
+
+        let (guardians, mut summary, recent_exams, incidents, mut recent_sessions, open_follow_ups) = tokio::try_join!(
+            GuardianRepository::get_by_student(pool, student_id),
+            StudentProfileRepository::get_summary(pool, student_id, &access),
+            StudentProfileRepository::get_recent_exams(pool, student_id, RECENT_LIMIT),
+            StudentProfileRepository::get_incidents(pool, student_id),
+            StudentProfileRepository::get_recent_sessions(pool, student_id, &access, RECENT_LIMIT),
+            StudentProfileRepository::get_open_follow_ups(pool, student_id, &access),
+        )?;
+        let (attendance, active_goals, active_interventions, ai_suggestions) = tokio::try_join!(
+            AttendanceRepository::get_year_summary(pool, student_id, &school_year),
+            AcademicRepository::get_academic_goals_by_student(pool, student_id),
+            StudentProfileRepository::get_active_interventions(pool, student_id),
+            AiSuggestionRepository::get_by_student(pool, student_id),
+        )?;
+
+        let sees = |event_type: &str| visible.contains(&event_type);
+        let incidents = if sees(TIMELINE_BEHAVIOR_INCIDENT) { incidents } else { Vec::new() };
+        let active_interventions = if sees(TIMELINE_INTERVENTION) { active_interventions } else { Vec::new() };
+        let ai_suggestions = if sees(TIMELINE_AI_SUGGESTION) { ai_suggestions } else { Vec::new() };
+        let (recent_sessions, open_follow_ups) = if !sees(TIMELINE_COUNSELING_SESSION) {
+            summary.sessionCount = 0;
+            summary.lastSessionDate = None;
+            (Vec::new(), Vec::new())
+        } else if viewer.role == "teacher" {
+            recent_sessions.iter_mut().for_each(redact_session);
+            (recent_sessions, Vec::new())
+        } else {
+            (recent_sessions, open_follow_ups)
+        };
+
+        summary.incidentCount = incidents.len() as i64;
+        summary.openIncidentCount = incidents.iter().filter(|i| i.status == "Açık").count() as i64;
+        summary.openFollowUpCount = open_follow_ups.len() as i64;
+        summary.activeGoalCount = active_goals.len() as i64;
+        summary.activeInterventionCount = active_interventions.len() as i64;
+        summary.pendingSuggestionCount = ai_suggestions.len() as i64;
+
+        Ok(StudentProfile {
+            student,
+            guardians,
+            summary,
+            latestExam: recent_exams.first().cloned(),
+            recentExams: recent_exams,
+            incidentsByCategory: count_by_category(&incidents),
+            recentIncidents: incidents.into_iter().take(RECENT_LIMIT as usize).collect(),
+            recentSessions: recent_sessions,
+            openFollowUps: open_follow_ups,
+            attendance,
+            activeGoals: active_goals,
+            activeInterventions: active_interventions,
+            aiSuggestions: ai_suggestions,
+        })
+    }
+}
+
+/// Incident counts per category, most frequent first
+fn count_by_category(incidents: &[BehaviorIncident]) -> Vec<CategoryCount> {
+    let mut counts: HashMap<&str, i64> = HashMap::new();
+    for incident in incidents {
+        *counts.entry(incident.behaviorCategory.as_str()).or_default() += 1;
+    }
+
+    let mut counts: Vec<CategoryCount> = counts
+        .into_iter()
+        .map(|(category, count)| CategoryCount { category: category.to_string(), count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.category.cmp(&b.category)));
+
+    counts
+}
+
+/// Keep that a session happened but not what was discussed, as the timeline does for teachers
+fn redact_session(session: &mut CounselingSession) {
+    session.topic = "Rehberlik görüşmesi".to_string();
+    session.otherParticipantDescription = None;
+    session.disciplineStatus = None;
+    session.institutionalCooperation = None;
+    session.sessionDetails = None;
+    session.detailedNotes = None;
+    session.sessionFlow = None;
+    session.studentParticipationLevel = None;
+    session.cooperationLevel = None;
+    session.emotionalState = None;
+    session.physicalState = None;
+    session.communicationQuality = None;
+    session.sessionTags = None;
+    session.achievedOutcomes = None;
+    session.followUpPlan = None;
+    session.actionItems = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+    use crate::error::AppError;
+
+    fn viewer(id: &str, role: &str) -> User {
+        User {
+            id: id.to_string(),
+            name: id.to_string(),
+            email: format!("{}@okul.test", id),
+            passwordHash: String::new(),
+            role: role.to_string(),
+            institution: "Okul".to_string(),
+            isActive: true,
+            isDistrictAdmin: false,
+            activeInstitutionId: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    async fn setup() -> SqlitePool {
+        let pool = test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO students (id, name, surname, enrollmentDate) VALUES ('s1', 'Ali', 'Yılmaz', '2024-09-01');
+            INSERT INTO behavior_incidents (
+                id, studentId, incidentDate, incidentTime, location, behaviorType, behaviorCategory, description, recordedBy
+            ) VALUES
+                ('b1', 's1', '2024-10-01', '10:00', 'Sınıf', 'Kavga', 'Saldırganlık', 'x', 'u1'),
+                ('b2', 's1', '2024-10-02', '10:00', 'Sınıf', 'Kavga', 'Saldırganlık', 'x', 'u1'),
+                ('b3', 's1', '2024-10-03', '10:00', 'Sınıf', 'Konuşma', 'Dikkat', 'x', 'u1');
+            INSERT INTO exam_results (id, studentId, examType, examName, examDate) VALUES
+                ('e1', 's1', 'TYT', 'Deneme 1', '2024-10-01'), ('e2', 's1', 'TYT', 'Deneme 2', '2024-11-01');
+            INSERT INTO counseling_sessions (
+                id, sessionType, counselorId, sessionDate, entryTime, topic, participantType, sessionMode,
+                sessionLocation, sessionDetails, isConfidential
+            ) VALUES
+                ('c1', 'individual', 'u1', '2024-10-01', '10:00', 'Sınav kaygısı', 'öğrenci', 'yüz_yüze', 'Oda', 'ayrıntı', 0),
+                ('c2', 'individual', 'u1', '2024-10-02', '10:00', 'Aile', 'öğrenci', 'yüz_yüze', 'Oda', 'gizli', 1);
+            INSERT INTO counseling_session_students (sessionId, studentId) VALUES ('c1', 's1'), ('c2', 's1');
+            INSERT INTO counseling_follow_ups (id, sessionId, followUpDate, assignedTo, actionItems) VALUES
+                ('f1', 'c1', '2024-10-10', 'u1', 'Veli ara'), ('f2', 'c2', '2024-10-10', 'u1', 'Gizli iş');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_profile_sections() {
+        let pool = setup().await;
+
+        let profile = StudentProfileService::get_profile(&pool, &viewer("u1", "counselor"), "s1").await.unwrap();
+
+        assert_eq!(profile.summary.examCount, 2);
+        assert_eq!(profile.latestExam.map(|e| e.id).as_deref(), Some("e2"));
+        assert_eq!((profile.summary.incidentCount, profile.summary.openIncidentCount), (3, 3));
+        assert_eq!(profile.incidentsByCategory[0].category, "Saldırganlık");
+        assert_eq!(profile.incidentsByCategory[0].count, 2);
+        assert_eq!(profile.recentSessions.len(), 2);
+        assert_eq!(profile.summary.openFollowUpCount, 2);
+        assert!(StudentProfileService::get_profile(&pool, &viewer("u1", "counselor"), "none").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_confidential_sessions_hidden_from_other_counselors() {
+        let pool = setup().await;
+
+        let profile = StudentProfileService::get_profile(&pool, &viewer("u2", "counselor"), "s1").await.unwrap();
+
+        let ids: Vec<&str> = profile.recentSessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["c1"]);
+        assert_eq!(profile.summary.sessionCount, 1);
+        assert_eq!(profile.openFollowUps.len(), 1);
+
+        let profile = StudentProfileService::get_profile(&pool, &viewer("u3", "admin"), "s1").await.unwrap();
+        assert_eq!(profile.recentSessions.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sessions_redacted_for_teachers_and_hidden_from_observers() {
+        let pool = setup().await;
+
+        let profile = StudentProfileService::get_profile(&pool, &viewer("t1", "teacher"), "s1").await.unwrap();
+        assert_eq!(profile.recentSessions.len(), 1);
+        assert_eq!(profile.recentSessions[0].topic, "Rehberlik görüşmesi");
+        assert_eq!(profile.recentSessions[0].sessionDetails, None);
+        assert!(profile.openFollowUps.is_empty());
+
+        let profile = StudentProfileService::get_profile(&pool, &viewer("o1", "observer"), "s1").await.unwrap();
+        assert!(profile.recentSessions.is_empty());
+        assert!(profile.recentIncidents.is_empty());
+        assert_eq!(profile.summary.sessionCount, 0);
+        assert_eq!(profile.summary.examCount, 2);
+    }
+
+    #[tokio::test]
+    async fn test_profile_of_other_institution_refused() {
+        let pool = setup().await;
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, name, email, passwordHash, role, institution) VALUES
+                ('u1', 'Rehber', 'u1@okul.test', '', 'counselor', 'Okul');
+            INSERT INTO institutions (id, name) VALUES ('i1', 'Okul 1'), ('i2', 'Okul 2');
+            INSERT INTO user_institutions (userId, institutionId) VALUES ('u1', 'i1');
+            UPDATE students SET institutionId = 'i2' WHERE id = 's1';
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let result = StudentProfileService::get_profile(&pool, &viewer("u1", "counselor"), "s1").await;
+        assert!(matches!(result, Err(AppError::Unauthorized)));
+
+        sqlx::query("UPDATE students SET institutionId = 'i1' WHERE id = 's1'").execute(&pool).await.unwrap();
+        assert!(StudentProfileService::get_profile(&pool, &viewer("u1", "counselor"), "s1").await.is_ok());
+    }
+}
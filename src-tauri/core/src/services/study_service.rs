@@ -0,0 +1,751 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use log::info;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AcademicGoal, CreateStudyAssignmentRequest, ExamResult, Progress, RecordStudySessionRequest, StudyAssignment,
+    StudyAssignmentDetail, StudyPlanDay, StudyPlanItem, StudyPlanTopic, StudySession, Topic, TopicProgress,
+    UpcomingExam, UpdateProgressRequest, UpdateStudyAssignmentRequest, User, WeeklyStudyPlan, ASSIGNMENT_COMPLETED,
+    ASSIGNMENT_OVERDUE, ASSIGNMENT_PENDING, ASSIGNMENT_STATUSES, EXAM_TYPE_AYT, EXAM_TYPE_TYT, EXAM_TYPE_YDT,
+    EXAM_TYPE_YKS, TOPIC_HARD, TOPIC_MEDIUM,
+};
+use crate::repositories::{AcademicRepository, CurriculumRepository, StudentRepository, StudyRepository};
+use crate::services::attendance_service::local_today;
+use crate::services::exam_topic_service::{ExamTopicService, WEAK_SUCCESS_RATE};
+
+pub const DEFAULT_DAILY_MINUTES: i32 = 120;
+/// Longest single block of one topic in a day of the plan
+pub const STUDY_BLOCK_MINUTES: i32 = 45;
+/// Most time the plan gives one topic in a week
+pub const MAX_TOPIC_MINUTES_PER_WEEK: i32 = 180;
+/// Exams further away than this do not raise a topic's priority
+pub const EXAM_HORIZON_DAYS: i64 = 180;
+
+const MAX_SESSION_MINUTES: i32 = 720;
+const LOW_EFFICIENCY: f64 = 60.0;
+
+pub struct StudyService;
+
+impl StudyService {
+    /// Save a study session and add its minutes to the student's progress on
+    /// the topic. Finishing the topic completes its open assignments.
+    pub async fn record_session(pool: &SqlitePool, req: RecordStudySessionRequest) -> AppResult<StudySession> {
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+        let topic = CurriculumRepository::get_topic_by_id(pool, &req.topicId).await?;
+
+        let minutes = session_minutes(&req.startTime, req.endTime.as_deref(), req.duration)?;
+        if let Some(efficiency) = req.efficiency {
+            validate_efficiency(efficiency)?;
+        }
+
+        let existing = StudyRepository::get_progress(pool, &req.studentId, &req.topicId).await?;
+        let mut progress = apply_minutes(
+            existing.unwrap_or_else(|| new_progress(&req.studentId, &topic)),
+            topic.estimatedHours,
+            minutes,
+        );
+        if progress.lastStudied.as_deref().is_none_or(|last| last < req.startTime.as_str()) {
+            progress.lastStudied = Some(req.startTime.clone());
+        }
+
+        let session = StudySession {
+            id: String::new(),
+            studentId: req.studentId,
+            topicId: req.topicId,
+            startTime: req.startTime,
+            endTime: req.endTime.and_then(non_empty),
+            duration: Some(minutes),
+            notes: req.notes.and_then(non_empty),
+            efficiency: req.efficiency,
+            created_at: String::new(),
+        };
+        let mut tx = pool.begin().await?;
+        let session = StudyRepository::create_session(&mut *tx, &session).await?;
+        StudyRepository::save_progress(&mut *tx, &progress).await?;
+        if progress.remaining == 0 {
+            StudyRepository::complete_topic_assignments(&mut *tx, &session.studentId, &session.topicId).await?;
+        }
+        tx.commit().await?;
+
+        Ok(session)
+    }
+
+    /// Delete a session and take its minutes back off the student's progress
+    pub async fn delete_session(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        let session = StudyRepository::get_session_by_id(pool, id).await?;
+        let topic = CurriculumRepository::get_topic_by_id(pool, &session.topicId).await?;
+
+        let existing = StudyRepository::get_progress(pool, &session.studentId, &session.topicId).await?;
+        let progress = apply_minutes(
+            existing.unwrap_or_else(|| new_progress(&session.studentId, &topic)),
+            topic.estimatedHours,
+            -session.duration.unwrap_or(0),
+        );
+
+        StudyRepository::delete_session(pool, id, &progress).await
+    }
+
+    pub async fn get_progress(pool: &SqlitePool, student_id: &str) -> AppResult<Vec<TopicProgress>> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+        StudyRepository::get_progress_by_student(pool, student_id).await
+    }
+
+    /// Correct a student's progress on a topic. Setting nothing remaining
+    /// completes the topic's open assignments.
+    pub async fn update_progress(
+        pool: &SqlitePool,
+        student_id: &str,
+        topic_id: &str,
+        req: UpdateProgressRequest,
+    ) -> AppResult<Progress> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+        let topic = CurriculumRepository::get_topic_by_id(pool, topic_id).await?;
+        let mut progress = StudyRepository::get_progress(pool, student_id, topic_id)
+            .await?
+            .unwrap_or_else(|| new_progress(student_id, &topic));
+
+        if let Some(completed) = req.completed {
+            progress.completed = completed;
+        }
+        if let Some(remaining) = req.remaining {
+            progress.remaining = remaining;
+        }
+        if let Some(notes) = req.notes {
+            progress.notes = non_empty(notes);
+        }
+        if progress.completed < 0 || progress.remaining < 0 {
+            return Err(AppError::ValidationError("Progress minutes cannot be negative".to_string()));
+        }
+
+        let mut tx = pool.begin().await?;
+        let progress = StudyRepository::save_progress(&mut *tx, &progress).await?;
+        if progress.remaining == 0 {
+            StudyRepository::complete_topic_assignments(&mut *tx, student_id, topic_id).await?;
+        }
+        tx.commit().await?;
+
+        Ok(progress)
+    }
+
+    pub async fn create_assignment(
+        pool: &SqlitePool,
+        viewer: &User,
+        req: CreateStudyAssignmentRequest,
+    ) -> AppResult<StudyAssignment> {
+        StudentRepository::get_by_id(pool, &req.studentId).await?;
+        CurriculumRepository::get_topic_by_id(pool, &req.topicId).await?;
+        let due = parse_date(&req.dueDate)?;
+
+        let assignment = StudyAssignment {
+            id: String::new(),
+            studentId: req.studentId,
+            topicId: req.topicId,
+            dueDate: due.format("%Y-%m-%d").to_string(),
+            status: ASSIGNMENT_PENDING.to_string(),
+            notes: req.notes.and_then(non_empty),
+            assignedBy: Some(viewer.id.clone()),
+            completedAt: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let today = local_today();
+        let assignment = StudyAssignment {
+            status: assignment_status(&assignment, today).to_string(),
+            ..assignment
+        };
+
+        StudyRepository::create_assignment(pool, &assignment).await
+    }
+
+    /// The student's assignments, with overdue ones marked first
+    pub async fn get_assignments(
+        pool: &SqlitePool,
+        student_id: &str,
+        status: Option<&str>,
+    ) -> AppResult<Vec<StudyAssignmentDetail>> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+        Self::mark_overdue(pool).await?;
+        StudyRepository::get_assignments_by_student(pool, student_id, status).await
+    }
+
+    pub async fn update_assignment(
+        pool: &SqlitePool,
+        id: &str,
+        req: UpdateStudyAssignmentRequest,
+    ) -> AppResult<StudyAssignment> {
+        let mut assignment = StudyRepository::get_assignment_by_id(pool, id).await?;
+
+        if let Some(due_date) = req.dueDate {
+            assignment.dueDate = parse_date(&due_date)?.format("%Y-%m-%d").to_string();
+        }
+        if let Some(notes) = req.notes {
+            assignment.notes = non_empty(notes);
+        }
+        match req.status {
+            Some(status) if !ASSIGNMENT_STATUSES.contains(&status.as_str()) => {
+                return Err(AppError::ValidationError(format!("Invalid assignment status: {}", status)));
+            }
+            Some(status) if status == ASSIGNMENT_COMPLETED => {
+                if assignment.status != ASSIGNMENT_COMPLETED {
+                    assignment.completedAt = Some(Utc::now().to_rfc3339());
+                }
+                assignment.status = status;
+            }
+            // Reopening: pending or overdue follows from the due date
+            Some(_) => {
+                assignment.status = ASSIGNMENT_PENDING.to_string();
+                assignment.completedAt = None;
+            }
+            None => {}
+        }
+        assignment.status = assignment_status(&assignment, local_today()).to_string();
+
+        StudyRepository::update_assignment(pool, &assignment).await
+    }
+
+    pub async fn delete_assignment(pool: &SqlitePool, id: &str) -> AppResult<()> {
+        StudyRepository::delete_assignment(pool, id).await
+    }
+
+    /// Mark pending assignments past their due date as overdue
+    pub async fn mark_overdue(pool: &SqlitePool) -> AppResult<u64> {
+        let today = local_today().format("%Y-%m-%d").to_string();
+        let marked = StudyRepository::mark_overdue_assignments(pool, &today).await?;
+        if marked > 0 {
+            info!("Marked {} study assignments overdue", marked);
+        }
+        Ok(marked)
+    }
+
+    /// A plan for the seven days from `week_start` (today by default), giving
+    /// time first to overdue and due assignments, unfinished topics, topics
+    /// weak in exams or studied with low efficiency, and topics of the
+    /// student's nearest exams.
+    pub async fn weekly_plan(
+        pool: &SqlitePool,
+        student_id: &str,
+        week_start: Option<&str>,
+        daily_minutes: Option<i32>,
+    ) -> AppResult<WeeklyStudyPlan> {
+        StudentRepository::get_by_id(pool, student_id).await?;
+        let today = local_today();
+        let week_start = match week_start {
+            Some(date) => parse_date(date)?,
+            None => today,
+        };
+        let daily_minutes = daily_minutes.unwrap_or(DEFAULT_DAILY_MINUTES);
+        if !(STUDY_BLOCK_MINUTES..=MAX_SESSION_MINUTES).contains(&daily_minutes) {
+            return Err(AppError::ValidationError(format!(
+                "Daily study time must be between {} and {} minutes",
+                STUDY_BLOCK_MINUTES, MAX_SESSION_MINUTES
+            )));
+        }
+
+        Self::mark_overdue(pool).await?;
+        let goals = AcademicRepository::get_academic_goals_by_student(pool, student_id).await?;
+        let results = AcademicRepository::get_exam_results_by_student(pool, student_id).await?;
+        let exams = upcoming_exams(&goals, &results, today);
+
+        let topics = Self::plan_topics(pool, student_id, &exams).await?;
+        let (days, unscheduled_minutes) = build_weekly_plan(&topics, &exams, week_start, today, daily_minutes);
+
+        Ok(WeeklyStudyPlan {
+            studentId: student_id.to_string(),
+            weekStart: week_start.format("%Y-%m-%d").to_string(),
+            dailyMinutes: daily_minutes,
+            upcomingExams: exams,
+            days,
+            unscheduledMinutes: unscheduled_minutes,
+        })
+    }
+
+    /// Topics the student has open assignments on, has started, is weak in
+    /// according to exam topic results, or will be asked in an upcoming exam
+    async fn plan_topics(pool: &SqlitePool, student_id: &str, exams: &[UpcomingExam]) -> AppResult<Vec<StudyPlanTopic>> {
+        let mut topics: Vec<StudyPlanTopic> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+
+        let progress = StudyRepository::get_progress_by_student(pool, student_id).await?;
+        for entry in &progress {
+            index.insert(entry.progress.topicId.clone(), topics.len());
+            topics.push(StudyPlanTopic {
+                topicId: entry.progress.topicId.clone(),
+                topicName: entry.topicName.clone(),
+                subjectName: entry.subjectName.clone(),
+                examType: entry.examType.clone(),
+                difficulty: entry.difficulty.clone(),
+                remainingMinutes: entry.progress.remaining,
+                completion: completion(entry.progress.completed, entry.progress.remaining),
+                averageEfficiency: entry.averageEfficiency,
+                examSuccessRate: None,
+                assignmentId: None,
+                dueDate: None,
+                isOverdue: false,
+            });
+        }
+
+        let assignments = StudyRepository::get_assignments_by_student(pool, student_id, None).await?;
+        for detail in assignments.iter().filter(|a| a.assignment.status != ASSIGNMENT_COMPLETED) {
+            let assignment = &detail.assignment;
+            let position = Self::plan_topic_position(pool, &mut topics, &mut index, &assignment.topicId).await?;
+
+            // Assignments come by due date, so the first open one is kept
+            let topic = &mut topics[position];
+            if topic.assignmentId.is_none() {
+                topic.assignmentId = Some(assignment.id.clone());
+                topic.dueDate = Some(assignment.dueDate.clone());
+                topic.isOverdue = assignment.status == ASSIGNMENT_OVERDUE;
+            }
+        }
+
+        let analysis = ExamTopicService::analyze_student(pool, student_id).await?;
+        for weak in &analysis.weakTopics {
+            let Some(topic_id) = &weak.topicId else {
+                continue;
+            };
+            let position = Self::plan_topic_position(pool, &mut topics, &mut index, topic_id).await?;
+            topics[position].examSuccessRate = Some(weak.successRate);
+        }
+
+        let mut exam_types: Vec<&str> = exams.iter().flat_map(|exam| covered_exam_types(&exam.examType)).collect();
+        exam_types.sort_unstable();
+        exam_types.dedup();
+        for exam_type in exam_types {
+            for subject in CurriculumRepository::get_subjects(pool, Some(exam_type), false).await? {
+                for topic in CurriculumRepository::get_topics(pool, &subject.id, false).await? {
+                    if !index.contains_key(&topic.id) {
+                        index.insert(topic.id.clone(), topics.len());
+                        topics.push(untouched_topic(&topic, subject.name.clone(), subject.examType.clone()));
+                    }
+                }
+            }
+        }
+
+        Ok(topics)
+    }
+
+    /// Index of a topic among the plan topics, adding it when missing
+    async fn plan_topic_position(
+        pool: &SqlitePool,
+        topics: &mut Vec<StudyPlanTopic>,
+        index: &mut HashMap<String, usize>,
+        topic_id: &str,
+    ) -> AppResult<usize> {
+        if let Some(&position) = index.get(topic_id) {
+            return Ok(position);
+        }
+
+        let topic = CurriculumRepository::get_topic_by_id(pool, topic_id).await?;
+        let subject = AcademicRepository::get_subject_by_id(pool, &topic.subjectId).await?;
+        index.insert(topic.id.clone(), topics.len());
+        topics.push(untouched_topic(&topic, subject.name, subject.examType));
+        Ok(topics.len() - 1)
+    }
+}
+
+fn untouched_topic(topic: &Topic, subject_name: String, exam_type: Option<String>) -> StudyPlanTopic {
+    StudyPlanTopic {
+        topicId: topic.id.clone(),
+        topicName: topic.name.clone(),
+        subjectName: subject_name,
+        examType: exam_type,
+        difficulty: topic.difficulty.clone(),
+        remainingMinutes: topic.estimatedHours * 60,
+        completion: 0.0,
+        averageEfficiency: None,
+        examSuccessRate: None,
+        assignmentId: None,
+        dueDate: None,
+        isOverdue: false,
+    }
+}
+
+fn new_progress(student_id: &str, topic: &Topic) -> Progress {
+    Progress {
+        id: String::new(),
+        studentId: student_id.to_string(),
+        topicId: topic.id.clone(),
+        completed: 0,
+        remaining: topic.estimatedHours * 60,
+        lastStudied: None,
+        notes: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    }
+}
+
+/// Add studied minutes to a topic's progress, or take them off when negative.
+/// Taking minutes off gives back remaining time up to the topic's estimate.
+pub fn apply_minutes(mut progress: Progress, estimated_hours: i32, minutes: i32) -> Progress {
+    progress.completed = (progress.completed + minutes).max(0);
+    progress.remaining = if minutes >= 0 {
+        (progress.remaining - minutes).max(0)
+    } else {
+        progress.remaining.max(estimated_hours * 60 - progress.completed).max(0)
+    };
+    progress
+}
+
+/// Length of a session in minutes: the given duration, or the time between
+/// its start and end
+pub fn session_minutes(start: &str, end: Option<&str>, duration: Option<i32>) -> AppResult<i32> {
+    let start_time = parse_datetime(start)?;
+    let minutes = match (duration, end.filter(|end| !end.trim().is_empty())) {
+        (Some(duration), _) => duration,
+        (None, Some(end)) => (parse_datetime(end)? - start_time).num_minutes() as i32,
+        (None, None) => {
+            return Err(AppError::ValidationError("A session needs a duration or an end time".to_string()));
+        }
+    };
+
+    if !(1..=MAX_SESSION_MINUTES).contains(&minutes) {
+        return Err(AppError::ValidationError(format!(
+            "Session duration must be between 1 and {} minutes",
+            MAX_SESSION_MINUTES
+        )));
+    }
+    Ok(minutes)
+}
+
+/// Pending assignments past their due date are overdue and overdue ones
+/// moved to a later date are pending again
+pub fn assignment_status(assignment: &StudyAssignment, today: NaiveDate) -> &'static str {
+    if assignment.status == ASSIGNMENT_COMPLETED {
+        return ASSIGNMENT_COMPLETED;
+    }
+    match NaiveDate::parse_from_str(&assignment.dueDate, "%Y-%m-%d") {
+        Ok(due) if due < today => ASSIGNMENT_OVERDUE,
+        _ => ASSIGNMENT_PENDING,
+    }
+}
+
+/// Exams ahead of the student, nearest first: deadlines of active goals with
+/// an exam type and exam results entered before the exam date
+pub fn upcoming_exams(goals: &[AcademicGoal], results: &[ExamResult], today: NaiveDate) -> Vec<UpcomingExam> {
+    let from_goals = goals.iter().filter_map(|goal| {
+        Some(UpcomingExam {
+            examType: goal.examType.clone()?,
+            examDate: date_part(goal.deadline.as_deref()?).to_string(),
+            title: goal.title.clone(),
+        })
+    });
+    let from_results = results.iter().map(|result| UpcomingExam {
+        examType: result.examType.clone(),
+        examDate: date_part(&result.examDate).to_string(),
+        title: result.examName.clone(),
+    });
+
+    let mut exams: Vec<UpcomingExam> = from_goals
+        .chain(from_results)
+        .filter(|exam| NaiveDate::parse_from_str(&exam.examDate, "%Y-%m-%d").is_ok_and(|date| date >= today))
+        .collect();
+    exams.sort_by(|a, b| a.examDate.cmp(&b.examDate).then_with(|| a.examType.cmp(&b.examType)));
+    exams.dedup_by(|a, b| a.examDate == b.examDate && a.examType == b.examType);
+    exams
+}
+
+/// Subject exam types an exam asks: YKS covers its three sessions
+pub fn covered_exam_types(exam_type: &str) -> Vec<&str> {
+    match exam_type {
+        EXAM_TYPE_YKS => vec![EXAM_TYPE_TYT, EXAM_TYPE_AYT, EXAM_TYPE_YDT],
+        other => vec![other],
+    }
+}
+
+/// How urgently a topic should be studied, with the reasons behind it
+pub fn topic_priority(topic: &StudyPlanTopic, exams: &[UpcomingExam], today: NaiveDate) -> (f64, Vec<String>) {
+    let mut priority = 0.0;
+    let mut reasons = Vec::new();
+
+    if topic.isOverdue {
+        priority += 40.0;
+        reasons.push("Süresi geçmiş ödev".to_string());
+    } else if let Some(due) = topic.dueDate.as_deref().and_then(|due| NaiveDate::parse_from_str(due, "%Y-%m-%d").ok()) {
+        let days = (due - today).num_days().max(0);
+        if days <= 7 {
+            priority += 20.0 + 2.0 * (7 - days) as f64;
+            reasons.push(format!("Ödev teslimi: {}", due.format("%Y-%m-%d")));
+        }
+    }
+
+    priority += (1.0 - topic.completion) * 30.0;
+    if topic.completion > 0.0 && topic.completion < 0.5 {
+        reasons.push(format!("Konunun %{:.0}'i tamamlandı", topic.completion * 100.0));
+    }
+    if let Some(efficiency) = topic.averageEfficiency.filter(|&efficiency| efficiency < LOW_EFFICIENCY) {
+        priority += (LOW_EFFICIENCY - efficiency) / LOW_EFFICIENCY * 20.0;
+        reasons.push(format!("Düşük çalışma verimi (%{:.0})", efficiency));
+    }
+    if let Some(rate) = topic.examSuccessRate.filter(|&rate| rate < WEAK_SUCCESS_RATE) {
+        priority += 10.0 + (WEAK_SUCCESS_RATE - rate) / WEAK_SUCCESS_RATE * 25.0;
+        reasons.push(format!("Denemelerde %{:.0} başarı", rate));
+    }
+    match topic.difficulty.as_deref() {
+        Some(TOPIC_HARD) => priority += 10.0,
+        Some(TOPIC_MEDIUM) => priority += 5.0,
+        _ => {}
+    }
+
+    let nearest = exams.iter().find(|exam| {
+        topic.examType.as_deref().is_some_and(|exam_type| covered_exam_types(&exam.examType).contains(&exam_type))
+    });
+    if let Some(exam) = nearest {
+        if let Ok(date) = NaiveDate::parse_from_str(&exam.examDate, "%Y-%m-%d") {
+            let days = (date - today).num_days();
+            if days <= EXAM_HORIZON_DAYS {
+                priority += 30.0 * (1.0 - days as f64 / EXAM_HORIZON_DAYS as f64);
+                reasons.push(format!("{} sınavına {} gün", exam.examType, days));
+            }
+        }
+    }
+
+    ((priority * 10.0).round() / 10.0, reasons)
+}
+
+/// Spread the topics over seven days in order of priority. Each topic gets at
+/// most one block a day, placed on the day with the most free time (by its
+/// due date when the assignment is due this week). Returns the days and the
+/// minutes that did not fit.
+pub fn build_weekly_plan(
+    topics: &[StudyPlanTopic],
+    exams: &[UpcomingExam],
+    week_start: NaiveDate,
+    today: NaiveDate,
+    daily_minutes: i32,
+) -> (Vec<StudyPlanDay>, i32) {
+    let mut days: Vec<StudyPlanDay> = (0..7)
+        .map(|offset| StudyPlanDay {
+            date: (week_start + Duration::days(offset)).format("%Y-%m-%d").to_string(),
+            totalMinutes: 0,
+            items: Vec::new(),
+        })
+        .collect();
+
+    let mut ranked: Vec<(&StudyPlanTopic, f64, Vec<String>)> = topics
+        .iter()
+        .filter(|topic| topic.remainingMinutes > 0)
+        .map(|topic| {
+            let (priority, reasons) = topic_priority(topic, exams, today);
+            (topic, priority, reasons)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.topicName.cmp(&b.0.topicName)));
+
+    let mut unscheduled = 0;
+    for (topic, priority, reasons) in ranked {
+        let mut minutes = topic.remainingMinutes.min(MAX_TOPIC_MINUTES_PER_WEEK);
+        let last_day = topic.dueDate.as_deref().and_then(|due| days.iter().rposition(|day| day.date.as_str() <= due));
+
+        while minutes > 0 {
+            let block = minutes.min(STUDY_BLOCK_MINUTES);
+            let free = |day: &StudyPlanDay| {
+                daily_minutes - day.totalMinutes >= block && day.items.iter().all(|item| item.topicId != topic.topicId)
+            };
+            let pick = |range: std::ops::Range<usize>| {
+                range
+                    .filter(|&i| free(&days[i]))
+                    .min_by_key(|&i| (days[i].totalMinutes, i))
+            };
+            let chosen = last_day.and_then(|last| pick(0..last + 1)).or_else(|| pick(0..days.len()));
+
+            let Some(i) = chosen else {
+                unscheduled += minutes;
+                break;
+            };
+            days[i].totalMinutes += block;
+            days[i].items.push(StudyPlanItem {
+                topicId: topic.topicId.clone(),
+                topicName: topic.topicName.clone(),
+                subjectName: topic.subjectName.clone(),
+                minutes: block,
+                priority,
+                reasons: reasons.clone(),
+                assignmentId: topic.assignmentId.clone(),
+            });
+            minutes -= block;
+        }
+    }
+
+    (days, unscheduled)
+}
+
+fn completion(completed: i32, remaining: i32) -> f64 {
+    let total = completed + remaining;
+    if total <= 0 {
+        return 1.0;
+    }
+    completed as f64 / total as f64
+}
+
+fn parse_datetime(value: &str) -> AppResult<NaiveDateTime> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.naive_utc())
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M"))
+        .map_err(|_| AppError::ValidationError(format!("Invalid date and time: {}", value)))
+}
+
+fn parse_date(value: &str) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(date_part(value.trim()), "%Y-%m-%d")
+        .map_err(|_| AppError::ValidationError(format!("Invalid date: {}", value)))
+}
+
+fn validate_efficiency(efficiency: f64) -> AppResult<()> {
+    if !(0.0..=100.0).contains(&efficiency) {
+        return Err(AppError::ValidationError("Efficiency must be between 0 and 100".to_string()));
+    }
+    Ok(())
+}
+
+fn date_part(value: &str) -> &str {
+    value.get(..10).unwrap_or(value)
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(completed: i32, remaining: i32) -> Progress {
+        Progress {
+            id: "p".to_string(),
+            studentId: "s".to_string(),
+            topicId: "t".to_string(),
+            completed,
+            remaining,
+            lastStudied: None,
+            notes: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn topic(id: &str, remaining: i32, completion: f64, exam_type: Option<&str>) -> StudyPlanTopic {
+        StudyPlanTopic {
+            topicId: id.to_string(),
+            topicName: id.to_string(),
+            subjectName: "Matematik".to_string(),
+            examType: exam_type.map(str::to_string),
+            difficulty: None,
+            remainingMinutes: remaining,
+            completion,
+            averageEfficiency: None,
+            examSuccessRate: None,
+            assignmentId: None,
+            dueDate: None,
+            isOverdue: false,
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_session_minutes_and_progress() {
+        assert_eq!(session_minutes("2024-03-01T10:00:00Z", Some("2024-03-01T10:50:00Z"), None).unwrap(), 50);
+        assert_eq!(session_minutes("2024-03-01 10:00", Some("2024-03-01 11:30"), None).unwrap(), 90);
+        assert_eq!(session_minutes("2024-03-01T10:00", Some("2024-03-01T11:30"), Some(40)).unwrap(), 40);
+        assert!(session_minutes("2024-03-01T10:00", Some("2024-03-01T09:30"), None).is_err());
+        assert!(session_minutes("2024-03-01T10:00", None, None).is_err());
+
+        let studied = apply_minutes(progress(0, 120), 2, 90);
+        assert_eq!((studied.completed, studied.remaining), (90, 30));
+        let studied = apply_minutes(studied, 2, 45);
+        assert_eq!((studied.completed, studied.remaining), (135, 0));
+        let undone = apply_minutes(studied, 2, -90);
+        assert_eq!((undone.completed, undone.remaining), (45, 75));
+        // A remaining time corrected by hand is kept when adding or taking off less
+        let undone = apply_minutes(progress(30, 200), 2, -30);
+        assert_eq!((undone.completed, undone.remaining), (0, 200));
+    }
+
+    #[test]
+    fn test_assignment_status_and_upcoming_exams() {
+        let mut assignment = StudyAssignment {
+            id: "a".to_string(),
+            studentId: "s".to_string(),
+            topicId: "t".to_string(),
+            dueDate: "2024-03-10".to_string(),
+            status: ASSIGNMENT_PENDING.to_string(),
+            notes: None,
+            assignedBy: None,
+            completedAt: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        assert_eq!(assignment_status(&assignment, date("2024-03-10")), ASSIGNMENT_PENDING);
+        assert_eq!(assignment_status(&assignment, date("2024-03-11")), ASSIGNMENT_OVERDUE);
+        assignment.status = ASSIGNMENT_COMPLETED.to_string();
+        assert_eq!(assignment_status(&assignment, date("2024-03-11")), ASSIGNMENT_COMPLETED);
+
+        assert_eq!(covered_exam_types("YKS"), vec!["TYT", "AYT", "YDT"]);
+        assert_eq!(covered_exam_types("LGS"), vec!["LGS"]);
+    }
+
+    #[test]
+    fn test_priority_favors_overdue_weak_and_exam_topics() {
+        let today = date("2024-03-01");
+        let exams = vec![UpcomingExam {
+            examType: "YKS".to_string(),
+            examDate: "2024-04-01".to_string(),
+            title: "YKS".to_string(),
+        }];
+
+        let (started, _) = topic_priority(&topic("a", 60, 0.5, None), &exams, today);
+        let (untouched, _) = topic_priority(&topic("b", 120, 0.0, None), &exams, today);
+        let (exam, reasons) = topic_priority(&topic("c", 120, 0.0, Some("TYT")), &exams, today);
+        assert!(started < untouched && untouched < exam);
+        assert_eq!(reasons, vec!["YKS sınavına 31 gün"]);
+
+        let overdue = StudyPlanTopic { isOverdue: true, ..topic("d", 60, 0.5, None) };
+        let weak = StudyPlanTopic { averageEfficiency: Some(30.0), ..topic("e", 60, 0.5, None) };
+        assert!(topic_priority(&overdue, &exams, today).0 > exam);
+        assert!(topic_priority(&weak, &exams, today).0 > started);
+        let missed = StudyPlanTopic { examSuccessRate: Some(20.0), ..topic("f", 60, 0.5, None) };
+        let (priority, reasons) = topic_priority(&missed, &exams, today);
+        assert!(priority > untouched);
+        assert_eq!(reasons, vec!["Denemelerde %20 başarı"]);
+    }
+
+    #[test]
+    fn test_weekly_plan_spreads_blocks_and_respects_due_dates() {
+        let start = date("2024-03-04");
+        let due = StudyPlanTopic {
+            dueDate: Some("2024-03-05".to_string()),
+            ..topic("due", 90, 0.0, None)
+        };
+        let topics = vec![topic("long", 600, 0.0, None), due, topic("done", 0, 1.0, None)];
+
+        let (days, unscheduled) = build_weekly_plan(&topics, &[], start, start, 60);
+        assert_eq!(days.len(), 7);
+        assert_eq!(days[6].date, "2024-03-10");
+        assert!(days.iter().all(|day| day.totalMinutes <= 60));
+
+        // The long topic is capped for the week and spread one block a day
+        let long: Vec<_> = days.iter().filter(|day| day.items.iter().any(|item| item.topicId == "long")).collect();
+        assert_eq!(long.len(), 4);
+        assert_eq!(long.iter().flat_map(|day| &day.items).filter(|i| i.topicId == "long").map(|i| i.minutes).sum::<i32>(), 180);
+
+        // The assignment is scheduled by its due date
+        let due_days: Vec<_> = days.iter().filter(|day| day.items.iter().any(|item| item.topicId == "due")).collect();
+        assert_eq!(due_days.len(), 2);
+        assert!(due_days.iter().all(|day| day.date.as_str() <= "2024-03-05"));
+        assert!(days.iter().all(|day| day.items.iter().all(|item| item.topicId != "done")));
+        assert_eq!(unscheduled, 0);
+
+        let (_, unscheduled) = build_weekly_plan(&[topic("long", 600, 0.0, None)], &[], start, start, 45);
+        assert_eq!(unscheduled, 0);
+        let many: Vec<_> = (0..20).map(|i| topic(&format!("t{}", i), 90, 0.0, None)).collect();
+        let (_, unscheduled) = build_weekly_plan(&many, &[], start, start, 45);
+        assert_eq!(unscheduled, 20 * 90 - 7 * 45);
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::NaiveDate;
+use sqlx::SqlitePool;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    TimelineEvent, TimelinePage, TimelineQuery, User, TIMELINE_AI_SUGGESTION, TIMELINE_ATTENDANCE,
+    TIMELINE_BEHAVIOR_INCIDENT, TIMELINE_COUNSELING_SESSION, TIMELINE_EXAM_RESULT, TIMELINE_HOME_VISIT,
+    TIMELINE_INTERVENTION, TIMELINE_MEETING_NOTE, TIMELINE_NOTIFICATION, TIMELINE_PARENT_MEETING,
+};
+use crate::repositories::{ConfidentialAccess, TimelineFilter, TimelineRepository};
+use crate::services::institution_service::accessible_student;
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+const ALL_EVENT_TYPES: &[&str] = &[
+    TIMELINE_EXAM_RESULT,
+    TIMELINE_BEHAVIOR_INCIDENT,
+    TIMELINE_COUNSELING_SESSION,
+    TIMELINE_MEETING_NOTE,
+    TIMELINE_PARENT_MEETING,
+    TIMELINE_HOME_VISIT,
+    TIMELINE_NOTIFICATION,
+    TIMELINE_AI_SUGGESTION,
+    TIMELINE_INTERVENTION,
+    TIMELINE_ATTENDANCE,
+];
+
+const TEACHER_EVENT_TYPES: &[&str] = &[
+    TIMELINE_EXAM_RESULT,
+    TIMELINE_BEHAVIOR_INCIDENT,
+    TIMELINE_COUNSELING_SESSION,
+    TIMELINE_PARENT_MEETING,
+    TIMELINE_INTERVENTION,
+    TIMELINE_ATTENDANCE,
+];
+
+const OBSERVER_EVENT_TYPES: &[&str] = &[TIMELINE_EXAM_RESULT, TIMELINE_ATTENDANCE];
+
+// Teachers see that these happened, but not what was discussed
+const TEACHER_REDACTED_TYPES: &[&str] = &[TIMELINE_COUNSELING_SESSION, TIMELINE_PARENT_MEETING];
+
+pub struct TimelineService;
+
+impl TimelineService {
+    /// One page of a student's history, newest first.
+    ///
+    /// Admins and counselors see every event type; teachers and observers see a
+    /// subset, with counseling content redacted for teachers. Confidential
+    /// records are shown to admins and to the counselor who owns them; those
+    /// without an owner only to admins.
+    pub async fn get_timeline(pool: &SqlitePool, viewer: &User, query: TimelineQuery) -> AppResult<TimelinePage> {
+        let visible = visible_event_types(&viewer.role)?;
+
+        let event_types: Vec<&str> = match &query.eventTypes {
+            Some(requested) if !requested.is_empty() => {
+                for event_type in requested {
+                    if !ALL_EVENT_TYPES.contains(&event_type.as_str()) {
+                        return Err(AppError::ValidationError(format!("Unknown event type: {}", event_type)));
+                    }
+                }
+                visible
+                    .iter()
+                    .copied()
+                    .filter(|event_type| requested.iter().any(|r| r == event_type))
+                    .collect()
+            }
+            _ => visible.to_vec(),
+        };
+
+        validate_date(query.from.as_deref())?;
+        validate_date(query.to.as_deref())?;
+
+        accessible_student(pool, viewer, &query.studentId).await?;
+
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.pageSize.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+        let filter = TimelineFilter {
+            student_id: &query.studentId,
+            event_types,
+            from: query.from.as_deref(),
+            to: query.to.as_deref(),
+            confidential: confidential_access(viewer),
+            limit: page_size,
+            offset: (page - 1) * page_size,
+        };
+
+        let total = TimelineRepository::count_events(pool, &filter).await?;
+        let mut events = TimelineRepository::get_events(pool, &filter).await?;
+
+        if viewer.role == "teacher" {
+            events.iter_mut().for_each(redact);
+        }
+
+        Ok(TimelinePage {
+            hasMore: page * page_size < total,
+            events,
+            total,
+            page,
+            pageSize: page_size,
+        })
+    }
+
+    /// Mark a counseling session, meeting note, parent meeting or home visit as
+    /// confidential; only admins and the record's owner may change it
+    pub async fn set_confidential(
+        pool: &SqlitePool,
+        viewer: &User,
+        event_type: &str,
+        id: &str,
+        is_confidential: bool,
+    ) -> AppResult<()> {
+        if !matches!(viewer.role.as_str(), "admin" | "counselor") {
+            return Err(AppError::Unauthorized);
+        }
+
+        let owner = TimelineRepository::get_owner(pool, event_type, id).await?;
+        if viewer.role != "admin" && owner.as_deref() != Some(viewer.id.as_str()) {
+            return Err(AppError::Unauthorized);
+        }
+
+        TimelineRepository::set_confidential(pool, event_type, id, is_confidential).await
+    }
+}
+
+pub(crate) fn visible_event_types(role: &str) -> AppResult<&'static [&'static str]> {
+    match role {
+        "admin" | "counselor" => Ok(ALL_EVENT_TYPES),
+        "teacher" => Ok(TEACHER_EVENT_TYPES),
+        "observer" => Ok(OBSERVER_EVENT_TYPES),
+        _ => Err(AppError::Unauthorized),
+    }
+}
+
+pub(crate) fn confidential_access(viewer: &User) -> ConfidentialAccess {
+    match viewer.role.as_str() {
+        "admin" => ConfidentialAccess::All,
+        "counselor" => ConfidentialAccess::Owned(viewer.id.clone()),
+        _ => ConfidentialAccess::None,
+    }
+}
+
+fn redact(event: &mut TimelineEvent) {
+    if TEACHER_REDACTED_TYPES.contains(&event.eventType.as_str()) {
+        event.title = match event.eventType.as_str() {
+            TIMELINE_COUNSELING_SESSION => "Rehberlik görüşmesi".to_string(),
+            _ => "Veli görüşmesi".to_string(),
+        };
+        event.summary = None;
+        event.redacted = true;
+    }
+}
+
+fn validate_date(date: Option<&str>) -> AppResult<()> {
+    if let Some(value) = date {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| AppError::ValidationError(format!("Invalid date: {}", value)))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_pool;
+
+    fn viewer(id: &str, role: &str) -> User {
+        User {
+            id: id.to_string(),
+            name: id.to_string(),
+            email: format!("{}@okul.test", id),
+            passwordHash: String::new(),
+            role: role.to_string(),
+            institution: "Okul".to_string(),
+            isActive: true,
+            isDistrictAdmin: false,
+            activeInstitutionId: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    async fn setup() -> SqlitePool {
+        let pool = test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, name, email, passwordHash, role, institution) VALUES
+                ('u1', 'Ayşe', 'u1@okul.test', '', 'counselor', 'Okul');
+            INSERT INTO students (id, name, surname, enrollmentDate) VALUES ('s1', 'Ali', 'Yılmaz', '2024-09-01');
+            INSERT INTO meeting_notes (id, studentId, date, type, note, createdBy) VALUES
+                ('n1', 's1', '2024-10-01', 'Bireysel', 'not', 'u1'),
+                ('n2', 's1', '2024-10-02', 'Bireysel', 'sahipsiz not', NULL);
+            INSERT INTO parent_meetings (id, studentId, meetingDate, attendees, topics, createdBy) VALUES
+                ('p1', 's1', '2024-10-04', 'Anne', 'ders durumu', 'u1');
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn query() -> TimelineQuery {
+        TimelineQuery {
+            studentId: "s1".to_string(),
+            eventTypes: None,
+            from: None,
+            to: None,
+            page: None,
+            pageSize: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeline_by_role() {
+        let pool = setup().await;
+
+        let page = TimelineService::get_timeline(&pool, &viewer("t1", "teacher"), query()).await.unwrap();
+        assert_eq!(page.total, 1);
+        let event = &page.events[0];
+        assert_eq!((event.sourceId.as_str(), event.title.as_str()), ("p1", "Veli görüşmesi"));
+        assert!(event.redacted);
+        assert_eq!(event.summary, None);
+
+        let page = TimelineService::get_timeline(&pool, &viewer("u1", "counselor"), query()).await.unwrap();
+        assert_eq!(page.total, 3);
+        assert!(page.events.iter().all(|e| !e.redacted && e.summary.is_some()));
+
+        let page = TimelineService::get_timeline(&pool, &viewer("o1", "observer"), query()).await.unwrap();
+        assert_eq!(page.total, 0);
+    }
+
+    #[tokio::test]
+    async fn test_set_confidential_requires_owner_or_admin() {
+        let pool = setup().await;
+        let owner = viewer("u1", "counselor");
+        let other = viewer("u2", "counselor");
+        let admin = viewer("a1", "admin");
+
+        assert!(TimelineService::set_confidential(&pool, &other, TIMELINE_MEETING_NOTE, "n1", true).await.is_err());
+        assert!(TimelineService::set_confidential(&pool, &owner, TIMELINE_MEETING_NOTE, "n2", true).await.is_err());
+        TimelineService::set_confidential(&pool, &owner, TIMELINE_MEETING_NOTE, "n1", true).await.unwrap();
+        TimelineService::set_confidential(&pool, &admin, TIMELINE_MEETING_NOTE, "n2", true).await.unwrap();
+
+        let page = TimelineService::get_timeline(&pool, &other, query()).await.unwrap();
+        let ids: Vec<&str> = page.events.iter().map(|e| e.sourceId.as_str()).collect();
+        assert_eq!(ids, vec!["p1"]);
+    }
+}